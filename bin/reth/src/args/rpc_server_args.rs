@@ -1,21 +1,30 @@
 //! clap [Args](clap::Args) for RPC related arguments.
 
-use crate::dirs::{JwtSecretPath, PlatformPath};
+use crate::dirs::{JwtSecretPath, KeystorePath, PlatformPath};
 use clap::Args;
 use jsonrpsee::{core::Error as RpcError, server::ServerHandle};
+use reth_interfaces::{events::ChainEventSubscriptions, sync::SyncStateSubscriptions};
 use reth_network_api::{NetworkInfo, Peers};
-use reth_provider::{BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory};
-use reth_rpc::{JwtError, JwtSecret};
+use reth_primitives::Address;
+use reth_provider::{
+    BadBlocks, BlockProvider, EvmEnvProvider, HeaderProvider, ReceiptProvider, StateProviderFactory,
+};
+use reth_rpc::{
+    eth::signer::{EthSigner, KeystoreSigner},
+    JwtError, JwtSecret,
+};
 use reth_rpc_builder::{
-    constants, IpcServerBuilder, RethRpcModule, RpcModuleSelection, RpcServerConfig,
-    RpcServerHandle, ServerBuilder, TransportRpcModuleConfig,
+    constants, EthConfig, IpcServerBuilder, RethRpcModule, RpcModuleBuilder, RpcModuleConfig,
+    RpcModuleSelection, RpcServerConfig, RpcServerHandle, ServerBuilder, TransportRpcModuleConfig,
 };
 use reth_rpc_engine_api::EngineApiHandle;
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
-    path::Path,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 
 /// Parameters for configuring the rpc more granularity via CLI
@@ -66,6 +75,24 @@ pub struct RpcServerArgs {
     #[arg(long)]
     pub ipcpath: Option<String>,
 
+    /// Maximum number of concurrently executing tracing requests, like `trace_block` or
+    /// `debug_traceTransaction`
+    #[arg(long = "rpc.max-tracing-requests", value_name = "COUNT")]
+    pub rpc_max_tracing_requests: Option<u32>,
+
+    /// Maximum number of blocks a `trace_filter` request can cover
+    #[arg(long = "rpc.max-trace-filter-blocks", value_name = "COUNT")]
+    pub rpc_max_trace_filter_blocks: Option<u64>,
+
+    /// Maximum time in seconds a javascript tracer of the debug namespace may run, requests can
+    /// only lower it
+    #[arg(long = "rpc.js-tracer-timeout", value_name = "SECONDS")]
+    pub rpc_js_tracer_timeout: Option<u64>,
+
+    /// Maximum memory in megabytes a javascript tracer of the debug namespace may allocate
+    #[arg(long = "rpc.js-tracer-memory", value_name = "MB")]
+    pub rpc_js_tracer_memory: Option<usize>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr")]
     pub auth_addr: Option<IpAddr>,
@@ -77,6 +104,19 @@ pub struct RpcServerArgs {
     /// Path to a JWT secret to use for authenticated RPC endpoints
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false)]
     auth_jwtsecret: Option<PlatformPath<JwtSecretPath>>,
+
+    /// Comma separated list of keystore accounts to unlock
+    #[arg(long, value_name = "ADDRESSES", value_delimiter = ',')]
+    pub unlock: Vec<Address>,
+
+    /// Password file to unlock the accounts with. The password of the n-th account is read from
+    /// the n-th line, the last line is used for all remaining accounts.
+    #[arg(long, value_name = "PATH", requires = "unlock")]
+    pub password: Option<PathBuf>,
+
+    /// Directory of the encrypted keystore files of the accounts
+    #[arg(long, value_name = "PATH")]
+    keystore: Option<PlatformPath<KeystorePath>>,
 }
 
 impl RpcServerArgs {
@@ -104,34 +144,70 @@ impl RpcServerArgs {
     }
 
     /// Convenience function for starting a rpc server with configs which extracted from cli args.
-    pub(crate) async fn start_rpc_server<Client, Pool, Network, Tasks>(
+    pub(crate) async fn start_rpc_server<Client, Pool, Network, Tasks, Events>(
         &self,
         client: Client,
         pool: Pool,
         network: Network,
         executor: Tasks,
+        events: Events,
+        bad_blocks: BadBlocks,
     ) -> Result<RpcServerHandle, RpcError>
     where
         Client: BlockProvider
             + HeaderProvider
+            + ReceiptProvider
             + StateProviderFactory
             + EvmEnvProvider
             + Clone
             + Unpin
             + 'static,
         Pool: TransactionPool + Clone + 'static,
-        Network: NetworkInfo + Peers + Clone + 'static,
+        Network: NetworkInfo + Peers + SyncStateSubscriptions + Clone + 'static,
         Tasks: TaskSpawner + Clone + 'static,
+        Events: ChainEventSubscriptions + Clone + 'static,
     {
-        reth_rpc_builder::launch(
-            client,
-            pool,
-            network,
-            self.transport_rpc_module_config(),
-            self.rpc_server_config(),
-            executor,
-        )
-        .await
+        let signers = self.signers().map_err(|err| RpcError::Custom(err.to_string()))?;
+        RpcModuleBuilder::new(client, pool, network, executor, events)
+            .with_signers(signers)
+            .with_bad_blocks(bad_blocks)
+            .build(self.transport_rpc_module_config())
+            .start_server(self.rpc_server_config())
+            .await
+    }
+
+    /// Opens the keystore and unlocks the accounts given by `--unlock` with the passwords of the
+    /// `--password` file.
+    ///
+    /// All accounts of the keystore are available via RPC, but only unlocked accounts can sign.
+    pub(crate) fn signers(&self) -> Result<Vec<Arc<dyn EthSigner>>, String> {
+        let keystore_path = self.keystore.clone().unwrap_or_default();
+        let keystore = KeystoreSigner::open(&keystore_path).map_err(|err| {
+            format!("failed to open keystore {}: {err}", keystore_path.as_ref().display())
+        })?;
+
+        if !self.unlock.is_empty() {
+            let passwords = match &self.password {
+                Some(path) => std::fs::read_to_string(path)
+                    .map_err(|err| format!("failed to read password file: {err}"))?
+                    .lines()
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+                None => Vec::new(),
+            };
+            let Some(last_password) = passwords.last() else {
+                return Err("a password file is required to unlock accounts".to_string())
+            };
+
+            for (idx, account) in self.unlock.iter().enumerate() {
+                let password = passwords.get(idx).unwrap_or(last_password);
+                keystore
+                    .unlock(*account, password)
+                    .map_err(|err| format!("failed to unlock account {account:?}: {err}"))?;
+            }
+        }
+
+        Ok(vec![Arc::new(keystore)])
     }
 
     /// Create Engine API server.
@@ -146,6 +222,7 @@ impl RpcServerArgs {
     where
         Client: BlockProvider
             + HeaderProvider
+            + ReceiptProvider
             + StateProviderFactory
             + EvmEnvProvider
             + Clone
@@ -174,7 +251,7 @@ impl RpcServerArgs {
 
     /// Creates the [TransportRpcModuleConfig] from cli args.
     fn transport_rpc_module_config(&self) -> TransportRpcModuleConfig {
-        let mut config = TransportRpcModuleConfig::default();
+        let mut config = TransportRpcModuleConfig::default().with_config(self.rpc_module_config());
         let rpc_modules =
             RpcModuleSelection::Selection(vec![RethRpcModule::Admin, RethRpcModule::Eth]);
         if self.http {
@@ -185,9 +262,32 @@ impl RpcServerArgs {
             config = config.with_ws(self.ws_api.as_ref().unwrap_or(&rpc_modules).clone());
         }
 
+        if !self.ipcdisable {
+            // the ipc endpoint is only accessible locally, so it serves all modules like geth
+            config = config.with_ipc(RpcModuleSelection::All);
+        }
+
         config
     }
 
+    /// Creates the [RpcModuleConfig] from cli args.
+    fn rpc_module_config(&self) -> RpcModuleConfig {
+        let mut eth = EthConfig::default();
+        if let Some(max_tracing_requests) = self.rpc_max_tracing_requests {
+            eth.max_tracing_requests = max_tracing_requests;
+        }
+        if let Some(max_trace_filter_blocks) = self.rpc_max_trace_filter_blocks {
+            eth.max_trace_filter_blocks = max_trace_filter_blocks;
+        }
+        if let Some(timeout) = self.rpc_js_tracer_timeout {
+            eth.js_tracer_limits.timeout = Duration::from_secs(timeout);
+        }
+        if let Some(memory) = self.rpc_js_tracer_memory {
+            eth.js_tracer_limits.memory_limit = memory * 1024 * 1024;
+        }
+        RpcModuleConfig::builder().eth(eth).build()
+    }
+
     /// Creates the [RpcServerConfig] from cli args.
     fn rpc_server_config(&self) -> RpcServerConfig {
         let mut config = RpcServerConfig::default();
@@ -206,9 +306,9 @@ impl RpcServerArgs {
         if self.ws {
             let socket_address = SocketAddr::new(
                 self.ws_addr.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
-                self.ws_port.unwrap_or(constants::DEFAULT_HTTP_RPC_PORT),
+                self.ws_port.unwrap_or(constants::DEFAULT_WS_RPC_PORT),
             );
-            config = config.with_ws_address(socket_address).with_http(ServerBuilder::new());
+            config = config.with_ws_address(socket_address).with_ws(ServerBuilder::new());
         }
 
         if !self.ipcdisable {
@@ -247,6 +347,29 @@ mod tests {
         assert_eq!(apis, expected);
     }
 
+    #[test]
+    fn test_unlock_args_parser() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--unlock",
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F,0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+            "--password",
+            "passwords.txt",
+        ])
+        .args;
+
+        assert_eq!(args.unlock.len(), 2);
+        assert_eq!(args.password, Some(PathBuf::from("passwords.txt")));
+
+        // a password file is only meaningful with accounts to unlock
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--password",
+            "passwords.txt"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_transport_rpc_module_config() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
@@ -264,6 +387,65 @@ mod tests {
             config.ws().cloned().unwrap().into_selection(),
             vec![RethRpcModule::Admin, RethRpcModule::Eth]
         );
+        assert_eq!(
+            config.ipc().cloned().unwrap().into_selection(),
+            RpcModuleSelection::all_modules()
+        );
+    }
+
+    #[test]
+    fn test_max_tracing_requests_args_parser() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_module_config(), RpcModuleConfig::default());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http.api",
+            "eth,trace",
+            "--rpc.max-tracing-requests",
+            "10",
+        ])
+        .args;
+        let expected = RpcModuleSelection::try_from_selection(["eth", "trace"]).unwrap();
+        assert_eq!(args.http_api.as_ref().unwrap(), &expected);
+        assert_eq!(
+            args.rpc_module_config(),
+            RpcModuleConfig::builder()
+                .eth(EthConfig { max_tracing_requests: 10, ..Default::default() })
+                .build()
+        );
+    }
+
+    #[test]
+    fn test_max_trace_filter_blocks_args_parser() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.max-trace-filter-blocks",
+            "1000",
+        ])
+        .args;
+        assert_eq!(
+            args.rpc_module_config(),
+            RpcModuleConfig::builder()
+                .eth(EthConfig { max_trace_filter_blocks: 1000, ..Default::default() })
+                .build()
+        );
+    }
+
+    #[test]
+    fn test_js_tracer_limits_args_parser() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.js-tracer-timeout",
+            "10",
+            "--rpc.js-tracer-memory",
+            "64",
+        ])
+        .args;
+        let mut eth = EthConfig::default();
+        eth.js_tracer_limits.timeout = Duration::from_secs(10);
+        eth.js_tracer_limits.memory_limit = 64 * 1024 * 1024;
+        assert_eq!(args.rpc_module_config(), RpcModuleConfig::builder().eth(eth).build());
     }
 
     #[test]
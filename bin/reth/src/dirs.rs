@@ -49,6 +49,13 @@ pub fn jwt_secret_dir() -> Option<PathBuf> {
     data_dir().map(|root| root.join("jwtsecret"))
 }
 
+/// Returns the path to the reth keystore directory.
+///
+/// Refer to [dirs_next::data_dir] for cross-platform behavior.
+pub fn keystore_dir() -> Option<PathBuf> {
+    data_dir().map(|root| root.join("keystore"))
+}
+
 /// Returns the path to the reth database.
 ///
 /// Refer to [dirs_next::data_dir] for cross-platform behavior.
@@ -75,6 +82,19 @@ impl XdgPath for JwtSecretPath {
     }
 }
 
+/// Returns the path to the default directory of the encrypted account keys.
+///
+/// Refer to [dirs_next::data_dir] for cross-platform behavior.
+#[derive(Default, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct KeystorePath;
+
+impl XdgPath for KeystorePath {
+    fn resolve() -> Option<PathBuf> {
+        keystore_dir()
+    }
+}
+
 /// Returns the path to the default reth configuration file.
 ///
 /// Refer to [dirs_next::config_dir] for cross-platform behavior.
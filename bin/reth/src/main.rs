@@ -1,3 +1,8 @@
+/// Accounts the memory of javascript tracers, so their memory limit can be enforced.
+#[global_allocator]
+static ALLOC: reth_revm::tracing::js::JsTracerAllocator =
+    reth_revm::tracing::js::JsTracerAllocator::system();
+
 fn main() {
     if let Err(err) = reth::cli::run() {
         eprintln!("Error: {err:?}");
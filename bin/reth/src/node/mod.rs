@@ -24,8 +24,13 @@ use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     headers::reverse_headers::ReverseHeadersDownloaderBuilder,
 };
+use reth_executor::blockchain_tree::{
+    BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
+};
 use reth_interfaces::{
+    blockchain_tree::BlockchainTreeEngine,
     consensus::{Consensus, ForkchoiceState},
+    events::ChainEventSubscriptions,
     p2p::{
         bodies::downloader::BodyDownloader,
         headers::{client::StatusUpdater, downloader::HeaderDownloader},
@@ -36,8 +41,9 @@ use reth_network::{
     error::NetworkError, FetchClient, NetworkConfig, NetworkHandle, NetworkManager,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{BlockHashOrNumber, ChainSpec, Head, H256};
-use reth_provider::{BlockProvider, HeaderProvider, ShareableDatabase};
+use reth_payload_builder::{PayloadBuilder, PayloadBuilderConfig, PayloadBuilderService};
+use reth_primitives::{BlockHashOrNumber, ChainSpec, Head, IntoRecoveredTransaction, H256};
+use reth_provider::{BadBlocks, BlockProvider, HeaderProvider, ShareableDatabase};
 use reth_rpc_engine_api::{EngineApi, EngineApiHandle};
 use reth_staged_sync::{
     utils::{
@@ -49,9 +55,13 @@ use reth_staged_sync::{
 };
 use reth_stages::{
     prelude::*,
-    stages::{ExecutionStage, SenderRecoveryStage, TotalDifficultyStage, FINISH},
+    stages::{
+        ExecutionStage, IndexCallAddressHistoryStage, IndexLogHistoryStage, SenderRecoveryStage,
+        TotalDifficultyStage, FINISH, INDEX_ACCOUNT_HISTORY,
+    },
 };
 use reth_tasks::TaskExecutor;
+use reth_transaction_pool::{PoolConfig, TransactionPool};
 use std::{net::SocketAddr, path::PathBuf, sync::Arc};
 use tokio::sync::{mpsc::unbounded_channel, watch};
 use tracing::*;
@@ -115,6 +125,18 @@ pub struct Command {
     #[arg(long = "debug.terminate", help_heading = "Debug")]
     terminate: bool,
 
+    /// Dumps every block that is rejected as invalid to this directory.
+    ///
+    /// The blocks are written RLP encoded to `<number>-<hash>.rlp`, so they can be imported with
+    /// `reth import` and replayed with `reth stage`.
+    #[arg(long = "debug.bad-blocks-dir", value_name = "PATH", help_heading = "Debug")]
+    bad_blocks_dir: Option<PathBuf>,
+
+    /// Persists local transactions to this file, so they are re-inserted into the transaction pool
+    /// after a restart.
+    #[arg(long = "txpool.journal", value_name = "FILE", help_heading = "TxPool")]
+    txpool_journal: Option<PathBuf>,
+
     #[clap(flatten)]
     rpc: RpcServerArgs,
 }
@@ -146,6 +168,19 @@ impl Command {
         let (consensus, forkchoice_state_tx) = self.init_consensus()?;
         info!(target: "reth::cli", "Consensus engine initialized");
 
+        // the tree executes the payloads of the engine API and makes them canonical
+        let tree_externals = TreeExternals::new(
+            Arc::clone(&db),
+            Arc::clone(&consensus),
+            reth_executor::Factory::new(Arc::new(self.chain.clone())),
+            Arc::new(self.chain.clone()),
+        );
+        let blockchain_tree = ShareableBlockchainTree::new(BlockchainTree::new(
+            tree_externals,
+            BlockchainTreeConfig::default(),
+        )?);
+        info!(target: "reth::cli", "Blockchain tree initialized");
+
         self.init_trusted_nodes(&mut config);
 
         info!(target: "reth::cli", "Connecting to P2P network");
@@ -154,9 +189,39 @@ impl Command {
         let network = self.start_network(network_config, &ctx.task_executor, ()).await?;
         info!(target: "reth::cli", peer_id = %network.peer_id(), local_addr = %network.local_addr(), "Connected to P2P network");
 
-        let test_transaction_pool = reth_transaction_pool::test_utils::testing_pool();
+        let pool_config = PoolConfig { journal: self.txpool_journal.clone(), ..Default::default() };
+        let test_transaction_pool =
+            reth_transaction_pool::test_utils::testing_pool_with_config(pool_config);
         info!(target: "reth::cli", "Test transaction pool initialized");
 
+        if let Some(journal) = &self.txpool_journal {
+            info!(target: "reth::cli", path = %journal.display(), "Journaling local transactions");
+            ctx.task_executor.spawn_critical(
+                "txpool journal task",
+                reth_transaction_pool::maintain::maintain_local_transaction_journal(
+                    test_transaction_pool.clone(),
+                ),
+            );
+        }
+
+        // the record of the blocks rejected by the engine API and the execution stage
+        let mut bad_blocks = BadBlocks::default();
+        if let Some(dir) = &self.bad_blocks_dir {
+            info!(target: "reth::cli", path = %dir.display(), "Dumping bad blocks");
+            bad_blocks = bad_blocks.with_dump_dir(dir);
+        }
+
+        // keep the pool in sync with the canonical chain
+        ctx.task_executor.spawn_critical(
+            "txpool maintenance task",
+            reth_transaction_pool::maintain::maintain_transaction_pool(
+                shareable_db.clone(),
+                test_transaction_pool.clone(),
+                blockchain_tree.subscribe_canon_state(),
+            ),
+        );
+        info!(target: "reth::cli", "Transaction pool maintenance task started");
+
         let _rpc_server = self
             .rpc
             .start_rpc_server(
@@ -164,12 +229,20 @@ impl Command {
                 test_transaction_pool.clone(),
                 network.clone(),
                 ctx.task_executor.clone(),
+                blockchain_tree.clone(),
+                bad_blocks.clone(),
             )
             .await?;
         info!(target: "reth::cli", "Started RPC server");
 
-        let engine_api_handle =
-            self.init_engine_api(Arc::clone(&db), forkchoice_state_tx, &ctx.task_executor);
+        let engine_api_handle = self.init_engine_api(
+            Arc::clone(&db),
+            blockchain_tree,
+            forkchoice_state_tx,
+            test_transaction_pool.clone(),
+            bad_blocks.clone(),
+            &ctx.task_executor,
+        );
         info!(target: "reth::cli", "Engine API handler initialized");
 
         let _auth_server = self
@@ -190,6 +263,7 @@ impl Command {
                 network.clone(),
                 &consensus,
                 db.clone(),
+                bad_blocks,
                 &ctx.task_executor,
             )
             .await?;
@@ -223,6 +297,7 @@ impl Command {
         network: NetworkHandle,
         consensus: &Arc<dyn Consensus>,
         db: Arc<Env<WriteMap>>,
+        bad_blocks: BadBlocks,
         task_executor: &TaskExecutor,
     ) -> eyre::Result<(Pipeline<Env<WriteMap>, impl SyncStateUpdater>, impl Stream<Item = NodeEvent>)>
     {
@@ -254,6 +329,7 @@ impl Command {
                 network.clone(),
                 consensus,
                 max_block,
+                bad_blocks,
             )
             .await?;
 
@@ -308,20 +384,38 @@ impl Command {
         Ok((consensus, notifier))
     }
 
-    fn init_engine_api(
+    fn init_engine_api<Pool, Tree>(
         &self,
         db: Arc<Env<WriteMap>>,
+        tree: Tree,
         forkchoice_state_tx: watch::Sender<ForkchoiceState>,
+        pool: Pool,
+        bad_blocks: BadBlocks,
         task_executor: &TaskExecutor,
-    ) -> EngineApiHandle {
+    ) -> EngineApiHandle
+    where
+        Pool: TransactionPool + Unpin + 'static,
+        Pool::Transaction: IntoRecoveredTransaction,
+        Tree: BlockchainTreeEngine + Unpin + 'static,
+    {
+        let payload_builder =
+            PayloadBuilder::new(Arc::clone(&db), pool, Arc::new(self.chain.clone()));
+        let (payload_service, payload_builder) =
+            PayloadBuilderService::new(payload_builder, PayloadBuilderConfig::default());
+        task_executor.spawn(payload_service);
+
         let (message_tx, message_rx) = unbounded_channel();
         let engine_api = EngineApi::new(
             ShareableDatabase::new(db, self.chain.clone()),
+            tree,
             self.chain.clone(),
             message_rx,
             forkchoice_state_tx,
-        );
-        task_executor.spawn(engine_api);
+            payload_builder,
+        )
+        .with_bad_blocks(bad_blocks);
+        // payloads are executed and committed on the engine API task
+        task_executor.spawn_critical_blocking("engine api task", engine_api);
         message_tx
     }
 
@@ -428,6 +522,7 @@ impl Command {
         updater: U,
         consensus: &Arc<dyn Consensus>,
         max_block: Option<u64>,
+        bad_blocks: BadBlocks,
     ) -> eyre::Result<Pipeline<Env<WriteMap>, U>>
     where
         H: HeaderDownloader + 'static,
@@ -444,26 +539,43 @@ impl Command {
         }
 
         let factory = reth_executor::Factory::new(Arc::new(self.chain.clone()));
-        let pipeline = builder
-            .with_sync_state_updater(updater.clone())
-            .add_stages(
-                DefaultStages::new(
-                    consensus.clone(),
-                    header_downloader,
-                    body_downloader,
-                    updater,
-                    factory.clone(),
-                )
-                .set(TotalDifficultyStage {
-                    chain_spec: self.chain.clone(),
-                    commit_threshold: stage_conf.total_difficulty.commit_threshold,
-                })
-                .set(SenderRecoveryStage {
-                    commit_threshold: stage_conf.sender_recovery.commit_threshold,
-                })
-                .set(ExecutionStage::new(factory, stage_conf.execution.commit_threshold)),
-            )
-            .build();
+        let mut stages = DefaultStages::new(
+            consensus.clone(),
+            header_downloader,
+            body_downloader,
+            updater.clone(),
+            factory.clone(),
+        )
+        .set(TotalDifficultyStage {
+            chain_spec: self.chain.clone(),
+            commit_threshold: stage_conf.total_difficulty.commit_threshold,
+        })
+        .set(SenderRecoveryStage { commit_threshold: stage_conf.sender_recovery.commit_threshold })
+        .set(
+            ExecutionStage::new(factory.clone(), stage_conf.execution.commit_threshold)
+                .with_bad_blocks(bad_blocks),
+        );
+
+        if stage_conf.index_call_address_history.enabled {
+            debug!(target: "reth::cli", "Enabling the call address history index");
+            stages = stages.add_after(
+                IndexCallAddressHistoryStage::new(
+                    factory,
+                    stage_conf.index_call_address_history.commit_threshold,
+                ),
+                INDEX_ACCOUNT_HISTORY,
+            );
+        }
+
+        if stage_conf.index_log_history.enabled {
+            debug!(target: "reth::cli", "Enabling the log history index");
+            stages = stages.add_after(
+                IndexLogHistoryStage::new(stage_conf.index_log_history.commit_threshold),
+                INDEX_ACCOUNT_HISTORY,
+            );
+        }
+
+        let pipeline = builder.with_sync_state_updater(updater).add_stages(stages).build();
 
         Ok(pipeline)
     }
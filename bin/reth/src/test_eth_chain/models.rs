@@ -73,6 +73,10 @@ pub struct Header {
     pub base_fee_per_gas: Option<JsonU256>,
     /// Withdrawals root.
     pub withdrawals_root: Option<H256>,
+    /// Blob gas used.
+    pub blob_gas_used: Option<JsonU256>,
+    /// Excess blob gas.
+    pub excess_blob_gas: Option<JsonU256>,
 }
 
 impl From<Header> for SealedHeader {
@@ -95,6 +99,8 @@ impl From<Header> for SealedHeader {
             parent_hash: value.parent_hash,
             logs_bloom: value.bloom,
             withdrawals_root: value.withdrawals_root,
+            blob_gas_used: value.blob_gas_used.map(|v| v.0.to::<u64>()),
+            excess_blob_gas: value.excess_blob_gas.map(|v| v.0.to::<u64>()),
         };
         header.seal(value.hash)
     }
@@ -198,6 +204,8 @@ pub enum ForkSpec {
     Merge,
     /// Shanghai
     Shanghai,
+    /// Cancun
+    Cancun,
     /// Merge EOF test
     #[serde(alias = "Merge+3540+3670")]
     MergeEOF,
@@ -237,6 +245,7 @@ impl From<ForkSpec> for ChainSpec {
             ForkSpec::MergeMeterInitCode => spec_builder.paris_activated(),
             ForkSpec::MergePush0 => spec_builder.paris_activated(),
             ForkSpec::Shanghai => spec_builder.shanghai_activated(),
+            ForkSpec::Cancun => spec_builder.cancun_activated(),
             ForkSpec::ByzantiumToConstantinopleAt5 | ForkSpec::Constantinople => {
                 panic!("Overridden with PETERSBURG")
             }
@@ -59,6 +59,30 @@ pub fn validate_header_standalone(
         return Err(ConsensusError::WithdrawalsRootUnexpected)
     }
 
+    // EIP-4844: Shard Blob Transactions
+    if chain_spec.fork(Hardfork::Cancun).active_at_timestamp(header.timestamp) {
+        let blob_gas_used = header.blob_gas_used.ok_or(ConsensusError::BlobGasUsedMissing)?;
+        if header.excess_blob_gas.is_none() {
+            return Err(ConsensusError::ExcessBlobGasMissing)
+        }
+        if blob_gas_used > constants::MAX_BLOB_GAS_PER_BLOCK {
+            return Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+                blob_gas_used,
+                max_blob_gas_per_block: constants::MAX_BLOB_GAS_PER_BLOCK,
+            })
+        }
+        if blob_gas_used % constants::GAS_PER_BLOB != 0 {
+            return Err(ConsensusError::BlobGasUsedNotMultipleOfBlobGasPerBlob {
+                blob_gas_used,
+                blob_gas_per_blob: constants::GAS_PER_BLOB,
+            })
+        }
+    } else if header.blob_gas_used.is_some() {
+        return Err(ConsensusError::BlobGasUsedUnexpected)
+    } else if header.excess_blob_gas.is_some() {
+        return Err(ConsensusError::ExcessBlobGasUnexpected)
+    }
+
     Ok(())
 }
 
@@ -107,6 +131,18 @@ pub fn validate_transaction_regarding_header(
 
             Some(*chain_id)
         }
+        Transaction::Eip4844(tx) => {
+            // EIP-4844: Shard Blob Transactions https://eips.ethereum.org/EIPS/eip-4844
+            //
+            // The cancun check and the blob fee check need the block header and are done in
+            // `validate_all_transaction_regarding_block_and_nonces`.
+            if tx.max_priority_fee_per_gas > tx.max_fee_per_gas {
+                return Err(InvalidTransactionError::PriorityFeeMoreThenMaxFee.into())
+            }
+            tx.ensure_valid_blobs()?;
+
+            Some(tx.chain_id)
+        }
     };
     if let Some(chain_id) = chain_id {
         if chain_id != chain_spec.chain().id() {
@@ -145,6 +181,18 @@ pub fn validate_all_transaction_regarding_block_and_nonces<
             header.base_fee_per_gas,
         )?;
 
+        // EIP-4844: blob transactions need to cover the blob gas price of the block
+        if let Some(max_fee_per_blob_gas) = transaction.max_fee_per_blob_gas() {
+            if !chain_spec.fork(Hardfork::Cancun).active_at_timestamp(header.timestamp) {
+                return Err(ConsensusError::from(InvalidTransactionError::Eip4844Disabled).into())
+            }
+            let blob_gasprice =
+                header.blob_gasprice().ok_or(ConsensusError::ExcessBlobGasMissing)?;
+            if max_fee_per_blob_gas < blob_gasprice {
+                return Err(ConsensusError::from(InvalidTransactionError::BlobFeeCapTooLow).into())
+            }
+        }
+
         // Get nonce, if there is previous transaction from same sender we need
         // to take that nonce.
         let nonce = match account_nonces.entry(transaction.signer()) {
@@ -240,33 +288,19 @@ pub fn validate_block_standalone(
         }
     }
 
-    Ok(())
-}
-
-/// Calculate base fee for next block. EIP-1559 spec
-pub fn calculate_next_block_base_fee(gas_used: u64, gas_limit: u64, base_fee: u64) -> u64 {
-    let gas_target = gas_limit / constants::EIP1559_ELASTICITY_MULTIPLIER;
-
-    if gas_used == gas_target {
-        return base_fee
+    // EIP-4844: the blob gas used in the header has to match the blobs of the transactions
+    if chain_spec.fork(Hardfork::Cancun).active_at_timestamp(block.timestamp) {
+        let header_blob_gas_used = block.blob_gas_used.ok_or(ConsensusError::BlobGasUsedMissing)?;
+        let blob_gas_used = block.body.iter().map(|tx| tx.transaction.blob_gas()).sum::<u64>();
+        if blob_gas_used != header_blob_gas_used {
+            return Err(ConsensusError::BlobGasUsedDiff {
+                got: header_blob_gas_used,
+                expected: blob_gas_used,
+            })
+        }
     }
-    if gas_used > gas_target {
-        let gas_used_delta = gas_used - gas_target;
-        let base_fee_delta = std::cmp::max(
-            1,
-            base_fee as u128 * gas_used_delta as u128 /
-                gas_target as u128 /
-                constants::EIP1559_BASE_FEE_MAX_CHANGE_DENOMINATOR as u128,
-        );
-        base_fee + (base_fee_delta as u64)
-    } else {
-        let gas_used_delta = gas_target - gas_used;
-        let base_fee_per_gas_delta = base_fee as u128 * gas_used_delta as u128 /
-            gas_target as u128 /
-            constants::EIP1559_BASE_FEE_MAX_CHANGE_DENOMINATOR as u128;
 
-        base_fee.saturating_sub(base_fee_per_gas_delta as u64)
-    }
+    Ok(())
 }
 
 /// Validate block in regards to parent
@@ -329,17 +363,27 @@ pub fn validate_header_regarding_parent(
                 constants::EIP1559_INITIAL_BASE_FEE
             } else {
                 // This BaseFeeMissing will not happen as previous blocks are checked to have them.
-                calculate_next_block_base_fee(
-                    parent.gas_used,
-                    parent.gas_limit,
-                    parent.base_fee_per_gas.ok_or(ConsensusError::BaseFeeMissing)?,
-                )
+                parent.next_block_base_fee().ok_or(ConsensusError::BaseFeeMissing)?
             };
         if expected_base_fee != base_fee {
             return Err(ConsensusError::BaseFeeDiff { expected: expected_base_fee, got: base_fee })
         }
     }
 
+    // EIP-4844 check excess blob gas
+    if chain_spec.fork(Hardfork::Cancun).active_at_timestamp(child.timestamp) {
+        let excess_blob_gas = child.excess_blob_gas.ok_or(ConsensusError::ExcessBlobGasMissing)?;
+
+        // The excess blob gas of the parent is zero on the transition block.
+        let expected_excess_blob_gas = parent.next_block_excess_blob_gas().unwrap_or_default();
+        if expected_excess_blob_gas != excess_blob_gas {
+            return Err(ConsensusError::ExcessBlobGasDiff {
+                expected: expected_excess_blob_gas,
+                got: excess_blob_gas,
+            })
+        }
+    }
+
     Ok(())
 }
 
@@ -408,33 +452,6 @@ mod tests {
     };
     use std::ops::RangeBounds;
 
-    #[test]
-    fn calculate_base_fee_success() {
-        let base_fee = [
-            1000000000, 1000000000, 1000000000, 1072671875, 1059263476, 1049238967, 1049238967, 0,
-            1, 2,
-        ];
-        let gas_used = [
-            10000000, 10000000, 10000000, 9000000, 10001000, 0, 10000000, 10000000, 10000000,
-            10000000,
-        ];
-        let gas_limit = [
-            10000000, 12000000, 14000000, 10000000, 14000000, 2000000, 18000000, 18000000,
-            18000000, 18000000,
-        ];
-        let next_base_fee = [
-            1125000000, 1083333333, 1053571428, 1179939062, 1116028649, 918084097, 1063811730, 1,
-            2, 3,
-        ];
-
-        for i in 0..base_fee.len() {
-            assert_eq!(
-                next_base_fee[i],
-                calculate_next_block_base_fee(gas_used[i], gas_limit[i], base_fee[i])
-            );
-        }
-    }
-
     struct Provider {
         is_known: bool,
         parent: Option<Header>,
@@ -525,7 +542,9 @@ mod tests {
             mix_hash: hex!("0000000000000000000000000000000000000000000000000000000000000000").into(),
             nonce: 0x0000000000000000,
             base_fee_per_gas: 0x28f0001df.into(),
-            withdrawals_root: None
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
         };
         // size: 0x9b5
 
@@ -673,4 +692,87 @@ mod tests {
 
         assert_eq!(validate_header_standalone(&header, &chain_spec), Ok(()));
     }
+
+    #[test]
+    fn cancun_blob_gas_header_fields() {
+        let chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+
+        let header = Header {
+            base_fee_per_gas: Some(1337u64),
+            withdrawals_root: Some(proofs::calculate_withdrawals_root(&[])),
+            blob_gas_used: Some(constants::GAS_PER_BLOB),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(validate_header_standalone(&header.clone().seal_slow(), &chain_spec), Ok(()));
+
+        let missing = Header { excess_blob_gas: None, ..header.clone() }.seal_slow();
+        assert_eq!(
+            validate_header_standalone(&missing, &chain_spec),
+            Err(ConsensusError::ExcessBlobGasMissing)
+        );
+
+        let not_multiple =
+            Header { blob_gas_used: Some(constants::GAS_PER_BLOB + 1), ..header.clone() }
+                .seal_slow();
+        assert_matches!(
+            validate_header_standalone(&not_multiple, &chain_spec),
+            Err(ConsensusError::BlobGasUsedNotMultipleOfBlobGasPerBlob { .. })
+        );
+
+        let too_much = Header {
+            blob_gas_used: Some(constants::MAX_BLOB_GAS_PER_BLOCK + constants::GAS_PER_BLOB),
+            ..header.clone()
+        }
+        .seal_slow();
+        assert_matches!(
+            validate_header_standalone(&too_much, &chain_spec),
+            Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock { .. })
+        );
+
+        // blob gas fields are not allowed before cancun
+        let shanghai = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        assert_eq!(
+            validate_header_standalone(&header.seal_slow(), &shanghai),
+            Err(ConsensusError::BlobGasUsedUnexpected)
+        );
+    }
+
+    #[test]
+    fn cancun_excess_blob_gas_regarding_parent() {
+        let chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+
+        let parent = Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1337u64),
+            blob_gas_used: Some(constants::MAX_BLOB_GAS_PER_BLOCK),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        };
+        let child = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: parent.next_block_base_fee(),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(constants::TARGET_BLOB_GAS_PER_BLOCK),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_header_regarding_parent(
+                &parent.clone().seal_slow(),
+                &child.clone().seal_slow(),
+                &chain_spec
+            ),
+            Ok(())
+        );
+
+        let child = Header { excess_blob_gas: Some(0), ..child }.seal_slow();
+        assert_eq!(
+            validate_header_regarding_parent(&parent.seal_slow(), &child, &chain_spec),
+            Err(ConsensusError::ExcessBlobGasDiff {
+                expected: constants::TARGET_BLOB_GAS_PER_BLOCK,
+                got: 0
+            })
+        );
+    }
 }
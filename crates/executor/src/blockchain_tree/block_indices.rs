@@ -0,0 +1,153 @@
+//! Indices of the blocks in the blockchain tree.
+
+use super::chain::{BlockChainId, Chain};
+use reth_primitives::{BlockHash, BlockNumber};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Internal indices of the blocks and chains.
+///
+/// This is the main connection between blocks, side chains and the canonical chain.
+#[derive(Debug, Default)]
+pub struct BlockIndices {
+    /// The last canonical blocks, side chains can only fork off these blocks.
+    canonical_chain: BTreeMap<BlockNumber, BlockHash>,
+    /// Fork block hash to the hashes of the first blocks of the chains forking off it.
+    ///
+    /// Needed to find and remove all descendants when a chain is discarded.
+    fork_to_child: HashMap<BlockHash, HashSet<BlockHash>>,
+    /// Side chain block hashes and the chain they belong to.
+    blocks_to_chain: HashMap<BlockHash, BlockChainId>,
+    /// Block number to the hashes of the side chain blocks with that number.
+    index_number_to_block: HashMap<BlockNumber, HashSet<BlockHash>>,
+}
+
+impl BlockIndices {
+    /// Create new block indices with the given canonical blocks.
+    pub fn new(canonical_chain: BTreeMap<BlockNumber, BlockHash>) -> Self {
+        Self { canonical_chain, ..Default::default() }
+    }
+
+    /// Returns the canonical blocks known to the tree.
+    pub fn canonical_chain(&self) -> &BTreeMap<BlockNumber, BlockHash> {
+        &self.canonical_chain
+    }
+
+    /// Returns the canonical tip.
+    pub fn canonical_tip(&self) -> Option<(BlockNumber, BlockHash)> {
+        self.canonical_chain.iter().last().map(|(number, hash)| (*number, *hash))
+    }
+
+    /// Returns the hash of the canonical block with the given number.
+    pub fn canonical_hash(&self, block_number: BlockNumber) -> Option<BlockHash> {
+        self.canonical_chain.get(&block_number).copied()
+    }
+
+    /// Returns `true` if the block is one of the canonical blocks known to the tree.
+    pub fn is_block_hash_canonical(&self, block_hash: &BlockHash) -> bool {
+        self.canonical_chain.values().any(|hash| hash == block_hash)
+    }
+
+    /// Returns the id of the side chain the block belongs to.
+    pub fn get_blocks_chain_id(&self, block_hash: &BlockHash) -> Option<BlockChainId> {
+        self.blocks_to_chain.get(block_hash).copied()
+    }
+
+    /// Returns the hashes of all side chain blocks with the given number.
+    pub fn pending_blocks(&self, block_number: BlockNumber) -> Vec<BlockHash> {
+        self.index_number_to_block
+            .get(&block_number)
+            .map(|hashes| hashes.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the ids of the side chains that fork off the given block.
+    pub fn fork_children(&self, block_hash: &BlockHash) -> Vec<BlockChainId> {
+        self.fork_to_child
+            .get(block_hash)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| self.get_blocks_chain_id(child))
+            .collect()
+    }
+
+    /// Returns `true` if there are side chains that fork off the given block.
+    pub fn has_fork_children(&self, block_hash: &BlockHash) -> bool {
+        self.fork_to_child.get(block_hash).map_or(false, |children| !children.is_empty())
+    }
+
+    /// Index a block that was appended to a side chain.
+    pub fn insert_non_fork_block(
+        &mut self,
+        block_number: BlockNumber,
+        block_hash: BlockHash,
+        chain_id: BlockChainId,
+    ) {
+        self.index_number_to_block.entry(block_number).or_default().insert(block_hash);
+        self.blocks_to_chain.insert(block_hash, chain_id);
+    }
+
+    /// Index all blocks of a side chain and its fork.
+    pub fn insert_chain(&mut self, chain_id: BlockChainId, chain: &Chain) {
+        for (number, block) in chain.blocks() {
+            self.insert_non_fork_block(*number, block.hash(), chain_id);
+        }
+        self.fork_to_child.entry(chain.fork_block().hash).or_default().insert(chain.first().hash());
+    }
+
+    /// Remove the blocks of a side chain and its fork from the indices.
+    ///
+    /// Chains that fork off the blocks of the chain are kept.
+    pub fn remove_chain(&mut self, chain: &Chain) {
+        let fork_hash = chain.fork_block().hash;
+        if let Some(children) = self.fork_to_child.get_mut(&fork_hash) {
+            children.remove(&chain.first().hash());
+            if children.is_empty() {
+                self.fork_to_child.remove(&fork_hash);
+            }
+        }
+        for (number, block) in chain.blocks() {
+            let hash = block.hash();
+            self.blocks_to_chain.remove(&hash);
+            if let Some(hashes) = self.index_number_to_block.get_mut(number) {
+                hashes.remove(&hash);
+                if hashes.is_empty() {
+                    self.index_number_to_block.remove(number);
+                }
+            }
+        }
+    }
+
+    /// Remove the fork links of the given block and return the ids of the chains forking off it.
+    pub fn take_fork_children(&mut self, block_hash: &BlockHash) -> Vec<BlockChainId> {
+        self.fork_to_child
+            .remove(block_hash)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| self.get_blocks_chain_id(&child))
+            .collect()
+    }
+
+    /// Insert blocks that became canonical.
+    pub fn canonicalize_blocks(
+        &mut self,
+        blocks: impl IntoIterator<Item = (BlockNumber, BlockHash)>,
+    ) {
+        self.canonical_chain.extend(blocks);
+    }
+
+    /// Remove all canonical blocks above the given block number.
+    pub fn unwind_canonical_chain(&mut self, unwind_to: BlockNumber) {
+        // the blocks above `unwind_to` are returned by `split_off` and dropped.
+        let _ = self.canonical_chain.split_off(&(unwind_to + 1));
+    }
+
+    /// Remove all canonical blocks below the given block number, no side chain can fork off them
+    /// anymore.
+    ///
+    /// Returns the ids of the side chains that fork off the removed blocks.
+    pub fn remove_canonical_below(&mut self, block_number: BlockNumber) -> Vec<BlockChainId> {
+        let kept = self.canonical_chain.split_off(&block_number);
+        let removed = std::mem::replace(&mut self.canonical_chain, kept);
+        removed.values().flat_map(|hash| self.take_fork_children(hash)).collect()
+    }
+}
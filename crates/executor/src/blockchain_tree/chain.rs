@@ -0,0 +1,249 @@
+//! A chain of side chain blocks that is kept in memory.
+
+use super::{
+    externals::TreeExternals,
+    post_state::{PostState, PostStateProvider},
+};
+use crate::execution_result::{AccountInfoChangeSet, ExecutionResult};
+use reth_db::database::Database;
+use reth_interfaces::{
+    consensus::Consensus, events::CanonicalBlock, executor::Error as ExecError, Result,
+};
+use reth_primitives::{
+    Address, BlockHash, BlockNumber, SealedBlockWithSenders, SealedHeader, U256,
+};
+use reth_provider::{BlockExecutor, ExecutorFactory, StateProvider};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The id of a side chain in the tree.
+pub type BlockChainId = u64;
+
+/// The canonical or side chain block a chain forks off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForkBlock {
+    /// Number of the fork block.
+    pub number: BlockNumber,
+    /// Hash of the fork block.
+    pub hash: BlockHash,
+}
+
+/// A chain of executed blocks that are not part of the canonical chain.
+///
+/// The chain holds the execution result of every block, which is needed to commit the chain to
+/// the database, and the accumulated [PostState] used to execute blocks on top of it.
+#[derive(Clone, Debug, Default)]
+pub struct Chain {
+    /// The state changes of the side chain blocks between the canonical chain and the fork block
+    /// of this chain. Empty if the chain forks off the canonical chain.
+    base_state: PostState,
+    /// `base_state` with the changes of all blocks in this chain applied.
+    state: PostState,
+    /// Execution results of the blocks, the n-th result belongs to the n-th block.
+    changesets: Vec<ExecutionResult>,
+    /// Blocks in this chain.
+    blocks: BTreeMap<BlockNumber, SealedBlockWithSenders>,
+}
+
+impl Chain {
+    /// Create a chain from executed blocks on top of the given base state.
+    pub fn new(
+        base_state: PostState,
+        blocks: impl IntoIterator<Item = (SealedBlockWithSenders, ExecutionResult)>,
+    ) -> Self {
+        let mut chain = Self { state: base_state.clone(), base_state, ..Default::default() };
+        for (block, changeset) in blocks {
+            chain.push(block, changeset);
+        }
+        chain
+    }
+
+    /// Returns the blocks of the chain.
+    pub fn blocks(&self) -> &BTreeMap<BlockNumber, SealedBlockWithSenders> {
+        &self.blocks
+    }
+
+    /// Returns the execution results of the blocks.
+    pub fn changesets(&self) -> &[ExecutionResult] {
+        &self.changesets
+    }
+
+    /// Returns the state at the tip of the chain, including the state of its non canonical
+    /// ancestors.
+    pub fn state(&self) -> &PostState {
+        &self.state
+    }
+
+    /// Consume the chain and return its blocks with their execution results, in order.
+    pub fn into_inner(self) -> Vec<(SealedBlockWithSenders, ExecutionResult)> {
+        self.blocks.into_values().zip(self.changesets).collect()
+    }
+
+    /// Returns the blocks of the chain together with the receipts of their transactions.
+    pub fn canonical_blocks(&self) -> Vec<CanonicalBlock> {
+        self.blocks
+            .values()
+            .zip(self.changesets.iter())
+            .map(|(block, changeset)| CanonicalBlock {
+                block: block.block.clone(),
+                receipts: changeset.tx_changesets.iter().map(|tx| tx.receipt.clone()).collect(),
+                changed_accounts: changed_accounts(changeset),
+            })
+            .collect()
+    }
+
+    /// Returns the block the chain forks off.
+    pub fn fork_block(&self) -> ForkBlock {
+        let first = self.first();
+        ForkBlock { number: first.number.saturating_sub(1), hash: first.parent_hash }
+    }
+
+    /// Returns the first block of the chain.
+    pub fn first(&self) -> &SealedBlockWithSenders {
+        self.blocks.values().next().expect("Chain has at least one block")
+    }
+
+    /// Returns the tip of the chain.
+    pub fn tip(&self) -> &SealedBlockWithSenders {
+        self.blocks.values().last().expect("Chain has at least one block")
+    }
+
+    /// Returns the number of the block with the given hash, if it is part of the chain.
+    pub fn block_number(&self, block_hash: BlockHash) -> Option<BlockNumber> {
+        self.blocks.values().find(|block| block.hash() == block_hash).map(|block| block.number)
+    }
+
+    /// Returns the hashes of all blocks in the chain.
+    pub fn block_hashes(&self) -> BTreeMap<BlockNumber, BlockHash> {
+        self.blocks.iter().map(|(number, block)| (*number, block.hash())).collect()
+    }
+
+    /// Returns the state after the given block, or `None` if the block is not part of the chain.
+    pub fn state_at(&self, block_hash: BlockHash) -> Option<PostState> {
+        if self.tip().hash() == block_hash {
+            return Some(self.state.clone())
+        }
+        let number = self.block_number(block_hash)?;
+        let mut state = self.base_state.clone();
+        let len = self.blocks.range(..=number).count();
+        for changeset in self.changesets.iter().take(len) {
+            state.apply_execution_result(changeset);
+        }
+        Some(state)
+    }
+
+    /// Replace the state of the non canonical ancestors of this chain and recompute its state.
+    pub fn rebase(&mut self, base_state: PostState) {
+        let mut state = base_state.clone();
+        for changeset in self.changesets.iter() {
+            state.apply_execution_result(changeset);
+        }
+        self.base_state = base_state;
+        self.state = state;
+    }
+
+    /// Split the chain at the given block.
+    ///
+    /// Returns the chain up to and including the block and, if the block is not the tip, the
+    /// chain of the blocks after it, which then forks off the block.
+    ///
+    /// # Panics
+    ///
+    /// If the block is not part of the chain.
+    pub fn split_at(self, block_hash: BlockHash) -> (Chain, Option<Chain>) {
+        let number = self.block_number(block_hash).expect("Block is part of the chain");
+        if number == self.tip().number {
+            return (self, None)
+        }
+
+        let Chain { base_state, state, mut changesets, mut blocks } = self;
+        let higher_blocks = blocks.split_off(&(number + 1));
+        let higher_changesets = changesets.split_off(blocks.len());
+
+        let lower = Chain::new(base_state, blocks.into_values().zip(changesets));
+        let higher = Chain {
+            base_state: lower.state.clone(),
+            state,
+            changesets: higher_changesets,
+            blocks: higher_blocks,
+        };
+        (lower, Some(higher))
+    }
+
+    /// Append the chain that forks off the tip of this chain.
+    ///
+    /// # Panics
+    ///
+    /// If the chain does not fork off the tip of this chain.
+    pub fn append_chain(&mut self, chain: Chain) {
+        assert_eq!(chain.fork_block().hash, self.tip().hash(), "Chain forks off the tip");
+        self.state = chain.state;
+        self.changesets.extend(chain.changesets);
+        self.blocks.extend(chain.blocks);
+    }
+
+    /// Append an executed block to the chain.
+    pub fn push(&mut self, block: SealedBlockWithSenders, changeset: ExecutionResult) {
+        self.state.apply_execution_result(&changeset);
+        self.changesets.push(changeset);
+        self.blocks.insert(block.number, block);
+    }
+}
+
+/// Returns the accounts whose info was changed by the transactions or the post block changes of
+/// the block, in ascending order.
+fn changed_accounts(result: &ExecutionResult) -> Vec<Address> {
+    result
+        .tx_changesets
+        .iter()
+        .flat_map(|tx| tx.changeset.iter().map(|(address, change)| (address, &change.account)))
+        .chain(result.block_changesets.iter())
+        .filter(|(_, change)| **change != AccountInfoChangeSet::NoChange)
+        .map(|(address, _)| *address)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Validate the block against its parent and execute it on top of the given state.
+///
+/// The `provider` is the state of the canonical block the side chain forks off, `post_state` is
+/// the state of the side chain up to and including the parent block and `block_hashes` are the
+/// hashes of the side chain blocks.
+///
+/// NOTE: The state root is verified separately by [verify_state_root], as it needs the execution
+/// results of the side chain blocks.
+pub(crate) fn validate_and_execute<DB, C: Consensus, EF: ExecutorFactory, SP: StateProvider>(
+    block: &SealedBlockWithSenders,
+    parent: &SealedHeader,
+    total_difficulty: U256,
+    post_state: &PostState,
+    block_hashes: BTreeMap<BlockNumber, BlockHash>,
+    provider: SP,
+    externals: &TreeExternals<DB, C, EF>,
+) -> Result<ExecutionResult> {
+    externals.consensus.validate_header(&block.header, total_difficulty)?;
+    externals.consensus.pre_validate_header(&block.header, parent)?;
+    externals.consensus.pre_validate_block(&block.block)?;
+
+    let (sealed, senders) = block.clone().into_components();
+    let provider = PostStateProvider::new(post_state, provider, block_hashes);
+    let mut executor = externals.executor_factory.with_sp(provider);
+    Ok(executor.execute_and_verify_receipt(&sealed.unseal(), total_difficulty, Some(senders))?)
+}
+
+/// Verify the state root of the executed block.
+///
+/// `canonical_fork` is the canonical block the side chain forks off and `changesets` are the
+/// execution results of the side chain blocks up to and including the block, in order.
+pub(crate) fn verify_state_root<'a, DB: Database, C, EF>(
+    block: &SealedBlockWithSenders,
+    canonical_fork: BlockNumber,
+    changesets: impl IntoIterator<Item = (BlockNumber, &'a ExecutionResult)>,
+    externals: &TreeExternals<DB, C, EF>,
+) -> Result<()> {
+    let state_root = externals.state_root_with_execution_results(canonical_fork, changesets)?;
+    if state_root != block.state_root {
+        return Err(ExecError::StateRootDiff { got: state_root, expected: block.state_root }.into())
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+//! Blockchain tree configuration
+
+/// The configuration for the blockchain tree.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockchainTreeConfig {
+    /// Number of blocks after the canonical tip that the tree accepts.
+    max_blocks_in_chain: u64,
+    /// The number of blocks that can be re-orged, side chains can only fork off the last
+    /// `max_reorg_depth` canonical blocks.
+    max_reorg_depth: u64,
+}
+
+impl Default for BlockchainTreeConfig {
+    fn default() -> Self {
+        // The maximum number of blocks that can be reorged is one epoch of the beacon chain.
+        Self { max_blocks_in_chain: 65, max_reorg_depth: 64 }
+    }
+}
+
+impl BlockchainTreeConfig {
+    /// Create tree configuration.
+    ///
+    /// # Panics
+    ///
+    /// If `max_reorg_depth` is larger than `max_blocks_in_chain`.
+    pub fn new(max_reorg_depth: u64, max_blocks_in_chain: u64) -> Self {
+        assert!(
+            max_reorg_depth <= max_blocks_in_chain,
+            "Side chain size should be more than finalization window"
+        );
+        Self { max_blocks_in_chain, max_reorg_depth }
+    }
+
+    /// Return the maximum reorg depth.
+    pub fn max_reorg_depth(&self) -> u64 {
+        self.max_reorg_depth
+    }
+
+    /// Return the maximum number of blocks after the canonical tip.
+    pub fn max_blocks_in_chain(&self) -> u64 {
+        self.max_blocks_in_chain
+    }
+}
@@ -0,0 +1,102 @@
+//! Blockchain tree externals.
+
+use crate::execution_result::ExecutionResult;
+use reth_db::{
+    cursor::DbCursorRO,
+    database::{Database, DatabaseGAT},
+    tables,
+    transaction::DbTx,
+};
+use reth_interfaces::{provider::ProviderError, Result};
+use reth_primitives::{BlockNumber, ChainSpec, Hardfork, SealedHeader, H256, U256};
+use reth_provider::{trie::DBTrieLoader, HistoricalStateProvider};
+use std::sync::Arc;
+
+/// Container for the external abstractions the tree needs: the database, the consensus engine
+/// and the executor factory.
+#[derive(Debug)]
+pub struct TreeExternals<DB, C, EF> {
+    /// The database, used to commit the canonical chain or unwind it.
+    pub db: DB,
+    /// The consensus engine, used to validate blocks.
+    pub consensus: C,
+    /// The executor factory, used to execute blocks.
+    pub executor_factory: EF,
+    /// The chain spec.
+    pub chain_spec: Arc<ChainSpec>,
+}
+
+impl<DB, C, EF> TreeExternals<DB, C, EF> {
+    /// Create new tree externals.
+    pub fn new(db: DB, consensus: C, executor_factory: EF, chain_spec: Arc<ChainSpec>) -> Self {
+        Self { db, consensus, executor_factory, chain_spec }
+    }
+}
+
+impl<DB: Database, C, EF> TreeExternals<DB, C, EF> {
+    /// Returns the state provider at the end of the canonical block.
+    pub(crate) fn historical_provider(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<HistoricalStateProvider<'_, <DB as DatabaseGAT<'_>>::TX>> {
+        let tx = self.db.tx()?;
+        let transition = tx
+            .get::<tables::BlockTransitionIndex>(block_number)?
+            .ok_or(ProviderError::BlockTransition { block_number })?;
+        Ok(HistoricalStateProvider::new(tx, transition))
+    }
+
+    /// Returns the sealed header and total difficulty of the canonical block.
+    pub(crate) fn canonical_header(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<(SealedHeader, U256)> {
+        let tx = self.db.tx()?;
+        let hash = tx
+            .get::<tables::CanonicalHeaders>(block_number)?
+            .ok_or(ProviderError::CanonicalHeader { block_number })?;
+        let header = tx
+            .get::<tables::Headers>(block_number)?
+            .ok_or(ProviderError::Header { number: block_number })?;
+        let td = tx
+            .get::<tables::HeaderTD>(block_number)?
+            .ok_or(ProviderError::TotalDifficulty { number: block_number })?;
+        Ok((header.seal(hash), td.into()))
+    }
+
+    /// Calculates the state root after the execution results, applied in order on top of the
+    /// state at the end of the canonical block.
+    ///
+    /// The state tries in the database hold the state of the canonical tip, the changes of the
+    /// canonical blocks above the given block are reverted in memory. The execution results are
+    /// paired with the numbers of their blocks.
+    pub(crate) fn state_root_with_execution_results<'a>(
+        &self,
+        block_number: BlockNumber,
+        results: impl IntoIterator<Item = (BlockNumber, &'a ExecutionResult)>,
+    ) -> Result<H256> {
+        let tx = self.db.tx()?;
+        let (tip, _) = tx
+            .cursor_read::<tables::CanonicalHeaders>()?
+            .last()?
+            .ok_or(ProviderError::HeaderNotFound)?;
+        let root = tx
+            .get::<tables::Headers>(tip)?
+            .ok_or(ProviderError::Header { number: tip })?
+            .state_root;
+        let transition = |block_number: BlockNumber| -> Result<_> {
+            Ok(tx
+                .get::<tables::BlockTransitionIndex>(block_number)?
+                .ok_or(ProviderError::BlockTransition { block_number })?)
+        };
+        let tid_range = transition(block_number)?..transition(tip)?;
+
+        let state_clear_fork = self.chain_spec.fork(Hardfork::SpuriousDragon);
+        let results = results
+            .into_iter()
+            .map(|(number, result)| (result, state_clear_fork.active_at_block(number)));
+        DBTrieLoader::default()
+            .calculate_historical_root_with_execution_results(&tx, root, tid_range, results)
+            .map_err(|err| ProviderError::StateRoot(err.to_string()).into())
+    }
+}
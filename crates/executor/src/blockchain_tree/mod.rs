@@ -0,0 +1,873 @@
+//! Implementation of the [BlockchainTree].
+//!
+//! The tree keeps executed blocks that are not part of the canonical chain in memory and can make
+//! any of them canonical by writing their changesets to the database, unwinding the current
+//! canonical blocks if needed.
+//!
+//! ```text
+//!                       B4 (side chain 2)
+//!                       |
+//!      B2 (side chain 1) - B3
+//!     /
+//!  C1 - C2 - C3 (canonical tip)
+//! ```
+
+pub mod block_indices;
+pub mod chain;
+pub mod config;
+pub mod externals;
+pub mod post_state;
+pub mod shareable;
+
+pub use block_indices::BlockIndices;
+pub use chain::{BlockChainId, Chain, ForkBlock};
+pub use config::BlockchainTreeConfig;
+pub use externals::TreeExternals;
+pub use post_state::{PostState, PostStateProvider};
+pub use shareable::ShareableBlockchainTree;
+
+use crate::execution_result::ExecutionResult;
+use chain::{validate_and_execute, verify_state_root};
+use reth_db::{cursor::DbCursorRO, database::Database, tables, transaction::DbTx};
+use reth_interfaces::{
+    consensus::Consensus,
+    events::{
+        CanonStateNotification, CanonStateNotifications, CanonicalBlock, NewBlockNotification,
+        NewBlockNotifications,
+    },
+    executor::Error as ExecError,
+    Result,
+};
+use reth_primitives::{BlockHash, BlockNumber, SealedBlock, SealedBlockWithSenders, U256};
+use reth_provider::{ExecutorFactory, Transaction};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tracing::{debug, info};
+
+/// Tree of side chains that fork off the last canonical blocks.
+///
+/// New blocks are validated and executed on top of the canonical chain or any side chain they
+/// extend. The execution results are kept in memory until a block is made canonical by
+/// [BlockchainTree::make_canonical], which writes the changesets of its chain to the database. If
+/// the chain forks off below the canonical tip, the canonical blocks above the fork are unwound
+/// first and kept in the tree as a side chain.
+pub struct BlockchainTree<DB, C, EF> {
+    /// The side chains, by id.
+    chains: HashMap<BlockChainId, Chain>,
+    /// The id assigned to the next side chain.
+    block_chain_id_generator: u64,
+    /// Indices of the blocks, their chains and the canonical chain.
+    block_indices: BlockIndices,
+    /// The database, consensus and executor factory.
+    externals: TreeExternals<DB, C, EF>,
+    /// The tree configuration.
+    config: BlockchainTreeConfig,
+    /// Listeners that are notified when the canonical chain changed.
+    canon_state_listeners: Vec<UnboundedSender<CanonStateNotification>>,
+    /// Listeners that are notified of every new canonical block.
+    new_block_listeners: Vec<UnboundedSender<NewBlockNotification>>,
+}
+
+impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF> {
+    /// Create a new tree on top of the canonical chain in the database.
+    pub fn new(externals: TreeExternals<DB, C, EF>, config: BlockchainTreeConfig) -> Result<Self> {
+        let canonical_chain = Self::read_canonical_chain(&externals, &config)?;
+        Ok(Self {
+            chains: Default::default(),
+            block_chain_id_generator: 0,
+            block_indices: BlockIndices::new(canonical_chain),
+            externals,
+            config,
+            canon_state_listeners: Vec::new(),
+            new_block_listeners: Vec::new(),
+        })
+    }
+
+    /// Read the last `max_reorg_depth` canonical block hashes and the block below them.
+    fn read_canonical_chain(
+        externals: &TreeExternals<DB, C, EF>,
+        config: &BlockchainTreeConfig,
+    ) -> Result<BTreeMap<BlockNumber, BlockHash>> {
+        let tx = externals.db.tx()?;
+        let canonical_chain = tx
+            .cursor_read::<tables::CanonicalHeaders>()?
+            .walk_back(None)?
+            .take(config.max_reorg_depth() as usize + 1)
+            .collect::<std::result::Result<BTreeMap<_, _>, _>>()?;
+        Ok(canonical_chain)
+    }
+
+    /// Reload the canonical chain if the database moved on without the tree, e.g. because the
+    /// pipeline synced or unwound it.
+    ///
+    /// The side chains are discarded, their blocks can be inserted again.
+    pub fn restore_canonical_hashes(&mut self) -> Result<()> {
+        let db_tip = self.externals.db.tx()?.cursor_read::<tables::CanonicalHeaders>()?.last()?;
+        if db_tip == self.block_indices.canonical_tip() {
+            return Ok(())
+        }
+
+        debug!(target: "blockchain_tree", ?db_tip, "Restoring canonical hashes");
+        let canonical_chain = Self::read_canonical_chain(&self.externals, &self.config)?;
+        self.chains.clear();
+        self.block_indices = BlockIndices::new(canonical_chain);
+        Ok(())
+    }
+
+    /// Returns a receiver that is notified whenever blocks are made canonical or the canonical
+    /// chain is reorged.
+    pub fn subscribe_canon_state(&mut self) -> CanonStateNotifications {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.canon_state_listeners.push(tx);
+        rx
+    }
+
+    /// Returns a receiver that is notified of every block that is made canonical, in ascending
+    /// order.
+    pub fn subscribe_new_blocks(&mut self) -> NewBlockNotifications {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.new_block_listeners.push(tx);
+        rx
+    }
+
+    /// Returns the side chains.
+    pub fn chains(&self) -> &HashMap<BlockChainId, Chain> {
+        &self.chains
+    }
+
+    /// Returns the block indices.
+    pub fn block_indices(&self) -> &BlockIndices {
+        &self.block_indices
+    }
+
+    /// Returns the side chain block with the given hash.
+    pub fn block_by_hash(&self, block_hash: BlockHash) -> Option<&SealedBlockWithSenders> {
+        let chain_id = self.block_indices.get_blocks_chain_id(&block_hash)?;
+        let chain = self.chains.get(&chain_id)?;
+        let number = chain.block_number(block_hash)?;
+        chain.blocks().get(&number)
+    }
+
+    /// Recover the senders of the block and insert it into the tree.
+    ///
+    /// See [BlockchainTree::insert_block_with_senders].
+    pub fn insert_block(&mut self, block: SealedBlock) -> Result<()> {
+        let block = block.seal_with_senders().ok_or(ExecError::SenderRecoveryError)?;
+        self.insert_block_with_senders(block)
+    }
+
+    /// Validate and execute the block on top of its parent and insert it into the tree.
+    ///
+    /// The parent must be one of the last canonical blocks or a block in the tree. If the parent
+    /// is the tip of a side chain the block extends that chain, otherwise a new side chain is
+    /// created. Blocks that are already known are ignored.
+    pub fn insert_block_with_senders(&mut self, block: SealedBlockWithSenders) -> Result<()> {
+        self.restore_canonical_hashes()?;
+        let block_hash = block.hash();
+        let block_number = block.number;
+
+        if self.block_indices.get_blocks_chain_id(&block_hash).is_some() ||
+            self.block_indices.is_block_hash_canonical(&block_hash)
+        {
+            return Ok(())
+        }
+
+        let (lowest_canonical, _) = self.lowest_canonical_block();
+        if block_number <= lowest_canonical {
+            return Err(ExecError::PendingBlockIsFinalized {
+                block_hash,
+                block_number,
+                last_finalized: lowest_canonical,
+            }
+            .into())
+        }
+
+        let (canonical_tip, _) = self.canonical_tip();
+        if block_number > canonical_tip + self.config.max_blocks_in_chain() {
+            return Err(ExecError::PendingBlockIsInFuture {
+                block_hash,
+                block_number,
+                canonical_tip,
+            }
+            .into())
+        }
+
+        if let Some(chain_id) = self.block_indices.get_blocks_chain_id(&block.parent_hash) {
+            return self.fork_side_chain(block, chain_id)
+        }
+
+        if self.block_indices.canonical_hash(block_number - 1) == Some(block.parent_hash) {
+            return self.fork_canonical_chain(block)
+        }
+
+        Err(ExecError::BlockParentUnknown {
+            block_hash,
+            block_number,
+            parent_hash: block.parent_hash,
+        }
+        .into())
+    }
+
+    /// Create a new side chain with the block, which extends a canonical block.
+    fn fork_canonical_chain(&mut self, block: SealedBlockWithSenders) -> Result<()> {
+        let parent_number = block.number - 1;
+        let (parent, parent_td) = self.externals.canonical_header(parent_number)?;
+        let provider = self.externals.historical_provider(parent_number)?;
+
+        let post_state = PostState::default();
+        let changeset = validate_and_execute(
+            &block,
+            &parent,
+            parent_td + block.difficulty,
+            &post_state,
+            BTreeMap::new(),
+            provider,
+            &self.externals,
+        )?;
+        verify_state_root(&block, parent_number, [(block.number, &changeset)], &self.externals)?;
+
+        debug!(target: "blockchain_tree", hash = ?block.hash(), number = block.number, "Forked canonical chain");
+        self.insert_chain(Chain::new(post_state, [(block, changeset)]));
+        Ok(())
+    }
+
+    /// Insert the block into the side chain that contains its parent.
+    ///
+    /// If the parent is the tip of the side chain the block is appended to it, otherwise a new
+    /// side chain that forks off the parent is created.
+    fn fork_side_chain(
+        &mut self,
+        block: SealedBlockWithSenders,
+        chain_id: BlockChainId,
+    ) -> Result<()> {
+        let parent_hash = block.parent_hash;
+        let (changeset, fork_state) = {
+            let SideChainAncestry { canonical_fork, block_hashes, changesets, total_difficulty } =
+                self.side_chain_ancestry(parent_hash)?;
+            let chain = self
+                .chains
+                .get(&chain_id)
+                .ok_or(ExecError::BlockHashNotFoundInChain { block_hash: parent_hash })?;
+            let parent = chain
+                .blocks()
+                .get(&(block.number - 1))
+                .filter(|parent| parent.hash() == parent_hash)
+                .ok_or(ExecError::BlockHashNotFoundInChain { block_hash: parent_hash })?;
+
+            let is_tip = chain.tip().hash() == parent_hash;
+            let post_state = if is_tip {
+                Cow::Borrowed(chain.state())
+            } else {
+                Cow::Owned(chain.state_at(parent_hash).expect("Parent is part of the chain"))
+            };
+
+            let provider = self.externals.historical_provider(canonical_fork.number)?;
+            let changeset = validate_and_execute(
+                &block,
+                &parent.header,
+                total_difficulty + block.difficulty,
+                &post_state,
+                block_hashes,
+                provider,
+                &self.externals,
+            )?;
+            let changesets = changesets.into_iter().chain([(block.number, &changeset)]);
+            verify_state_root(&block, canonical_fork.number, changesets, &self.externals)?;
+            (changeset, (!is_tip).then(|| post_state.into_owned()))
+        };
+
+        match fork_state {
+            None => {
+                debug!(target: "blockchain_tree", hash = ?block.hash(), number = block.number, chain_id, "Appended block to side chain");
+                let (number, hash) = (block.number, block.hash());
+                self.chains.get_mut(&chain_id).expect("Chain exists").push(block, changeset);
+                self.block_indices.insert_non_fork_block(number, hash, chain_id);
+            }
+            Some(fork_state) => {
+                debug!(target: "blockchain_tree", hash = ?block.hash(), number = block.number, chain_id, "Forked side chain");
+                self.insert_chain(Chain::new(fork_state, [(block, changeset)]));
+            }
+        }
+        Ok(())
+    }
+
+    /// Walk the side chains from the given block down to the canonical chain.
+    fn side_chain_ancestry(&self, block_hash: BlockHash) -> Result<SideChainAncestry<'_>> {
+        let mut block_hashes = BTreeMap::new();
+        let mut changesets = BTreeMap::new();
+        let mut difficulty = U256::ZERO;
+        let mut current = block_hash;
+        let mut fork = None;
+
+        while let Some(chain_id) = self.block_indices.get_blocks_chain_id(&current) {
+            let chain = self
+                .chains
+                .get(&chain_id)
+                .ok_or(ExecError::BlockHashNotFoundInChain { block_hash: current })?;
+            let number = chain
+                .block_number(current)
+                .ok_or(ExecError::BlockHashNotFoundInChain { block_hash: current })?;
+            for ((number, block), changeset) in
+                chain.blocks().range(..=number).zip(chain.changesets())
+            {
+                block_hashes.insert(*number, block.hash());
+                changesets.insert(*number, changeset);
+                difficulty += block.difficulty;
+            }
+            let chain_fork = chain.fork_block();
+            current = chain_fork.hash;
+            fork = Some(chain_fork);
+        }
+
+        let canonical_fork = fork.ok_or(ExecError::BlockHashNotFoundInChain { block_hash })?;
+        let (_, fork_td) = self.externals.canonical_header(canonical_fork.number)?;
+        Ok(SideChainAncestry {
+            canonical_fork,
+            block_hashes,
+            changesets,
+            total_difficulty: fork_td + difficulty,
+        })
+    }
+
+    /// Make the block and all its ancestors canonical.
+    ///
+    /// The side chains leading to the block are merged and written to the database. If they fork
+    /// off below the canonical tip, the canonical blocks above the fork are unwound first and kept
+    /// in the tree as a side chain. Blocks that are already canonical are ignored.
+    pub fn make_canonical(&mut self, block_hash: &BlockHash) -> Result<()> {
+        self.restore_canonical_hashes()?;
+        if self.block_indices.is_block_hash_canonical(block_hash) {
+            return Ok(())
+        }
+
+        let chain_id = self
+            .block_indices
+            .get_blocks_chain_id(block_hash)
+            .ok_or(ExecError::BlockHashNotFoundInChain { block_hash: *block_hash })?;
+
+        // the blocks above the new tip stay in the tree as a side chain.
+        let (mut canonical, pending) = self.take_chain(chain_id).split_at(*block_hash);
+        if let Some(pending) = pending {
+            self.insert_chain(pending);
+        }
+
+        // merge the side chains down to the canonical chain.
+        while let Some(parent_id) =
+            self.block_indices.get_blocks_chain_id(&canonical.fork_block().hash)
+        {
+            let (mut parent, pending) =
+                self.take_chain(parent_id).split_at(canonical.fork_block().hash);
+            if let Some(pending) = pending {
+                self.insert_chain(pending);
+            }
+            parent.append_chain(canonical);
+            canonical = parent;
+        }
+
+        let fork = canonical.fork_block();
+        let (canonical_tip, _) = self.canonical_tip();
+        let unwind_to = (fork.number < canonical_tip).then_some(fork.number);
+
+        info!(target: "blockchain_tree", fork = fork.number, tip = canonical.tip().number, "Making chain canonical");
+        let reverted = match self.write_canonical(unwind_to, &canonical) {
+            Ok(reverted) => reverted,
+            Err(error) => {
+                // nothing was written, the chain stays in the tree.
+                self.insert_chain(canonical);
+                return Err(error)
+            }
+        };
+        let reverted = match unwind_to {
+            Some(unwind_to) => self.revert_canonical(unwind_to, reverted),
+            None => Vec::new(),
+        };
+        let committed = Arc::new(canonical.canonical_blocks());
+        self.commit_canonical(canonical);
+
+        for block in committed.iter() {
+            self.notify_new_block(NewBlockNotification {
+                hash: block.block.hash(),
+                header: Arc::new(block.block.header.clone().unseal()),
+            });
+        }
+        let notification = if reverted.is_empty() {
+            CanonStateNotification::Commit { new: committed }
+        } else {
+            CanonStateNotification::Reorg { old: Arc::new(reverted), new: committed }
+        };
+        self.notify_canon_state(notification);
+        Ok(())
+    }
+
+    /// Discard the canonical blocks below the finalized block, together with the side chains
+    /// that fork off them.
+    pub fn finalize_block(&mut self, finalized_block: BlockNumber) {
+        for chain_id in self.block_indices.remove_canonical_below(finalized_block) {
+            self.remove_chain_with_descendants(chain_id);
+        }
+    }
+
+    /// Unwind the canonical blocks above `unwind_to`, if any, and write the blocks of the chain to
+    /// the database.
+    ///
+    /// Everything is written in one transaction, so the database is left unchanged if any of the
+    /// blocks can not be unwound or written.
+    ///
+    /// Returns the unwound blocks with the execution results read from their stored changesets.
+    fn write_canonical(
+        &self,
+        unwind_to: Option<BlockNumber>,
+        chain: &Chain,
+    ) -> Result<Vec<(SealedBlockWithSenders, ExecutionResult)>> {
+        let mut tx = Transaction::new(&self.externals.db)?;
+        let reverted = match unwind_to {
+            Some(unwind_to) => tx
+                .take_canonical_blocks(unwind_to, &self.externals.executor_factory)
+                .map_err(|error| ExecError::CanonicalRevert { inner: error.to_string() })?,
+            None => Vec::new(),
+        };
+        for (block, changeset) in chain.blocks().values().zip(chain.changesets()) {
+            tx.insert_block(&block.block, &self.externals.executor_factory, changeset.clone())
+                .map_err(|error| ExecError::CanonicalCommit { inner: error.to_string() })?;
+        }
+        tx.commit()?;
+        Ok(reverted)
+    }
+
+    /// Make the blocks of the chain, which were written to the database, canonical in the tree.
+    fn commit_canonical(&mut self, chain: Chain) {
+        let block_hashes = chain.block_hashes();
+        self.block_indices.canonicalize_blocks(block_hashes.clone());
+
+        // side chains can only fork off the last `max_reorg_depth` canonical blocks.
+        let (canonical_tip, _) = self.canonical_tip();
+        let lowest = canonical_tip.saturating_sub(self.config.max_reorg_depth());
+        for chain_id in self.block_indices.remove_canonical_below(lowest) {
+            self.remove_chain_with_descendants(chain_id);
+        }
+
+        // the committed state is now in the database.
+        let forks = block_hashes
+            .into_values()
+            .filter(|hash| self.block_indices.has_fork_children(hash))
+            .map(|hash| (hash, PostState::default()))
+            .collect();
+        self.rebase_descendants(forks);
+    }
+
+    /// Unwind the canonical blocks above `unwind_to`, which were removed from the database, and
+    /// keep them in the tree as a side chain.
+    ///
+    /// Returns the unwound blocks with their receipts.
+    fn revert_canonical(
+        &mut self,
+        unwind_to: BlockNumber,
+        blocks: Vec<(SealedBlockWithSenders, ExecutionResult)>,
+    ) -> Vec<CanonicalBlock> {
+        self.block_indices.unwind_canonical_chain(unwind_to);
+        if blocks.is_empty() {
+            return Vec::new()
+        }
+
+        let chain = Chain::new(PostState::default(), blocks);
+
+        // the side chains that forked off the unwound blocks now fork off this side chain.
+        let forks = chain
+            .blocks()
+            .values()
+            .map(|block| block.hash())
+            .filter(|hash| self.block_indices.has_fork_children(hash))
+            .map(|hash| (hash, chain.state_at(hash).expect("Block is part of the chain")))
+            .collect();
+        debug!(target: "blockchain_tree", unwind_to, tip = chain.tip().number, "Reverted canonical chain");
+        let reverted = chain.canonical_blocks();
+        self.insert_chain(chain);
+        self.rebase_descendants(forks);
+        reverted
+    }
+
+    /// Send the notification to all canonical state listeners, dropping closed listeners.
+    fn notify_canon_state(&mut self, notification: CanonStateNotification) {
+        self.canon_state_listeners.retain(|listener| listener.send(notification.clone()).is_ok());
+    }
+
+    /// Send the notification to all new block listeners, dropping closed listeners.
+    fn notify_new_block(&mut self, notification: NewBlockNotification) {
+        self.new_block_listeners.retain(|listener| listener.send(notification.clone()).is_ok());
+    }
+
+    /// Recompute the state of the side chains that fork off the given blocks, and of their
+    /// descendants, on top of the new state of the fork blocks.
+    fn rebase_descendants(&mut self, mut forks: Vec<(BlockHash, PostState)>) {
+        while let Some((fork_hash, base_state)) = forks.pop() {
+            for child_id in self.block_indices.fork_children(&fork_hash) {
+                let Some(child) = self.chains.get_mut(&child_id) else { continue };
+                child.rebase(base_state.clone());
+                for block in child.blocks().values() {
+                    let hash = block.hash();
+                    if self.block_indices.has_fork_children(&hash) {
+                        forks.push((
+                            hash,
+                            child.state_at(hash).expect("Block is part of the chain"),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Insert a new side chain and return its id.
+    fn insert_chain(&mut self, chain: Chain) -> BlockChainId {
+        let chain_id = self.block_chain_id_generator;
+        self.block_chain_id_generator += 1;
+        self.block_indices.insert_chain(chain_id, &chain);
+        self.chains.insert(chain_id, chain);
+        chain_id
+    }
+
+    /// Remove the side chain from the tree, keeping the chains that fork off it.
+    fn take_chain(&mut self, chain_id: BlockChainId) -> Chain {
+        let chain = self.chains.remove(&chain_id).expect("Indexed chain exists");
+        self.block_indices.remove_chain(&chain);
+        chain
+    }
+
+    /// Remove the side chain and all chains that fork off it from the tree.
+    fn remove_chain_with_descendants(&mut self, chain_id: BlockChainId) {
+        let mut to_remove = vec![chain_id];
+        while let Some(chain_id) = to_remove.pop() {
+            let Some(chain) = self.chains.remove(&chain_id) else { continue };
+            for block in chain.blocks().values() {
+                to_remove.extend(self.block_indices.take_fork_children(&block.hash()));
+            }
+            self.block_indices.remove_chain(&chain);
+        }
+    }
+
+    fn canonical_tip(&self) -> (BlockNumber, BlockHash) {
+        self.block_indices.canonical_tip().expect("Canonical chain is not empty")
+    }
+
+    fn lowest_canonical_block(&self) -> (BlockNumber, BlockHash) {
+        self.block_indices
+            .canonical_chain()
+            .iter()
+            .next()
+            .map(|(number, hash)| (*number, *hash))
+            .expect("Canonical chain is not empty")
+    }
+}
+
+/// The side chain blocks that lead to a block, down to the canonical chain.
+struct SideChainAncestry<'a> {
+    /// The canonical block the side chains fork off.
+    canonical_fork: ForkBlock,
+    /// The hashes of the side chain blocks, up to and including the block.
+    block_hashes: BTreeMap<BlockNumber, BlockHash>,
+    /// The execution results of the side chain blocks, up to and including the block.
+    changesets: BTreeMap<BlockNumber, &'a ExecutionResult>,
+    /// The total difficulty of the block.
+    total_difficulty: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_result::{ExecutionResult, TransactionChangeSet};
+    use reth_db::{
+        mdbx::{test_utils::create_test_db, EnvKind, WriteMap},
+        models::{
+            stage::{INDEX_CALL_ADDRESS_HISTORY, INDEX_LOG_HISTORY},
+            ShardedKey,
+        },
+        transaction::DbTxMut,
+    };
+    use reth_interfaces::{
+        test_utils::{generators::random_signed_tx, TestConsensus},
+        Error,
+    };
+    use reth_primitives::{
+        proofs::EMPTY_ROOT, Address, Header, IntegerList, Log, Receipt, SealedHeader,
+        TransactionKind, H256, MAINNET,
+    };
+    use reth_provider::{insert_canonical_block, test_utils::TestExecutorFactory};
+
+    fn exec_res() -> ExecutionResult {
+        ExecutionResult { tx_changesets: Vec::new(), block_changesets: Default::default() }
+    }
+
+    fn block(parent: &SealedHeader, extra_data: u8) -> SealedBlockWithSenders {
+        let header = Header {
+            number: parent.number + 1,
+            parent_hash: parent.hash(),
+            extra_data: vec![extra_data].into(),
+            ..Default::default()
+        };
+        SealedBlockWithSenders::new(
+            SealedBlock { header: header.seal_slow(), ..Default::default() },
+            Vec::new(),
+        )
+        .unwrap()
+    }
+
+    fn setup() -> (
+        BlockchainTree<Arc<reth_db::mdbx::Env<WriteMap>>, TestConsensus, TestExecutorFactory>,
+        TestExecutorFactory,
+        SealedBlock,
+    ) {
+        let db = create_test_db::<WriteMap>(EnvKind::RW);
+        let genesis = SealedBlock::default();
+        let tx = db.tx_mut().unwrap();
+        insert_canonical_block(&tx, &genesis, false).unwrap();
+        tx.commit().unwrap();
+
+        let chain_spec = Arc::new(MAINNET.clone());
+        let factory = TestExecutorFactory::new(chain_spec.clone());
+        let externals =
+            TreeExternals::new(db, TestConsensus::default(), factory.clone(), chain_spec);
+        let tree = BlockchainTree::new(externals, BlockchainTreeConfig::default()).unwrap();
+        (tree, factory, genesis)
+    }
+
+    #[test]
+    fn insert_side_chains() {
+        let (mut tree, factory, genesis) = setup();
+        factory.extend(vec![exec_res(), exec_res(), exec_res()]);
+
+        let block1 = block(&genesis.header, 0);
+        let block2 = block(&block1.header, 0);
+        let block2a = block(&block1.header, 1);
+
+        // block1 forks off the canonical genesis block
+        tree.insert_block_with_senders(block1.clone()).unwrap();
+        assert_eq!(tree.chains().len(), 1);
+        let chain_id = tree.block_indices().get_blocks_chain_id(&block1.hash()).unwrap();
+
+        // block2 extends the side chain
+        tree.insert_block_with_senders(block2.clone()).unwrap();
+        assert_eq!(tree.chains().len(), 1);
+        assert_eq!(tree.block_indices().get_blocks_chain_id(&block2.hash()), Some(chain_id));
+
+        // block2a forks off the side chain
+        tree.insert_block_with_senders(block2a.clone()).unwrap();
+        assert_eq!(tree.chains().len(), 2);
+        let fork_id = tree.block_indices().get_blocks_chain_id(&block2a.hash()).unwrap();
+        assert_ne!(fork_id, chain_id);
+        assert_eq!(tree.block_indices().fork_children(&block1.hash()), vec![fork_id]);
+
+        // known blocks are ignored
+        tree.insert_block_with_senders(block2.clone()).unwrap();
+        assert_eq!(tree.chains().len(), 2);
+
+        // blocks with unknown parent are rejected
+        let orphan = block(&block(&block2.header, 0).header, 0);
+        assert_eq!(
+            tree.insert_block_with_senders(orphan.clone()),
+            Err(Error::Execution(ExecError::BlockParentUnknown {
+                block_hash: orphan.hash(),
+                block_number: orphan.number,
+                parent_hash: orphan.parent_hash,
+            }))
+        );
+    }
+
+    #[test]
+    fn reject_state_root_mismatch() {
+        let (mut tree, factory, genesis) = setup();
+        factory.extend(vec![exec_res()]);
+
+        let header = Header {
+            number: 1,
+            parent_hash: genesis.hash(),
+            state_root: H256::random(),
+            ..Default::default()
+        };
+        let block1 = SealedBlockWithSenders::new(
+            SealedBlock { header: header.seal_slow(), ..Default::default() },
+            Vec::new(),
+        )
+        .unwrap();
+
+        // the block doesn't change the state, so its root is the root of the genesis state
+        assert_eq!(
+            tree.insert_block_with_senders(block1.clone()),
+            Err(Error::Execution(ExecError::StateRootDiff {
+                got: EMPTY_ROOT,
+                expected: block1.state_root,
+            }))
+        );
+        assert!(tree.chains().is_empty());
+        assert_eq!(tree.block_indices().get_blocks_chain_id(&block1.hash()), None);
+    }
+
+    #[test]
+    fn make_canonical_and_reorg() {
+        let (mut tree, factory, genesis) = setup();
+        // block1, block2 and block2a, the unwound block2 is not executed again.
+        factory.extend(vec![exec_res(), exec_res(), exec_res()]);
+
+        let mut canon_state = tree.subscribe_canon_state();
+        let mut new_blocks = tree.subscribe_new_blocks();
+        let mut new_block_hash = || new_blocks.try_recv().map(|notification| notification.hash);
+
+        let block1 = block(&genesis.header, 0);
+        let block2 = block(&block1.header, 0);
+        let block2a = block(&block1.header, 1);
+        tree.insert_block_with_senders(block1.clone()).unwrap();
+        tree.insert_block_with_senders(block2.clone()).unwrap();
+        tree.insert_block_with_senders(block2a.clone()).unwrap();
+        let canonical = |block: &SealedBlockWithSenders| CanonicalBlock {
+            block: block.block.clone(),
+            receipts: Vec::new(),
+            changed_accounts: Vec::new(),
+        };
+
+        // block2 becomes canonical, block2a now forks off the canonical chain
+        tree.make_canonical(&block2.hash()).unwrap();
+        assert_eq!(
+            canon_state.try_recv(),
+            Ok(CanonStateNotification::Commit {
+                new: Arc::new(vec![canonical(&block1), canonical(&block2)])
+            })
+        );
+        assert_eq!(new_block_hash(), Ok(block1.hash()));
+        assert_eq!(new_block_hash(), Ok(block2.hash()));
+        assert_eq!(tree.block_indices().canonical_tip(), Some((2, block2.hash())));
+        assert_eq!(tree.block_indices().canonical_hash(1), Some(block1.hash()));
+        assert_eq!(tree.chains().len(), 1);
+        assert!(tree.block_by_hash(block2a.hash()).is_some());
+
+        // reorg to block2a, block2 is unwound and kept as side chain
+        tree.make_canonical(&block2a.hash()).unwrap();
+        assert_eq!(tree.block_indices().canonical_tip(), Some((2, block2a.hash())));
+        assert_eq!(tree.chains().len(), 1);
+        assert!(tree.block_by_hash(block2.hash()).is_some());
+        assert!(tree.block_by_hash(block2a.hash()).is_none());
+        assert_eq!(
+            canon_state.try_recv(),
+            Ok(CanonStateNotification::Reorg {
+                old: Arc::new(vec![canonical(&block2)]),
+                new: Arc::new(vec![canonical(&block2a)]),
+            })
+        );
+        assert_eq!(new_block_hash(), Ok(block2a.hash()));
+
+        // blocks below the finalized block are not accepted anymore
+        tree.finalize_block(2);
+        assert!(tree.chains().is_empty());
+        let stale = block(&block1.header, 2);
+        assert_eq!(
+            tree.insert_block_with_senders(stale.clone()),
+            Err(Error::Execution(ExecError::PendingBlockIsFinalized {
+                block_hash: stale.hash(),
+                block_number: stale.number,
+                last_finalized: 2,
+            }))
+        );
+    }
+
+    #[test]
+    fn unwind_history_indices_on_reorg() {
+        let (mut tree, factory, genesis) = setup();
+        // the optional call address and log indices are synced up to the genesis block
+        let tx = tree.externals.db.tx_mut().unwrap();
+        INDEX_CALL_ADDRESS_HISTORY.save_progress(&tx, 0).unwrap();
+        INDEX_LOG_HISTORY.save_progress(&tx, 0).unwrap();
+        tx.commit().unwrap();
+
+        let transaction = random_signed_tx();
+        let TransactionKind::Call(recipient) = *transaction.kind() else {
+            unreachable!("Random transactions are calls")
+        };
+        let sender = Address::random();
+        let block1 = SealedBlockWithSenders::new(
+            SealedBlock { body: vec![transaction], ..block(&genesis.header, 0).block },
+            vec![sender],
+        )
+        .unwrap();
+        let block1a = block(&genesis.header, 1);
+        let log = Log {
+            address: Address::random(),
+            topics: vec![H256::random()],
+            data: Default::default(),
+        };
+        let tx_changeset = TransactionChangeSet {
+            receipt: Receipt { logs: vec![log.clone()], ..Default::default() },
+            changeset: Default::default(),
+            new_bytecodes: Default::default(),
+        };
+        factory.extend(vec![
+            ExecutionResult {
+                tx_changesets: vec![tx_changeset],
+                block_changesets: Default::default(),
+            },
+            exec_res(),
+        ]);
+        tree.insert_block_with_senders(block1.clone()).unwrap();
+        tree.insert_block_with_senders(block1a.clone()).unwrap();
+
+        let history = |tree: &BlockchainTree<_, _, _>| {
+            let tx = tree.externals.db.tx().unwrap();
+            let last_shard = |list: Option<IntegerList>| list.map(|list| list.iter(0).collect());
+            let call_history = |address| {
+                last_shard(
+                    tx.get::<tables::CallAddressHistory>(ShardedKey::new(address, u64::MAX))
+                        .unwrap(),
+                )
+            };
+            let indices: [Option<Vec<usize>>; 4] = [
+                call_history(sender),
+                call_history(recipient),
+                last_shard(
+                    tx.get::<tables::LogAddressHistory>(ShardedKey::new(log.address, u64::MAX))
+                        .unwrap(),
+                ),
+                last_shard(
+                    tx.get::<tables::LogTopicHistory>(ShardedKey::new(log.topics[0], u64::MAX))
+                        .unwrap(),
+                ),
+            ];
+            let progress = [INDEX_CALL_ADDRESS_HISTORY, INDEX_LOG_HISTORY]
+                .map(|stage| stage.get_progress(&tx).unwrap());
+            (indices, progress)
+        };
+
+        // block1 is indexed when it is made canonical
+        tree.make_canonical(&block1.hash()).unwrap();
+        let transition = tree.externals.db.tx().unwrap().get::<tables::BlockTransitionIndex>(0);
+        let transition = transition.unwrap().unwrap() as usize;
+        let indexed = [vec![transition], vec![transition], vec![1], vec![1]].map(Some);
+        assert_eq!(history(&tree), (indexed, [Some(1); 2]));
+
+        // the indices of block1 are unwound by the reorg and the indices stay synced
+        tree.make_canonical(&block1a.hash()).unwrap();
+        assert_eq!(history(&tree), ([None, None, None, None], [Some(1); 2]));
+    }
+
+    #[test]
+    fn restore_canonical_hashes() {
+        let (mut tree, factory, genesis) = setup();
+        factory.extend(vec![exec_res(), exec_res()]);
+
+        let block1 = block(&genesis.header, 0);
+        let block1a = block(&genesis.header, 1);
+        tree.insert_block_with_senders(block1a.clone()).unwrap();
+
+        // block1 is synced without the tree
+        let tx = tree.externals.db.tx_mut().unwrap();
+        insert_canonical_block(&tx, &block1.block, false).unwrap();
+        tx.commit().unwrap();
+
+        // block2 extends the new canonical tip and the stale side chain is discarded
+        let block2 = block(&block1.header, 0);
+        tree.insert_block_with_senders(block2.clone()).unwrap();
+        assert_eq!(tree.block_indices().canonical_tip(), Some((1, block1.hash())));
+        assert_eq!(tree.chains().len(), 1);
+        assert_eq!(tree.block_indices().get_blocks_chain_id(&block1a.hash()), None);
+        assert!(tree.block_indices().get_blocks_chain_id(&block2.hash()).is_some());
+    }
+}
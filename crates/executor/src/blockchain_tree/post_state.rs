@@ -0,0 +1,211 @@
+//! In-memory state of side chains.
+
+use crate::execution_result::{AccountChangeSet, AccountInfoChangeSet, ExecutionResult};
+use reth_interfaces::Result;
+use reth_primitives::{
+    Account, Address, BlockHash, BlockNumber, Bytecode, StorageKey, StorageValue, H256, U256,
+};
+use reth_provider::{
+    trie::AccountProof, AccountProvider, BlockHashProvider, ProviderError, StateProvider,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// The accumulated state changes of executed blocks that are not yet committed to the database.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PostState {
+    /// Changed accounts, `None` if the account was destroyed.
+    accounts: HashMap<Address, Option<Account>>,
+    /// Changed storage of accounts.
+    storages: HashMap<Address, PostStorage>,
+    /// Bytecodes created by the blocks.
+    bytecodes: HashMap<H256, Bytecode>,
+}
+
+/// Changed storage of an account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct PostStorage {
+    /// Whether the storage was wiped, in which case slots that are not in `slots` are zero.
+    wiped: bool,
+    /// Changed storage slots.
+    slots: HashMap<U256, U256>,
+}
+
+impl PostState {
+    /// Apply the changes of an executed block.
+    pub fn apply_execution_result(&mut self, result: &ExecutionResult) {
+        for tx_changeset in result.tx_changesets.iter() {
+            for (address, changeset) in tx_changeset.changeset.iter() {
+                let AccountChangeSet { account, storage, wipe_storage } = changeset;
+                self.apply_account_change(*address, account);
+
+                if *wipe_storage || !storage.is_empty() {
+                    let post_storage = self.storages.entry(*address).or_default();
+                    if *wipe_storage {
+                        post_storage.wiped = true;
+                        post_storage.slots.clear();
+                    }
+                    post_storage
+                        .slots
+                        .extend(storage.iter().map(|(key, (_, new_value))| (*key, *new_value)));
+                }
+            }
+            self.bytecodes.extend(
+                tx_changeset
+                    .new_bytecodes
+                    .iter()
+                    .map(|(hash, bytecode)| (*hash, Bytecode(bytecode.clone()))),
+            );
+        }
+
+        for (address, account) in result.block_changesets.iter() {
+            self.apply_account_change(*address, account);
+        }
+    }
+
+    fn apply_account_change(&mut self, address: Address, change: &AccountInfoChangeSet) {
+        match change {
+            AccountInfoChangeSet::Created { new } | AccountInfoChangeSet::Changed { new, .. } => {
+                self.accounts.insert(address, Some(*new));
+            }
+            AccountInfoChangeSet::Destroyed { .. } => {
+                self.accounts.insert(address, None);
+            }
+            AccountInfoChangeSet::NoChange => {}
+        }
+    }
+
+    /// Returns the changed account, `Some(None)` if it was destroyed and `None` if it was not
+    /// touched.
+    pub fn account(&self, address: &Address) -> Option<Option<Account>> {
+        self.accounts.get(address).copied()
+    }
+
+    /// Returns the changed storage value, `None` if the slot was not touched.
+    pub fn storage(&self, address: &Address, key: &U256) -> Option<U256> {
+        let storage = self.storages.get(address)?;
+        storage.slots.get(key).copied().or(storage.wiped.then_some(U256::ZERO))
+    }
+
+    /// Returns a bytecode created by one of the blocks.
+    pub fn bytecode(&self, code_hash: &H256) -> Option<&Bytecode> {
+        self.bytecodes.get(code_hash)
+    }
+}
+
+/// A [StateProvider] that serves the [PostState] of a side chain on top of the state of the
+/// canonical block the side chain forks off.
+pub struct PostStateProvider<'a, SP> {
+    /// The state of the side chain.
+    pub(crate) post_state: &'a PostState,
+    /// The state of the canonical fork block.
+    pub(crate) provider: SP,
+    /// The block hashes of the side chain, needed for the `BLOCKHASH` opcode.
+    pub(crate) sidechain_block_hashes: BTreeMap<BlockNumber, BlockHash>,
+}
+
+impl<'a, SP> PostStateProvider<'a, SP> {
+    /// Create new post state provider.
+    pub fn new(
+        post_state: &'a PostState,
+        provider: SP,
+        sidechain_block_hashes: BTreeMap<BlockNumber, BlockHash>,
+    ) -> Self {
+        Self { post_state, provider, sidechain_block_hashes }
+    }
+}
+
+impl<'a, SP: StateProvider> BlockHashProvider for PostStateProvider<'a, SP> {
+    fn block_hash(&self, number: U256) -> Result<Option<H256>> {
+        let block_number: Option<BlockNumber> = number.try_into().ok();
+        if let Some(hash) = block_number.and_then(|n| self.sidechain_block_hashes.get(&n)) {
+            return Ok(Some(*hash))
+        }
+        self.provider.block_hash(number)
+    }
+}
+
+impl<'a, SP: StateProvider> AccountProvider for PostStateProvider<'a, SP> {
+    fn basic_account(&self, address: Address) -> Result<Option<Account>> {
+        if let Some(account) = self.post_state.account(&address) {
+            return Ok(account)
+        }
+        self.provider.basic_account(address)
+    }
+}
+
+impl<'a, SP: StateProvider> StateProvider for PostStateProvider<'a, SP> {
+    fn storage(&self, account: Address, storage_key: StorageKey) -> Result<Option<StorageValue>> {
+        let key = U256::from_be_bytes(storage_key.0);
+        if let Some(value) = self.post_state.storage(&account, &key) {
+            return Ok(Some(value))
+        }
+        self.provider.storage(account, storage_key)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: H256) -> Result<Option<Bytecode>> {
+        if let Some(bytecode) = self.post_state.bytecode(&code_hash) {
+            return Ok(Some(bytecode.clone()))
+        }
+        self.provider.bytecode_by_hash(code_hash)
+    }
+
+    /// Proofs can not be generated for state that is not committed to the database.
+    fn proof(&self, _address: Address, _keys: &[H256]) -> Result<AccountProof> {
+        Err(ProviderError::StateProofUnsupported.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_result::TransactionChangeSet;
+    use reth_primitives::Receipt;
+
+    fn changeset(
+        address: Address,
+        account: AccountInfoChangeSet,
+        storage: BTreeMap<U256, (U256, U256)>,
+        wipe_storage: bool,
+    ) -> ExecutionResult {
+        ExecutionResult {
+            tx_changesets: vec![TransactionChangeSet {
+                receipt: Receipt::default(),
+                changeset: BTreeMap::from([(
+                    address,
+                    AccountChangeSet { account, storage, wipe_storage },
+                )]),
+                new_bytecodes: Default::default(),
+            }],
+            block_changesets: Default::default(),
+        }
+    }
+
+    #[test]
+    fn apply_execution_results() {
+        let address = Address::random();
+        let account = Account { nonce: 1, ..Default::default() };
+        let key = U256::from(1);
+        let mut state = PostState::default();
+
+        state.apply_execution_result(&changeset(
+            address,
+            AccountInfoChangeSet::Created { new: account },
+            BTreeMap::from([(key, (U256::ZERO, U256::from(2)))]),
+            false,
+        ));
+        assert_eq!(state.account(&address), Some(Some(account)));
+        assert_eq!(state.storage(&address, &key), Some(U256::from(2)));
+        assert_eq!(state.storage(&address, &U256::from(3)), None);
+
+        state.apply_execution_result(&changeset(
+            address,
+            AccountInfoChangeSet::Destroyed { old: account },
+            BTreeMap::new(),
+            true,
+        ));
+        assert_eq!(state.account(&address), Some(None));
+        assert_eq!(state.storage(&address, &key), Some(U256::ZERO));
+        assert_eq!(state.storage(&address, &U256::from(3)), Some(U256::ZERO));
+        assert_eq!(state.account(&Address::random()), None);
+    }
+}
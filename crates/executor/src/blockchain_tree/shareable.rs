@@ -0,0 +1,64 @@
+//! Wrapper around the [BlockchainTree] that can be shared between tasks.
+
+use super::BlockchainTree;
+use parking_lot::RwLock;
+use reth_db::database::Database;
+use reth_interfaces::{
+    blockchain_tree::BlockchainTreeEngine,
+    consensus::Consensus,
+    events::{CanonStateNotifications, ChainEventSubscriptions, NewBlockNotifications},
+    Result,
+};
+use reth_primitives::{BlockHash, BlockNumber, SealedBlock};
+use reth_provider::ExecutorFactory;
+use std::sync::Arc;
+
+/// Shareable handle to the [BlockchainTree].
+///
+/// Every call locks the tree, blocks are executed and committed while the lock is held.
+pub struct ShareableBlockchainTree<DB, C, EF> {
+    /// The locked tree.
+    pub tree: Arc<RwLock<BlockchainTree<DB, C, EF>>>,
+}
+
+// derived `Clone` would require the generics to be `Clone`
+impl<DB, C, EF> Clone for ShareableBlockchainTree<DB, C, EF> {
+    fn clone(&self) -> Self {
+        Self { tree: Arc::clone(&self.tree) }
+    }
+}
+
+impl<DB, C, EF> ShareableBlockchainTree<DB, C, EF> {
+    /// Wrap the tree.
+    pub fn new(tree: BlockchainTree<DB, C, EF>) -> Self {
+        Self { tree: Arc::new(RwLock::new(tree)) }
+    }
+}
+
+impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTreeEngine
+    for ShareableBlockchainTree<DB, C, EF>
+{
+    fn insert_block(&self, block: SealedBlock) -> Result<()> {
+        self.tree.write().insert_block(block)
+    }
+
+    fn make_canonical(&self, block_hash: &BlockHash) -> Result<()> {
+        self.tree.write().make_canonical(block_hash)
+    }
+
+    fn finalize_block(&self, finalized_block: BlockNumber) {
+        self.tree.write().finalize_block(finalized_block)
+    }
+}
+
+impl<DB: Database, C: Consensus, EF: ExecutorFactory> ChainEventSubscriptions
+    for ShareableBlockchainTree<DB, C, EF>
+{
+    fn subscribe_new_blocks(&self) -> NewBlockNotifications {
+        self.tree.write().subscribe_new_blocks()
+    }
+
+    fn subscribe_canon_state(&self) -> CanonStateNotifications {
+        self.tree.write().subscribe_canon_state()
+    }
+}
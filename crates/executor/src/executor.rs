@@ -8,22 +8,25 @@ use reth_primitives::{
 };
 use reth_provider::{BlockExecutor, StateProvider};
 use reth_revm::{
-    config::{WEI_2ETH, WEI_3ETH, WEI_5ETH},
+    config::{block_reward, ommer_reward, WEI_2ETH, WEI_3ETH, WEI_5ETH},
     database::SubState,
     env::{fill_cfg_and_block_env, fill_tx_env},
     into_reth_log, to_reth_acc,
 };
-use reth_revm_inspectors::stack::{InspectorStack, InspectorStackConfig};
+use reth_revm_inspectors::{
+    call_addresses::CallAddressInspector,
+    stack::{InspectorStack, InspectorStackConfig},
+};
 use revm::{
     db::AccountState,
     primitives::{
         hash_map::{self, Entry},
         Account as RevmAccount, AccountInfo, Bytecode, ResultAndState,
     },
-    EVM,
+    DatabaseCommit, EVM,
 };
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     sync::Arc,
 };
 
@@ -90,7 +93,7 @@ where
     }
 
     /// Initializes the config and block env.
-    fn init_env(&mut self, header: &Header, total_difficulty: U256) {
+    pub fn init_env(&mut self, header: &Header, total_difficulty: U256) {
         fill_cfg_and_block_env(
             &mut self.evm.env.cfg,
             &mut self.evm.env.block,
@@ -238,10 +241,8 @@ where
         // Collect balance increments for block and uncle rewards.
         if let Some(reward) = self.get_block_reward(block, td) {
             // Calculate Uncle reward
-            // OpenEthereum code: https://github.com/openethereum/openethereum/blob/6c2d392d867b058ff867c4373e40850ca3f96969/crates/ethcore/src/ethereum/ethash.rs#L319-L333
             for ommer in block.ommers.iter() {
-                let ommer_reward =
-                    U256::from(((8 + ommer.number - block.number) as u128 * reward) >> 3);
+                let ommer_reward = U256::from(ommer_reward(reward, block.number, ommer.number));
                 // From yellowpaper Page 15:
                 // If there are collisions of the beneficiary addresses between ommers and the
                 // block (i.e. two ommers with the same beneficiary address
@@ -251,7 +252,7 @@ where
             }
 
             // Increment balance for main block reward.
-            let block_reward = U256::from(reward + (reward >> 5) * block.ommers.len() as u128);
+            let block_reward = U256::from(block_reward(reward, block.ommers.len()));
             *balance_increments.entry(block.beneficiary).or_default() += block_reward;
         }
 
@@ -420,35 +421,74 @@ where
                 })
             }
             // Execute transaction.
-            let ResultAndState { result, state } = self.transact(transaction, sender)?;
-
-            // commit changes
-            let (changeset, new_bytecodes) = self.commit_changes(state);
-
-            // append gas used
-            cumulative_gas_used += result.gas_used();
-
-            // cast revm logs to reth logs
-            let logs: Vec<Log> = result.logs().into_iter().map(into_reth_log).collect();
-
-            // Push transaction changeset and calculate header bloom filter for receipt.
-            tx_changesets.push(TransactionChangeSet {
-                receipt: Receipt {
-                    tx_type: transaction.tx_type(),
-                    // Success flag was added in `EIP-658: Embedding transaction status code in
-                    // receipts`.
-                    success: result.is_success(),
-                    cumulative_gas_used,
-                    bloom: logs_bloom(logs.iter()),
-                    logs,
-                },
-                changeset,
-                new_bytecodes,
-            });
+            let changeset =
+                self.execute_and_commit_transaction(transaction, sender, cumulative_gas_used)?;
+            cumulative_gas_used = changeset.receipt.cumulative_gas_used;
+            tx_changesets.push(changeset);
         }
 
         Ok((tx_changesets, cumulative_gas_used))
     }
+
+    /// Runs a single transaction on top of the state committed so far and commits its changes.
+    ///
+    /// `cumulative_gas_used` is the gas used by the preceding transactions of the block. The
+    /// returned changeset contains the receipt of the transaction.
+    ///
+    /// Assumes the rest of the block environment has been filled via `init_env`.
+    pub fn execute_and_commit_transaction(
+        &mut self,
+        transaction: &TransactionSigned,
+        sender: Address,
+        cumulative_gas_used: u64,
+    ) -> Result<TransactionChangeSet, Error> {
+        let ResultAndState { result, state } = self.transact(transaction, sender)?;
+
+        // commit changes
+        let (changeset, new_bytecodes) = self.commit_changes(state);
+
+        // cast revm logs to reth logs
+        let logs: Vec<Log> = result.logs().into_iter().map(into_reth_log).collect();
+
+        // Return transaction changeset and calculate header bloom filter for receipt.
+        Ok(TransactionChangeSet {
+            receipt: Receipt {
+                tx_type: transaction.tx_type(),
+                // Success flag was added in `EIP-658: Embedding transaction status code in
+                // receipts`.
+                success: result.is_success(),
+                cumulative_gas_used: cumulative_gas_used + result.gas_used(),
+                bloom: logs_bloom(logs.iter()),
+                logs,
+            },
+            changeset,
+            new_bytecodes,
+        })
+    }
+
+    /// Applies the block and ommer rewards, the withdrawals and the irregular state changes of
+    /// the block to the state and returns the changesets of the affected accounts.
+    pub fn apply_post_block_changes(
+        &mut self,
+        block: &Block,
+        total_difficulty: U256,
+    ) -> Result<BTreeMap<Address, AccountInfoChangeSet>, Error> {
+        let mut block_changesets = BTreeMap::default();
+        let balance_increments = self.post_block_balance_increments(block, total_difficulty)?;
+        for (address, increment) in balance_increments {
+            let changeset = self.account_balance_increment_changeset(address, increment)?;
+            block_changesets.insert(address, changeset);
+        }
+
+        if self.chain_spec.fork(Hardfork::Dao).transitions_at_block(block.number) {
+            for (address, changeset) in self.dao_fork_changeset()? {
+                // No account collision between rewarded accounts and DAO fork related accounts.
+                block_changesets.insert(address, changeset);
+            }
+        }
+
+        Ok(block_changesets)
+    }
 }
 
 impl<DB> BlockExecutor<DB> for Executor<DB>
@@ -469,19 +509,7 @@ where
             return Err(Error::BlockGasUsed { got: cumulative_gas_used, expected: block.gas_used })
         }
 
-        let mut block_changesets = BTreeMap::default();
-        let balance_increments = self.post_block_balance_increments(block, total_difficulty)?;
-        for (address, increment) in balance_increments {
-            let changeset = self.account_balance_increment_changeset(address, increment)?;
-            block_changesets.insert(address, changeset);
-        }
-
-        if self.chain_spec.fork(Hardfork::Dao).transitions_at_block(block.number) {
-            for (address, changeset) in self.dao_fork_changeset()? {
-                // No account collision between rewarded accounts and DAO fork related accounts.
-                block_changesets.insert(address, changeset);
-            }
-        }
+        let block_changesets = self.apply_post_block_changes(block, total_difficulty)?;
 
         Ok(ExecutionResult { tx_changesets, block_changesets })
     }
@@ -508,6 +536,32 @@ where
 
         Ok(execution_result)
     }
+
+    fn call_addresses(
+        &mut self,
+        block: &Block,
+        total_difficulty: U256,
+        senders: Option<Vec<Address>>,
+    ) -> Result<Vec<BTreeSet<Address>>, Error> {
+        let senders = self.recover_senders(&block.body, senders)?;
+
+        self.init_env(&block.header, total_difficulty);
+
+        let mut call_addresses = Vec::with_capacity(block.body.len());
+        for (transaction, sender) in block.body.iter().zip(senders.into_iter()) {
+            fill_tx_env(&mut self.evm.env.tx, transaction, sender);
+
+            let mut inspector = CallAddressInspector::default();
+            let ResultAndState { state, .. } =
+                self.evm.inspect(&mut inspector).map_err(|e| Error::EVM(format!("{e:?}")))?;
+
+            // the following transactions are executed on top of the changes
+            self.db().commit(state);
+            call_addresses.push(inspector.into_addresses());
+        }
+
+        Ok(call_addresses)
+    }
 }
 
 /// Verify receipts
@@ -540,7 +594,7 @@ mod tests {
         hex_literal::hex, keccak256, Account, Address, Bytecode, Bytes, ChainSpecBuilder,
         ForkCondition, StorageKey, H256, MAINNET, U256,
     };
-    use reth_provider::{AccountProvider, BlockHashProvider, StateProvider};
+    use reth_provider::{trie::AccountProof, AccountProvider, BlockHashProvider, StateProvider};
     use reth_revm::database::State;
     use reth_rlp::Decodable;
     use std::{collections::HashMap, str::FromStr};
@@ -598,6 +652,14 @@ mod tests {
         fn bytecode_by_hash(&self, code_hash: H256) -> reth_interfaces::Result<Option<Bytecode>> {
             Ok(self.contracts.get(&code_hash).cloned())
         }
+
+        fn proof(
+            &self,
+            _address: Address,
+            _keys: &[H256],
+        ) -> reth_interfaces::Result<AccountProof> {
+            unimplemented!("proof generation is not supported")
+        }
     }
 
     #[test]
@@ -905,6 +967,51 @@ mod tests {
         assert!(selfdestroyer_changeset.wipe_storage);
     }
 
+    #[test]
+    fn call_addresses_with_selfdestruct() {
+        // Same block as in `test_selfdestruct`: the caller calls a contract that selfdestructs and
+        // sends its balance to the address of its own balance.
+        let mut block_rlp = hex!("f9025ff901f7a0c86e8cc0310ae7c531c758678ddbfd16fc51c8cef8cec650b032de9869e8b94fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa050554882fbbda2c2fd93fdc466db9946ea262a67f7a76cc169e714f105ab583da00967f09ef1dfed20c0eacfaa94d5cd4002eda3242ac47eae68972d07b106d192a0e3c8b47fbfc94667ef4cceb17e5cc21e3b1eebd442cebb27f07562b33836290db90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008302000001830f42408238108203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f862f860800a83061a8094095e7baea6a6c7c4c2dfeb977efac326af552d8780801ba072ed817487b84ba367d15d2f039b5fc5f087d0a8882fbdf73e8cb49357e1ce30a0403d800545b8fc544f92ce8124e2255f8c3c6af93f28243a120585d4c4c6a2a3c0").as_slice();
+        let block = Block::decode(&mut block_rlp).unwrap();
+        let mut db = StateProviderTest::default();
+
+        let address_caller = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+        let address_selfdestruct =
+            Address::from_str("095e7baea6a6c7c4c2dfeb977efac326af552d87").unwrap();
+
+        db.insert_account(
+            address_caller,
+            Account {
+                balance: U256::from(0x0de0b6b3a7640000u64),
+                nonce: 0x00,
+                bytecode_hash: None,
+            },
+            None,
+            HashMap::new(),
+        );
+        db.insert_account(
+            address_selfdestruct,
+            Account {
+                balance: U256::ZERO,
+                nonce: 0x00,
+                bytecode_hash: Some(H256(hex!(
+                    "56a7d44a4ecf086c34482ad1feb1007087fc56fae6dbefbd3f416002933f1705"
+                ))),
+            },
+            Some(hex!("73095e7baea6a6c7c4c2dfeb977efac326af552d8731ff00").into()),
+            HashMap::new(),
+        );
+
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build());
+        let mut executor = Executor::new(chain_spec, SubState::new(State::new(db)));
+        let call_addresses = executor.call_addresses(&block, U256::ZERO, None).unwrap();
+
+        assert_eq!(
+            call_addresses,
+            vec![BTreeSet::from([address_caller, address_selfdestruct, Address::zero()])]
+        );
+    }
+
     // Test vector from https://github.com/ethereum/tests/blob/3156db5389921125bb9e04142d18e0e7b0cf8d64/BlockchainTests/EIPTests/bc4895-withdrawals/twoIdenticalIndexDifferentValidator.json
     #[test]
     fn test_withdrawals() {
@@ -9,6 +9,9 @@
 
 pub mod eth_dao_fork;
 
+pub mod blockchain_tree;
+pub use blockchain_tree::{BlockchainTree, BlockchainTreeConfig, TreeExternals};
+
 /// Execution result types.
 pub use reth_provider::execution_result;
 /// Executor
@@ -0,0 +1,18 @@
+use crate::Result;
+use reth_primitives::{BlockHash, BlockNumber, SealedBlock};
+
+/// Interface of the blockchain tree that is driven by the consensus layer.
+///
+/// New payloads are inserted into the tree, which validates and executes them, and forkchoice
+/// updates make the head canonical and drop the blocks below the finalized block.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BlockchainTreeEngine: Send + Sync {
+    /// Validate and execute the block and insert it into the tree.
+    fn insert_block(&self, block: SealedBlock) -> Result<()>;
+
+    /// Make the block and all its ancestors canonical.
+    fn make_canonical(&self, block_hash: &BlockHash) -> Result<()>;
+
+    /// Discard the blocks below the finalized block, together with their side chains.
+    fn finalize_block(&self, finalized_block: BlockNumber);
+}
@@ -1,14 +1,20 @@
-use reth_primitives::{Header, H256};
+use reth_primitives::{Address, Header, Receipt, SealedBlock, H256};
 use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedReceiver;
 
 /// Type alias for a receiver that receives [NewBlockNotification]
 pub type NewBlockNotifications = UnboundedReceiver<NewBlockNotification>;
 
+/// Type alias for a receiver that receives [CanonStateNotification]
+pub type CanonStateNotifications = UnboundedReceiver<CanonStateNotification>;
+
 /// A type that allows to register chain related event subscriptions.
 pub trait ChainEventSubscriptions: Send + Sync {
     /// Get notified when a new block was imported.
     fn subscribe_new_blocks(&self) -> NewBlockNotifications;
+
+    /// Get notified when the canonical chain changed.
+    fn subscribe_canon_state(&self) -> CanonStateNotifications;
 }
 
 /// A notification that's emitted when a new block was imported.
@@ -19,3 +25,52 @@ pub struct NewBlockNotification {
     /// The block header of the new block
     pub header: Arc<Header>,
 }
+
+/// A block of the canonical chain together with the receipts of its transactions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CanonicalBlock {
+    /// The block.
+    pub block: SealedBlock,
+    /// The receipts of the transactions of the block, in order.
+    pub receipts: Vec<Receipt>,
+    /// The accounts whose nonce, balance or code was changed by the block, including value
+    /// transfers of internal calls and the block rewards, in ascending order.
+    pub changed_accounts: Vec<Address>,
+}
+
+/// A notification that's emitted when the canonical chain changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CanonStateNotification {
+    /// Blocks were appended to the canonical chain.
+    Commit {
+        /// The new canonical blocks, in ascending order.
+        new: Arc<Vec<CanonicalBlock>>,
+    },
+    /// The canonical chain was reorged: the blocks above the fork block were unwound and
+    /// replaced by the blocks of another chain.
+    Reorg {
+        /// The blocks that are no longer canonical, in ascending order.
+        old: Arc<Vec<CanonicalBlock>>,
+        /// The new canonical blocks, in ascending order.
+        new: Arc<Vec<CanonicalBlock>>,
+    },
+}
+
+impl CanonStateNotification {
+    /// Returns the blocks that were removed from the canonical chain.
+    pub fn reverted(&self) -> &[CanonicalBlock] {
+        match self {
+            CanonStateNotification::Commit { .. } => &[],
+            CanonStateNotification::Reorg { old, .. } => old,
+        }
+    }
+
+    /// Returns the blocks that were added to the canonical chain.
+    pub fn committed(&self) -> &[CanonicalBlock] {
+        match self {
+            CanonStateNotification::Commit { new } | CanonStateNotification::Reorg { new, .. } => {
+                new
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use reth_primitives::{Bloom, H256};
+use reth_primitives::{BlockHash, BlockNumber, Bloom, H256};
 use thiserror::Error;
 
 /// BlockExecutor Errors
@@ -23,6 +23,8 @@ pub enum Error {
     ExecutionSuccessDiff { got: bool, expected: bool },
     #[error("Receipt root {got:?} is different than expected {expected:?}.")]
     ReceiptRootDiff { got: H256, expected: H256 },
+    #[error("State root {got:?} is different than expected {expected:?}.")]
+    StateRootDiff { got: H256, expected: H256 },
     #[error("Header bloom filter {got:?} is different than expected {expected:?}.")]
     BloomLogDiff { got: Box<Bloom>, expected: Box<Bloom> },
     #[error("Transaction gas limit {transaction_gas_limit} is more than blocks available gas {block_available_gas}")]
@@ -34,4 +36,26 @@ pub enum Error {
     BlockGasUsed { got: u64, expected: u64 },
     #[error("Provider error")]
     ProviderError,
+    #[error("Block {block_hash:?} was not found in the blockchain tree")]
+    BlockHashNotFoundInChain { block_hash: BlockHash },
+    #[error(
+        "Block #{block_number} {block_hash:?} is below the last finalized block #{last_finalized}"
+    )]
+    PendingBlockIsFinalized {
+        block_hash: BlockHash,
+        block_number: BlockNumber,
+        last_finalized: BlockNumber,
+    },
+    #[error("Block #{block_number} {block_hash:?} is too far ahead of the canonical tip #{canonical_tip}")]
+    PendingBlockIsInFuture {
+        block_hash: BlockHash,
+        block_number: BlockNumber,
+        canonical_tip: BlockNumber,
+    },
+    #[error("Block parent {parent_hash:?} of block #{block_number} {block_hash:?} is unknown")]
+    BlockParentUnknown { block_hash: BlockHash, block_number: BlockNumber, parent_hash: BlockHash },
+    #[error("Failed to commit chain to the database: {inner}")]
+    CanonicalCommit { inner: String },
+    #[error("Failed to revert canonical chain: {inner}")]
+    CanonicalRevert { inner: String },
 }
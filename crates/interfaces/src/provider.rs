@@ -50,6 +50,12 @@ pub enum ProviderError {
         /// The transaction id
         id: TxNumber,
     },
+    /// The receipt of the transaction is missing.
+    #[error("Receipt of transaction #{id} not found")]
+    Receipt {
+        /// The transaction id
+        id: TxNumber,
+    },
     /// A ommers are missing.
     #[error("Block ommers not found for block #{number}")]
     Ommers {
@@ -74,4 +80,13 @@ pub enum ProviderError {
     /// Thrown when the cache service task dropped
     #[error("cache service task stopped")]
     CacheServiceUnavailable,
+    /// Thrown when generating the merkle proof of an account failed.
+    #[error("Failed to generate state proof: {0}")]
+    StateProof(String),
+    /// Thrown when calculating the state root of a block failed.
+    #[error("Failed to calculate state root: {0}")]
+    StateRoot(String),
+    /// Thrown when the state provider can not generate merkle proofs.
+    #[error("State proofs are not supported by this state provider")]
+    StateProofUnsupported,
 }
@@ -1,6 +1,10 @@
 //! Traits used when interacting with the sync status of the network.
 
 use reth_primitives::BlockNumber;
+use tokio::sync::watch;
+
+/// Type alias for a receiver that observes [SyncState] updates.
+pub type SyncStateNotifications = watch::Receiver<SyncState>;
 
 /// A type that provides information about whether the node is currently syncing and the network is
 /// currently serving syncing related requests.
@@ -28,6 +32,13 @@ pub trait SyncStateUpdater: SyncStateProvider {
     fn update_sync_state(&self, state: SyncState);
 }
 
+/// A type that allows to subscribe to the [SyncState] updates published by a [SyncStateUpdater].
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait SyncStateSubscriptions: Send + Sync {
+    /// Returns a receiver that observes the current [SyncState] and all following updates.
+    fn subscribe_sync_state(&self) -> SyncStateNotifications;
+}
+
 /// The state the network is currently in when it comes to synchronization.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum SyncState {
@@ -75,3 +86,16 @@ impl SyncStateProvider for NoopSyncStateUpdate {
 impl SyncStateUpdater for NoopSyncStateUpdate {
     fn update_sync_state(&self, _state: SyncState) {}
 }
+
+impl SyncStateSubscriptions for NoopSyncStateUpdate {
+    fn subscribe_sync_state(&self) -> SyncStateNotifications {
+        watch::channel(SyncState::Idle).1
+    }
+}
+
+#[cfg(feature = "test-utils")]
+impl SyncStateSubscriptions for reth_network_api::test_utils::NoopNetwork {
+    fn subscribe_sync_state(&self) -> SyncStateNotifications {
+        watch::channel(SyncState::Idle).1
+    }
+}
@@ -0,0 +1,43 @@
+//! Testing support for chain event subscriptions.
+use crate::events::{
+    CanonStateNotification, CanonStateNotifications, ChainEventSubscriptions, NewBlockNotification,
+    NewBlockNotifications,
+};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+/// A [ChainEventSubscriptions] implementation that sends the notifications it is given to all
+/// subscribers.
+#[derive(Clone, Debug, Default)]
+pub struct TestChainEventSubscriptions {
+    new_block_listeners: Arc<Mutex<Vec<UnboundedSender<NewBlockNotification>>>>,
+    canon_state_listeners: Arc<Mutex<Vec<UnboundedSender<CanonStateNotification>>>>,
+}
+
+impl TestChainEventSubscriptions {
+    /// Sends the notification to all new block subscribers.
+    pub fn notify_new_block(&self, notification: NewBlockNotification) {
+        let mut listeners = self.new_block_listeners.lock().unwrap();
+        listeners.retain(|listener| listener.send(notification.clone()).is_ok());
+    }
+
+    /// Sends the notification to all canonical state subscribers.
+    pub fn notify_canon_state(&self, notification: CanonStateNotification) {
+        let mut listeners = self.canon_state_listeners.lock().unwrap();
+        listeners.retain(|listener| listener.send(notification.clone()).is_ok());
+    }
+}
+
+impl ChainEventSubscriptions for TestChainEventSubscriptions {
+    fn subscribe_new_blocks(&self) -> NewBlockNotifications {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.new_block_listeners.lock().unwrap().push(tx);
+        rx
+    }
+
+    fn subscribe_canon_state(&self) -> CanonStateNotifications {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.canon_state_listeners.lock().unwrap().push(tx);
+        rx
+    }
+}
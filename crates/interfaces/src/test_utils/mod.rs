@@ -1,10 +1,12 @@
 #![allow(unused)]
 
 mod bodies;
+mod events;
 mod headers;
 
 /// Generators for different data structures like block headers, block bodies and ranges of those.
 pub mod generators;
 
 pub use bodies::*;
+pub use events::*;
 pub use headers::*;
@@ -283,6 +283,8 @@ mod test {
                     nonce: 0x0000000000000000u64,
                     base_fee_per_gas: None,
                     withdrawals_root: None,
+                    blob_gas_used: None,
+                    excess_blob_gas: None,
                 },
             ]),
         }.encode(&mut data);
@@ -314,6 +316,8 @@ mod test {
                     nonce: 0x0000000000000000u64,
                     base_fee_per_gas: None,
                     withdrawals_root: None,
+                    blob_gas_used: None,
+                    excess_blob_gas: None,
                 },
             ]),
         };
@@ -426,6 +430,8 @@ mod test {
                             nonce: 0x0000000000000000u64,
                             base_fee_per_gas: None,
                             withdrawals_root: None,
+                            blob_gas_used: None,
+                            excess_blob_gas: None,
                         },
                     ],
                     withdrawals: None,
@@ -510,6 +516,8 @@ mod test {
                             nonce: 0x0000000000000000u64,
                             base_fee_per_gas: None,
                             withdrawals_root: None,
+                            blob_gas_used: None,
+                            excess_blob_gas: None,
                         },
                     ],
                     withdrawals: None,
@@ -11,7 +11,7 @@ use reth_eth_wire::{
     SharedTransactions, Transactions,
 };
 use reth_interfaces::p2p::error::{RequestError, RequestResult};
-use reth_primitives::{Bytes, Header, PeerId, Receipt, TransactionSigned, H256};
+use reth_primitives::{Bytes, Header, PeerId, PooledTransactionsElement, Receipt, H256};
 use std::{
     fmt,
     sync::Arc,
@@ -197,7 +197,7 @@ impl PeerResponse {
 pub enum PeerResponseResult {
     BlockHeaders(RequestResult<Vec<Header>>),
     BlockBodies(RequestResult<Vec<BlockBody>>),
-    PooledTransactions(RequestResult<Vec<TransactionSigned>>),
+    PooledTransactions(RequestResult<Vec<PooledTransactionsElement>>),
     NodeData(RequestResult<Vec<Bytes>>),
     Receipts(RequestResult<Vec<Vec<Receipt>>>),
 }
@@ -7,7 +7,10 @@ use parking_lot::Mutex;
 use reth_eth_wire::{DisconnectReason, NewBlock, NewPooledTransactionHashes, SharedTransactions};
 use reth_interfaces::{
     p2p::headers::client::StatusUpdater,
-    sync::{SyncState, SyncStateProvider, SyncStateUpdater},
+    sync::{
+        SyncState, SyncStateNotifications, SyncStateProvider, SyncStateSubscriptions,
+        SyncStateUpdater,
+    },
 };
 use reth_net_common::bandwidth_meter::BandwidthMeter;
 use reth_network_api::{
@@ -21,7 +24,7 @@ use std::{
         Arc,
     },
 };
-use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
+use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot, watch};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 /// A _shareable_ network frontend. Used to interact with the network.
@@ -57,6 +60,7 @@ impl NetworkHandle {
             network_mode,
             bandwidth_meter,
             is_syncing: Arc::new(Default::default()),
+            sync_state: watch::channel(SyncState::Idle).0,
             chain_id,
         };
         Self { inner: Arc::new(inner) }
@@ -247,7 +251,14 @@ impl SyncStateProvider for NetworkHandle {
 impl SyncStateUpdater for NetworkHandle {
     fn update_sync_state(&self, state: SyncState) {
         let is_syncing = state.is_syncing();
-        self.inner.is_syncing.store(is_syncing, Ordering::Relaxed)
+        self.inner.is_syncing.store(is_syncing, Ordering::Relaxed);
+        self.inner.sync_state.send_replace(state);
+    }
+}
+
+impl SyncStateSubscriptions for NetworkHandle {
+    fn subscribe_sync_state(&self) -> SyncStateNotifications {
+        self.inner.sync_state.subscribe()
     }
 }
 
@@ -269,6 +280,8 @@ struct NetworkInner {
     bandwidth_meter: BandwidthMeter,
     /// Represents if the network is currently syncing.
     is_syncing: Arc<AtomicBool>,
+    /// The latest [SyncState], observed by sync state subscriptions.
+    sync_state: watch::Sender<SyncState>,
     /// The chain id
     chain_id: Arc<AtomicU64>,
 }
@@ -15,12 +15,12 @@ use reth_eth_wire::{
 use reth_interfaces::{p2p::error::RequestResult, sync::SyncStateProvider};
 use reth_network_api::{Peers, ReputationChangeKind};
 use reth_primitives::{
-    FromRecoveredTransaction, IntoRecoveredTransaction, PeerId, TransactionSigned, TxHash, H256,
+    FromRecoveredTransaction, IntoRecoveredTransaction, PeerId, PooledTransactionsElement,
+    TransactionSigned, TxHash, TxType, EIP4844_TX_TYPE_ID, H256,
 };
-use reth_rlp::Encodable;
 use reth_transaction_pool::{
-    error::PoolResult, PoolTransaction, PropagateKind, PropagatedTransactions, TransactionPool,
-    ValidPoolTransaction,
+    error::PoolResult, PoolTransaction, PropagateKind, PropagatedTransactions, TransactionOrigin,
+    TransactionPool, ValidPoolTransaction,
 };
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -167,7 +167,9 @@ where
                 .pool
                 .get_all(request.0)
                 .into_iter()
-                .map(|tx| tx.transaction.to_recovered_transaction().into_signed())
+                // private transactions are never shared with peers
+                .filter(|tx| !tx.is_private())
+                .filter_map(|tx| pooled_transactions_element(&self.pool, &tx))
                 .collect::<Vec<_>>();
 
             // we sent a response at which point we assume that the peer is aware of the transaction
@@ -180,6 +182,8 @@ where
 
     /// Invoked when a new transaction is pending.
     ///
+    /// Private transactions are never propagated.
+    ///
     /// When new transactions appear in the pool, we propagate them to the network using the
     /// `Transactions` and `NewPooledTransactionHashes` messages. The Transactions message relays
     /// complete transaction objects and is typically sent to a small, random fraction of connected
@@ -201,10 +205,8 @@ where
             self.pool
                 .get_all(hashes)
                 .into_iter()
-                .map(|tx| {
-                    let tx = Arc::new(tx.transaction.to_recovered_transaction().into_signed());
-                    PropagateTransaction::new(tx)
-                })
+                .filter(|tx| !tx.is_private())
+                .map(PropagateTransaction::new)
                 .collect(),
         );
 
@@ -228,39 +230,44 @@ where
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
         for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
+            // determine whether to send full tx objects or hashes.
+            let send_full = peer_idx <= max_num_full;
+
             // filter all transactions unknown to the peer
             let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
             let mut full_transactions = Vec::new();
             for tx in to_propagate.iter() {
                 if peer.transactions.insert(tx.hash()) {
-                    hashes.push(tx);
-                    full_transactions.push(Arc::clone(&tx.transaction));
+                    // blob transactions are never broadcast in full, only announced
+                    if send_full && !tx.is_blob() {
+                        full_transactions.push(Arc::clone(&tx.transaction));
+                    } else {
+                        hashes.push(tx);
+                    }
                 }
             }
             let mut new_pooled_hashes = hashes.build();
 
-            if !full_transactions.is_empty() {
-                // determine whether to send full tx objects or hashes.
-                if peer_idx > max_num_full {
-                    // enforce tx soft limit per message for the (unlikely) event the number of
-                    // hashes exceeds it
-                    new_pooled_hashes.truncate(NEW_POOLED_TRANSACTION_HASHES_SOFT_LIMIT);
-
-                    for hash in new_pooled_hashes.iter_hashes().copied() {
-                        propagated.0.entry(hash).or_default().push(PropagateKind::Hash(*peer_id));
-                    }
-                    // send hashes of transactions
-                    self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
-                } else {
-                    // TODO ensure max message size
+            if !new_pooled_hashes.is_empty() {
+                // enforce tx soft limit per message for the (unlikely) event the number of
+                // hashes exceeds it
+                new_pooled_hashes.truncate(NEW_POOLED_TRANSACTION_HASHES_SOFT_LIMIT);
 
-                    // send full transactions
-                    self.network.send_transactions(*peer_id, full_transactions);
+                for hash in new_pooled_hashes.iter_hashes().copied() {
+                    propagated.0.entry(hash).or_default().push(PropagateKind::Hash(*peer_id));
+                }
+                // send hashes of transactions
+                self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
+            }
 
-                    for hash in new_pooled_hashes.into_iter_hashes() {
-                        propagated.0.entry(hash).or_default().push(PropagateKind::Full(*peer_id));
-                    }
+            if !full_transactions.is_empty() {
+                // TODO ensure max message size
+
+                for tx in full_transactions.iter() {
+                    propagated.0.entry(tx.hash).or_default().push(PropagateKind::Full(*peer_id));
                 }
+                // send full transactions
+                self.network.send_transactions(*peer_id, full_transactions);
             }
         }
 
@@ -320,7 +327,11 @@ where
     fn on_network_tx_event(&mut self, event: NetworkTransactionEvent) {
         match event {
             NetworkTransactionEvent::IncomingTransactions { peer_id, msg } => {
-                self.import_transactions(peer_id, msg.0, TransactionSource::Broadcast);
+                self.import_transactions(
+                    peer_id,
+                    msg.0.into_iter().map(Into::into).collect(),
+                    TransactionSource::Broadcast,
+                );
             }
             NetworkTransactionEvent::IncomingPooledTransactionHashes { peer_id, msg } => {
                 self.on_new_pooled_transaction_hashes(peer_id, msg)
@@ -372,6 +383,7 @@ where
                         .pool
                         .pooled_transactions()
                         .into_iter()
+                        .filter(|tx| !tx.is_private())
                         .take(NEW_POOLED_TRANSACTION_HASHES_SOFT_LIMIT)
                     {
                         peer.transactions.insert(*pooled_tx.hash());
@@ -388,10 +400,13 @@ where
     }
 
     /// Starts the import process for the given transactions.
+    ///
+    /// Blob transactions are only accepted together with their sidecar, which means they can't be
+    /// part of a `Transactions` broadcast.
     fn import_transactions(
         &mut self,
         peer_id: PeerId,
-        transactions: Vec<TransactionSigned>,
+        transactions: Vec<PooledTransactionsElement>,
         source: TransactionSource,
     ) {
         // If the node is currently syncing, ignore transactions
@@ -405,6 +420,12 @@ where
 
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             for tx in transactions {
+                let (tx, sidecar) = tx.into_parts();
+                if tx.tx_type() == TxType::EIP4844 && sidecar.is_none() {
+                    has_bad_transactions = true;
+                    continue
+                }
+
                 // recover transaction
                 let tx = if let Some(tx) = tx.into_ecrecovered() {
                     tx
@@ -432,7 +453,17 @@ where
 
                         let pool = self.pool.clone();
                         let import = Box::pin(async move {
-                            pool.add_external_transaction(pool_transaction).await
+                            match sidecar {
+                                Some(sidecar) => {
+                                    pool.add_blob_transaction(
+                                        TransactionOrigin::External,
+                                        pool_transaction,
+                                        sidecar,
+                                    )
+                                    .await
+                                }
+                                None => pool.add_external_transaction(pool_transaction).await,
+                            }
                         });
 
                         self.pool_imports.push(import);
@@ -539,9 +570,27 @@ where
     }
 }
 
+/// Returns the transaction in the form it's shared with peers.
+///
+/// Blob transactions are paired with their sidecar, they can't be shared if the pool doesn't have
+/// the sidecar.
+fn pooled_transactions_element<Pool: TransactionPool>(
+    pool: &Pool,
+    tx: &ValidPoolTransaction<Pool::Transaction>,
+) -> Option<PooledTransactionsElement> {
+    let transaction = tx.transaction.to_recovered_transaction().into_signed();
+    if tx.tx_type() == EIP4844_TX_TYPE_ID {
+        let sidecar = pool.get_blob_sidecar(tx.hash())?;
+        Some(PooledTransactionsElement::blob_transaction(transaction, sidecar))
+    } else {
+        Some(transaction.into())
+    }
+}
+
 /// A transaction that's about to be propagated to multiple peers.
 struct PropagateTransaction {
     tx_type: u8,
+    /// The length of the transaction in the form it's shared with peers.
     length: usize,
     transaction: Arc<TransactionSigned>,
 }
@@ -553,8 +602,14 @@ impl PropagateTransaction {
         self.transaction.hash
     }
 
-    fn new(transaction: Arc<TransactionSigned>) -> Self {
-        Self { tx_type: transaction.tx_type().into(), length: transaction.length(), transaction }
+    /// Whether this is a blob transaction, which is only announced and never broadcast in full.
+    fn is_blob(&self) -> bool {
+        self.tx_type == EIP4844_TX_TYPE_ID
+    }
+
+    fn new<T: PoolTransaction>(tx: Arc<ValidPoolTransaction<T>>) -> Self {
+        let transaction = Arc::new(tx.transaction.to_recovered_transaction().into_signed());
+        Self { tx_type: tx.tx_type(), length: tx.encoded_length, transaction }
     }
 }
 
@@ -667,12 +722,72 @@ pub enum NetworkTransactionEvent {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{NetworkConfigBuilder, NetworkManager};
+    use crate::{test_utils::unused_tcp_udp, NetworkConfigBuilder, NetworkManager};
+    use reth_eth_wire::{capability::Capabilities, Status};
     use reth_interfaces::sync::{SyncState, SyncStateUpdater};
     use reth_provider::test_utils::NoopProvider;
-    use reth_transaction_pool::test_utils::testing_pool;
+    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction, TestPool};
     use secp256k1::SecretKey;
 
+    /// Creates a transactions manager for the pool, the network listens on unused ports.
+    async fn transactions_manager(pool: TestPool) -> TransactionsManager<TestPool> {
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        let (listener_addr, discovery_addr) = unused_tcp_udp();
+        let config = NetworkConfigBuilder::new(secret_key)
+            .listener_addr(listener_addr)
+            .discovery_addr(discovery_addr)
+            .disable_dns_discovery()
+            .build(NoopProvider::default());
+        let (_, network, transactions, _) = NetworkManager::new(config)
+            .await
+            .unwrap()
+            .into_builder()
+            .transactions(pool)
+            .split_with_handle();
+        tokio::task::spawn(network);
+        transactions
+    }
+
+    /// Returns a request channel of a session with the peer.
+    fn session(peer_id: PeerId) -> (PeerRequestSender, mpsc::Receiver<PeerRequest>) {
+        let (tx, rx) = mpsc::channel(1);
+        (PeerRequestSender::new(peer_id, tx), rx)
+    }
+
+    /// Adds a connected peer without announcing the pooled transactions to it and returns the
+    /// receiver of its requests.
+    fn add_peer(
+        transactions: &mut TransactionsManager<TestPool>,
+        peer_id: PeerId,
+    ) -> mpsc::Receiver<PeerRequest> {
+        let (request_tx, rx) = session(peer_id);
+        let peer = Peer {
+            transactions: LruCache::new(NonZeroUsize::new(PEER_TRANSACTION_CACHE_LIMIT).unwrap()),
+            request_tx,
+            version: EthVersion::Eth68,
+        };
+        transactions.peers.insert(peer_id, peer);
+        rx
+    }
+
+    /// Adds an external and a private transaction to the pool and returns their hashes.
+    async fn add_external_and_private(pool: &TestPool) -> (TxHash, TxHash) {
+        let external = pool
+            .add_transaction(TransactionOrigin::External, MockTransaction::eip1559())
+            .await
+            .unwrap();
+        let private = pool
+            .add_transaction(TransactionOrigin::Private, MockTransaction::eip1559())
+            .await
+            .unwrap();
+        (external, private)
+    }
+
+    /// Returns the hash of the pooled transaction in the form it's shared with peers.
+    fn shared_hash(pool: &TestPool, hash: TxHash) -> TxHash {
+        pool.get(&hash).unwrap().transaction.to_recovered_transaction().hash()
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_ignored_tx_broadcasts_while_syncing() {
         reth_tracing::init_test_tracing();
@@ -703,4 +818,64 @@ mod tests {
 
         assert!(pool.is_empty());
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_private_txs_are_not_propagated() {
+        let pool = testing_pool();
+        let mut transactions = transactions_manager(pool.clone()).await;
+        let (external, private) = add_external_and_private(&pool).await;
+
+        let peer_id = PeerId::random();
+        let _rx = add_peer(&mut transactions, peer_id);
+
+        transactions.on_new_transactions([external, private]);
+
+        // only transactions that were sent are marked as known by the peer
+        let peer = &transactions.peers[&peer_id];
+        assert!(peer.transactions.contains(&shared_hash(&pool, external)));
+        assert!(!peer.transactions.contains(&shared_hash(&pool, private)));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_private_txs_are_not_served() {
+        let pool = testing_pool();
+        let mut transactions = transactions_manager(pool.clone()).await;
+        let (external, private) = add_external_and_private(&pool).await;
+
+        let peer_id = PeerId::random();
+        let _rx = add_peer(&mut transactions, peer_id);
+
+        let (response, rx) = oneshot::channel();
+        transactions.on_network_tx_event(NetworkTransactionEvent::GetPooledTransactions {
+            peer_id,
+            request: GetPooledTransactions(vec![external, private]),
+            response,
+        });
+
+        let PooledTransactions(served) = rx.await.unwrap().unwrap();
+        let served = served.iter().map(PooledTransactionsElement::hash).collect::<Vec<_>>();
+        assert_eq!(served, vec![shared_hash(&pool, external)]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_private_txs_are_not_announced_to_new_peers() {
+        let pool = testing_pool();
+        let mut transactions = transactions_manager(pool.clone()).await;
+        let (external, private) = add_external_and_private(&pool).await;
+
+        let peer_id = PeerId::random();
+        let (messages, _rx) = session(peer_id);
+        transactions.on_network_event(NetworkEvent::SessionEstablished {
+            peer_id,
+            capabilities: Arc::new(Capabilities::from(vec![])),
+            messages,
+            status: Status::default(),
+            version: EthVersion::Eth68,
+        });
+
+        // the announced transactions are marked as known by the peer
+        let peer = &transactions.peers[&peer_id];
+        assert!(peer.transactions.contains(&external));
+        assert!(!peer.transactions.contains(&private));
+    }
 }
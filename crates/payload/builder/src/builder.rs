@@ -0,0 +1,269 @@
+use crate::{BuiltPayload, PayloadBuilderAttributes, PayloadBuilderError};
+use reth_db::{cursor::DbCursorRO, database::Database, tables, transaction::DbTx};
+use reth_executor::{execution_result::ExecutionResult, executor::Executor};
+use reth_primitives::{
+    constants::{EIP1559_INITIAL_BASE_FEE, MAX_BLOB_GAS_PER_BLOCK},
+    eip4844::calculate_blob_gasprice,
+    proofs::{self, EMPTY_LIST_HASH},
+    Block, Bloom, ChainSpec, Hardfork, Header, IntoRecoveredTransaction, SealedBlock, U256,
+};
+use reth_provider::{trie::DBTrieLoader, LatestStateProviderRef, ProviderError};
+use reth_revm::database::{State, SubState};
+use reth_transaction_pool::{BestTransactions, TransactionPool};
+use std::sync::Arc;
+use tracing::trace;
+
+/// Builds payloads on top of the canonical tip out of the best transactions of the pool.
+#[derive(Debug)]
+pub struct PayloadBuilder<DB, Pool> {
+    /// The database, used to read the parent state and the state trie.
+    db: DB,
+    /// The transaction pool the transactions are taken from.
+    pool: Pool,
+    /// The chain spec.
+    chain_spec: Arc<ChainSpec>,
+}
+
+impl<DB, Pool> PayloadBuilder<DB, Pool> {
+    /// Create a new payload builder.
+    pub fn new(db: DB, pool: Pool, chain_spec: Arc<ChainSpec>) -> Self {
+        Self { db, pool, chain_spec }
+    }
+}
+
+impl<DB, Pool> PayloadBuilder<DB, Pool>
+where
+    DB: Database,
+    Pool: TransactionPool,
+    Pool::Transaction: IntoRecoveredTransaction,
+{
+    /// Build a payload with the given attributes.
+    ///
+    /// Transactions are executed in the order of [TransactionPool::best_transactions] until the
+    /// block is full. Transactions that do not fit into the block or fail to execute are skipped
+    /// together with their descendants.
+    ///
+    /// The payload is built on a read-only database transaction, the state root is calculated by
+    /// applying the changes of the block to the state trie of the parent in memory.
+    pub fn build(
+        &self,
+        attributes: &PayloadBuilderAttributes,
+    ) -> Result<BuiltPayload, PayloadBuilderError> {
+        let tx = self.db.tx()?;
+
+        let parent_number = tx
+            .get::<tables::HeaderNumbers>(attributes.parent)?
+            .ok_or(PayloadBuilderError::UnknownParent(attributes.parent))?;
+        let tip = tx.cursor_read::<tables::CanonicalHeaders>()?.last()?.unwrap_or_default().0;
+        if parent_number != tip {
+            return Err(PayloadBuilderError::ParentNotCanonicalTip {
+                parent: attributes.parent,
+                tip,
+            })
+        }
+        let parent = tx
+            .get::<tables::Headers>(parent_number)?
+            .ok_or(ProviderError::Header { number: parent_number })?;
+        let parent_td = tx
+            .get::<tables::HeaderTD>(parent_number)?
+            .ok_or(ProviderError::TotalDifficulty { number: parent_number })?
+            .into();
+
+        let base_fee = parent.next_block_base_fee().unwrap_or(EIP1559_INITIAL_BASE_FEE);
+        let is_shanghai =
+            self.chain_spec.fork(Hardfork::Shanghai).active_at_timestamp(attributes.timestamp);
+        let withdrawals = if is_shanghai {
+            Some(attributes.withdrawals.clone().unwrap_or_default())
+        } else {
+            None
+        };
+        let is_cancun =
+            self.chain_spec.fork(Hardfork::Cancun).active_at_timestamp(attributes.timestamp);
+        // The excess blob gas of the parent is zero on the transition block.
+        let excess_blob_gas =
+            is_cancun.then(|| parent.next_block_excess_blob_gas().unwrap_or_default());
+        let blob_gasprice = excess_blob_gas.map(calculate_blob_gasprice);
+
+        let mut header = Header {
+            parent_hash: attributes.parent,
+            ommers_hash: EMPTY_LIST_HASH,
+            beneficiary: attributes.suggested_fee_recipient,
+            withdrawals_root: withdrawals
+                .as_ref()
+                .map(|withdrawals| proofs::calculate_withdrawals_root(withdrawals.iter())),
+            number: parent.number + 1,
+            gas_limit: parent.gas_limit,
+            timestamp: attributes.timestamp,
+            mix_hash: attributes.prev_randao,
+            base_fee_per_gas: Some(base_fee),
+            excess_blob_gas,
+            ..Default::default()
+        };
+
+        let mut body = Vec::new();
+        let mut fees = U256::ZERO;
+        let (tx_changesets, block_changesets) = {
+            let state = LatestStateProviderRef::new(&tx);
+            let mut executor =
+                Executor::new(self.chain_spec.clone(), SubState::new(State::new(state)));
+            executor.init_env(&header, parent_td);
+
+            let mut cumulative_gas_used = 0;
+            let mut cumulative_blob_gas_used = 0;
+            let mut tx_changesets = Vec::new();
+            let mut best_txs = self.pool.best_transactions();
+            while let Some(pool_tx) = best_txs.next() {
+                // Skip the transaction and its descendants if it does not fit into the block.
+                if cumulative_gas_used + pool_tx.gas_limit() > header.gas_limit {
+                    best_txs.mark_invalid(&pool_tx);
+                    continue
+                }
+
+                let transaction = pool_tx.transaction.to_recovered_transaction();
+
+                // Skip blob transactions before Cancun, if they don't pay the blob gas price or
+                // if their blobs don't fit into the block.
+                if let Some(max_fee_per_blob_gas) = transaction.max_fee_per_blob_gas() {
+                    let fits = blob_gasprice.map_or(false, |blob_gasprice| {
+                        max_fee_per_blob_gas >= blob_gasprice &&
+                            cumulative_blob_gas_used + transaction.blob_gas() <=
+                                MAX_BLOB_GAS_PER_BLOCK
+                    });
+                    if !fits {
+                        best_txs.mark_invalid(&pool_tx);
+                        continue
+                    }
+                }
+
+                let changeset = match executor.execute_and_commit_transaction(
+                    &transaction,
+                    transaction.signer(),
+                    cumulative_gas_used,
+                ) {
+                    Ok(changeset) => changeset,
+                    Err(error) => {
+                        trace!(target: "payload_builder", ?error, hash = ?transaction.hash(), "Skipping invalid transaction");
+                        best_txs.mark_invalid(&pool_tx);
+                        continue
+                    }
+                };
+
+                let gas_used = changeset.receipt.cumulative_gas_used - cumulative_gas_used;
+                let tip = transaction
+                    .effective_gas_price(Some(base_fee))
+                    .saturating_sub(base_fee as u128);
+                fees += U256::from(tip) * U256::from(gas_used);
+
+                cumulative_gas_used = changeset.receipt.cumulative_gas_used;
+                cumulative_blob_gas_used += transaction.blob_gas();
+                tx_changesets.push(changeset);
+                body.push(transaction.into_signed());
+            }
+
+            header.gas_used = cumulative_gas_used;
+            header.blob_gas_used = is_cancun.then_some(cumulative_blob_gas_used);
+            let block = Block {
+                header: header.clone(),
+                body: Vec::new(),
+                ommers: Vec::new(),
+                withdrawals: withdrawals.clone(),
+            };
+            let block_changesets = executor.apply_post_block_changes(&block, parent_td)?;
+            (tx_changesets, block_changesets)
+        };
+
+        let receipts = tx_changesets.iter().map(|changeset| &changeset.receipt);
+        header.receipts_root = proofs::calculate_receipt_root(receipts.clone());
+        header.logs_bloom = receipts.fold(Bloom::zero(), |bloom, receipt| bloom | receipt.bloom);
+        header.transactions_root = proofs::calculate_transaction_root(body.iter());
+
+        let has_state_clear_eip =
+            self.chain_spec.fork(Hardfork::SpuriousDragon).active_at_block(header.number);
+        header.state_root = DBTrieLoader::default().calculate_root_with_execution_result(
+            &tx,
+            parent.state_root,
+            &ExecutionResult { tx_changesets, block_changesets },
+            has_state_clear_eip,
+        )?;
+
+        let block =
+            SealedBlock { header: header.seal_slow(), body, ommers: Vec::new(), withdrawals };
+        Ok(BuiltPayload::new(attributes.id, block, fees))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::mdbx::{test_utils::create_test_db, EnvKind, WriteMap};
+    use reth_primitives::{
+        proofs::EMPTY_ROOT, Address, ChainSpecBuilder, GenesisAccount, Withdrawal, H256, H64,
+    };
+    use reth_provider::insert_canonical_block;
+    use reth_transaction_pool::test_utils::testing_pool;
+    use std::collections::HashMap;
+
+    #[test]
+    fn build_empty_payload_with_withdrawals() {
+        let db = create_test_db::<WriteMap>(EnvKind::RW);
+        let genesis = SealedBlock {
+            header: Header {
+                state_root: EMPTY_ROOT,
+                gas_limit: 30_000_000,
+                base_fee_per_gas: Some(EIP1559_INITIAL_BASE_FEE),
+                ..Default::default()
+            }
+            .seal_slow(),
+            ..Default::default()
+        };
+        let tx = db.tx_mut().unwrap();
+        insert_canonical_block(&tx, &genesis, false).unwrap();
+        tx.commit().unwrap();
+
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().shanghai_activated().build());
+        let builder = PayloadBuilder::new(db, testing_pool(), chain_spec);
+
+        let withdrawal = Withdrawal { address: Address::random(), amount: 1, ..Default::default() };
+        let attributes = PayloadBuilderAttributes {
+            id: H64::random(),
+            parent: genesis.hash(),
+            timestamp: 1,
+            suggested_fee_recipient: Address::random(),
+            prev_randao: H256::random(),
+            withdrawals: Some(vec![withdrawal.clone()]),
+        };
+        let payload = builder.build(&attributes).unwrap();
+        let block = payload.block();
+
+        assert_eq!(payload.id(), attributes.id);
+        assert_eq!(payload.fees(), U256::ZERO);
+        assert_eq!(block.number, 1);
+        assert_eq!(block.parent_hash, genesis.hash());
+        assert_eq!(block.mix_hash, attributes.prev_randao);
+        assert_eq!(block.gas_used, 0);
+        assert!(block.body.is_empty());
+        assert_eq!(block.withdrawals, Some(vec![withdrawal.clone()]));
+        assert_eq!(
+            block.withdrawals_root,
+            Some(proofs::calculate_withdrawals_root([withdrawal.clone()].iter()))
+        );
+        // The withdrawal created an account.
+        let account = GenesisAccount { balance: withdrawal.amount_wei(), ..Default::default() };
+        assert_eq!(
+            block.state_root,
+            proofs::genesis_state_root(&HashMap::from([(withdrawal.address, account)]))
+        );
+        // The block was not written to the database.
+        let tx = builder.db.tx().unwrap();
+        assert_eq!(tx.get::<tables::HeaderNumbers>(block.hash()).unwrap(), None);
+        assert_eq!(
+            tx.cursor_read::<tables::CanonicalHeaders>().unwrap().last().unwrap().unwrap().0,
+            0
+        );
+        drop(tx);
+
+        // Payloads can only be built on top of the canonical tip.
+        let unknown = PayloadBuilderAttributes { parent: H256::random(), ..attributes };
+        assert!(matches!(builder.build(&unknown), Err(PayloadBuilderError::UnknownParent(_))));
+    }
+}
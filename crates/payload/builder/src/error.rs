@@ -0,0 +1,31 @@
+use reth_primitives::{BlockNumber, H256};
+use reth_provider::{trie::TrieError, ProviderError};
+use thiserror::Error;
+
+/// Error returned by the [`PayloadBuilder`][crate::PayloadBuilder].
+#[derive(Error, Debug)]
+pub enum PayloadBuilderError {
+    /// The parent block of the payload is unknown.
+    #[error("Unknown parent block: {0:?}")]
+    UnknownParent(H256),
+    /// Payloads can only be built on top of the canonical tip.
+    #[error("Parent block {parent:?} is not the canonical tip {tip}")]
+    ParentNotCanonicalTip {
+        /// The parent block hash of the payload.
+        parent: H256,
+        /// The number of the canonical tip.
+        tip: BlockNumber,
+    },
+    /// Failed to execute the post block changes.
+    #[error(transparent)]
+    Execution(#[from] reth_interfaces::executor::Error),
+    /// Failed to read the database.
+    #[error(transparent)]
+    Database(#[from] reth_interfaces::db::Error),
+    /// The database is missing data of the parent block.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// Failed to calculate the state root.
+    #[error(transparent)]
+    Trie(#[from] TrieError),
+}
@@ -0,0 +1,32 @@
+#![warn(missing_docs, unreachable_pub)]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![doc(test(
+    no_crate_inject,
+    attr(deny(warnings, rust_2018_idioms), allow(dead_code, unused_variables))
+))]
+
+//! Local payload builder for the Engine API.
+//!
+//! The [PayloadBuilderService] starts a build job for every `engine_forkchoiceUpdated` call that
+//! carries payload attributes. A job repeatedly builds a block on top of the parent state out of
+//! the best transactions of the pool and keeps the most valuable one until the consensus layer
+//! requests it via `engine_getPayload`.
+
+/// The payload builder.
+mod builder;
+
+/// Payload builder error.
+mod error;
+
+/// Payload types.
+mod payload;
+
+/// The payload builder service and its handle.
+mod service;
+
+pub use builder::PayloadBuilder;
+pub use error::PayloadBuilderError;
+pub use payload::{BuiltPayload, PayloadBuilderAttributes};
+pub use service::{
+    PayloadBuilderConfig, PayloadBuilderHandle, PayloadBuilderService, PayloadServiceCommand,
+};
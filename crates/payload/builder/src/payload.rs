@@ -0,0 +1,121 @@
+use reth_primitives::{keccak256, Address, SealedBlock, Withdrawal, H256, H64, U256};
+use reth_rlp::Encodable;
+use reth_rpc_types::engine::{ExecutionPayload, PayloadAttributes};
+
+/// The attributes of a payload build job, derived from the payload attributes of an
+/// `engine_forkchoiceUpdated` call and the head block it was issued for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PayloadBuilderAttributes {
+    /// Id of the payload.
+    pub id: H64,
+    /// Parent block to build the payload on top of.
+    pub parent: H256,
+    /// Timestamp of the payload.
+    pub timestamp: u64,
+    /// Address of the recipient of the transaction fees.
+    pub suggested_fee_recipient: Address,
+    /// The `prevRandao` value of the payload, stored as the header mix hash.
+    pub prev_randao: H256,
+    /// Withdrawals to include in the payload, present from Shanghai on.
+    pub withdrawals: Option<Vec<Withdrawal>>,
+}
+
+impl PayloadBuilderAttributes {
+    /// Create new payload builder attributes for the given parent block.
+    ///
+    /// The payload id is derived from the parent and the attributes, so the same request always
+    /// maps to the same payload.
+    pub fn new(parent: H256, attributes: PayloadAttributes) -> Self {
+        let id = payload_id(&parent, &attributes);
+        Self {
+            id,
+            parent,
+            timestamp: attributes.timestamp.as_u64(),
+            suggested_fee_recipient: attributes.suggested_fee_recipient,
+            prev_randao: attributes.prev_randao,
+            withdrawals: attributes.withdrawals,
+        }
+    }
+}
+
+/// Compute the payload id by hashing the parent block hash and the payload attributes, similar to
+/// geth.
+fn payload_id(parent: &H256, attributes: &PayloadAttributes) -> H64 {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(parent.as_bytes());
+    buf.extend_from_slice(&attributes.timestamp.as_u64().to_be_bytes());
+    buf.extend_from_slice(attributes.prev_randao.as_bytes());
+    buf.extend_from_slice(attributes.suggested_fee_recipient.as_bytes());
+    if let Some(withdrawals) = &attributes.withdrawals {
+        withdrawals.encode(&mut buf);
+    }
+    H64::from_slice(&keccak256(buf)[..8])
+}
+
+/// A payload built by the [`PayloadBuilder`][crate::PayloadBuilder].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuiltPayload {
+    /// Id of the payload.
+    id: H64,
+    /// The built block.
+    block: SealedBlock,
+    /// The fees paid to the fee recipient by the transactions of the block.
+    fees: U256,
+}
+
+impl BuiltPayload {
+    /// Create a new built payload.
+    pub fn new(id: H64, block: SealedBlock, fees: U256) -> Self {
+        Self { id, block, fees }
+    }
+
+    /// Returns the id of the payload.
+    pub fn id(&self) -> H64 {
+        self.id
+    }
+
+    /// Returns the built block.
+    pub fn block(&self) -> &SealedBlock {
+        &self.block
+    }
+
+    /// Returns the fees collected by the block.
+    pub fn fees(&self) -> U256 {
+        self.fees
+    }
+}
+
+impl From<BuiltPayload> for ExecutionPayload {
+    fn from(value: BuiltPayload) -> Self {
+        value.block.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_id_is_deterministic() {
+        let attributes = PayloadAttributes {
+            timestamp: 1.into(),
+            prev_randao: H256::random(),
+            suggested_fee_recipient: Address::random(),
+            withdrawals: None,
+        };
+        let parent = H256::random();
+
+        let id = PayloadBuilderAttributes::new(parent, attributes.clone()).id;
+        assert_eq!(id, PayloadBuilderAttributes::new(parent, attributes.clone()).id);
+
+        let other_parent = PayloadBuilderAttributes::new(H256::random(), attributes.clone()).id;
+        assert_ne!(id, other_parent);
+
+        let with_withdrawals = PayloadBuilderAttributes::new(
+            parent,
+            PayloadAttributes { withdrawals: Some(vec![Withdrawal::default()]), ..attributes },
+        )
+        .id;
+        assert_ne!(id, with_withdrawals);
+    }
+}
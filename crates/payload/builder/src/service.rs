@@ -0,0 +1,288 @@
+use crate::{BuiltPayload, PayloadBuilder, PayloadBuilderAttributes, PayloadBuilderError};
+use futures_util::{future::BoxFuture, FutureExt, StreamExt};
+use reth_db::database::Database;
+use reth_primitives::{IntoRecoveredTransaction, H64};
+use reth_transaction_pool::TransactionPool;
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::{Instant, Interval, MissedTickBehavior, Sleep},
+};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{debug, trace, warn};
+
+/// Settings of the [PayloadBuilderService].
+#[derive(Debug, Clone, Copy)]
+pub struct PayloadBuilderConfig {
+    /// How often a payload job rebuilds its payload.
+    pub interval: Duration,
+    /// How long a payload job is kept after it was started. Jobs that are not resolved within the
+    /// deadline are discarded.
+    pub deadline: Duration,
+}
+
+impl Default for PayloadBuilderConfig {
+    fn default() -> Self {
+        Self { interval: Duration::from_secs(1), deadline: Duration::from_secs(12) }
+    }
+}
+
+/// Message type for communicating with the [PayloadBuilderService].
+#[derive(Debug)]
+pub enum PayloadServiceCommand {
+    /// Start a new payload job. Jobs with an already known id are ignored.
+    BuildNewPayload(PayloadBuilderAttributes),
+    /// Stop the job of the given payload and return its best payload.
+    Resolve(H64, oneshot::Sender<Option<Arc<BuiltPayload>>>),
+}
+
+/// A handle to the [PayloadBuilderService].
+#[derive(Debug, Clone)]
+pub struct PayloadBuilderHandle {
+    to_service: mpsc::UnboundedSender<PayloadServiceCommand>,
+}
+
+impl PayloadBuilderHandle {
+    /// Create a new handle that sends commands to the service over the given channel.
+    pub fn new(to_service: mpsc::UnboundedSender<PayloadServiceCommand>) -> Self {
+        Self { to_service }
+    }
+
+    /// Start building a payload with the given attributes and return its id.
+    ///
+    /// The payload can be fetched with [PayloadBuilderHandle::resolve] as soon as the first build
+    /// finished.
+    pub fn new_payload(&self, attributes: PayloadBuilderAttributes) -> H64 {
+        let id = attributes.id;
+        let _ = self.to_service.send(PayloadServiceCommand::BuildNewPayload(attributes));
+        id
+    }
+
+    /// Stop building the payload with the given id and return the best payload built so far.
+    ///
+    /// If no payload was built yet, this waits for the build in progress. Resolves to `None` if
+    /// the payload is unknown.
+    pub fn resolve(&self, id: H64) -> impl Future<Output = Option<Arc<BuiltPayload>>> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(PayloadServiceCommand::Resolve(id, tx));
+        async move { rx.await.ok().flatten() }
+    }
+}
+
+/// The result of a build that runs on a blocking thread.
+type PendingPayload = oneshot::Receiver<Result<BuiltPayload, PayloadBuilderError>>;
+
+/// A payload that is periodically rebuilt until it is resolved or the deadline is reached.
+struct PayloadJob {
+    attributes: Arc<PayloadBuilderAttributes>,
+    /// The most valuable payload built so far.
+    best_payload: Option<Arc<BuiltPayload>>,
+    /// The build in progress, if any.
+    pending: Option<PendingPayload>,
+    interval: Interval,
+    deadline: Pin<Box<Sleep>>,
+}
+
+impl PayloadJob {
+    /// Keep the built payload if it pays more fees than the best one so far.
+    fn on_build_result(
+        &mut self,
+        result: Result<Result<BuiltPayload, PayloadBuilderError>, oneshot::error::RecvError>,
+    ) {
+        let id = self.attributes.id;
+        match result {
+            Ok(Ok(payload)) => {
+                let is_better =
+                    self.best_payload.as_ref().map_or(true, |best| payload.fees() > best.fees());
+                if is_better {
+                    trace!(target: "payload_builder", ?id, fees = %payload.fees(), "Built better payload");
+                    self.best_payload = Some(Arc::new(payload));
+                }
+            }
+            Ok(Err(error)) => {
+                warn!(target: "payload_builder", ?id, ?error, "Failed to build payload");
+            }
+            Err(_) => {
+                warn!(target: "payload_builder", ?id, "Payload build was cancelled");
+            }
+        }
+    }
+
+    /// Resolve the job to the best payload built so far, or the one that is currently built.
+    fn resolve(self) -> BoxFuture<'static, Option<Arc<BuiltPayload>>> {
+        match (self.best_payload, self.pending) {
+            (Some(payload), _) => futures_util::future::ready(Some(payload)).boxed(),
+            (None, Some(pending)) => async move { pending.await.ok()?.ok().map(Arc::new) }.boxed(),
+            (None, None) => futures_util::future::ready(None).boxed(),
+        }
+    }
+}
+
+/// A service that builds payloads for the Engine API.
+///
+/// Every job rebuilds its payload on the configured interval and keeps the one that pays the
+/// highest fees, since the pool may have received more valuable transactions in the meantime.
+/// Builds run on blocking threads.
+#[must_use = "PayloadBuilderService does nothing unless polled."]
+pub struct PayloadBuilderService<DB, Pool> {
+    builder: Arc<PayloadBuilder<DB, Pool>>,
+    config: PayloadBuilderConfig,
+    jobs: HashMap<H64, PayloadJob>,
+    command_rx: UnboundedReceiverStream<PayloadServiceCommand>,
+}
+
+impl<DB, Pool> PayloadBuilderService<DB, Pool>
+where
+    DB: Database + 'static,
+    Pool: TransactionPool + 'static,
+    Pool::Transaction: IntoRecoveredTransaction,
+{
+    /// Create a new payload builder service and the handle to it.
+    pub fn new(
+        builder: PayloadBuilder<DB, Pool>,
+        config: PayloadBuilderConfig,
+    ) -> (Self, PayloadBuilderHandle) {
+        let (to_service, command_rx) = mpsc::unbounded_channel();
+        let service = Self {
+            builder: Arc::new(builder),
+            config,
+            jobs: HashMap::new(),
+            command_rx: UnboundedReceiverStream::new(command_rx),
+        };
+        (service, PayloadBuilderHandle::new(to_service))
+    }
+
+    fn on_command(&mut self, command: PayloadServiceCommand) {
+        match command {
+            PayloadServiceCommand::BuildNewPayload(attributes) => {
+                if self.jobs.contains_key(&attributes.id) {
+                    return
+                }
+                debug!(target: "payload_builder", id = ?attributes.id, parent = ?attributes.parent, "Starting payload job");
+                // The first build starts right away, rebuilds follow on the interval.
+                let mut interval = tokio::time::interval_at(
+                    Instant::now() + self.config.interval,
+                    self.config.interval,
+                );
+                interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+                let attributes = Arc::new(attributes);
+                let job = PayloadJob {
+                    pending: Some(spawn_build(&self.builder, Arc::clone(&attributes))),
+                    attributes,
+                    best_payload: None,
+                    interval,
+                    deadline: Box::pin(tokio::time::sleep(self.config.deadline)),
+                };
+                self.jobs.insert(job.attributes.id, job);
+            }
+            PayloadServiceCommand::Resolve(id, tx) => {
+                let Some(job) = self.jobs.remove(&id) else {
+                    let _ = tx.send(None);
+                    return
+                };
+                trace!(target: "payload_builder", ?id, "Resolving payload");
+                let payload = job.resolve();
+                tokio::spawn(async move {
+                    let _ = tx.send(payload.await);
+                });
+            }
+        }
+    }
+
+    /// Advance all jobs and drop the ones that reached their deadline.
+    fn poll_jobs(&mut self, cx: &mut Context<'_>) {
+        let mut expired = Vec::new();
+        for (id, job) in self.jobs.iter_mut() {
+            if job.deadline.as_mut().poll(cx).is_ready() {
+                expired.push(*id);
+                continue
+            }
+
+            loop {
+                // Only one build per job at a time.
+                if let Some(pending) = job.pending.as_mut() {
+                    let Poll::Ready(result) = pending.poll_unpin(cx) else { break };
+                    job.pending = None;
+                    job.on_build_result(result);
+                }
+
+                if job.interval.poll_tick(cx).is_pending() {
+                    break
+                }
+                job.pending = Some(spawn_build(&self.builder, Arc::clone(&job.attributes)));
+            }
+        }
+
+        for id in expired {
+            debug!(target: "payload_builder", ?id, "Payload job reached its deadline");
+            self.jobs.remove(&id);
+        }
+    }
+}
+
+/// Build a payload on a blocking thread.
+fn spawn_build<DB, Pool>(
+    builder: &Arc<PayloadBuilder<DB, Pool>>,
+    attributes: Arc<PayloadBuilderAttributes>,
+) -> PendingPayload
+where
+    DB: Database + 'static,
+    Pool: TransactionPool + 'static,
+    Pool::Transaction: IntoRecoveredTransaction,
+{
+    let (tx, rx) = oneshot::channel();
+    let builder = Arc::clone(builder);
+    tokio::task::spawn_blocking(move || {
+        let _ = tx.send(builder.build(&attributes));
+    });
+    rx
+}
+
+impl<DB, Pool> Future for PayloadBuilderService<DB, Pool>
+where
+    DB: Database + Unpin + 'static,
+    Pool: TransactionPool + Unpin + 'static,
+    Pool::Transaction: IntoRecoveredTransaction,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            match this.command_rx.poll_next_unpin(cx) {
+                Poll::Ready(Some(command)) => this.on_command(command),
+                // channel closed
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Pending => break,
+            }
+        }
+
+        this.poll_jobs(cx);
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::mdbx::{test_utils::create_test_db, EnvKind, WriteMap};
+    use reth_primitives::MAINNET;
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    #[tokio::test]
+    async fn resolve_unknown_payload() {
+        let db = create_test_db::<WriteMap>(EnvKind::RW);
+        let builder = PayloadBuilder::new(db, testing_pool(), Arc::new(MAINNET.clone()));
+        let (service, handle) = PayloadBuilderService::new(builder, Default::default());
+        tokio::spawn(service);
+
+        assert_eq!(handle.resolve(H64::random()).await, None);
+    }
+}
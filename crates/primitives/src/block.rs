@@ -1,4 +1,4 @@
-use crate::{Header, SealedHeader, TransactionSigned, Withdrawal, H256};
+use crate::{Address, Header, SealedHeader, TransactionSigned, Withdrawal, H256};
 use ethers_core::types::BlockNumber;
 use reth_codecs::derive_arbitrary;
 use reth_rlp::{Decodable, DecodeError, Encodable, RlpDecodable, RlpEncodable};
@@ -7,7 +7,12 @@ use serde::{
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::{fmt, fmt::Formatter, ops::Deref, str::FromStr};
+use std::{
+    fmt,
+    fmt::Formatter,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
 
 /// Ethereum full block.
 ///
@@ -74,6 +79,21 @@ impl SealedBlock {
             withdrawals: self.withdrawals,
         }
     }
+
+    /// Recover the senders of all transactions in the block.
+    ///
+    /// Returns `None` if any of the transactions has an invalid signature.
+    pub fn senders(&self) -> Option<Vec<Address>> {
+        self.body.iter().map(|tx| tx.recover_signer()).collect()
+    }
+
+    /// Recover the senders and seal the block together with them.
+    ///
+    /// Returns `None` if any of the transactions has an invalid signature.
+    pub fn seal_with_senders(self) -> Option<SealedBlockWithSenders> {
+        let senders = self.senders()?;
+        Some(SealedBlockWithSenders { block: self, senders })
+    }
 }
 
 impl Deref for SealedBlock {
@@ -83,6 +103,42 @@ impl Deref for SealedBlock {
     }
 }
 
+/// Sealed block with the senders of its transactions recovered.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SealedBlockWithSenders {
+    /// Sealed block
+    pub block: SealedBlock,
+    /// List of senders that match the transactions of the block.
+    pub senders: Vec<Address>,
+}
+
+impl SealedBlockWithSenders {
+    /// Create a new sealed block with senders.
+    ///
+    /// Returns `None` if the number of senders does not match the number of transactions.
+    pub fn new(block: SealedBlock, senders: Vec<Address>) -> Option<Self> {
+        (block.body.len() == senders.len()).then_some(Self { block, senders })
+    }
+
+    /// Split the structure into the sealed block and its senders.
+    pub fn into_components(self) -> (SealedBlock, Vec<Address>) {
+        (self.block, self.senders)
+    }
+}
+
+impl Deref for SealedBlockWithSenders {
+    type Target = SealedBlock;
+    fn deref(&self) -> &Self::Target {
+        &self.block
+    }
+}
+
+impl DerefMut for SealedBlockWithSenders {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.block
+    }
+}
+
 /// Either a block hash _or_ a block number
 #[derive_arbitrary(rlp)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -154,6 +154,15 @@ impl ChainSpec {
                 None
             };
 
+        // If cancun is activated, initialize the header with zero blob gas used and zero excess
+        // blob gas.
+        let (blob_gas_used, excess_blob_gas) =
+            if self.fork(Hardfork::Cancun).active_at_timestamp(self.genesis.timestamp) {
+                (Some(0), Some(0))
+            } else {
+                (None, None)
+            };
+
         Header {
             gas_limit: self.genesis.gas_limit,
             difficulty: self.genesis.difficulty,
@@ -165,6 +174,8 @@ impl ChainSpec {
             beneficiary: self.genesis.coinbase,
             base_fee_per_gas,
             withdrawals_root,
+            blob_gas_used,
+            excess_blob_gas,
             ..Default::default()
         }
     }
@@ -456,6 +467,13 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Enable Cancun at genesis.
+    pub fn cancun_activated(mut self) -> Self {
+        self = self.shanghai_activated();
+        self.hardforks.insert(Hardfork::Cancun, ForkCondition::Timestamp(0));
+        self
+    }
+
     /// Build the resulting [`ChainSpec`].
     ///
     /// # Panics
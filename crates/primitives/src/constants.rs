@@ -15,6 +15,44 @@ pub const EIP1559_BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
 /// Elasticity multiplier as defined in [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)
 pub const EIP1559_ELASTICITY_MULTIPLIER: u64 = 2;
 
+/// Gas consumption of a single blob as defined in [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const GAS_PER_BLOB: u64 = 131_072;
+
+/// Target blob gas consumed per block as defined in [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const TARGET_BLOB_GAS_PER_BLOCK: u64 = 393_216;
+
+/// Maximum blob gas consumed per block as defined in [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const MAX_BLOB_GAS_PER_BLOCK: u64 = 786_432;
+
+/// Maximum number of blobs in a single block.
+pub const MAX_BLOBS_PER_BLOCK: u64 = MAX_BLOB_GAS_PER_BLOCK / GAS_PER_BLOB;
+
+/// Minimum price of blob gas as defined in [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const MIN_BLOB_GASPRICE: u128 = 1;
+
+/// Controls the maximum rate of change of the blob gas price as defined in
+/// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const BLOB_GASPRICE_UPDATE_FRACTION: u128 = 3_338_477;
+
+/// Version byte of versioned hashes derived from KZG commitments, see
+/// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Number of field elements in a single blob.
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+
+/// Size of a single field element in bytes.
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+
+/// Size of a single blob in bytes.
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+
+/// Size of a KZG commitment in bytes.
+pub const BYTES_PER_COMMITMENT: usize = 48;
+
+/// Size of a KZG proof in bytes.
+pub const BYTES_PER_PROOF: usize = 48;
+
 /// Multiplier for converting gwei to wei.
 pub const GWEI_TO_WEI: u64 = 1_000_000_000;
 
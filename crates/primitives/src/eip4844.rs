@@ -0,0 +1,165 @@
+//! Helpers for working with [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) blob transactions
+
+use crate::{
+    constants::{
+        BLOB_GASPRICE_UPDATE_FRACTION, MIN_BLOB_GASPRICE, TARGET_BLOB_GAS_PER_BLOCK,
+        VERSIONED_HASH_VERSION_KZG,
+    },
+    H256,
+};
+use c_kzg::{BYTES_PER_G1_POINT, BYTES_PER_G2_POINT};
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+pub use c_kzg::{Error as KzgError, KzgSettings};
+
+/// The KZG settings of the mainnet [trusted setup](https://ceremony.ethereum.org/), which are used
+/// to verify the KZG proofs of blobs.
+pub static MAINNET_KZG_TRUSTED_SETUP: Lazy<Arc<KzgSettings>> = Lazy::new(|| {
+    Arc::new(
+        load_trusted_setup(include_str!("../res/eip4844/trusted_setup.txt"))
+            .expect("failed to load the mainnet KZG trusted setup"),
+    )
+});
+
+/// Loads the KZG settings from a trusted setup in the text format of the `c-kzg` library: the
+/// number of g1 and g2 points, followed by the hex encoded g1 and g2 points, one per line.
+pub fn load_trusted_setup(trusted_setup: &str) -> Result<KzgSettings, KzgError> {
+    let mut lines = trusted_setup.lines().map(str::trim).filter(|line| !line.is_empty());
+    let mut next_count = || {
+        lines
+            .next()
+            .and_then(|line| line.parse::<usize>().ok())
+            .ok_or_else(|| KzgError::InvalidTrustedSetup("missing number of points".to_string()))
+    };
+    let num_g1_points = next_count()?;
+    let num_g2_points = next_count()?;
+
+    let g1_points = lines
+        .by_ref()
+        .take(num_g1_points)
+        .map(decode_point::<BYTES_PER_G1_POINT>)
+        .collect::<Result<Vec<_>, _>>()?;
+    let g2_points = lines
+        .take(num_g2_points)
+        .map(decode_point::<BYTES_PER_G2_POINT>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // this also checks that the setup contains the expected number of points
+    KzgSettings::load_trusted_setup(&g1_points, &g2_points)
+}
+
+/// Decodes a hex encoded point of the trusted setup.
+fn decode_point<const N: usize>(line: &str) -> Result<[u8; N], KzgError> {
+    let mut point = [0u8; N];
+    hex::decode_to_slice(line, &mut point)
+        .map_err(|err| KzgError::InvalidHexFormat(err.to_string()))?;
+    Ok(point)
+}
+
+/// Calculates the excess blob gas of the next block, given the excess blob gas and the blob gas
+/// used of the parent block.
+pub fn calculate_excess_blob_gas(parent_excess_blob_gas: u64, parent_blob_gas_used: u64) -> u64 {
+    (parent_excess_blob_gas + parent_blob_gas_used).saturating_sub(TARGET_BLOB_GAS_PER_BLOCK)
+}
+
+/// Calculates the blob gas price for a block with the given excess blob gas.
+pub fn calculate_blob_gasprice(excess_blob_gas: u64) -> u128 {
+    fake_exponential(MIN_BLOB_GASPRICE, excess_blob_gas as u128, BLOB_GASPRICE_UPDATE_FRACTION)
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion.
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers).
+pub fn fake_exponential(factor: u128, numerator: u128, denominator: u128) -> u128 {
+    let mut i = 1;
+    let mut output = 0u128;
+    let mut numerator_accum = factor * denominator;
+    while numerator_accum > 0 {
+        output = output.saturating_add(numerator_accum);
+        numerator_accum = numerator_accum.saturating_mul(numerator) / (denominator * i);
+        i += 1;
+    }
+    output / denominator
+}
+
+/// Computes the versioned hash of a KZG commitment: the sha256 hash of the commitment with the
+/// first byte replaced by [`VERSIONED_HASH_VERSION_KZG`].
+pub fn kzg_to_versioned_hash(commitment: &[u8]) -> H256 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    H256(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::GAS_PER_BLOB;
+
+    #[test]
+    fn fake_exp() {
+        for (factor, numerator, denominator, expected) in [
+            (1u128, 0u128, 1u128, 1u128),
+            (38493, 0, 1000, 38493),
+            (0, 1234, 2345, 0),
+            (1, 2, 1, 6),
+            (1, 4, 2, 6),
+            (1, 3, 1, 16),
+            (1, 6, 2, 18),
+            (1, 4, 1, 49),
+            (1, 8, 2, 50),
+            (10, 8, 2, 542),
+            (11, 8, 2, 596),
+            (1, 5, 1, 136),
+            (1, 5, 2, 11),
+            (2, 5, 2, 23),
+            (1, 50000000, 2225652, 5709098764),
+        ] {
+            assert_eq!(fake_exponential(factor, numerator, denominator), expected);
+        }
+    }
+
+    #[test]
+    fn excess_blob_gas() {
+        assert_eq!(calculate_excess_blob_gas(0, 0), 0);
+        assert_eq!(calculate_excess_blob_gas(0, TARGET_BLOB_GAS_PER_BLOCK), 0);
+        assert_eq!(
+            calculate_excess_blob_gas(0, TARGET_BLOB_GAS_PER_BLOCK + GAS_PER_BLOB),
+            GAS_PER_BLOB
+        );
+        assert_eq!(
+            calculate_excess_blob_gas(TARGET_BLOB_GAS_PER_BLOCK, TARGET_BLOB_GAS_PER_BLOCK),
+            TARGET_BLOB_GAS_PER_BLOCK
+        );
+        assert_eq!(calculate_excess_blob_gas(GAS_PER_BLOB, 0), 0);
+    }
+
+    #[test]
+    fn blob_gasprice() {
+        assert_eq!(calculate_blob_gasprice(0), MIN_BLOB_GASPRICE);
+        assert_eq!(calculate_blob_gasprice(2314057), 1);
+        assert_eq!(calculate_blob_gasprice(2314058), 2);
+        assert_eq!(calculate_blob_gasprice(10 * 1024 * 1024), 23);
+    }
+
+    #[test]
+    fn versioned_hash() {
+        let hash = kzg_to_versioned_hash(&[0u8; 48]);
+        assert_eq!(hash.0[0], VERSIONED_HASH_VERSION_KZG);
+        assert_eq!(&hash.0[1..], &Sha256::digest([0u8; 48])[1..]);
+    }
+
+    #[test]
+    fn mainnet_trusted_setup() {
+        Lazy::force(&MAINNET_KZG_TRUSTED_SETUP);
+
+        let truncated = include_str!("../res/eip4844/trusted_setup.txt")
+            .lines()
+            .take(100)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(matches!(load_trusted_setup(&truncated), Err(KzgError::InvalidTrustedSetup(_))));
+        assert!(matches!(load_trusted_setup(""), Err(KzgError::InvalidTrustedSetup(_))));
+    }
+}
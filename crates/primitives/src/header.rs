@@ -1,4 +1,6 @@
 use crate::{
+    basefee::calculate_next_block_base_fee,
+    eip4844::{calculate_blob_gasprice, calculate_excess_blob_gas},
     keccak256,
     proofs::{EMPTY_LIST_HASH, EMPTY_ROOT},
     BlockHash, BlockNumber, Bloom, Bytes, H160, H256, U256,
@@ -6,7 +8,7 @@ use crate::{
 use bytes::{Buf, BufMut, BytesMut};
 use ethers_core::types::{Block, H256 as EthersH256, H64};
 use reth_codecs::{add_arbitrary_tests, derive_arbitrary, main_codec, Compact};
-use reth_rlp::{length_of_length, Decodable, Encodable, EMPTY_STRING_CODE};
+use reth_rlp::{length_of_length, Decodable, Encodable, EMPTY_LIST_CODE, EMPTY_STRING_CODE};
 use serde::{Deserialize, Serialize};
 use std::ops::Deref;
 
@@ -87,6 +89,14 @@ pub struct Header {
     /// above the gas target, and decreasing when blocks are below the gas target. The base fee per
     /// gas is burned.
     pub base_fee_per_gas: Option<u64>,
+    /// The total amount of blob gas consumed by the transactions within the block, added in
+    /// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844).
+    pub blob_gas_used: Option<u64>,
+    /// A running total of blob gas consumed in excess of the target, prior to the block. Blocks
+    /// with above-target blob gas consumption increase this value, blocks with below-target blob
+    /// gas consumption decrease it (bounded at 0). This was added in
+    /// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844).
+    pub excess_blob_gas: Option<u64>,
     /// An arbitrary byte array containing data relevant to this block. This must be 32 bytes or
     /// fewer; formally Hx.
     pub extra_data: Bytes,
@@ -112,6 +122,8 @@ impl Default for Header {
             nonce: 0,
             base_fee_per_gas: None,
             withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
         }
     }
 }
@@ -125,6 +137,27 @@ impl Header {
         keccak256(&out)
     }
 
+    /// Calculate base fee for next block according to the EIP-1559 spec.
+    ///
+    /// Returns a `None` if no base fee is set, no EIP-1559 support
+    pub fn next_block_base_fee(&self) -> Option<u64> {
+        Some(calculate_next_block_base_fee(self.gas_used, self.gas_limit, self.base_fee_per_gas?))
+    }
+
+    /// Calculate excess blob gas for the next block according to the EIP-4844 spec.
+    ///
+    /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
+    pub fn next_block_excess_blob_gas(&self) -> Option<u64> {
+        Some(calculate_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
+    }
+
+    /// Returns the blob gas price of the block according to the EIP-4844 spec.
+    ///
+    /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
+    pub fn blob_gasprice(&self) -> Option<u128> {
+        Some(calculate_blob_gasprice(self.excess_blob_gas?))
+    }
+
     /// Checks if the header is empty - has no transactions and no ommers
     pub fn is_empty(&self) -> bool {
         let txs_and_ommers_empty = self.transaction_root_is_empty() && self.ommers_hash_is_empty();
@@ -178,11 +211,24 @@ impl Header {
 
         if let Some(base_fee) = self.base_fee_per_gas {
             length += U256::from(base_fee).length();
-        } else if self.withdrawals_root.is_some() {
+        } else if self.withdrawals_root.is_some() ||
+            self.blob_gas_used.is_some() ||
+            self.excess_blob_gas.is_some()
+        {
             length += 1; // EMTY STRING CODE
         }
         if let Some(root) = self.withdrawals_root {
             length += root.length();
+        } else if self.blob_gas_used.is_some() || self.excess_blob_gas.is_some() {
+            length += 1; // EMTY STRING CODE
+        }
+        if let Some(blob_gas_used) = self.blob_gas_used {
+            length += U256::from(blob_gas_used).length();
+        } else if self.excess_blob_gas.is_some() {
+            length += 1; // EMPTY LIST CODE
+        }
+        if let Some(excess_blob_gas) = self.excess_blob_gas {
+            length += U256::from(excess_blob_gas).length();
         }
 
         length
@@ -211,15 +257,34 @@ impl Encodable for Header {
         H64::from_low_u64_be(self.nonce).encode(out);
 
         // Encode base fee. Put empty string if base fee is missing,
-        // but withdrawals root is present.
+        // but withdrawals root or any of the blob fields is present.
         if let Some(ref base_fee) = self.base_fee_per_gas {
             U256::from(*base_fee).encode(out);
-        } else if self.withdrawals_root.is_some() {
+        } else if self.withdrawals_root.is_some() ||
+            self.blob_gas_used.is_some() ||
+            self.excess_blob_gas.is_some()
+        {
             out.put_u8(EMPTY_STRING_CODE);
         }
 
+        // Encode withdrawals root. Put empty string if withdrawals root is missing,
+        // but any of the blob fields is present.
         if let Some(ref root) = self.withdrawals_root {
             root.encode(out);
+        } else if self.blob_gas_used.is_some() || self.excess_blob_gas.is_some() {
+            out.put_u8(EMPTY_STRING_CODE);
+        }
+
+        // Encode blob gas used. Put empty list if blob gas used is missing, but excess blob gas
+        // is present. An empty string can't be used here since it is a valid encoding of zero.
+        if let Some(ref blob_gas_used) = self.blob_gas_used {
+            U256::from(*blob_gas_used).encode(out);
+        } else if self.excess_blob_gas.is_some() {
+            out.put_u8(EMPTY_LIST_CODE);
+        }
+
+        if let Some(ref excess_blob_gas) = self.excess_blob_gas {
+            U256::from(*excess_blob_gas).encode(out);
         }
     }
 
@@ -256,6 +321,8 @@ impl Decodable for Header {
             nonce: H64::decode(buf)?.to_low_u64_be(),
             base_fee_per_gas: None,
             withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
         };
         if started_len - buf.len() < rlp_head.payload_length {
             if buf.first().map(|b| *b == EMPTY_STRING_CODE).unwrap_or_default() {
@@ -265,7 +332,21 @@ impl Decodable for Header {
             }
         }
         if started_len - buf.len() < rlp_head.payload_length {
-            this.withdrawals_root = Some(Decodable::decode(buf)?);
+            if buf.first().map(|b| *b == EMPTY_STRING_CODE).unwrap_or_default() {
+                buf.advance(1)
+            } else {
+                this.withdrawals_root = Some(Decodable::decode(buf)?);
+            }
+        }
+        if started_len - buf.len() < rlp_head.payload_length {
+            if buf.first().map(|b| *b == EMPTY_LIST_CODE).unwrap_or_default() {
+                buf.advance(1)
+            } else {
+                this.blob_gas_used = Some(U256::decode(buf)?.to::<u64>());
+            }
+        }
+        if started_len - buf.len() < rlp_head.payload_length {
+            this.excess_blob_gas = Some(U256::decode(buf)?.to::<u64>());
         }
         let consumed = started_len - buf.len();
         if consumed != rlp_head.payload_length {
@@ -344,6 +425,8 @@ impl From<&Block<EthersH256>> for Header {
             ommers_hash: block.uncles_hash.0.into(),
             gas_used: block.gas_used.as_u64(),
             withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
             logs_bloom: block.logs_bloom.unwrap_or_default().0.into(),
         }
     }
@@ -536,6 +619,8 @@ mod tests {
             nonce: 0,
             base_fee_per_gas: Some(0x036b_u64),
             withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
         };
         assert_eq!(header.hash_slow(), expected_hash);
     }
@@ -614,6 +699,28 @@ mod tests {
         assert_eq!(header.hash_slow(), expected_hash);
     }
 
+    #[test]
+    fn encode_decode_blob_header_fields() {
+        let header = Header {
+            base_fee_per_gas: Some(0x09),
+            withdrawals_root: Some(H256::zero()),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0x020000),
+            ..Default::default()
+        };
+        let mut data = vec![];
+        header.encode(&mut data);
+        assert_eq!(header.length(), data.len());
+        assert_eq!(<Header as Decodable>::decode(&mut data.as_slice()).unwrap(), header);
+
+        // missing fields before the blob fields are encoded as placeholders
+        let header = Header { excess_blob_gas: Some(0), ..Default::default() };
+        let mut data = vec![];
+        header.encode(&mut data);
+        assert_eq!(header.length(), data.len());
+        assert_eq!(<Header as Decodable>::decode(&mut data.as_slice()).unwrap(), header);
+    }
+
     #[test]
     fn sanity_direction() {
         let reverse = true;
@@ -10,12 +10,14 @@
 //! This crate contains Ethereum primitive types and helper functions.
 
 mod account;
+pub mod basefee;
 mod bits;
 mod block;
 pub mod bloom;
 mod chain;
 pub mod constants;
 pub mod contract;
+pub mod eip4844;
 mod error;
 pub mod filter;
 mod forkid;
@@ -37,7 +39,9 @@ pub mod proofs;
 
 pub use account::{Account, Bytecode};
 pub use bits::H512;
-pub use block::{Block, BlockHashOrNumber, BlockId, BlockNumberOrTag, SealedBlock};
+pub use block::{
+    Block, BlockHashOrNumber, BlockId, BlockNumberOrTag, SealedBlock, SealedBlockWithSenders,
+};
 pub use bloom::Bloom;
 pub use chain::{
     AllGenesisFormats, Chain, ChainInfo, ChainSpec, ChainSpecBuilder, ForkCondition, GOERLI,
@@ -60,10 +64,12 @@ pub use revm_primitives::JumpMap;
 pub use serde_helper::JsonU256;
 pub use storage::{StorageEntry, StorageTrieEntry};
 pub use transaction::{
-    AccessList, AccessListItem, AccessListWithGasUsed, FromRecoveredTransaction,
-    IntoRecoveredTransaction, InvalidTransactionError, Signature, Transaction, TransactionKind,
-    TransactionSigned, TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxLegacy, TxType,
-    EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
+    AccessList, AccessListItem, AccessListWithGasUsed, BlobTransactionSidecar,
+    FromRecoveredTransaction, IntoRecoveredTransaction, InvalidBlobSidecarError,
+    InvalidTransactionError, PooledTransactionsElement, Signature, Transaction, TransactionKind,
+    TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TxEip1559, TxEip2930,
+    TxEip4844, TxLegacy, TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    LEGACY_TX_TYPE_ID,
 };
 pub use withdrawal::Withdrawal;
 
@@ -1,4 +1,4 @@
-use crate::U256;
+use crate::{H256, U256};
 
 /// Represents error variants that can happen when trying to validate a
 /// [Transaction](crate::Transaction)
@@ -21,6 +21,8 @@ pub enum InvalidTransactionError {
     Eip2930Disabled,
     #[error("Eip2930 transaction is enabled after london hardfork.")]
     Eip1559Disabled,
+    #[error("Eip4844 transaction is enabled after cancun hardfork.")]
+    Eip4844Disabled,
     /// Thrown when calculating gas usage
     #[error("gas uint64 overflow")]
     GasUintOverflow,
@@ -44,4 +46,20 @@ pub enum InvalidTransactionError {
     /// Thrown if the sender of a transaction is a contract.
     #[error("Transaction signer has bytecode set.")]
     SignerAccountHasBytecode,
+    /// Thrown if a blob transaction is a contract creation.
+    #[error("blob transaction can't create a contract")]
+    BlobCreateTransaction,
+    /// Thrown if a blob transaction doesn't carry any blobs.
+    #[error("blob transaction without blobs")]
+    EmptyBlobs,
+    /// Thrown if a blob transaction carries more blobs than fit into a block.
+    #[error("blob transaction has too many blobs: {have}, max {max}")]
+    TooManyBlobs { have: usize, max: usize },
+    /// Thrown if a versioned hash of a blob transaction has an unsupported version.
+    #[error("blob versioned hash {0:?} has an unsupported version")]
+    InvalidBlobVersionedHash(H256),
+    /// Thrown post Cancun if the transaction's blob fee is less than the blob gas price of the
+    /// block
+    #[error("max fee per blob gas less than block blob gas fee")]
+    BlobFeeCapTooLow,
 }
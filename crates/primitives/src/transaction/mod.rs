@@ -1,17 +1,28 @@
-use crate::{keccak256, Address, Bytes, ChainId, TxHash, H256};
+use crate::{
+    constants::{GAS_PER_BLOB, MAX_BLOBS_PER_BLOCK, VERSIONED_HASH_VERSION_KZG},
+    keccak256, Address, Bytes, ChainId, TxHash, H256,
+};
 pub use access_list::{AccessList, AccessListItem, AccessListWithGasUsed};
 use bytes::{Buf, BytesMut};
 use derive_more::{AsRef, Deref};
 pub use error::InvalidTransactionError;
+pub use meta::TransactionMeta;
+pub use pooled::PooledTransactionsElement;
 use reth_codecs::{add_arbitrary_tests, main_codec, Compact};
 use reth_rlp::{
     length_of_length, Decodable, DecodeError, Encodable, Header, EMPTY_LIST_CODE, EMPTY_STRING_CODE,
 };
+pub use sidecar::{BlobTransactionSidecar, InvalidBlobSidecarError};
 pub use signature::Signature;
-pub use tx_type::{TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, LEGACY_TX_TYPE_ID};
+pub use tx_type::{
+    TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
+};
 
 mod access_list;
 mod error;
+mod meta;
+mod pooled;
+mod sidecar;
 mod signature;
 mod tx_type;
 mod util;
@@ -162,6 +173,82 @@ pub struct TxEip1559 {
     pub input: Bytes,
 }
 
+/// A blob transaction ([EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)).
+///
+/// The blobs, commitments and proofs that belong to the transaction are not part of the
+/// transaction itself, they're only exchanged in the network form, see
+/// [`BlobTransactionSidecar`].
+#[main_codec]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TxEip4844 {
+    /// Added as EIP-155: Simple replay attack protection
+    pub chain_id: u64,
+    /// A scalar value equal to the number of transactions sent by the sender; formally Tn.
+    pub nonce: u64,
+    /// A scalar value equal to the maximum
+    /// amount of gas that should be used in executing
+    /// this transaction. This is paid up-front, before any
+    /// computation is done and may not be increased
+    /// later; formally Tg.
+    pub gas_limit: u64,
+    /// Max fee per unit of gas, see [`TxEip1559::max_fee_per_gas`].
+    pub max_fee_per_gas: u128,
+    /// Max Priority fee that transaction is paying, see
+    /// [`TxEip1559::max_priority_fee_per_gas`].
+    pub max_priority_fee_per_gas: u128,
+    /// The 160-bit address of the message call’s recipient.
+    ///
+    /// Blob transactions can't create contracts, a [`TransactionKind::Create`] is rejected during
+    /// validation.
+    pub to: TransactionKind,
+    /// A scalar value equal to the number of Wei to
+    /// be transferred to the message call’s recipient; formally Tv.
+    pub value: u128,
+    /// The accessList specifies a list of addresses and storage keys;
+    /// these addresses and storage keys are added into the `accessed_addresses`
+    /// and `accessed_storage_keys` global sets (introduced in EIP-2929).
+    /// A gas cost is charged, though at a discount relative to the cost of
+    /// accessing outside the list.
+    pub access_list: AccessList,
+    /// Versioned hashes of the KZG commitments of the blobs carried by this transaction.
+    pub blob_versioned_hashes: Vec<H256>,
+    /// Max fee per unit of blob gas the sender is willing to pay.
+    pub max_fee_per_blob_gas: u128,
+    /// Input data of the message call, formally Td.
+    pub input: Bytes,
+}
+
+impl TxEip4844 {
+    /// Returns the total amount of blob gas consumed by the transaction.
+    pub fn blob_gas(&self) -> u64 {
+        self.blob_versioned_hashes.len() as u64 * GAS_PER_BLOB
+    }
+
+    /// Checks the static constraints of a blob transaction: it must call an address, carry at
+    /// least one and at most [`MAX_BLOBS_PER_BLOCK`] blobs and all versioned hashes must be KZG
+    /// versioned hashes.
+    pub fn ensure_valid_blobs(&self) -> Result<(), InvalidTransactionError> {
+        if self.to == TransactionKind::Create {
+            return Err(InvalidTransactionError::BlobCreateTransaction)
+        }
+        if self.blob_versioned_hashes.is_empty() {
+            return Err(InvalidTransactionError::EmptyBlobs)
+        }
+        if self.blob_versioned_hashes.len() > MAX_BLOBS_PER_BLOCK as usize {
+            return Err(InvalidTransactionError::TooManyBlobs {
+                have: self.blob_versioned_hashes.len(),
+                max: MAX_BLOBS_PER_BLOCK as usize,
+            })
+        }
+        if let Some(hash) =
+            self.blob_versioned_hashes.iter().find(|hash| hash.0[0] != VERSIONED_HASH_VERSION_KZG)
+        {
+            return Err(InvalidTransactionError::InvalidBlobVersionedHash(*hash))
+        }
+        Ok(())
+    }
+}
+
 /// A raw transaction.
 ///
 /// Transaction types were introduced in [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718).
@@ -174,6 +261,8 @@ pub enum Transaction {
     Eip2930(TxEip2930),
     /// A transaction with a priority fee ([EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)).
     Eip1559(TxEip1559),
+    /// A blob transaction ([EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)).
+    Eip4844(TxEip4844),
 }
 
 impl Transaction {
@@ -191,6 +280,7 @@ impl Transaction {
             Transaction::Legacy(TxLegacy { chain_id, .. }) => *chain_id,
             Transaction::Eip2930(TxEip2930 { chain_id, .. }) => Some(*chain_id),
             Transaction::Eip1559(TxEip1559 { chain_id, .. }) => Some(*chain_id),
+            Transaction::Eip4844(TxEip4844 { chain_id, .. }) => Some(*chain_id),
         }
     }
 
@@ -200,6 +290,7 @@ impl Transaction {
             Transaction::Legacy(TxLegacy { chain_id: ref mut c, .. }) => *c = Some(chain_id),
             Transaction::Eip2930(TxEip2930 { chain_id: ref mut c, .. }) => *c = chain_id,
             Transaction::Eip1559(TxEip1559 { chain_id: ref mut c, .. }) => *c = chain_id,
+            Transaction::Eip4844(TxEip4844 { chain_id: ref mut c, .. }) => *c = chain_id,
         }
     }
 
@@ -209,7 +300,8 @@ impl Transaction {
         match self {
             Transaction::Legacy(TxLegacy { to, .. }) |
             Transaction::Eip2930(TxEip2930 { to, .. }) |
-            Transaction::Eip1559(TxEip1559 { to, .. }) => to,
+            Transaction::Eip1559(TxEip1559 { to, .. }) |
+            Transaction::Eip4844(TxEip4844 { to, .. }) => to,
         }
     }
 
@@ -219,6 +311,7 @@ impl Transaction {
             Transaction::Legacy { .. } => TxType::Legacy,
             Transaction::Eip2930 { .. } => TxType::EIP2930,
             Transaction::Eip1559 { .. } => TxType::EIP1559,
+            Transaction::Eip4844 { .. } => TxType::EIP4844,
         }
     }
 
@@ -228,6 +321,7 @@ impl Transaction {
             Transaction::Legacy(TxLegacy { value, .. }) => value,
             Transaction::Eip2930(TxEip2930 { value, .. }) => value,
             Transaction::Eip1559(TxEip1559 { value, .. }) => value,
+            Transaction::Eip4844(TxEip4844 { value, .. }) => value,
         }
     }
 
@@ -237,6 +331,7 @@ impl Transaction {
             Transaction::Legacy(TxLegacy { nonce, .. }) => *nonce,
             Transaction::Eip2930(TxEip2930 { nonce, .. }) => *nonce,
             Transaction::Eip1559(TxEip1559 { nonce, .. }) => *nonce,
+            Transaction::Eip4844(TxEip4844 { nonce, .. }) => *nonce,
         }
     }
 
@@ -245,7 +340,8 @@ impl Transaction {
         match self {
             Transaction::Legacy(TxLegacy { gas_limit, .. }) |
             Transaction::Eip2930(TxEip2930 { gas_limit, .. }) |
-            Transaction::Eip1559(TxEip1559 { gas_limit, .. }) => *gas_limit,
+            Transaction::Eip1559(TxEip1559 { gas_limit, .. }) |
+            Transaction::Eip4844(TxEip4844 { gas_limit, .. }) => *gas_limit,
         }
     }
 
@@ -254,7 +350,8 @@ impl Transaction {
         match self {
             Transaction::Legacy(TxLegacy { gas_price, .. }) |
             Transaction::Eip2930(TxEip2930 { gas_price, .. }) => *gas_price,
-            Transaction::Eip1559(TxEip1559 { max_fee_per_gas, .. }) => *max_fee_per_gas,
+            Transaction::Eip1559(TxEip1559 { max_fee_per_gas, .. }) |
+            Transaction::Eip4844(TxEip4844 { max_fee_per_gas, .. }) => *max_fee_per_gas,
         }
     }
 
@@ -264,18 +361,104 @@ impl Transaction {
         match self {
             Transaction::Legacy(_) => None,
             Transaction::Eip2930(_) => None,
-            Transaction::Eip1559(TxEip1559 { max_priority_fee_per_gas, .. }) => {
+            Transaction::Eip1559(TxEip1559 { max_priority_fee_per_gas, .. }) |
+            Transaction::Eip4844(TxEip4844 { max_priority_fee_per_gas, .. }) => {
                 Some(*max_priority_fee_per_gas)
             }
         }
     }
 
+    /// Max fee per unit of blob gas for blob transactions, `None` for all other transactions.
+    pub fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        match self {
+            Transaction::Eip4844(TxEip4844 { max_fee_per_blob_gas, .. }) => {
+                Some(*max_fee_per_blob_gas)
+            }
+            _ => None,
+        }
+    }
+
+    /// Versioned hashes of the blobs of a blob transaction, empty for all other transactions.
+    pub fn blob_versioned_hashes(&self) -> &[H256] {
+        match self {
+            Transaction::Eip4844(TxEip4844 { blob_versioned_hashes, .. }) => blob_versioned_hashes,
+            _ => &[],
+        }
+    }
+
+    /// Returns the blob gas consumed by the transaction, which is zero for all non blob
+    /// transactions.
+    pub fn blob_gas(&self) -> u64 {
+        match self {
+            Transaction::Eip4844(tx) => tx.blob_gas(),
+            _ => 0,
+        }
+    }
+
+    /// Returns the effective gas price for the given base fee.
+    ///
+    /// If the transaction is a legacy or EIP2930 transaction, the gas price is returned.
+    ///
+    /// For EIP1559 transactions this is `min(max_fee_per_gas, base_fee + max_priority_fee_per_gas)`
+    /// and `max_fee_per_gas` if no base fee is provided.
+    pub fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+        match self {
+            Transaction::Legacy(tx) => tx.gas_price,
+            Transaction::Eip2930(tx) => tx.gas_price,
+            Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas, max_priority_fee_per_gas, ..
+            }) |
+            Transaction::Eip4844(TxEip4844 {
+                max_fee_per_gas, max_priority_fee_per_gas, ..
+            }) => match base_fee {
+                None => *max_fee_per_gas,
+                Some(base_fee) => {
+                    let tip = max_fee_per_gas.saturating_sub(base_fee as u128);
+                    if tip > *max_priority_fee_per_gas {
+                        max_priority_fee_per_gas + base_fee as u128
+                    } else {
+                        *max_fee_per_gas
+                    }
+                }
+            },
+        }
+    }
+
+    /// Returns the effective miner tip per gas for the given base fee.
+    ///
+    /// For legacy and EIP2930 transactions this is `gas_price - base_fee`, for EIP1559
+    /// transactions this is `min(max_fee_per_gas - base_fee, max_priority_fee_per_gas)`.
+    ///
+    /// Returns `None` if the fee cap of the transaction is below the base fee.
+    pub fn effective_tip_per_gas(&self, base_fee: Option<u64>) -> Option<u128> {
+        let base_fee = match base_fee {
+            Some(base_fee) => base_fee as u128,
+            None => return Some(self.priority_fee_or_price()),
+        };
+        let max_fee_per_gas = self.max_fee_per_gas();
+        if max_fee_per_gas < base_fee {
+            return None
+        }
+        let fee = max_fee_per_gas - base_fee;
+        match self.max_priority_fee_per_gas() {
+            Some(priority_fee) => Some(fee.min(priority_fee)),
+            None => Some(fee),
+        }
+    }
+
+    /// Returns the priority fee per gas for EIP1559 transactions and the gas price for legacy and
+    /// EIP2930 transactions.
+    pub fn priority_fee_or_price(&self) -> u128 {
+        self.max_priority_fee_per_gas().unwrap_or_else(|| self.max_fee_per_gas())
+    }
+
     /// Get the transaction's input field.
     pub fn input(&self) -> &Bytes {
         match self {
             Transaction::Legacy(TxLegacy { input, .. }) => input,
             Transaction::Eip2930(TxEip2930 { input, .. }) => input,
             Transaction::Eip1559(TxEip1559 { input, .. }) => input,
+            Transaction::Eip4844(TxEip4844 { input, .. }) => input,
         }
     }
 
@@ -372,6 +555,33 @@ impl Transaction {
                 len += access_list.length();
                 len
             }
+            Transaction::Eip4844(TxEip4844 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                access_list,
+                blob_versioned_hashes,
+                max_fee_per_blob_gas,
+                input,
+            }) => {
+                let mut len = 0;
+                len += chain_id.length();
+                len += nonce.length();
+                len += max_priority_fee_per_gas.length();
+                len += max_fee_per_gas.length();
+                len += gas_limit.length();
+                len += to.length();
+                len += value.length();
+                len += input.0.length();
+                len += access_list.length();
+                len += max_fee_per_blob_gas.length();
+                len += blob_versioned_hashes.length();
+                len
+            }
         }
     }
 
@@ -434,6 +644,31 @@ impl Transaction {
                 input.0.encode(out);
                 access_list.encode(out);
             }
+            Transaction::Eip4844(TxEip4844 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                access_list,
+                blob_versioned_hashes,
+                max_fee_per_blob_gas,
+                input,
+            }) => {
+                chain_id.encode(out);
+                nonce.encode(out);
+                max_priority_fee_per_gas.encode(out);
+                max_fee_per_gas.encode(out);
+                gas_limit.encode(out);
+                to.encode(out);
+                value.encode(out);
+                input.0.encode(out);
+                access_list.encode(out);
+                max_fee_per_blob_gas.encode(out);
+                blob_versioned_hashes.encode(out);
+            }
         }
     }
 }
@@ -688,7 +923,7 @@ impl TransactionSigned {
     /// Decodes en enveloped EIP-2718 typed transaction.
     ///
     /// CAUTION: this expects that `data` is `[id, rlp(tx)]`
-    fn decode_enveloped_typed_transaction(
+    pub(crate) fn decode_enveloped_typed_transaction(
         data: &mut &[u8],
     ) -> Result<TransactionSigned, DecodeError> {
         // keep this around so we can use it to calculate the hash
@@ -728,6 +963,19 @@ impl TransactionSigned {
                 input: Bytes(Decodable::decode(data)?),
                 access_list: Decodable::decode(data)?,
             }),
+            3 => Transaction::Eip4844(TxEip4844 {
+                chain_id: Decodable::decode(data)?,
+                nonce: Decodable::decode(data)?,
+                max_priority_fee_per_gas: Decodable::decode(data)?,
+                max_fee_per_gas: Decodable::decode(data)?,
+                gas_limit: Decodable::decode(data)?,
+                to: Decodable::decode(data)?,
+                value: Decodable::decode(data)?,
+                input: Bytes(Decodable::decode(data)?),
+                access_list: Decodable::decode(data)?,
+                max_fee_per_blob_gas: Decodable::decode(data)?,
+                blob_versioned_hashes: Decodable::decode(data)?,
+            }),
             _ => return Err(DecodeError::Custom("unsupported typed transaction type")),
         };
 
@@ -939,6 +1187,45 @@ mod tests {
     use reth_rlp::{Decodable, DecodeError, Encodable};
     use std::str::FromStr;
 
+    #[test]
+    fn test_effective_gas_price() {
+        let legacy = Transaction::Legacy(TxLegacy { gas_price: 10, ..Default::default() });
+        assert_eq!(legacy.effective_gas_price(None), 10);
+        assert_eq!(legacy.effective_gas_price(Some(7)), 10);
+
+        let dynamic = Transaction::Eip1559(TxEip1559 {
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: 10,
+            ..Default::default()
+        });
+        assert_eq!(dynamic.effective_gas_price(None), 100);
+        // tip fits into the max fee
+        assert_eq!(dynamic.effective_gas_price(Some(50)), 60);
+        // capped by the max fee
+        assert_eq!(dynamic.effective_gas_price(Some(95)), 100);
+    }
+
+    #[test]
+    fn test_effective_tip_per_gas() {
+        let legacy = Transaction::Legacy(TxLegacy { gas_price: 10, ..Default::default() });
+        assert_eq!(legacy.effective_tip_per_gas(None), Some(10));
+        assert_eq!(legacy.effective_tip_per_gas(Some(7)), Some(3));
+        assert_eq!(legacy.effective_tip_per_gas(Some(11)), None);
+
+        let dynamic = Transaction::Eip1559(TxEip1559 {
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: 10,
+            ..Default::default()
+        });
+        assert_eq!(dynamic.effective_tip_per_gas(None), Some(10));
+        // tip fits into the max fee
+        assert_eq!(dynamic.effective_tip_per_gas(Some(50)), Some(10));
+        // capped by the max fee
+        assert_eq!(dynamic.effective_tip_per_gas(Some(95)), Some(5));
+        // max fee below the base fee
+        assert_eq!(dynamic.effective_tip_per_gas(Some(101)), None);
+    }
+
     #[test]
     fn test_decode_empty_typed_tx() {
         let input = [0x80u8];
@@ -0,0 +1,262 @@
+use crate::{BlobTransactionSidecar, TransactionSigned, TxHash, EIP4844_TX_TYPE_ID};
+use bytes::Buf;
+use reth_rlp::{length_of_length, Decodable, DecodeError, Encodable, Header};
+use serde::{Deserialize, Serialize};
+
+/// A transaction as it is exchanged in a `PooledTransactions` response.
+///
+/// Blob transactions are sent together with their [`BlobTransactionSidecar`], encoded as
+/// `type || rlp([tx_payload_body, blobs, commitments, proofs])`, all other transactions are
+/// encoded like a [`TransactionSigned`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PooledTransactionsElement {
+    /// A transaction that isn't a blob transaction.
+    Transaction(TransactionSigned),
+    /// A blob transaction with its sidecar.
+    BlobTransaction {
+        /// The signed blob transaction.
+        transaction: TransactionSigned,
+        /// The blobs, commitments and proofs of the transaction.
+        sidecar: BlobTransactionSidecar,
+    },
+}
+
+// === impl PooledTransactionsElement ===
+
+impl PooledTransactionsElement {
+    /// Creates the element for a blob transaction and its sidecar.
+    pub fn blob_transaction(
+        transaction: TransactionSigned,
+        sidecar: BlobTransactionSidecar,
+    ) -> Self {
+        Self::BlobTransaction { transaction, sidecar }
+    }
+
+    /// Returns the hash of the transaction.
+    pub fn hash(&self) -> TxHash {
+        self.transaction().hash
+    }
+
+    /// Returns the signed transaction.
+    pub fn transaction(&self) -> &TransactionSigned {
+        match self {
+            Self::Transaction(transaction) | Self::BlobTransaction { transaction, .. } => {
+                transaction
+            }
+        }
+    }
+
+    /// Returns the sidecar if this is a blob transaction.
+    pub fn sidecar(&self) -> Option<&BlobTransactionSidecar> {
+        match self {
+            Self::Transaction(_) => None,
+            Self::BlobTransaction { sidecar, .. } => Some(sidecar),
+        }
+    }
+
+    /// Consumes the type and returns the signed transaction and the sidecar, if any.
+    pub fn into_parts(self) -> (TransactionSigned, Option<BlobTransactionSidecar>) {
+        match self {
+            Self::Transaction(transaction) => (transaction, None),
+            Self::BlobTransaction { transaction, sidecar } => (transaction, Some(sidecar)),
+        }
+    }
+
+    /// Returns the length of `rlp([tx_payload_body, blobs, commitments, proofs])`, without the
+    /// list header.
+    fn blob_payload_len(
+        transaction: &TransactionSigned,
+        sidecar: &BlobTransactionSidecar,
+    ) -> usize {
+        let tx_payload_length =
+            transaction.transaction.fields_len() + transaction.signature.payload_len();
+        length_of_length(tx_payload_length) + tx_payload_length + sidecar.fields_len()
+    }
+}
+
+impl From<TransactionSigned> for PooledTransactionsElement {
+    fn from(transaction: TransactionSigned) -> Self {
+        Self::Transaction(transaction)
+    }
+}
+
+impl Encodable for PooledTransactionsElement {
+    fn encode(&self, out: &mut dyn bytes::BufMut) {
+        match self {
+            Self::Transaction(transaction) => transaction.encode(out),
+            Self::BlobTransaction { transaction, sidecar } => {
+                let payload_length = Self::blob_payload_len(transaction, sidecar);
+                Header {
+                    list: false,
+                    payload_length: 1 + length_of_length(payload_length) + payload_length,
+                }
+                .encode(out);
+                out.put_u8(EIP4844_TX_TYPE_ID);
+                Header { list: true, payload_length }.encode(out);
+                Header {
+                    list: true,
+                    payload_length: transaction.transaction.fields_len() +
+                        transaction.signature.payload_len(),
+                }
+                .encode(out);
+                transaction.transaction.encode_fields(out);
+                transaction.signature.encode(out);
+                sidecar.encode_fields(out);
+            }
+        }
+    }
+
+    fn length(&self) -> usize {
+        match self {
+            Self::Transaction(transaction) => transaction.length(),
+            Self::BlobTransaction { transaction, sidecar } => {
+                let payload_length = Self::blob_payload_len(transaction, sidecar);
+                // 'transaction type byte length' + 'header length' + 'payload length'
+                let len = 1 + length_of_length(payload_length) + payload_length;
+                length_of_length(len) + len
+            }
+        }
+    }
+}
+
+/// Decodes the network form of a pooled transaction.
+///
+/// A blob transaction is only accepted together with its sidecar.
+impl Decodable for PooledTransactionsElement {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut original_encoding = *buf;
+        let header = Header::decode(buf)?;
+
+        // legacy transactions are encoded as a list
+        if header.list {
+            let transaction = TransactionSigned::decode(&mut original_encoding)?;
+            *buf = original_encoding;
+            return Ok(Self::Transaction(transaction))
+        }
+
+        if buf.first() != Some(&EIP4844_TX_TYPE_ID) {
+            return Ok(Self::Transaction(TransactionSigned::decode_enveloped_typed_transaction(
+                buf,
+            )?))
+        }
+        buf.advance(1);
+
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(DecodeError::Custom("blob tx network form must be encoded as a list"))
+        }
+        let started_len = buf.len();
+
+        // the transaction hash is computed over `type || rlp(tx_payload_body)`, which is the
+        // enveloped form of the transaction
+        let tx_header = Header::decode(&mut &buf[..])?;
+        let tx_length = tx_header.length() + tx_header.payload_length;
+        if buf.len() < tx_length {
+            return Err(DecodeError::InputTooShort)
+        }
+        let mut enveloped = Vec::with_capacity(1 + tx_length);
+        enveloped.push(EIP4844_TX_TYPE_ID);
+        enveloped.extend_from_slice(&buf[..tx_length]);
+        let transaction =
+            TransactionSigned::decode_enveloped_typed_transaction(&mut enveloped.as_slice())?;
+        buf.advance(tx_length);
+
+        let sidecar = BlobTransactionSidecar::decode_fields(buf)?;
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(DecodeError::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+
+        Ok(Self::BlobTransaction { transaction, sidecar })
+    }
+}
+
+#[cfg(any(test, feature = "arbitrary"))]
+impl proptest::arbitrary::Arbitrary for PooledTransactionsElement {
+    type Parameters = ();
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use crate::TxType;
+        use proptest::prelude::{any, Strategy};
+
+        any::<(TransactionSigned, BlobTransactionSidecar)>()
+            .prop_map(move |(transaction, sidecar)| {
+                if transaction.tx_type() == TxType::EIP4844 {
+                    Self::BlobTransaction { transaction, sidecar }
+                } else {
+                    Self::Transaction(transaction)
+                }
+            })
+            .boxed()
+    }
+
+    type Strategy = proptest::strategy::BoxedStrategy<PooledTransactionsElement>;
+}
+
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> arbitrary::Arbitrary<'a> for PooledTransactionsElement {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use crate::TxType;
+        use arbitrary::Arbitrary;
+
+        let transaction = TransactionSigned::arbitrary(u)?;
+        if transaction.tx_type() == TxType::EIP4844 {
+            Ok(Self::BlobTransaction {
+                transaction,
+                sidecar: BlobTransactionSidecar::arbitrary(u)?,
+            })
+        } else {
+            Ok(Self::Transaction(transaction))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bytes, Signature, Transaction, TransactionKind, TxEip4844, H256};
+
+    #[test]
+    fn blob_transaction_network_form_roundtrip() {
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![Bytes::from(vec![1u8; 64])],
+            commitments: vec![Bytes::from(vec![2u8; 48])],
+            proofs: vec![Bytes::from(vec![3u8; 48])],
+        };
+        let transaction = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip4844(TxEip4844 {
+                chain_id: 1,
+                nonce: 2,
+                gas_limit: 21_000,
+                max_fee_per_gas: 10,
+                max_priority_fee_per_gas: 1,
+                to: TransactionKind::Call(Default::default()),
+                value: 3,
+                access_list: Default::default(),
+                blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+                max_fee_per_blob_gas: 5,
+                input: Default::default(),
+            }),
+            Signature { r: Default::default(), s: Default::default(), odd_y_parity: true },
+        );
+        let element = PooledTransactionsElement::blob_transaction(transaction.clone(), sidecar);
+
+        let mut buf = Vec::new();
+        element.encode(&mut buf);
+        assert_eq!(buf.len(), element.length());
+
+        let decoded = PooledTransactionsElement::decode(&mut &buf[..]).unwrap();
+        assert_eq!(decoded, element);
+        assert_eq!(decoded.hash(), transaction.hash);
+        assert_ne!(decoded.hash(), H256::zero());
+
+        // the canonical encoding of the transaction doesn't carry the sidecar
+        let mut canonical = Vec::new();
+        transaction.encode(&mut canonical);
+        assert_eq!(TransactionSigned::decode(&mut &canonical[..]).unwrap(), transaction);
+        assert!(canonical.len() < buf.len());
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{
+    constants::{BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_PROOF},
+    eip4844::{kzg_to_versioned_hash, KzgError, KzgSettings},
+    Bytes, H256,
+};
+use c_kzg::{Blob, Bytes48, KzgCommitment, KzgProof};
+use reth_codecs::derive_arbitrary;
+use reth_rlp::{Decodable, DecodeError, Encodable, Header};
+use serde::{Deserialize, Serialize};
+
+/// The blobs of a blob transaction and their KZG commitments and proofs.
+///
+/// The sidecar isn't part of the transaction, which only commits to the blobs via the versioned
+/// hashes of the commitments. It is only exchanged between peers as part of the network form of
+/// the transaction, see [`PooledTransactionsElement`](crate::PooledTransactionsElement).
+#[derive_arbitrary(rlp, 25)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct BlobTransactionSidecar {
+    /// The blob data.
+    pub blobs: Vec<Bytes>,
+    /// The KZG commitments of the blobs.
+    pub commitments: Vec<Bytes>,
+    /// The KZG proofs of the blobs.
+    pub proofs: Vec<Bytes>,
+}
+
+// === impl BlobTransactionSidecar ===
+
+impl BlobTransactionSidecar {
+    /// Creates the sidecar of the given blobs by computing their KZG commitments and proofs with
+    /// the given trusted setup.
+    pub fn from_blobs(blobs: Vec<Bytes>, kzg_settings: &KzgSettings) -> Result<Self, KzgError> {
+        let mut sidecar = Self::default();
+        for data in blobs {
+            let blob = Blob::from_bytes(&data)?;
+            let commitment = KzgCommitment::blob_to_kzg_commitment(&blob, kzg_settings)?;
+            let proof =
+                KzgProof::compute_blob_kzg_proof(&blob, &commitment.to_bytes(), kzg_settings)?;
+            sidecar.blobs.push(data);
+            sidecar.commitments.push(commitment.to_bytes().into_inner().to_vec().into());
+            sidecar.proofs.push(proof.to_bytes().into_inner().to_vec().into());
+        }
+        Ok(sidecar)
+    }
+
+    /// Returns the versioned hashes of the commitments.
+    pub fn versioned_hashes(&self) -> impl Iterator<Item = H256> + '_ {
+        self.commitments.iter().map(|commitment| kzg_to_versioned_hash(commitment))
+    }
+
+    /// Checks that the sidecar matches the given versioned hashes of a blob transaction.
+    ///
+    /// This ensures there's one blob, commitment and proof per versioned hash, that all of them
+    /// are well sized, that the commitments hash to the versioned hashes and that the KZG proofs
+    /// verify the blobs against the commitments with the given trusted setup, see
+    /// [`MAINNET_KZG_TRUSTED_SETUP`](crate::eip4844::MAINNET_KZG_TRUSTED_SETUP).
+    pub fn validate(
+        &self,
+        versioned_hashes: &[H256],
+        kzg_settings: &KzgSettings,
+    ) -> Result<(), InvalidBlobSidecarError> {
+        if self.blobs.len() != versioned_hashes.len() ||
+            self.commitments.len() != versioned_hashes.len() ||
+            self.proofs.len() != versioned_hashes.len()
+        {
+            return Err(InvalidBlobSidecarError::LengthMismatch {
+                hashes: versioned_hashes.len(),
+                blobs: self.blobs.len(),
+                commitments: self.commitments.len(),
+                proofs: self.proofs.len(),
+            })
+        }
+        if let Some(blob) = self.blobs.iter().find(|blob| blob.len() != BYTES_PER_BLOB) {
+            return Err(InvalidBlobSidecarError::InvalidBlobSize(blob.len()))
+        }
+        if let Some(commitment) =
+            self.commitments.iter().find(|commitment| commitment.len() != BYTES_PER_COMMITMENT)
+        {
+            return Err(InvalidBlobSidecarError::InvalidCommitmentSize(commitment.len()))
+        }
+        if let Some(proof) = self.proofs.iter().find(|proof| proof.len() != BYTES_PER_PROOF) {
+            return Err(InvalidBlobSidecarError::InvalidProofSize(proof.len()))
+        }
+        for (index, (expected, got)) in
+            versioned_hashes.iter().copied().zip(self.versioned_hashes()).enumerate()
+        {
+            if expected != got {
+                return Err(InvalidBlobSidecarError::VersionedHashMismatch { index, expected, got })
+            }
+        }
+
+        let blobs =
+            self.blobs.iter().map(|blob| Blob::from_bytes(blob)).collect::<Result<Vec<_>, _>>()?;
+        let commitments = self
+            .commitments
+            .iter()
+            .map(|commitment| Bytes48::from_bytes(commitment))
+            .collect::<Result<Vec<_>, _>>()?;
+        let proofs = self
+            .proofs
+            .iter()
+            .map(|proof| Bytes48::from_bytes(proof))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !KzgProof::verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, kzg_settings)? {
+            return Err(InvalidBlobSidecarError::InvalidKzgProof)
+        }
+        Ok(())
+    }
+
+    /// Returns the approximate in-memory size of the sidecar.
+    pub fn size(&self) -> usize {
+        self.blobs.iter().chain(&self.commitments).chain(&self.proofs).map(|b| b.len()).sum()
+    }
+
+    /// Outputs the length of the sidecar's fields, without a RLP header.
+    pub(crate) fn fields_len(&self) -> usize {
+        self.blobs.length() + self.commitments.length() + self.proofs.length()
+    }
+
+    /// Encodes the sidecar's fields into the desired buffer, without a RLP header.
+    ///
+    /// This is used for the network form of blob transactions, which encodes the fields next to
+    /// the signed transaction.
+    pub(crate) fn encode_fields(&self, out: &mut dyn bytes::BufMut) {
+        self.blobs.encode(out);
+        self.commitments.encode(out);
+        self.proofs.encode(out);
+    }
+
+    /// Decodes the sidecar's fields, without a RLP header.
+    pub(crate) fn decode_fields(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            blobs: Decodable::decode(buf)?,
+            commitments: Decodable::decode(buf)?,
+            proofs: Decodable::decode(buf)?,
+        })
+    }
+}
+
+impl Encodable for BlobTransactionSidecar {
+    fn encode(&self, out: &mut dyn bytes::BufMut) {
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.encode_fields(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.fields_len();
+        payload_length + reth_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for BlobTransactionSidecar {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(DecodeError::UnexpectedString)
+        }
+        let started_len = buf.len();
+        let sidecar = Self::decode_fields(buf)?;
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(DecodeError::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(sidecar)
+    }
+}
+
+/// Represents errors that can happen when checking a [`BlobTransactionSidecar`] against the blob
+/// transaction it belongs to.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum InvalidBlobSidecarError {
+    #[error("Sidecar with {blobs} blobs, {commitments} commitments and {proofs} proofs doesn't match {hashes} versioned hashes.")]
+    LengthMismatch { hashes: usize, blobs: usize, commitments: usize, proofs: usize },
+    #[error("Invalid blob size {0}.")]
+    InvalidBlobSize(usize),
+    #[error("Invalid KZG commitment size {0}.")]
+    InvalidCommitmentSize(usize),
+    #[error("Invalid KZG proof size {0}.")]
+    InvalidProofSize(usize),
+    #[error("Versioned hash mismatch at index {index}: expected {expected:?}, got {got:?}.")]
+    VersionedHashMismatch { index: usize, expected: H256, got: H256 },
+    #[error("The KZG proofs don't verify the blobs against the commitments.")]
+    InvalidKzgProof,
+    /// The blobs, commitments or proofs couldn't be processed, for example because a blob
+    /// contains a non-canonical field element or a commitment isn't a valid point.
+    #[error("KZG error: {0}")]
+    Kzg(String),
+}
+
+impl From<KzgError> for InvalidBlobSidecarError {
+    fn from(err: KzgError) -> Self {
+        InvalidBlobSidecarError::Kzg(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip4844::MAINNET_KZG_TRUSTED_SETUP;
+
+    /// Creates a sidecar with valid commitments and proofs for blobs filled with small field
+    /// elements.
+    fn sidecar(num_blobs: usize) -> BlobTransactionSidecar {
+        let blobs = (0..num_blobs)
+            .map(|i| {
+                // the first byte of each field element is kept zero so it's canonical
+                let mut data = vec![0u8; BYTES_PER_BLOB];
+                data.iter_mut().skip(1).step_by(32).for_each(|b| *b = i as u8 + 1);
+                data.into()
+            })
+            .collect();
+        BlobTransactionSidecar::from_blobs(blobs, &MAINNET_KZG_TRUSTED_SETUP).unwrap()
+    }
+
+    #[test]
+    fn validate_sidecar() {
+        let settings = &MAINNET_KZG_TRUSTED_SETUP;
+        let sidecar = sidecar(2);
+        let hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+        assert_eq!(sidecar.validate(&hashes, settings), Ok(()));
+
+        assert!(matches!(
+            sidecar.validate(&hashes[..1], settings),
+            Err(InvalidBlobSidecarError::LengthMismatch { .. })
+        ));
+        assert!(matches!(
+            sidecar.validate(&[hashes[1], hashes[0]], settings),
+            Err(InvalidBlobSidecarError::VersionedHashMismatch { index: 0, .. })
+        ));
+
+        let mut short_blob = sidecar.clone();
+        short_blob.blobs[1] = Bytes::from(vec![0u8; 31]);
+        assert_eq!(
+            short_blob.validate(&hashes, settings),
+            Err(InvalidBlobSidecarError::InvalidBlobSize(31))
+        );
+        let mut short_proof = sidecar.clone();
+        short_proof.proofs[0] = Bytes::from(vec![0u8; BYTES_PER_PROOF - 1]);
+        assert_eq!(
+            short_proof.validate(&hashes, settings),
+            Err(InvalidBlobSidecarError::InvalidProofSize(BYTES_PER_PROOF - 1))
+        );
+    }
+
+    #[test]
+    fn validate_sidecar_kzg_proofs() {
+        let settings = &MAINNET_KZG_TRUSTED_SETUP;
+        let sidecar = sidecar(2);
+        let hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+
+        // the proofs of the blobs are swapped
+        let mut swapped_proofs = sidecar.clone();
+        swapped_proofs.proofs.swap(0, 1);
+        assert_eq!(
+            swapped_proofs.validate(&hashes, settings),
+            Err(InvalidBlobSidecarError::InvalidKzgProof)
+        );
+
+        // the blob doesn't match its commitment
+        let mut changed_blob = sidecar.clone();
+        changed_blob.blobs[0] = sidecar.blobs[1].clone();
+        assert_eq!(
+            changed_blob.validate(&hashes, settings),
+            Err(InvalidBlobSidecarError::InvalidKzgProof)
+        );
+
+        // a field element that's not canonical, i.e. greater than the BLS modulus
+        let mut invalid_blob = sidecar.clone();
+        invalid_blob.blobs[0] = Bytes::from(vec![0xff; BYTES_PER_BLOB]);
+        assert!(matches!(
+            invalid_blob.validate(&hashes, settings),
+            Err(InvalidBlobSidecarError::Kzg(_))
+        ));
+
+        // commitments that aren't valid points fail before the proofs are checked
+        let mut invalid_commitments = sidecar;
+        invalid_commitments.commitments = vec![Bytes::from(vec![0u8; BYTES_PER_COMMITMENT]); 2];
+        let hashes = invalid_commitments.versioned_hashes().collect::<Vec<_>>();
+        assert!(matches!(
+            invalid_commitments.validate(&hashes, settings),
+            Err(InvalidBlobSidecarError::Kzg(_))
+        ));
+    }
+}
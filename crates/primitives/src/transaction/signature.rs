@@ -87,6 +87,16 @@ impl Signature {
         })
     }
 
+    /// Returns the 65 bytes `r || s || v` encoding of the signature, with `v` being `27` or `28`
+    /// as returned by `eth_sign`.
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut sig = [0u8; 65];
+        sig[..32].copy_from_slice(&self.r.to_be_bytes::<32>());
+        sig[32..64].copy_from_slice(&self.s.to_be_bytes::<32>());
+        sig[64] = self.v(None) as u8;
+        sig
+    }
+
     /// Recover signature from hash.
     pub(crate) fn recover_signer(&self, hash: H256) -> Option<Address> {
         let mut sig: [u8; 65] = [0; 65];
@@ -109,6 +119,15 @@ mod tests {
 
     use crate::{Address, Signature, H256, U256};
 
+    #[test]
+    fn test_to_bytes() {
+        let signature = Signature { r: U256::from(1), s: U256::from(2), odd_y_parity: true };
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes[31], 1);
+        assert_eq!(bytes[63], 2);
+        assert_eq!(bytes[64], 28);
+    }
+
     #[test]
     fn test_payload_len_with_eip155_chain_id() {
         let signature = Signature { r: U256::default(), s: U256::default(), odd_y_parity: false };
@@ -0,0 +1,81 @@
+use reth_primitives::{bytes::Bytes, Address};
+use revm::{
+    interpreter::{CallInputs, CallScheme, CreateInputs, Gas, InstructionResult},
+    Database, EVMData, Inspector,
+};
+use std::collections::BTreeSet;
+
+/// An [Inspector] that collects the addresses that sent or received a call, including internal
+/// calls, contract creations and selfdestructs.
+///
+/// The `from` and `to` of a call are determined in the same way as by the
+/// [TracingInspector](crate::tracing::TracingInspector), so the collected addresses are the ones
+/// that appear in the parity traces of the transaction.
+#[derive(Default, Debug)]
+pub struct CallAddressInspector {
+    /// All addresses that sent or received a call
+    addresses: BTreeSet<Address>,
+}
+
+impl CallAddressInspector {
+    /// Returns the collected addresses.
+    pub fn addresses(&self) -> &BTreeSet<Address> {
+        &self.addresses
+    }
+
+    /// Consumes the inspector and returns the collected addresses.
+    pub fn into_addresses(self) -> BTreeSet<Address> {
+        self.addresses
+    }
+}
+
+impl<DB> Inspector<DB> for CallAddressInspector
+where
+    DB: Database,
+{
+    fn call(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+        _is_static: bool,
+    ) -> (InstructionResult, Gas, Bytes) {
+        let (from, to) = match inputs.context.scheme {
+            CallScheme::DelegateCall | CallScheme::CallCode => {
+                (inputs.context.address, inputs.context.code_address)
+            }
+            _ => (inputs.context.caller, inputs.context.address),
+        };
+        self.addresses.extend([from, to]);
+
+        (InstructionResult::Continue, Gas::new(0), Bytes::new())
+    }
+
+    fn create(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
+        self.addresses.insert(inputs.caller);
+
+        (InstructionResult::Continue, None, Gas::new(0), Bytes::new())
+    }
+
+    fn create_end(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        _inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<Address>,
+        remaining_gas: Gas,
+        out: Bytes,
+    ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
+        // the created contract is the recipient of the call
+        self.addresses.extend(address);
+
+        (ret, address, remaining_gas, out)
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address) {
+        self.addresses.extend([contract, target]);
+    }
+}
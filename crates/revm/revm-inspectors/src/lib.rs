@@ -10,6 +10,9 @@
 /// An inspector implementation for an EIP2930 Accesslist
 pub mod access_list;
 
+/// An inspector that collects the senders and recipients of all calls of a transaction
+pub mod call_addresses;
+
 /// An inspector stack abstracting the implementation details of
 /// each inspector and allowing to hook on block/transaciton execution,
 /// used in the main RETH executor.
@@ -1,10 +1,12 @@
 use crate::tracing::types::{CallTrace, CallTraceNode, LogCallOrder};
 use reth_primitives::{Address, JsonU256, H256, U256};
 use reth_rpc_types::trace::{
-    geth::{DefaultFrame, GethDebugTracingOptions, StructLog},
-    parity::{ActionType, TransactionTrace},
+    geth::{CallConfig, CallFrame, DefaultFrame, GethDebugTracingOptions, StructLog},
+    parity::{
+        MemoryDelta, StorageDelta, TransactionTrace, VmExecutedOperation, VmInstruction, VmTrace,
+    },
 };
-use revm::interpreter::{opcode, InstructionResult};
+use revm::interpreter::opcode;
 use std::collections::{BTreeMap, HashMap};
 
 /// An arena of recorded traces.
@@ -50,33 +52,154 @@ impl CallTraceArena {
         }
     }
 
+    /// Returns the trace address of the node with the given index: the path of child positions
+    /// from the root call to the node.
+    fn trace_address(&self, idx: usize) -> Vec<usize> {
+        let mut trace_address = Vec::new();
+        let mut node = &self.arena[idx];
+        while let Some(parent) = node.parent {
+            let parent_node = &self.arena[parent];
+            let position = parent_node
+                .children
+                .iter()
+                .position(|child| *child == node.idx)
+                .expect("node is a child of its parent");
+            trace_address.push(position);
+            node = parent_node;
+        }
+        trace_address.reverse();
+        trace_address
+    }
+
     /// Returns the traces of the transaction for `trace_transaction`
     pub fn parity_traces(&self) -> Vec<TransactionTrace> {
-        let traces = Vec::with_capacity(self.arena.len());
-        for (_idx, node) in self.arena.iter().cloned().enumerate() {
-            let _action = node.parity_action();
-            let _result = node.parity_result();
-
-            let _action_type = if node.status() == InstructionResult::SelfDestruct {
-                ActionType::Selfdestruct
-            } else {
-                node.kind().into()
-            };
-
-            todo!()
-
-            // let trace = TransactionTrace {
-            //     action,
-            //     result: Some(result),
-            //     trace_address: self.info.trace_address(idx),
-            //     subtraces: node.children.len(),
-            // };
-            // traces.push(trace)
+        let mut traces = Vec::with_capacity(self.arena.len());
+        let mut has_selfdestructs = false;
+        for node in self.arena.iter() {
+            let trace_address = self.trace_address(node.idx);
+
+            if let Some(action) = node.parity_selfdestruct_action() {
+                // the selfdestruct is reported as the last child of the call
+                let mut selfdestruct_address = trace_address.clone();
+                selfdestruct_address.push(node.children.len());
+                traces.push(TransactionTrace {
+                    trace_address: selfdestruct_address,
+                    subtraces: 0,
+                    action,
+                    result: None,
+                });
+                has_selfdestructs = true;
+            }
+
+            traces.push(TransactionTrace {
+                trace_address,
+                subtraces: node.parity_subtraces(),
+                action: node.parity_action(),
+                result: Some(node.parity_trace_result()),
+            });
+        }
+
+        if has_selfdestructs {
+            // traces are expected in depth-first order, which is the order of the trace addresses
+            traces.sort_by(|a, b| a.trace_address.cmp(&b.trace_address));
         }
 
         traces
     }
 
+    /// Returns the parity `vmTrace` of the transaction.
+    ///
+    /// This requires that the steps were recorded, see
+    /// [TracingInspector::with_steps_recording](crate::tracing::TracingInspector::with_steps_recording).
+    pub fn vm_trace(&self) -> VmTrace {
+        match self.arena.first() {
+            Some(root) => self.vm_trace_for(root),
+            None => VmTrace { code: Default::default(), ops: Vec::new() },
+        }
+    }
+
+    /// Recursively builds the `vmTrace` of the given node and its children.
+    fn vm_trace_for(&self, node: &CallTraceNode) -> VmTrace {
+        let mut children = node.children.iter();
+        let ops = node
+            .trace
+            .steps
+            .iter()
+            .map(|step| {
+                let op = step.op.u8();
+                let sub = match op {
+                    opcode::CREATE |
+                    opcode::CREATE2 |
+                    opcode::DELEGATECALL |
+                    opcode::CALL |
+                    opcode::STATICCALL |
+                    opcode::CALLCODE
+                        if !step.is_error() =>
+                    {
+                        children.next().map(|child| self.vm_trace_for(&self.arena[*child]))
+                    }
+                    _ => None,
+                };
+
+                let ex = (!step.is_error()).then(|| VmExecutedOperation {
+                    used: step.gas.saturating_sub(step.gas_cost),
+                    push: step.push_stack.clone().unwrap_or_default(),
+                    mem: step
+                        .memory_write
+                        .clone()
+                        .map(|(off, data)| MemoryDelta { off, data: data.into() }),
+                    store: if op == opcode::SSTORE {
+                        step.state_diff.map(|(key, val)| StorageDelta { key, val })
+                    } else {
+                        None
+                    },
+                });
+
+                VmInstruction { pc: step.pc, cost: step.gas_cost, ex, sub }
+            })
+            .collect();
+
+        VmTrace { code: node.trace.code.clone().into(), ops }
+    }
+
+    /// Generate a geth-style call trace, e.g. for `debug_traceTransaction` with the
+    /// `callTracer`.
+    pub fn geth_call_traces(&self, config: CallConfig) -> CallFrame {
+        match self.arena.first() {
+            Some(root) => self.geth_call_frame_for(
+                root,
+                config.only_top_call.unwrap_or_default(),
+                config.with_log.unwrap_or_default(),
+            ),
+            None => Default::default(),
+        }
+    }
+
+    /// Recursively builds the geth [CallFrame] of the given node and its children.
+    ///
+    /// Logs of failed calls are not included, same as geth.
+    fn geth_call_frame_for(
+        &self,
+        node: &CallTraceNode,
+        only_top_call: bool,
+        with_log: bool,
+    ) -> CallFrame {
+        let with_log = with_log && node.trace.success;
+        let mut call_frame = node.geth_empty_call_frame(with_log);
+        if only_top_call {
+            return call_frame
+        }
+
+        call_frame.calls = node
+            .children
+            .iter()
+            .map(|child| self.geth_call_frame_for(&self.arena[*child], false, with_log))
+            .collect();
+        call_frame.calls.extend(node.geth_selfdestruct_call_frame());
+
+        call_frame
+    }
+
     /// Recursively fill in the geth trace by going through the traces
     ///
     /// TODO rewrite this iteratively
@@ -117,14 +240,13 @@ impl CallTraceArena {
                 opcode::DELEGATECALL |
                 opcode::CALL |
                 opcode::STATICCALL |
-                opcode::CALLCODE => {
-                    self.add_to_geth_trace(
-                        storage,
-                        &self.arena[trace_node.children[child_id]],
-                        struct_logs,
-                        opts,
-                    );
-                    child_id += 1;
+                opcode::CALLCODE
+                    if !step.is_error() =>
+                {
+                    if let Some(child) = trace_node.children.get(child_id) {
+                        self.add_to_geth_trace(storage, &self.arena[*child], struct_logs, opts);
+                        child_id += 1;
+                    }
                 }
                 _ => {}
             }
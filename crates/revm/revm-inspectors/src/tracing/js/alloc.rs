@@ -0,0 +1,146 @@
+//! Accounting of the memory that javascript tracers allocate.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+thread_local! {
+    /// Whether the allocations of this thread are currently accounted to a tracer.
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+    /// The net number of bytes this thread allocated while tracking.
+    static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+}
+
+/// A [GlobalAlloc] that accounts the allocations of javascript tracers and passes everything on to
+/// the wrapped allocator.
+///
+/// The binary that runs the tracers must install it as its `#[global_allocator]`, in its
+/// `main.rs`, to enforce [JsTracerLimits::memory_limit](super::JsTracerLimits::memory_limit),
+/// without it the memory of tracers is not limited. Libraries must not install it, as a binary can
+/// only have one global allocator.
+///
+/// Only allocations of a thread that is currently running a tracer hook are accounted, for all
+/// other allocations this costs a single thread local lookup.
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: JsTracerAllocator = JsTracerAllocator::system();
+/// ```
+#[derive(Debug, Default)]
+pub struct JsTracerAllocator<A = System> {
+    inner: A,
+}
+
+impl JsTracerAllocator<System> {
+    /// Creates a new allocator that wraps the [System] allocator.
+    pub const fn system() -> Self {
+        Self { inner: System }
+    }
+}
+
+impl<A> JsTracerAllocator<A> {
+    /// Creates a new allocator that wraps the given allocator.
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+// SAFETY: all calls are forwarded to the wrapped allocator.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for JsTracerAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        record(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+/// Accounts the allocated bytes if the current thread is tracking.
+#[inline]
+fn record(bytes: isize) {
+    // allocations can happen while the thread locals are destroyed, in which case they're ignored
+    let _ = TRACKING.try_with(|tracking| {
+        if tracking.get() {
+            let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + bytes));
+        }
+    });
+}
+
+/// Accounts the allocations of the current thread while it exists.
+#[derive(Debug)]
+pub(crate) struct AllocationScope {
+    /// Whether the thread was already tracking when the scope was entered.
+    was_tracking: bool,
+    /// The allocated bytes when the scope was entered.
+    start: isize,
+}
+
+impl AllocationScope {
+    /// Starts accounting the allocations of the current thread.
+    pub(crate) fn enter() -> Self {
+        let was_tracking = TRACKING.with(|tracking| tracking.replace(true));
+        Self { was_tracking, start: ALLOCATED.with(Cell::get) }
+    }
+
+    /// Returns the net number of bytes that were allocated since the scope was entered.
+    ///
+    /// This is negative if more memory was freed than allocated.
+    pub(crate) fn allocated(&self) -> isize {
+        ALLOCATED.with(Cell::get) - self.start
+    }
+}
+
+impl Drop for AllocationScope {
+    fn drop(&mut self) {
+        TRACKING.with(|tracking| tracking.set(self.was_tracking));
+    }
+}
+
+// the allocator is installed for the tests of this crate so the memory limit can be tested
+#[cfg(test)]
+#[global_allocator]
+static ALLOC: JsTracerAllocator = JsTracerAllocator::system();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocation_scope() {
+        let scope = AllocationScope::enter();
+        let data = vec![0u8; 1024 * 1024];
+        assert!(scope.allocated() >= 1024 * 1024);
+        drop(data);
+        assert!(scope.allocated() < 1024);
+        drop(scope);
+
+        // allocations outside of a scope are not accounted
+        let before = ALLOCATED.with(Cell::get);
+        let data = vec![0u8; 1024];
+        assert_eq!(ALLOCATED.with(Cell::get), before);
+        drop(data);
+    }
+}
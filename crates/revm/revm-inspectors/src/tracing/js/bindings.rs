@@ -0,0 +1,332 @@
+//! Native functions that back the javascript tracer environment.
+
+use boa_engine::{Context, JsNativeError, JsResult, JsString, JsValue, NativeFunction};
+use reth_primitives::{
+    bytes::Bytes,
+    contract::{create2_address_from_code, create_address},
+    hex, Address, H256, U256,
+};
+use revm::{
+    db::DatabaseRef,
+    primitives::{AccountInfo, Bytecode, State as EvmState, KECCAK_EMPTY},
+    Database, EVMData,
+};
+use std::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    rc::Rc,
+};
+
+/// Read access to the state for the `db` object of the javascript tracer.
+pub(crate) trait JsDb {
+    /// Returns the account info of the given address.
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, String>;
+
+    /// Returns the bytecode for the given code hash.
+    fn code_by_hash(&mut self, code_hash: H256) -> Result<Bytecode, String>;
+
+    /// Returns the value of the storage slot of the given address.
+    fn storage(&mut self, address: Address, slot: U256) -> Result<U256, String>;
+}
+
+/// A [JsDb] that reads the state of an ongoing execution.
+///
+/// Accounts and slots that are not yet loaded are read from the database directly, so that
+/// accessing them from the tracer does not warm them up.
+pub(crate) struct EvmDb<'a, 'b, DB: Database> {
+    data: &'a mut EVMData<'b, DB>,
+}
+
+impl<'a, 'b, DB: Database> EvmDb<'a, 'b, DB> {
+    pub(crate) fn new(data: &'a mut EVMData<'b, DB>) -> Self {
+        Self { data }
+    }
+}
+
+impl<DB: Database> JsDb for EvmDb<'_, '_, DB> {
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, String> {
+        if let Some(account) = self.data.journaled_state.state.get(&address) {
+            return Ok(Some(account.info.clone()))
+        }
+        self.data.db.basic(address).map_err(|_| format!("failed to load account {address:?}"))
+    }
+
+    fn code_by_hash(&mut self, code_hash: H256) -> Result<Bytecode, String> {
+        self.data
+            .db
+            .code_by_hash(code_hash)
+            .map_err(|_| format!("failed to load code {code_hash:?}"))
+    }
+
+    fn storage(&mut self, address: Address, slot: U256) -> Result<U256, String> {
+        if let Some(account) = self.data.journaled_state.state.get(&address) {
+            if let Some(value) = account.storage.get(&slot) {
+                return Ok(value.present_value())
+            }
+            if account.storage_cleared {
+                return Ok(U256::ZERO)
+            }
+        }
+        self.data.db.storage(address, slot).map_err(|_| format!("failed to load slot {slot}"))
+    }
+}
+
+/// A [JsDb] that reads the state after an execution by applying the changes on top of the
+/// database.
+pub(crate) struct StateDb<'a, DB> {
+    changes: &'a EvmState,
+    db: &'a DB,
+}
+
+impl<'a, DB: DatabaseRef> StateDb<'a, DB> {
+    pub(crate) fn new(changes: &'a EvmState, db: &'a DB) -> Self {
+        Self { changes, db }
+    }
+}
+
+impl<DB: DatabaseRef> JsDb for StateDb<'_, DB> {
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, String> {
+        if let Some(account) = self.changes.get(&address) {
+            return Ok((!account.is_destroyed).then(|| account.info.clone()))
+        }
+        self.db.basic(address).map_err(|_| format!("failed to load account {address:?}"))
+    }
+
+    fn code_by_hash(&mut self, code_hash: H256) -> Result<Bytecode, String> {
+        self.db.code_by_hash(code_hash).map_err(|_| format!("failed to load code {code_hash:?}"))
+    }
+
+    fn storage(&mut self, address: Address, slot: U256) -> Result<U256, String> {
+        if let Some(account) = self.changes.get(&address) {
+            if account.is_destroyed || account.storage_cleared {
+                return Ok(account
+                    .storage
+                    .get(&slot)
+                    .map(|value| value.present_value())
+                    .unwrap_or_default())
+            }
+            if let Some(value) = account.storage.get(&slot) {
+                return Ok(value.present_value())
+            }
+        }
+        self.db.storage(address, slot).map_err(|_| format!("failed to load slot {slot}"))
+    }
+}
+
+/// Shares the [JsDb] of the currently executing hook with the javascript environment.
+#[derive(Clone, Default)]
+pub(crate) struct DbSlot(Rc<Cell<Option<*mut (dyn JsDb + 'static)>>>);
+
+impl DbSlot {
+    /// Makes the database available to the `db` object until the returned guard is dropped.
+    pub(crate) fn set<'a>(&self, db: &'a mut (dyn JsDb + 'a)) -> DbGuard<'a> {
+        let ptr = db as *mut (dyn JsDb + 'a);
+        // SAFETY: only the lifetime is erased here, the returned guard borrows the database and
+        // clears the slot when it is dropped, so the pointer is never accessed after the database
+        // went out of scope.
+        let ptr =
+            unsafe { std::mem::transmute::<*mut (dyn JsDb + 'a), *mut (dyn JsDb + 'static)>(ptr) };
+        self.0.set(Some(ptr));
+        DbGuard { slot: self.clone(), _db: PhantomData }
+    }
+
+    /// Calls the closure with the database of the currently executing hook.
+    ///
+    /// Returns `None` if no database is set.
+    fn with<R>(&self, f: impl FnOnce(&mut dyn JsDb) -> R) -> Option<R> {
+        let ptr = self.0.get()?;
+        // SAFETY: the pointer is only set while the [DbGuard] that borrows the database is alive
+        Some(f(unsafe { &mut *ptr }))
+    }
+}
+
+/// Clears the [DbSlot] on drop.
+pub(crate) struct DbGuard<'a> {
+    slot: DbSlot,
+    _db: PhantomData<&'a mut ()>,
+}
+
+impl Drop for DbGuard<'_> {
+    fn drop(&mut self) {
+        self.slot.0.set(None);
+    }
+}
+
+/// The stack, memory and input of the step that is currently passed to the tracer.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StepData {
+    pub(crate) stack: Vec<U256>,
+    pub(crate) memory: Bytes,
+    pub(crate) input: Bytes,
+}
+
+/// Shares the [StepData] of the current step with the javascript environment.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StepSlot(Rc<RefCell<StepData>>);
+
+impl StepSlot {
+    /// Replaces the current step data.
+    pub(crate) fn set(&self, data: StepData) {
+        *self.0.borrow_mut() = data;
+    }
+}
+
+/// Registers all native functions that are used by the prelude.
+pub(crate) fn register_natives(ctx: &mut Context<'_>, db: DbSlot, step: StepSlot) -> JsResult<()> {
+    ctx.register_global_callable(
+        "__toContractAddress",
+        2,
+        NativeFunction::from_fn_ptr(to_contract_address),
+    )?;
+    ctx.register_global_callable("__toContract2", 3, NativeFunction::from_fn_ptr(to_contract2))?;
+
+    // SAFETY: the closures don't capture any garbage collected values
+    let db_fn =
+        unsafe { NativeFunction::from_closure(move |_this, args, ctx| db_access(&db, args, ctx)) };
+    ctx.register_global_callable("__db", 3, db_fn)?;
+
+    // SAFETY: the closures don't capture any garbage collected values
+    let step_fn = unsafe {
+        NativeFunction::from_closure(move |_this, args, ctx| step_access(&step, args, ctx))
+    };
+    ctx.register_global_callable("__step", 3, step_fn)?;
+
+    Ok(())
+}
+
+/// `__db(kind, address, slot)`
+fn db_access(db: &DbSlot, args: &[JsValue], ctx: &mut Context<'_>) -> JsResult<JsValue> {
+    let kind = string_arg(args, 0, ctx)?;
+    let address = address_arg(args, 1, ctx)?;
+    let slot = if kind == "state" {
+        let slot = string_arg(args, 2, ctx)?;
+        let slot = slot.parse::<H256>().map_err(|_| {
+            JsNativeError::typ().with_message(format!("invalid storage slot {slot}"))
+        })?;
+        Some(U256::from_be_bytes(slot.0))
+    } else {
+        None
+    };
+
+    let value = db
+        .with(|db| -> Result<String, String> {
+            let info = db.basic(address)?;
+            let value = match kind.as_str() {
+                "balance" => format!("0x{:x}", info.map(|info| info.balance).unwrap_or_default()),
+                "nonce" => info.map(|info| info.nonce).unwrap_or_default().to_string(),
+                "code" => {
+                    let code = match info {
+                        Some(info) if info.code_hash != KECCAK_EMPTY => match info.code {
+                            Some(code) => code,
+                            None => db.code_by_hash(info.code_hash)?,
+                        },
+                        _ => Bytecode::default(),
+                    };
+                    format!("0x{}", hex::encode(&code.bytes()[..code.len()]))
+                }
+                "state" => {
+                    let value = db.storage(address, slot.unwrap_or_default())?;
+                    format!("{:?}", H256(value.to_be_bytes()))
+                }
+                "exists" => info.map_or(false, |info| !info.is_empty()).to_string(),
+                kind => return Err(format!("unknown db access {kind}")),
+            };
+            Ok(value)
+        })
+        .ok_or_else(|| JsNativeError::error().with_message("db is not available"))?
+        .map_err(|err| JsNativeError::error().with_message(err))?;
+
+    Ok(JsString::from(value.as_str()).into())
+}
+
+/// `__step(kind, a, b)`
+fn step_access(step: &StepSlot, args: &[JsValue], ctx: &mut Context<'_>) -> JsResult<JsValue> {
+    let kind = string_arg(args, 0, ctx)?;
+    let step = step.0.borrow();
+    let value = match kind.as_str() {
+        "stackLength" => JsValue::from(step.stack.len() as f64),
+        "stackPeek" => {
+            let idx = index_arg(args, 1, ctx)?;
+            let len = step.stack.len();
+            if idx >= len {
+                return Err(JsNativeError::error()
+                    .with_message(format!(
+                        "tracer accessed out of bound stack: size {len}, index {idx}"
+                    ))
+                    .into())
+            }
+            hex_string(format!("0x{:x}", step.stack[len - 1 - idx]))
+        }
+        "memoryLength" => JsValue::from(step.memory.len() as f64),
+        "memorySlice" => {
+            let start = index_arg(args, 1, ctx)?;
+            let end = index_arg(args, 2, ctx)?;
+            let Some(slice) = step.memory.get(start..end) else {
+                return Err(JsNativeError::error()
+                    .with_message(format!(
+                        "tracer accessed out of bound memory: available {}, offset {start}, size {}",
+                        step.memory.len(),
+                        end.saturating_sub(start)
+                    ))
+                    .into())
+            };
+            hex_bytes(slice)
+        }
+        "input" => hex_bytes(&step.input),
+        kind => {
+            return Err(JsNativeError::error()
+                .with_message(format!("unknown step access {kind}"))
+                .into())
+        }
+    };
+    Ok(value)
+}
+
+/// `__toContractAddress(from, nonce)`
+fn to_contract_address(
+    _this: &JsValue,
+    args: &[JsValue],
+    ctx: &mut Context<'_>,
+) -> JsResult<JsValue> {
+    let from = address_arg(args, 0, ctx)?;
+    let nonce = index_arg(args, 1, ctx)? as u64;
+    Ok(hex_bytes(create_address(from, nonce)))
+}
+
+/// `__toContract2(from, salt, initcode)`
+fn to_contract2(_this: &JsValue, args: &[JsValue], ctx: &mut Context<'_>) -> JsResult<JsValue> {
+    let from = address_arg(args, 0, ctx)?;
+    let salt = string_arg(args, 1, ctx)?
+        .parse::<H256>()
+        .map_err(|_| JsNativeError::typ().with_message("invalid salt"))?;
+    let init_code = hex::decode(string_arg(args, 2, ctx)?.trim_start_matches("0x"))
+        .map_err(|_| JsNativeError::typ().with_message("invalid init code"))?;
+    Ok(hex_bytes(create2_address_from_code(from, init_code, U256::from_be_bytes(salt.0))))
+}
+
+fn string_arg(args: &[JsValue], idx: usize, ctx: &mut Context<'_>) -> JsResult<String> {
+    Ok(args.get(idx).cloned().unwrap_or_default().to_string(ctx)?.to_std_string_escaped())
+}
+
+fn address_arg(args: &[JsValue], idx: usize, ctx: &mut Context<'_>) -> JsResult<Address> {
+    let address = string_arg(args, idx, ctx)?;
+    address
+        .parse::<Address>()
+        .map_err(|_| JsNativeError::typ().with_message(format!("invalid address {address}")).into())
+}
+
+fn index_arg(args: &[JsValue], idx: usize, ctx: &mut Context<'_>) -> JsResult<usize> {
+    let value = args.get(idx).cloned().unwrap_or_default().to_number(ctx)?;
+    if value < 0.0 || value.fract() != 0.0 {
+        return Err(JsNativeError::range().with_message(format!("invalid index {value}")).into())
+    }
+    Ok(value as usize)
+}
+
+fn hex_bytes(bytes: impl AsRef<[u8]>) -> JsValue {
+    hex_string(format!("0x{}", hex::encode(bytes)))
+}
+
+fn hex_string(value: String) -> JsValue {
+    JsString::from(value.as_str()).into()
+}
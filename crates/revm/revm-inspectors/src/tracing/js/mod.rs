@@ -0,0 +1,803 @@
+//! Javascript inspector
+
+use crate::tracing::{
+    js::{
+        alloc::AllocationScope,
+        bindings::{register_natives, DbSlot, EvmDb, JsDb, StateDb, StepData, StepSlot},
+    },
+    types::CallKind,
+    utils::{get_create_address, geth_error_message},
+};
+use boa_engine::{js_string, Context, JsObject, JsResult, JsString, JsValue, Source};
+use reth_primitives::{bytes::Bytes, contract::create_address, Address, H256, U256};
+use revm::{
+    db::DatabaseRef,
+    inspectors::GasInspector,
+    interpreter::{
+        opcode, return_ok, CallInputs, CallScheme, CreateInputs, Gas, InstructionResult,
+        Interpreter, OpCode,
+    },
+    primitives::{Env, ExecutionResult, Output, ResultAndState, TransactTo},
+    Database, EVMData, Inspector,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+mod alloc;
+mod bindings;
+
+pub use alloc::JsTracerAllocator;
+
+/// The javascript code that sets up the environment of the tracer.
+const PRELUDE: &str = include_str!("prelude.js");
+
+/// Limits for the execution of a javascript tracer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsTracerLimits {
+    /// The maximum time the tracer may take, this includes the execution of the transaction.
+    pub timeout: Duration,
+    /// The maximum number of bytes the tracer may allocate.
+    ///
+    /// The memory is checked whenever a hook of the tracer returns, which requires the
+    /// [JsTracerAllocator] to be installed as global allocator.
+    pub memory_limit: usize,
+    /// The maximum number of iterations of a single loop in the tracer.
+    pub loop_iteration_limit: u64,
+    /// The maximum depth of recursive function calls in the tracer.
+    pub recursion_limit: usize,
+    /// The maximum number of values on the stack of the javascript VM.
+    pub stack_size_limit: usize,
+}
+
+impl Default for JsTracerLimits {
+    fn default() -> Self {
+        Self {
+            // same as geth
+            timeout: Duration::from_secs(5),
+            memory_limit: 256 * 1024 * 1024,
+            loop_iteration_limit: 10_000_000,
+            recursion_limit: 400,
+            stack_size_limit: 10 * 1024,
+        }
+    }
+}
+
+/// Additional context of the traced transaction that is exposed via the `ctx` object.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionContext {
+    /// Hash of the block the transaction is contained in.
+    pub block_hash: Option<H256>,
+    /// Index of the transaction in the block.
+    pub tx_index: Option<usize>,
+    /// Hash of the transaction.
+    pub tx_hash: Option<H256>,
+}
+
+/// Errors that can occur when running a javascript tracer.
+#[derive(Debug, thiserror::Error)]
+pub enum JsInspectorError {
+    /// The tracer code could not be evaluated.
+    #[error("failed to evaluate tracer code: {0}")]
+    EvalCode(String),
+    /// The tracer code did not evaluate to an object.
+    #[error("tracer must be a javascript object")]
+    ExpectedJsObject,
+    /// The tracer object is missing the `result` function.
+    #[error("trace object must expose a function result()")]
+    ResultFunctionMissing,
+    /// The tracer object is missing the `fault` function.
+    #[error("trace object must expose a function fault()")]
+    FaultFunctionMissing,
+    /// The tracer object only exposes one of `enter` and `exit`.
+    #[error("trace object must expose either both or none of enter() and exit()")]
+    EnterExitMismatch,
+    /// The `setup` function of the tracer failed.
+    #[error("failed to setup tracer: {0}")]
+    SetupFailed(String),
+    /// An error that was thrown by the tracer.
+    #[error("javascript error: {0}")]
+    JsError(String),
+    /// The tracer exceeded the configured timeout.
+    #[error("execution timeout")]
+    Timeout,
+    /// The tracer allocated more memory than the configured limit.
+    #[error("tracer exceeded the memory limit of {0} bytes")]
+    MemoryLimitExceeded(usize),
+}
+
+/// An inspector that runs a geth style javascript tracer.
+///
+/// The tracer is a javascript object that exposes the hooks `step`, `fault`, `enter`, `exit` and
+/// `result`, see <https://geth.ethereum.org/docs/developers/evm-tracing/custom-tracer>
+///
+/// The tracer runs in a sandboxed interpreter that has no access to anything but the objects that
+/// are passed to its hooks.
+pub struct JsInspector {
+    ctx: Context<'static>,
+    /// The tracer object
+    obj: JsObject,
+    result_fn: JsObject,
+    fault_fn: JsObject,
+    step_fn: Option<JsObject>,
+    enter_fn: Option<JsObject>,
+    exit_fn: Option<JsObject>,
+    /// The `db` object that is passed to the hooks
+    db_obj: JsValue,
+    /// Functions of the prelude that create the hook arguments
+    make_log: JsObject,
+    make_frame: JsObject,
+    make_frame_result: JsObject,
+    make_ctx: JsObject,
+    /// Shares the database of the currently executing hook with the `db` object
+    db: DbSlot,
+    /// Shares the stack and memory of the current step with the `log` object
+    step: StepSlot,
+    /// The step that is passed to the tracer once its cost is known.
+    pending_step: Option<PendingStep>,
+    /// The balance of the contract that executes `SELFDESTRUCT`, recorded before it is moved.
+    selfdestruct_balance: U256,
+    /// The `SELFDESTRUCT` frame that is passed to the tracer after the step.
+    pending_selfdestruct: Option<(Address, Address, U256)>,
+    /// The gas limit of the top level call, used to determine the intrinsic gas.
+    top_call_gas_limit: Option<u64>,
+    transaction_context: TransactionContext,
+    /// The first error that occurred while running the tracer
+    error: Option<JsInspectorError>,
+    started: Instant,
+    deadline: Instant,
+    /// The net number of bytes the tracer allocated so far.
+    memory_used: isize,
+    memory_limit: usize,
+    gas_inspector: GasInspector,
+}
+
+// === impl JsInspector ===
+
+impl JsInspector {
+    /// Creates a new inspector for the given tracer code and config with the default
+    /// [JsTracerLimits].
+    pub fn new(code: String, config: serde_json::Value) -> Result<Self, JsInspectorError> {
+        Self::with_limits(code, config, JsTracerLimits::default())
+    }
+
+    /// Creates a new inspector for the given tracer code and config.
+    ///
+    /// This evaluates the code and calls the `setup` function of the tracer, if it exists.
+    pub fn with_limits(
+        code: String,
+        config: serde_json::Value,
+        limits: JsTracerLimits,
+    ) -> Result<Self, JsInspectorError> {
+        let mut ctx = Context::default();
+        ctx.runtime_limits_mut().set_loop_iteration_limit(limits.loop_iteration_limit);
+        ctx.runtime_limits_mut().set_recursion_limit(limits.recursion_limit);
+        ctx.runtime_limits_mut().set_stack_size_limit(limits.stack_size_limit);
+
+        let db = DbSlot::default();
+        let step = StepSlot::default();
+        register_natives(&mut ctx, db.clone(), step.clone()).map_err(js_error)?;
+        ctx.eval(Source::from_bytes(PRELUDE)).map_err(js_error)?;
+
+        // the memory of the tracer includes everything it sets up
+        let memory_scope = AllocationScope::enter();
+
+        // the tracer is an object literal, which needs to be wrapped in parentheses to be evaluated
+        // as an expression
+        let code = format!("({code})");
+        let obj = ctx
+            .eval(Source::from_bytes(code.as_bytes()))
+            .map_err(|err| JsInspectorError::EvalCode(err.to_string()))?
+            .as_object()
+            .cloned()
+            .ok_or(JsInspectorError::ExpectedJsObject)?;
+
+        let result_fn = get_function(&obj, "result", &mut ctx)?
+            .ok_or(JsInspectorError::ResultFunctionMissing)?;
+        let fault_fn =
+            get_function(&obj, "fault", &mut ctx)?.ok_or(JsInspectorError::FaultFunctionMissing)?;
+        let step_fn = get_function(&obj, "step", &mut ctx)?;
+        let enter_fn = get_function(&obj, "enter", &mut ctx)?;
+        let exit_fn = get_function(&obj, "exit", &mut ctx)?;
+        if enter_fn.is_some() != exit_fn.is_some() {
+            return Err(JsInspectorError::EnterExitMismatch)
+        }
+
+        let global = ctx.global_object();
+        let db_obj = global.get(js_string!("db"), &mut ctx).map_err(js_error)?;
+        let make_log = get_function(&global, "__makeLog", &mut ctx)?.expect("defined in prelude");
+        let make_frame =
+            get_function(&global, "__makeFrame", &mut ctx)?.expect("defined in prelude");
+        let make_frame_result =
+            get_function(&global, "__makeFrameResult", &mut ctx)?.expect("defined in prelude");
+        let make_ctx = get_function(&global, "__makeCtx", &mut ctx)?.expect("defined in prelude");
+
+        if let Some(setup_fn) = get_function(&obj, "setup", &mut ctx)? {
+            // geth passes the config as json string
+            let config = if config.is_null() { "{}".to_string() } else { config.to_string() };
+            setup_fn
+                .call(&obj.clone().into(), &[JsString::from(config.as_str()).into()], &mut ctx)
+                .map_err(|err| JsInspectorError::SetupFailed(err.to_string()))?;
+        }
+
+        let memory_used = memory_scope.allocated();
+        drop(memory_scope);
+        if memory_used > limits.memory_limit as isize {
+            return Err(JsInspectorError::MemoryLimitExceeded(limits.memory_limit))
+        }
+
+        let started = Instant::now();
+        Ok(Self {
+            ctx,
+            obj,
+            result_fn,
+            fault_fn,
+            step_fn,
+            enter_fn,
+            exit_fn,
+            db_obj,
+            make_log,
+            make_frame,
+            make_frame_result,
+            make_ctx,
+            db,
+            step,
+            pending_step: None,
+            selfdestruct_balance: U256::ZERO,
+            pending_selfdestruct: None,
+            top_call_gas_limit: None,
+            transaction_context: Default::default(),
+            error: None,
+            started,
+            deadline: started + limits.timeout,
+            memory_used,
+            memory_limit: limits.memory_limit,
+            gas_inspector: GasInspector::default(),
+        })
+    }
+
+    /// Sets the [TransactionContext] that is exposed via the `ctx` object.
+    pub fn with_transaction_context(mut self, transaction_context: TransactionContext) -> Self {
+        self.transaction_context = transaction_context;
+        self
+    }
+
+    /// Sets the addresses of the active precompiles that are used by `isPrecompiled`.
+    pub fn with_precompiles(
+        mut self,
+        precompiles: impl IntoIterator<Item = Address>,
+    ) -> Result<Self, JsInspectorError> {
+        let precompiles =
+            serde_json::Value::from_iter(precompiles.into_iter().map(|address| json!(address)));
+        let precompiles = JsValue::from_json(&precompiles, &mut self.ctx).map_err(js_error)?;
+        self.ctx
+            .global_object()
+            .set(js_string!("__precompiles"), precompiles, false, &mut self.ctx)
+            .map_err(js_error)?;
+        Ok(self)
+    }
+
+    /// Calls the `result` function of the tracer and returns its json value.
+    ///
+    /// The `db` object of the `result` function reads the state after the execution, this expects
+    /// that the changes of the [ResultAndState] have not been committed to the database yet.
+    pub fn json_result<DB: DatabaseRef>(
+        &mut self,
+        res: ResultAndState,
+        env: &Env,
+        db: &DB,
+    ) -> Result<serde_json::Value, JsInspectorError> {
+        if let Some(err) = self.error.take() {
+            return Err(err)
+        }
+
+        let ResultAndState { result, state } = res;
+        let gas_used = result.gas_used();
+        let (output, error, created) = match result {
+            ExecutionResult::Success { output, .. } => match output {
+                Output::Call(output) => (output, None, None),
+                Output::Create(output, address) => (output, None, address),
+            },
+            ExecutionResult::Revert { output, .. } => {
+                (output, Some(geth_error_message(InstructionResult::Revert)), None)
+            }
+            ExecutionResult::Halt { reason, .. } => {
+                (Bytes::new(), Some(format!("{reason:?}")), None)
+            }
+        };
+        let (typ, to) = match env.tx.transact_to {
+            TransactTo::Call(to) => ("CALL", Some(to)),
+            TransactTo::Create(_) => (
+                "CREATE",
+                created.or_else(|| env.tx.nonce.map(|nonce| create_address(env.tx.caller, nonce))),
+            ),
+        };
+        let intrinsic_gas = self
+            .top_call_gas_limit
+            .map(|gas_limit| env.tx.gas_limit.saturating_sub(gas_limit))
+            .unwrap_or_default();
+
+        let ctx = json!({
+            "type": typ,
+            "from": env.tx.caller,
+            "to": to,
+            "input": reth_primitives::Bytes::from(env.tx.data.clone()),
+            "gas": env.tx.gas_limit,
+            "gasUsed": gas_used,
+            "gasPrice": env.effective_gas_price(),
+            "intrinsicGas": intrinsic_gas,
+            "value": env.tx.value,
+            "block": u64::try_from(env.block.number).unwrap_or(u64::MAX),
+            "output": reth_primitives::Bytes::from(output),
+            "time": format!("{:?}", self.started.elapsed()),
+            "error": error,
+            "blockHash": self.transaction_context.block_hash,
+            "txIndex": self.transaction_context.tx_index,
+            "txHash": self.transaction_context.tx_hash,
+        });
+
+        let mut db = StateDb::new(&state, db);
+        let _guard = self.db.set(&mut db);
+        let memory_scope = AllocationScope::enter();
+        let result = call_hook(
+            &mut self.ctx,
+            &self.obj,
+            &self.result_fn,
+            &self.make_ctx,
+            &ctx,
+            &self.db_obj,
+        )
+        .and_then(|result| result.to_json(&mut self.ctx));
+        self.account_memory(memory_scope)?;
+        result.map_err(js_error)
+    }
+
+    /// Adds the allocations of the scope to the memory of the tracer and returns an error if the
+    /// memory limit is exceeded.
+    fn account_memory(&mut self, scope: AllocationScope) -> Result<(), JsInspectorError> {
+        self.memory_used += scope.allocated();
+        drop(scope);
+        if self.memory_used > self.memory_limit as isize {
+            return Err(JsInspectorError::MemoryLimitExceeded(self.memory_limit))
+        }
+        Ok(())
+    }
+
+    /// Calls the hook with the object the factory creates from the given data.
+    ///
+    /// Records the first error that occurs, after which no more hooks are called.
+    fn invoke(
+        &mut self,
+        hook: &JsObject,
+        factory: &JsObject,
+        data: serde_json::Value,
+        db: Option<&mut dyn JsDb>,
+    ) {
+        if self.error.is_some() {
+            return
+        }
+        if Instant::now() > self.deadline {
+            self.error = Some(JsInspectorError::Timeout);
+            return
+        }
+
+        let _guard = db.map(|db| self.db.set(db));
+        let memory_scope = AllocationScope::enter();
+        let res = call_hook(&mut self.ctx, &self.obj, hook, factory, &data, &self.db_obj);
+        if let Err(err) = self.account_memory(memory_scope) {
+            self.error = Some(err);
+        } else if let Err(err) = res {
+            self.error = Some(js_error(err));
+        }
+    }
+
+    /// Passes the pending step to the `step` function of the tracer, and to the `fault` function
+    /// if the step failed.
+    fn flush_step(&mut self, db: &mut dyn JsDb, cost: u64, status: InstructionResult) {
+        let Some(step) = self.pending_step.take() else { return };
+
+        let error = is_step_error(status).then(|| geth_error_message(status));
+        let log = json!({
+            "op": step.op,
+            "opName": OpCode::try_from_u8(step.op)
+                .map(|op| op.to_string())
+                .unwrap_or_else(|| "INVALID".to_string()),
+            "pc": step.pc,
+            "gas": step.gas,
+            "cost": cost,
+            "depth": step.depth,
+            "refund": step.refund,
+            "error": error,
+            "contract": {
+                "caller": step.caller,
+                "address": step.address,
+                "value": step.value,
+            },
+        });
+        self.step.set(step.data.unwrap_or_default());
+
+        let make_log = self.make_log.clone();
+        if let Some(step_fn) = self.step_fn.clone() {
+            self.invoke(&step_fn, &make_log, log.clone(), Some(&mut *db));
+        }
+        if error.is_some() {
+            let fault_fn = self.fault_fn.clone();
+            self.invoke(&fault_fn, &make_log, log, Some(db));
+        }
+    }
+
+    /// Passes a new call frame to the `enter` function of the tracer.
+    fn enter(
+        &mut self,
+        kind: &str,
+        from: Address,
+        to: Address,
+        input: Bytes,
+        gas: u64,
+        value: Option<U256>,
+    ) {
+        let Some(enter_fn) = self.enter_fn.clone() else { return };
+        let frame = json!({
+            "type": kind,
+            "from": from,
+            "to": to,
+            "input": reth_primitives::Bytes::from(input),
+            "gas": gas,
+            "value": value,
+        });
+        let make_frame = self.make_frame.clone();
+        self.invoke(&enter_fn, &make_frame, frame, None);
+    }
+
+    /// Passes the result of a call frame to the `exit` function of the tracer.
+    fn exit(&mut self, gas_used: u64, output: Bytes, status: InstructionResult) {
+        let Some(exit_fn) = self.exit_fn.clone() else { return };
+        let error = (!matches!(status, return_ok!())).then(|| geth_error_message(status));
+        let frame_result = json!({
+            "gasUsed": gas_used,
+            "output": reth_primitives::Bytes::from(output),
+            "error": error,
+        });
+        let make_frame_result = self.make_frame_result.clone();
+        self.invoke(&exit_fn, &make_frame_result, frame_result, None);
+    }
+
+    /// Passes the pending `SELFDESTRUCT` as a call frame to the tracer.
+    fn flush_selfdestruct(&mut self) {
+        let Some((contract, target, value)) = self.pending_selfdestruct.take() else { return };
+        self.enter("SELFDESTRUCT", contract, target, Bytes::new(), 0, Some(value));
+        self.exit(0, Bytes::new(), InstructionResult::Continue);
+    }
+}
+
+impl<DB> Inspector<DB> for JsInspector
+where
+    DB: Database,
+{
+    fn initialize_interp(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        is_static: bool,
+    ) -> InstructionResult {
+        self.gas_inspector.initialize_interp(interp, data, is_static)
+    }
+
+    fn step(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        is_static: bool,
+    ) -> InstructionResult {
+        self.gas_inspector.step(interp, data, is_static);
+        if self.error.is_some() {
+            // abort the execution, the result is discarded anyway
+            return InstructionResult::Stop
+        }
+
+        let pc = interp.program_counter();
+        let op = interp.contract.bytecode.bytecode()[pc];
+
+        if op == opcode::SELFDESTRUCT && self.enter_fn.is_some() {
+            // the balance is transferred to the refund target when the instruction is executed, so
+            // we need to record it beforehand
+            self.selfdestruct_balance = data
+                .journaled_state
+                .state
+                .get(&interp.contract.address)
+                .map(|account| account.info.balance)
+                .unwrap_or_default();
+        }
+
+        // the step is passed to the tracer once its cost is known, copying the stack and memory is
+        // only required for the `step` function, the `fault` function gets them in `step_end`
+        self.pending_step = Some(PendingStep {
+            op,
+            pc,
+            gas: self.gas_inspector.gas_remaining(),
+            depth: data.journaled_state.depth(),
+            refund: interp.gas.refunded() as u64,
+            caller: interp.contract.caller,
+            address: interp.contract.address,
+            value: interp.contract.value,
+            data: self.step_fn.is_some().then(|| step_data(interp)),
+        });
+
+        InstructionResult::Continue
+    }
+
+    fn step_end(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        is_static: bool,
+        eval: InstructionResult,
+    ) -> InstructionResult {
+        self.gas_inspector.step_end(interp, data, is_static, eval);
+
+        if let Some(step) = &mut self.pending_step {
+            if step.data.is_none() && is_step_error(eval) {
+                // only the `fault` function is called, which sees the state after the failed step
+                step.data = Some(step_data(interp));
+            }
+            let cost = step.gas.saturating_sub(self.gas_inspector.gas_remaining());
+            self.flush_step(&mut EvmDb::new(data), cost, eval);
+        }
+        self.flush_selfdestruct();
+
+        InstructionResult::Continue
+    }
+
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+        is_static: bool,
+    ) -> (InstructionResult, Gas, Bytes) {
+        self.gas_inspector.call(data, inputs, is_static);
+
+        // the call opcode is passed to the tracer before the new frame is entered, the gas that is
+        // forwarded to the call makes up the cost of the opcode
+        self.flush_step(&mut EvmDb::new(data), inputs.gas_limit, InstructionResult::Continue);
+
+        if data.journaled_state.depth() == 0 {
+            self.top_call_gas_limit = Some(inputs.gas_limit);
+        } else {
+            // determine correct `from` and `to`  based on the call scheme
+            let (from, to) = match inputs.context.scheme {
+                CallScheme::DelegateCall | CallScheme::CallCode => {
+                    (inputs.context.address, inputs.context.code_address)
+                }
+                _ => (inputs.context.caller, inputs.context.address),
+            };
+            let value = match inputs.context.scheme {
+                CallScheme::DelegateCall | CallScheme::StaticCall => None,
+                _ => Some(inputs.transfer.value),
+            };
+            let kind = CallKind::from(inputs.context.scheme).to_string();
+            self.enter(&kind, from, to, inputs.input.clone(), inputs.gas_limit, value);
+        }
+
+        (InstructionResult::Continue, Gas::new(0), Bytes::new())
+    }
+
+    fn call_end(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &CallInputs,
+        gas: Gas,
+        ret: InstructionResult,
+        out: Bytes,
+        is_static: bool,
+    ) -> (InstructionResult, Gas, Bytes) {
+        self.gas_inspector.call_end(data, inputs, gas, ret, out.clone(), is_static);
+
+        if data.journaled_state.depth() > 0 {
+            self.exit(gas.spend(), out.clone(), ret);
+        }
+
+        (ret, gas, out)
+    }
+
+    fn create(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
+        self.gas_inspector.create(data, inputs);
+
+        self.flush_step(&mut EvmDb::new(data), inputs.gas_limit, InstructionResult::Continue);
+
+        if data.journaled_state.depth() == 0 {
+            self.top_call_gas_limit = Some(inputs.gas_limit);
+        } else {
+            let _ = data.journaled_state.load_account(inputs.caller, data.db);
+            let nonce = data.journaled_state.account(inputs.caller).info.nonce;
+            let kind = CallKind::from(inputs.scheme).to_string();
+            self.enter(
+                &kind,
+                inputs.caller,
+                get_create_address(inputs, nonce),
+                inputs.init_code.clone(),
+                inputs.gas_limit,
+                Some(inputs.value),
+            );
+        }
+
+        (InstructionResult::Continue, None, Gas::new(inputs.gas_limit), Bytes::default())
+    }
+
+    fn create_end(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &CreateInputs,
+        status: InstructionResult,
+        address: Option<Address>,
+        gas: Gas,
+        retdata: Bytes,
+    ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
+        self.gas_inspector.create_end(data, inputs, status, address, gas, retdata.clone());
+
+        if data.journaled_state.depth() > 0 {
+            self.exit(gas.spend(), retdata.clone(), status);
+        }
+
+        (status, address, gas, retdata)
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address) {
+        if self.enter_fn.is_some() {
+            self.pending_selfdestruct = Some((contract, target, self.selfdestruct_balance));
+        }
+    }
+}
+
+impl std::fmt::Debug for JsInspector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsInspector").finish_non_exhaustive()
+    }
+}
+
+/// A step that has not been passed to the tracer yet.
+#[derive(Debug)]
+struct PendingStep {
+    op: u8,
+    pc: usize,
+    gas: u64,
+    depth: u64,
+    refund: u64,
+    caller: Address,
+    address: Address,
+    value: U256,
+    /// The stack and memory of the step, only captured if the tracer needs them.
+    data: Option<StepData>,
+}
+
+/// Returns the stack, memory and input of the current step.
+fn step_data(interp: &Interpreter) -> StepData {
+    StepData {
+        stack: interp.stack.data().clone(),
+        memory: Bytes::copy_from_slice(interp.memory.data()),
+        input: interp.contract.input.clone(),
+    }
+}
+
+/// Returns `true` if the step failed, in which case the `fault` function of the tracer is called.
+fn is_step_error(status: InstructionResult) -> bool {
+    status as u8 >= InstructionResult::Revert as u8
+}
+
+/// Returns the function with the given name of the object, if it exists.
+fn get_function(
+    obj: &JsObject,
+    name: &str,
+    ctx: &mut Context<'_>,
+) -> Result<Option<JsObject>, JsInspectorError> {
+    let value = obj.get(JsString::from(name), ctx).map_err(js_error)?;
+    Ok(value.as_callable().cloned())
+}
+
+/// Calls the hook of the tracer object with the object the factory creates from the data and the
+/// `db` object.
+fn call_hook(
+    ctx: &mut Context<'_>,
+    obj: &JsObject,
+    hook: &JsObject,
+    factory: &JsObject,
+    data: &serde_json::Value,
+    db: &JsValue,
+) -> JsResult<JsValue> {
+    let data = JsValue::from_json(data, ctx)?;
+    let arg = factory.call(&JsValue::undefined(), &[data], ctx)?;
+    hook.call(&obj.clone().into(), &[arg, db.clone()], ctx)
+}
+
+fn js_error(err: boa_engine::JsError) -> JsInspectorError {
+    JsInspectorError::JsError(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracer_hooks() {
+        let code = r#"{
+            setup: function (config) { this.config = JSON.parse(config); },
+            step: function (log, db) {},
+            fault: function (log, db) {},
+            result: function (ctx, db) { return this.config; }
+        }"#;
+        let inspector = JsInspector::new(code.to_string(), json!({ "foo": 1 })).unwrap();
+        assert!(inspector.step_fn.is_some());
+        assert!(inspector.enter_fn.is_none());
+    }
+
+    #[test]
+    fn test_tracer_requires_result() {
+        let code = "{ fault: function (log, db) {} }";
+        let err = JsInspector::new(code.to_string(), serde_json::Value::Null).unwrap_err();
+        assert!(matches!(err, JsInspectorError::ResultFunctionMissing));
+
+        let code = "{ result: function () {}, fault: function () {}, enter: function () {} }";
+        let err = JsInspector::new(code.to_string(), serde_json::Value::Null).unwrap_err();
+        assert!(matches!(err, JsInspectorError::EnterExitMismatch));
+    }
+
+    #[test]
+    fn test_tracer_memory_limit() {
+        let code = r#"{
+            data: [],
+            enter: function (frame) {
+                for (var i = 0; i < 100000; i++) { this.data.push("entry " + i); }
+            },
+            exit: function () {},
+            fault: function () {},
+            result: function () { return this.data.length; }
+        }"#;
+        let limits = JsTracerLimits { memory_limit: 1024 * 1024, ..Default::default() };
+        let mut inspector =
+            JsInspector::with_limits(code.to_string(), serde_json::Value::Null, limits).unwrap();
+        inspector.enter("CALL", Address::zero(), Address::zero(), Bytes::new(), 0, None);
+        assert!(matches!(inspector.error, Some(JsInspectorError::MemoryLimitExceeded(_))));
+
+        // the code of the tracer is accounted as well
+        let code = r#"{
+            data: new Array(1000000).fill(1),
+            fault: function () {},
+            result: function () {}
+        }"#;
+        let err = JsInspector::with_limits(code.to_string(), serde_json::Value::Null, limits)
+            .unwrap_err();
+        assert!(matches!(err, JsInspectorError::MemoryLimitExceeded(_)));
+    }
+
+    #[test]
+    fn test_prelude_helpers() {
+        let code = r#"{
+            fault: function () {},
+            result: function () {
+                return [
+                    toHex(toAddress("0x01")),
+                    toHex(slice([1, 2, 3, 4], 1, 3)),
+                    toHex(toContractAddress("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0", 0)),
+                ];
+            }
+        }"#;
+        let mut inspector = JsInspector::new(code.to_string(), serde_json::Value::Null).unwrap();
+        let result_fn = inspector.result_fn.clone();
+        let result = result_fn
+            .call(&inspector.obj.clone().into(), &[], &mut inspector.ctx)
+            .unwrap()
+            .to_json(&mut inspector.ctx)
+            .unwrap();
+        assert_eq!(
+            result,
+            json!([
+                "0x0000000000000000000000000000000000000001",
+                "0x0203",
+                "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"
+            ])
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// The environment that is available to javascript tracers.
+//
+// This mirrors the objects and helper functions geth exposes to its javascript tracers, see
+// <https://geth.ethereum.org/docs/developers/evm-tracing/custom-tracer>
+//
+// Functions prefixed with `__` are internal and backed by native functions or used by the
+// inspector to construct the hook arguments.
+
+// The addresses of the active precompiles, set by the inspector.
+var __precompiles = [];
+
+// Converts a hex string into an array of bytes.
+function __bytes(hex) {
+    if (hex === null || hex === undefined) {
+        return undefined;
+    }
+    if (hex.startsWith("0x")) {
+        hex = hex.slice(2);
+    }
+    if (hex.length % 2 !== 0) {
+        hex = "0" + hex;
+    }
+    var bytes = new Array(hex.length / 2);
+    for (var i = 0; i < bytes.length; i++) {
+        bytes[i] = parseInt(hex.substr(i * 2, 2), 16);
+    }
+    return bytes;
+}
+
+// Left pads or truncates the given bytes to `length` bytes.
+function __fixed(value, length) {
+    var bytes = typeof value === "string" ? __bytes(value) : Array.from(value);
+    if (bytes.length > length) {
+        return bytes.slice(bytes.length - length);
+    }
+    while (bytes.length < length) {
+        bytes.unshift(0);
+    }
+    return bytes;
+}
+
+function toHex(value) {
+    if (typeof value === "string") {
+        return value.startsWith("0x") ? value : "0x" + value;
+    }
+    var hex = "0x";
+    for (var i = 0; i < value.length; i++) {
+        hex += ("0" + (value[i] & 0xff).toString(16)).slice(-2);
+    }
+    return hex;
+}
+
+function toWord(value) {
+    return __fixed(value, 32);
+}
+
+function toAddress(value) {
+    return __fixed(value, 20);
+}
+
+function toContractAddress(from, nonce) {
+    return __bytes(__toContractAddress(toHex(toAddress(from)), nonce === undefined ? 0 : nonce));
+}
+
+function toContract2(from, salt, initcode) {
+    return __bytes(__toContract2(toHex(toAddress(from)), toHex(toWord(salt)), toHex(initcode)));
+}
+
+function isPrecompiled(address) {
+    return __precompiles.indexOf(toHex(toAddress(address))) !== -1;
+}
+
+function slice(buf, start, end) {
+    if (start < 0 || start > end || end > buf.length) {
+        throw new Error(
+            "Tracer accessed out of bound memory: available " + buf.length + ", offset " + start +
+            ", size " + (end - start));
+    }
+    return buf.slice(start, end);
+}
+
+var db = {
+    getBalance: function (address) {
+        return BigInt(__db("balance", toHex(toAddress(address))));
+    },
+    getNonce: function (address) {
+        return Number(__db("nonce", toHex(toAddress(address))));
+    },
+    getCode: function (address) {
+        return __bytes(__db("code", toHex(toAddress(address))));
+    },
+    getState: function (address, slot) {
+        return __bytes(__db("state", toHex(toAddress(address)), toHex(toWord(slot))));
+    },
+    exists: function (address) {
+        return __db("exists", toHex(toAddress(address))) === "true";
+    },
+};
+
+function __makeLog(d) {
+    return {
+        op: {
+            toNumber: function () { return d.op; },
+            toString: function () { return d.opName; },
+            isPush: function () { return d.op >= 0x5f && d.op <= 0x7f; },
+        },
+        stack: {
+            length: function () { return __step("stackLength"); },
+            peek: function (idx) { return BigInt(__step("stackPeek", idx)); },
+        },
+        memory: {
+            length: function () { return __step("memoryLength"); },
+            slice: function (start, end) { return __bytes(__step("memorySlice", start, end)); },
+            getUint: function (offset) { return BigInt(__step("memorySlice", offset, offset + 32)); },
+        },
+        contract: {
+            getCaller: function () { return __bytes(d.contract.caller); },
+            getAddress: function () { return __bytes(d.contract.address); },
+            getValue: function () { return BigInt(d.contract.value); },
+            getInput: function () { return __bytes(__step("input")); },
+        },
+        getPC: function () { return d.pc; },
+        getGas: function () { return d.gas; },
+        getCost: function () { return d.cost; },
+        getDepth: function () { return d.depth; },
+        getRefund: function () { return d.refund; },
+        getError: function () { return d.error === null ? undefined : d.error; },
+    };
+}
+
+function __makeFrame(d) {
+    return {
+        getType: function () { return d.type; },
+        getFrom: function () { return __bytes(d.from); },
+        getTo: function () { return __bytes(d.to); },
+        getInput: function () { return __bytes(d.input); },
+        getGas: function () { return d.gas; },
+        getValue: function () { return d.value === null ? undefined : BigInt(d.value); },
+    };
+}
+
+function __makeFrameResult(d) {
+    return {
+        getGasUsed: function () { return d.gasUsed; },
+        getOutput: function () { return __bytes(d.output); },
+        getError: function () { return d.error === null ? undefined : d.error; },
+    };
+}
+
+function __makeCtx(d) {
+    var ctx = {
+        type: d.type,
+        from: __bytes(d.from),
+        to: __bytes(d.to),
+        input: __bytes(d.input),
+        gas: d.gas,
+        gasUsed: d.gasUsed,
+        gasPrice: BigInt(d.gasPrice),
+        intrinsicGas: d.intrinsicGas,
+        value: BigInt(d.value),
+        block: d.block,
+        output: __bytes(d.output),
+        time: d.time,
+    };
+    if (d.error !== null) {
+        ctx.error = d.error;
+    }
+    if (d.blockHash !== null) {
+        ctx.blockHash = __bytes(d.blockHash);
+    }
+    if (d.txIndex !== null) {
+        ctx.txIndex = d.txIndex;
+    }
+    if (d.txHash !== null) {
+        ctx.txHash = __bytes(d.txHash);
+    }
+    return ctx;
+}
@@ -2,7 +2,7 @@ use crate::{
     stack::MaybeOwnedInspector,
     tracing::{
         types::{CallKind, LogCallOrder, RawLog},
-        utils::{gas_used, get_create_address},
+        utils::{gas_used, get_create_address, memory_write_range, stack_push_count},
     },
 };
 pub use arena::CallTraceArena;
@@ -21,6 +21,10 @@ mod arena;
 mod types;
 mod utils;
 
+/// An inspector for running geth style javascript tracers
+#[cfg(feature = "js-tracer")]
+pub mod js;
+
 /// An inspector that collects call traces.
 ///
 /// This [Inspector] can be hooked into the [EVM](revm::EVM) which then calls the inspector
@@ -106,6 +110,7 @@ impl TracingInspector {
         value: U256,
         kind: CallKind,
         caller: Address,
+        gas_limit: u64,
     ) {
         self.trace_stack.push(self.traces.push_trace(
             0,
@@ -117,6 +122,7 @@ impl TracingInspector {
                 value,
                 status: InstructionResult::Continue,
                 caller,
+                gas_limit,
                 ..Default::default()
             },
         ));
@@ -181,6 +187,8 @@ impl TracingInspector {
             // fields will be populated end of call
             gas_cost: 0,
             state_diff: None,
+            push_stack: None,
+            memory_write: None,
             status: InstructionResult::Continue,
         });
     }
@@ -223,6 +231,21 @@ impl TracingInspector {
             };
 
             step.gas_cost = step.gas - self.gas_inspector.as_ref().gas_remaining();
+
+            if matches!(status, return_ok!()) {
+                let stack = interp.stack.data();
+                let push_count = stack_push_count(op).min(stack.len());
+                step.push_stack = Some(stack[stack.len() - push_count..].to_vec());
+
+                step.memory_write =
+                    memory_write_range(op, &step.stack).and_then(|(offset, len)| {
+                        interp
+                            .memory
+                            .data()
+                            .get(offset..offset + len)
+                            .map(|data| (offset, Bytes::copy_from_slice(data)))
+                    });
+            }
         }
 
         // set the status
@@ -240,6 +263,11 @@ where
         data: &mut EVMData<'_, DB>,
         is_static: bool,
     ) -> InstructionResult {
+        if self.record_steps {
+            let trace_idx = self.last_trace_idx();
+            self.traces.arena[trace_idx].trace.code =
+                Bytes::copy_from_slice(interp.contract.bytecode.original_bytecode_slice());
+        }
         self.gas_inspector.initialize_interp(interp, data, is_static)
     }
 
@@ -249,6 +277,16 @@ where
         data: &mut EVMData<'_, DB>,
         is_static: bool,
     ) -> InstructionResult {
+        if interp.contract.bytecode.bytecode()[interp.program_counter()] == opcode::SELFDESTRUCT {
+            // the balance is transferred to the refund target when the instruction is executed, so
+            // we need to record it beforehand
+            let trace_idx = self.last_trace_idx();
+            let address = interp.contract.address;
+            let _ = data.journaled_state.load_account(address, data.db);
+            self.traces.arena[trace_idx].trace.selfdestruct_balance =
+                data.journaled_state.account(address).info.balance;
+        }
+
         if self.record_steps {
             self.gas_inspector.step(interp, data, is_static);
             self.start_step(interp, data);
@@ -310,6 +348,7 @@ where
             inputs.transfer.value,
             inputs.context.scheme.into(),
             from,
+            inputs.gas_limit,
         );
 
         (InstructionResult::Continue, Gas::new(0), Bytes::new())
@@ -352,6 +391,7 @@ where
             inputs.value,
             inputs.scheme.into(),
             inputs.caller,
+            inputs.gas_limit,
         );
 
         (InstructionResult::Continue, None, Gas::new(inputs.gas_limit), Bytes::default())
@@ -1,12 +1,12 @@
 //! Types for representing call trace items.
 
-use crate::tracing::utils::convert_memory;
+use crate::tracing::utils::{convert_memory, geth_error_message};
 use reth_primitives::{bytes::Bytes, Address, H256, U256};
 use reth_rpc_types::trace::{
-    geth::StructLog,
+    geth::{CallFrame, CallLogFrame, StructLog},
     parity::{
         Action, ActionType, CallAction, CallOutput, CallType, CreateAction, CreateOutput,
-        SelfdestructAction, TraceOutput,
+        SelfdestructAction, TraceOutput, TraceResult,
     },
 };
 use revm::interpreter::{
@@ -28,6 +28,19 @@ pub enum CallKind {
     Create2,
 }
 
+impl std::fmt::Display for CallKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CallKind::Call => write!(f, "CALL"),
+            CallKind::StaticCall => write!(f, "STATICCALL"),
+            CallKind::CallCode => write!(f, "CALLCODE"),
+            CallKind::DelegateCall => write!(f, "DELEGATECALL"),
+            CallKind::Create => write!(f, "CREATE"),
+            CallKind::Create2 => write!(f, "CREATE2"),
+        }
+    }
+}
+
 impl From<CallScheme> for CallKind {
     fn from(scheme: CallScheme) -> Self {
         match scheme {
@@ -90,6 +103,9 @@ pub(crate) struct CallTrace {
     /// Holds the target for the selfdestruct refund target if `status` is
     /// [InstructionResult::SelfDestruct]
     pub(crate) selfdestruct_refund_target: Option<Address>,
+    /// The balance of the contract that was transferred to the refund target if `status` is
+    /// [InstructionResult::SelfDestruct]
+    pub(crate) selfdestruct_balance: U256,
     /// The kind of call this is
     pub(crate) kind: CallKind,
     /// The value transferred in the call
@@ -99,12 +115,16 @@ pub(crate) struct CallTrace {
     /// The return data of the call if this was not a contract creation, otherwise it is the
     /// runtime bytecode of the created contract
     pub(crate) output: Bytes,
+    /// The gas limit of the call
+    pub(crate) gas_limit: u64,
     /// The gas cost of the call
     pub(crate) gas_used: u64,
     /// The status of the trace's call
     pub(crate) status: InstructionResult,
     /// call context of the runtime
     pub(crate) call_context: Option<CallContext>,
+    /// The bytecode that was executed in this call, only recorded if steps are recorded
+    pub(crate) code: Bytes,
     /// Opcode-level execution steps
     pub(crate) steps: Vec<CallTraceStep>,
 }
@@ -117,13 +137,16 @@ impl Default for CallTrace {
             caller: Default::default(),
             address: Default::default(),
             selfdestruct_refund_target: None,
+            selfdestruct_balance: Default::default(),
             kind: Default::default(),
             value: Default::default(),
             data: Default::default(),
             output: Default::default(),
+            gas_limit: Default::default(),
             gas_used: Default::default(),
             status: InstructionResult::Continue,
             call_context: Default::default(),
+            code: Default::default(),
             steps: Default::default(),
         }
     }
@@ -174,22 +197,57 @@ impl CallTraceNode {
         }
     }
 
+    /// Returns `true` if the call selfdestructed the contract
+    pub(crate) fn is_selfdestruct(&self) -> bool {
+        self.status() == InstructionResult::SelfDestruct
+    }
+
+    /// Returns the error message of the call in the format used by parity, if it failed.
+    pub(crate) fn parity_error(&self) -> Option<String> {
+        if self.trace.success {
+            return None
+        }
+        let error = match self.status() {
+            InstructionResult::Revert => "Reverted".to_string(),
+            InstructionResult::OutOfGas |
+            InstructionResult::MemoryOOG |
+            InstructionResult::MemoryLimitOOG |
+            InstructionResult::PrecompileOOG |
+            InstructionResult::InvalidOperandOOG => "Out of gas".to_string(),
+            InstructionResult::OpcodeNotFound | InstructionResult::InvalidFEOpcode => {
+                "Bad instruction".to_string()
+            }
+            InstructionResult::InvalidJump => "Bad jump destination".to_string(),
+            InstructionResult::StackUnderflow => "Stack underflow".to_string(),
+            InstructionResult::StackOverflow | InstructionResult::CallTooDeep => {
+                "Out of stack".to_string()
+            }
+            InstructionResult::StateChangeDuringStaticCall => {
+                "Mutable Call In Static Context".to_string()
+            }
+            InstructionResult::OutOfOffset => "Out of bounds".to_string(),
+            status => format!("{status:?}"),
+        };
+        Some(error)
+    }
+
+    /// Returns the result of the call for a parity trace: either the output or the error.
+    pub(crate) fn parity_trace_result(&self) -> TraceResult {
+        match self.parity_error() {
+            Some(error) => TraceResult::Error { error },
+            None => TraceResult::Success { result: self.parity_result() },
+        }
+    }
+
     /// Returns the `Action` for a parity trace
     pub(crate) fn parity_action(&self) -> Action {
-        if self.status() == InstructionResult::SelfDestruct {
-            return Action::Selfdestruct(SelfdestructAction {
-                address: self.trace.address,
-                refund_address: self.trace.selfdestruct_refund_target.unwrap_or_default(),
-                balance: self.trace.value,
-            })
-        }
         match self.kind() {
             CallKind::Call | CallKind::StaticCall | CallKind::CallCode | CallKind::DelegateCall => {
                 Action::Call(CallAction {
                     from: self.trace.caller,
                     to: self.trace.address,
                     value: self.trace.value,
-                    gas: self.trace.gas_used.into(),
+                    gas: self.trace.gas_limit.into(),
                     input: self.trace.data.clone().into(),
                     call_type: self.kind().into(),
                 })
@@ -197,11 +255,100 @@ impl CallTraceNode {
             CallKind::Create | CallKind::Create2 => Action::Create(CreateAction {
                 from: self.trace.caller,
                 value: self.trace.value,
-                gas: self.trace.gas_used.into(),
+                gas: self.trace.gas_limit.into(),
                 init: self.trace.data.clone().into(),
             }),
         }
     }
+
+    /// Returns the selfdestruct `Action` for a parity trace, if the contract selfdestructed.
+    ///
+    /// Parity reports a selfdestruct as an additional trace that is the last child of the call
+    /// that executed the `SELFDESTRUCT` instruction.
+    pub(crate) fn parity_selfdestruct_action(&self) -> Option<Action> {
+        if !self.is_selfdestruct() {
+            return None
+        }
+        Some(Action::Selfdestruct(SelfdestructAction {
+            address: self.trace.address,
+            refund_address: self.trace.selfdestruct_refund_target.unwrap_or_default(),
+            balance: self.trace.selfdestruct_balance,
+        }))
+    }
+
+    /// Returns the number of subtraces of this call in a parity trace.
+    pub(crate) fn parity_subtraces(&self) -> usize {
+        self.children.len() + self.is_selfdestruct() as usize
+    }
+
+    /// Returns the error message of the call in the format used by geth, if it failed.
+    pub(crate) fn geth_error(&self) -> Option<String> {
+        if self.trace.success {
+            return None
+        }
+        Some(geth_error_message(self.status()))
+    }
+
+    /// Returns the geth [CallFrame] of this call, without any of its child calls.
+    ///
+    /// Logs are only included if `include_logs` is set.
+    pub(crate) fn geth_empty_call_frame(&self, include_logs: bool) -> CallFrame {
+        let mut call_frame = CallFrame {
+            typ: self.kind().to_string(),
+            from: self.trace.caller,
+            to: Some(self.trace.address),
+            value: Some(self.trace.value),
+            gas: U256::from(self.trace.gas_limit),
+            gas_used: U256::from(self.trace.gas_used),
+            input: self.trace.data.clone().into(),
+            output: (!self.trace.output.is_empty()).then(|| self.trace.output.clone().into()),
+            error: None,
+            revert_reason: None,
+            calls: Default::default(),
+            logs: Default::default(),
+        };
+
+        // geth does not report a value for these calls
+        if matches!(self.kind(), CallKind::StaticCall | CallKind::DelegateCall) {
+            call_frame.value = None;
+        }
+
+        if let Some(error) = self.geth_error() {
+            // only reverts return data
+            if self.status() != InstructionResult::Revert {
+                call_frame.output = None;
+            }
+            call_frame.error = Some(error);
+        }
+
+        if include_logs {
+            call_frame.logs = self
+                .logs
+                .iter()
+                .map(|log| CallLogFrame {
+                    address: self.trace.address,
+                    topics: log.topics.clone(),
+                    data: log.data.clone().into(),
+                })
+                .collect();
+        }
+
+        call_frame
+    }
+
+    /// Returns the geth `SELFDESTRUCT` [CallFrame], if the contract selfdestructed.
+    pub(crate) fn geth_selfdestruct_call_frame(&self) -> Option<CallFrame> {
+        if !self.is_selfdestruct() {
+            return None
+        }
+        Some(CallFrame {
+            typ: "SELFDESTRUCT".to_string(),
+            from: self.trace.address,
+            to: self.trace.selfdestruct_refund_target,
+            value: Some(self.trace.selfdestruct_balance),
+            ..Default::default()
+        })
+    }
 }
 
 /// Ordering enum for calls and logs
@@ -248,6 +395,10 @@ pub struct CallTraceStep {
     pub gas_cost: u64,
     /// Change of the contract state after step execution (effect of the SLOAD/SSTORE instructions)
     pub state_diff: Option<(U256, U256)>,
+    /// The values pushed onto the stack by this step, if it completed successfully
+    pub push_stack: Option<Vec<U256>>,
+    /// The offset and the data written to memory by this step, if any
+    pub memory_write: Option<(usize, Bytes)>,
     /// Final status of the call
     pub status: InstructionResult,
 }
@@ -5,7 +5,7 @@ use reth_primitives::{
     hex, Address,
 };
 use revm::{
-    interpreter::CreateInputs,
+    interpreter::{opcode, CreateInputs, InstructionResult, Stack},
     primitives::{CreateScheme, SpecId},
 };
 
@@ -38,3 +38,90 @@ pub(crate) fn get_create_address(call: &CreateInputs, nonce: u64) -> Address {
         }
     }
 }
+
+/// Returns the geth error message for the given erroneous [InstructionResult].
+pub(crate) fn geth_error_message(status: InstructionResult) -> String {
+    match status {
+        InstructionResult::Revert => "execution reverted".to_string(),
+        InstructionResult::OutOfGas |
+        InstructionResult::MemoryOOG |
+        InstructionResult::MemoryLimitOOG |
+        InstructionResult::PrecompileOOG |
+        InstructionResult::InvalidOperandOOG => "out of gas".to_string(),
+        InstructionResult::OpcodeNotFound | InstructionResult::InvalidFEOpcode => {
+            "invalid opcode".to_string()
+        }
+        InstructionResult::InvalidJump => "invalid jump destination".to_string(),
+        InstructionResult::StackUnderflow => "stack underflow".to_string(),
+        InstructionResult::StackOverflow => "stack overflow".to_string(),
+        InstructionResult::CallTooDeep => "max call depth exceeded".to_string(),
+        InstructionResult::OutOfFund => "insufficient balance for transfer".to_string(),
+        InstructionResult::StateChangeDuringStaticCall => "write protection".to_string(),
+        InstructionResult::CreateCollision => "contract address collision".to_string(),
+        InstructionResult::CreateContractSizeLimit => "max code size exceeded".to_string(),
+        status => format!("{status:?}"),
+    }
+}
+
+/// Returns the number of items the given opcode pushes onto the stack.
+///
+/// This matches parity's `ret` field of the instruction info: `DUPn` and `SWAPn` report all the
+/// stack items they touched.
+#[inline]
+pub(crate) fn stack_push_count(op: u8) -> usize {
+    match op {
+        opcode::DUP1..=opcode::DUP16 => (op - opcode::DUP1) as usize + 2,
+        opcode::SWAP1..=opcode::SWAP16 => (op - opcode::SWAP1) as usize + 2,
+        opcode::STOP |
+        opcode::CALLDATACOPY |
+        opcode::CODECOPY |
+        opcode::EXTCODECOPY |
+        opcode::RETURNDATACOPY |
+        opcode::POP |
+        opcode::MSTORE |
+        opcode::MSTORE8 |
+        opcode::SSTORE |
+        opcode::JUMP |
+        opcode::JUMPI |
+        opcode::JUMPDEST |
+        opcode::LOG0..=opcode::LOG4 |
+        opcode::RETURN |
+        opcode::REVERT |
+        opcode::INVALID |
+        opcode::SELFDESTRUCT => 0,
+        _ => 1,
+    }
+}
+
+/// Returns the `(offset, len)` of the memory region the given opcode writes to, derived from the
+/// stack _before_ the opcode was executed.
+#[inline]
+pub(crate) fn memory_write_range(op: u8, stack: &Stack) -> Option<(usize, usize)> {
+    let peek = |idx: usize| stack.peek(idx).ok().and_then(|val| usize::try_from(val).ok());
+    let (offset, len) = match op {
+        opcode::MSTORE => (peek(0)?, 32),
+        opcode::MSTORE8 => (peek(0)?, 1),
+        opcode::CALLDATACOPY | opcode::CODECOPY | opcode::RETURNDATACOPY => (peek(0)?, peek(2)?),
+        opcode::EXTCODECOPY => (peek(1)?, peek(3)?),
+        opcode::CALL | opcode::CALLCODE => (peek(5)?, peek(6)?),
+        opcode::DELEGATECALL | opcode::STATICCALL => (peek(4)?, peek(5)?),
+        _ => return None,
+    };
+    (len > 0).then_some((offset, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_push_count() {
+        assert_eq!(stack_push_count(opcode::ADD), 1);
+        assert_eq!(stack_push_count(opcode::PUSH1), 1);
+        assert_eq!(stack_push_count(opcode::DUP1), 2);
+        assert_eq!(stack_push_count(opcode::DUP16), 17);
+        assert_eq!(stack_push_count(opcode::SWAP3), 4);
+        assert_eq!(stack_push_count(opcode::SSTORE), 0);
+        assert_eq!(stack_push_count(opcode::LOG2), 0);
+    }
+}
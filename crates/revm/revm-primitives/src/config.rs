@@ -1,6 +1,7 @@
 //! Reth block execution/validation configuration and constants
 
-use reth_primitives::{ChainSpec, Hardfork, Head};
+use reth_primitives::{BlockNumber, ChainSpec, Hardfork, Head};
+use revm::primitives::SpecId;
 
 /// Two ethereum worth of wei
 pub const WEI_2ETH: u128 = 2000000000000000000u128;
@@ -9,6 +10,38 @@ pub const WEI_3ETH: u128 = 3000000000000000000u128;
 /// Five ethereum worth of wei
 pub const WEI_5ETH: u128 = 5000000000000000000u128;
 
+/// Returns the base reward of a block that is executed with the given spec, which is `None` from
+/// the merge on.
+pub fn base_block_reward(spec_id: SpecId) -> Option<u128> {
+    if spec_id >= SpecId::MERGE {
+        None
+    } else if spec_id >= SpecId::PETERSBURG {
+        Some(WEI_2ETH)
+    } else if spec_id >= SpecId::BYZANTIUM {
+        Some(WEI_3ETH)
+    } else {
+        Some(WEI_5ETH)
+    }
+}
+
+/// Returns the reward of the beneficiary of a block with the given number of ommers: the base
+/// block reward and an additional 1/32 of it for each ommer.
+pub fn block_reward(base_block_reward: u128, ommers: usize) -> u128 {
+    base_block_reward + (base_block_reward >> 5) * ommers as u128
+}
+
+/// Returns the reward of the beneficiary of an ommer, which depends on how many blocks the ommer
+/// precedes the block it's included in.
+///
+/// OpenEthereum code: <https://github.com/openethereum/openethereum/blob/6c2d392d867b058ff867c4373e40850ca3f96969/crates/ethcore/src/ethereum/ethash.rs#L319-L333>
+pub fn ommer_reward(
+    base_block_reward: u128,
+    block_number: BlockNumber,
+    ommer_block_number: BlockNumber,
+) -> u128 {
+    ((8 + ommer_block_number - block_number) as u128 * base_block_reward) >> 3
+}
+
 /// return revm_spec from spec configuration.
 pub fn revm_spec(chain_spec: &ChainSpec, block: Head) -> revm::primitives::SpecId {
     if chain_spec.fork(Hardfork::Shanghai).active_at_head(&block) {
@@ -40,7 +73,7 @@ pub fn revm_spec(chain_spec: &ChainSpec, block: Head) -> revm::primitives::SpecI
 
 #[cfg(test)]
 mod tests {
-    use crate::config::revm_spec;
+    use crate::config::*;
     use reth_primitives::{ChainSpecBuilder, Head, MAINNET, U256};
     #[test]
     fn test_to_revm_spec() {
@@ -92,6 +125,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_block_rewards() {
+        assert_eq!(base_block_reward(revm::primitives::MERGE), None);
+        assert_eq!(base_block_reward(revm::primitives::LONDON), Some(WEI_2ETH));
+        assert_eq!(base_block_reward(revm::primitives::BYZANTIUM), Some(WEI_3ETH));
+        assert_eq!(base_block_reward(revm::primitives::FRONTIER), Some(WEI_5ETH));
+
+        assert_eq!(block_reward(WEI_2ETH, 0), WEI_2ETH);
+        assert_eq!(block_reward(WEI_2ETH, 2), WEI_2ETH + 2 * (WEI_2ETH / 32));
+        assert_eq!(ommer_reward(WEI_2ETH, 10, 9), WEI_2ETH * 7 / 8);
+        assert_eq!(ommer_reward(WEI_2ETH, 10, 4), WEI_2ETH * 2 / 8);
+    }
+
     #[test]
     fn test_eth_spec() {
         assert_eq!(
@@ -1,7 +1,7 @@
 use crate::config::revm_spec;
 use reth_primitives::{
     Address, ChainSpec, Head, Header, Transaction, TransactionKind, TransactionSigned, TxEip1559,
-    TxEip2930, TxLegacy, U256,
+    TxEip2930, TxEip4844, TxLegacy, U256,
 };
 use revm::primitives::{AnalysisKind, BlockEnv, CfgEnv, SpecId, TransactTo, TxEnv};
 
@@ -117,6 +117,8 @@ pub fn fill_tx_env(tx_env: &mut TxEnv, transaction: &TransactionSigned, sender:
                 })
                 .collect();
         }
+        // The blob fields of EIP-4844 transactions are not supported by the EVM yet, so they're
+        // executed like EIP-1559 transactions.
         Transaction::Eip1559(TxEip1559 {
             nonce,
             chain_id,
@@ -127,6 +129,18 @@ pub fn fill_tx_env(tx_env: &mut TxEnv, transaction: &TransactionSigned, sender:
             value,
             input,
             access_list,
+        }) |
+        Transaction::Eip4844(TxEip4844 {
+            nonce,
+            chain_id,
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            to,
+            value,
+            input,
+            access_list,
+            ..
         }) => {
             tx_env.gas_limit = *gas_limit;
             tx_env.gas_price = U256::from(*max_fee_per_gas);
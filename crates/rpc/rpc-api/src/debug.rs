@@ -36,6 +36,7 @@ pub trait DebugApi {
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
     ) -> Result<Vec<BlockTraceResult>>;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
@@ -49,7 +50,7 @@ pub trait DebugApi {
     async fn debug_trace_block(
         &self,
         rlp_block: Bytes,
-        opts: GethDebugTracingOptions,
+        opts: Option<GethDebugTracingOptions>,
     ) -> Result<Vec<TraceResult>>;
 
     /// Similar to `debug_traceBlock`, `debug_traceBlockByHash` accepts a block hash and will replay
@@ -59,7 +60,7 @@ pub trait DebugApi {
     async fn debug_trace_block_by_hash(
         &self,
         block: H256,
-        opts: GethDebugTracingOptions,
+        opts: Option<GethDebugTracingOptions>,
     ) -> Result<Vec<TraceResult>>;
 
     /// Similar to `debug_traceBlockByNumber`, `debug_traceBlockByHash` accepts a block number
@@ -69,7 +70,7 @@ pub trait DebugApi {
     async fn debug_trace_block_by_number(
         &self,
         block: BlockNumberOrTag,
-        opts: GethDebugTracingOptions,
+        opts: Option<GethDebugTracingOptions>,
     ) -> Result<Vec<TraceResult>>;
 
     /// The `debug_traceTransaction` debugging method will attempt to run the transaction in the
@@ -80,7 +81,7 @@ pub trait DebugApi {
     async fn debug_trace_transaction(
         &self,
         tx_hash: H256,
-        opts: GethDebugTracingOptions,
+        opts: Option<GethDebugTracingOptions>,
     ) -> Result<GethTraceFrame>;
 
     /// The debug_traceCall method lets you run an `eth_call` within the context of the given block
@@ -97,6 +98,6 @@ pub trait DebugApi {
         &self,
         request: CallRequest,
         block_number: Option<BlockId>,
-        opts: GethDebugTracingOptions,
+        opts: Option<GethDebugTracingOptions>,
     ) -> Result<GethTraceFrame>;
 }
@@ -3,8 +3,9 @@ use reth_primitives::{
     AccessListWithGasUsed, Address, BlockId, BlockNumberOrTag, Bytes, H256, H64, U256, U64,
 };
 use reth_rpc_types::{
-    state::StateOverride, CallRequest, EIP1186AccountProofResponse, FeeHistory, Index, RichBlock,
-    SyncStatus, Transaction, TransactionReceipt, TransactionRequest, Work,
+    state::{BlockOverrides, StateOverride},
+    CallRequest, EIP1186AccountProofResponse, FeeHistory, Index, RichBlock, SyncStatus,
+    Transaction, TransactionReceipt, TransactionRequest, Work,
 };
 
 /// Eth rpc interface: <https://ethereum.github.io/execution-apis/api-documentation/>
@@ -107,6 +108,10 @@ pub trait EthApi {
     #[method(name = "eth_getTransactionReceipt")]
     async fn transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>>;
 
+    /// Returns all transaction receipts of the given block.
+    #[method(name = "eth_getBlockReceipts")]
+    async fn block_receipts(&self, block_id: BlockId) -> Result<Option<Vec<TransactionReceipt>>>;
+
     /// Returns the balance of the account of given address.
     #[method(name = "eth_getBalance")]
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> Result<U256>;
@@ -139,6 +144,7 @@ pub trait EthApi {
         request: CallRequest,
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<Bytes>;
 
     /// Generates an access list for a transaction.
@@ -169,6 +175,8 @@ pub trait EthApi {
         &self,
         request: CallRequest,
         block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<U256>;
 
     /// Returns the current price per gas in wei.
@@ -224,6 +232,10 @@ pub trait EthApi {
     #[method(name = "eth_sendRawTransaction")]
     async fn send_raw_transaction(&self, bytes: Bytes) -> Result<H256>;
 
+    /// Sends signed transaction that is never propagated to the network, returning its hash.
+    #[method(name = "eth_sendPrivateRawTransaction")]
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> Result<H256>;
+
     /// Returns an Ethereum specific signature with: sign(keccak256("\x19Ethereum Signed Message:\n"
     /// + len(message) + message))).
     #[method(name = "eth_sign")]
@@ -17,6 +17,7 @@ mod eth_filter;
 mod eth_pubsub;
 mod net;
 mod trace;
+mod txpool;
 mod web3;
 
 /// re-export of all server traits
@@ -27,7 +28,7 @@ pub mod servers {
     pub use crate::{
         admin::AdminApiServer, debug::DebugApiServer, engine::EngineApiServer, eth::EthApiServer,
         eth_filter::EthFilterApiServer, eth_pubsub::EthPubSubApiServer, net::NetApiServer,
-        trace::TraceApiServer, web3::Web3ApiServer,
+        trace::TraceApiServer, txpool::TxPoolApiServer, web3::Web3ApiServer,
     };
 }
 
@@ -40,6 +41,6 @@ pub use clients::*;
 pub mod clients {
     pub use crate::{
         admin::AdminApiClient, debug::DebugApiClient, engine::EngineApiClient, eth::EthApiClient,
-        net::NetApiClient, trace::TraceApiClient, web3::Web3ApiClient,
+        net::NetApiClient, trace::TraceApiClient, txpool::TxPoolApiClient, web3::Web3ApiClient,
     };
 }
@@ -66,9 +66,16 @@ pub trait TraceApi {
 
     /// Returns transaction trace at given index.
     #[method(name = "trace_get")]
-    fn trace(&self, hash: H256, indices: Vec<Index>) -> Result<Option<LocalizedTransactionTrace>>;
+    async fn trace(
+        &self,
+        hash: H256,
+        indices: Vec<Index>,
+    ) -> Result<Option<LocalizedTransactionTrace>>;
 
     /// Returns all traces of given transaction.
     #[method(name = "trace_transaction")]
-    fn transaction_traces(&self, hash: H256) -> Result<Option<Vec<LocalizedTransactionTrace>>>;
+    async fn transaction_traces(
+        &self,
+        hash: H256,
+    ) -> Result<Option<Vec<LocalizedTransactionTrace>>>;
 }
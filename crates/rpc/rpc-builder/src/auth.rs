@@ -12,10 +12,13 @@ use jsonrpsee::{
 use reth_ipc::server::IpcServer;
 pub use reth_ipc::server::{Builder as IpcServerBuilder, Endpoint};
 use reth_network_api::{NetworkInfo, Peers};
-use reth_provider::{BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory};
+use reth_provider::{
+    BlockProvider, EvmEnvProvider, HeaderProvider, ReceiptProvider, StateProviderFactory,
+};
 use reth_rpc::{
-    eth::cache::EthStateCache, AdminApi, AuthLayer, DebugApi, EngineApi, EthApi, JwtAuthValidator,
-    JwtSecret, NetApi, TraceApi, Web3Api,
+    eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
+    AdminApi, AuthLayer, DebugApi, EngineApi, EthApi, JwtAuthValidator, JwtSecret, NetApi,
+    TraceApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_engine_api::EngineApiHandle;
@@ -45,6 +48,7 @@ pub async fn launch<Client, Pool, Network, Tasks>(
 where
     Client: BlockProvider
         + HeaderProvider
+        + ReceiptProvider
         + StateProviderFactory
         + EvmEnvProvider
         + Clone
@@ -56,8 +60,14 @@ where
 {
     // spawn a new cache task
     let eth_cache = EthStateCache::spawn_with(client.clone(), Default::default(), executor);
-    launch_with_eth_api(EthApi::new(client, pool, network, eth_cache), handle, socket_addr, secret)
-        .await
+    let gas_oracle = GasPriceOracle::new(client.clone(), Default::default(), eth_cache.clone());
+    launch_with_eth_api(
+        EthApi::new(client, pool, network, eth_cache, gas_oracle),
+        handle,
+        socket_addr,
+        secret,
+    )
+    .await
 }
 
 /// Configure and launch an auth server with existing EthApi implementation.
@@ -70,6 +80,7 @@ pub async fn launch_with_eth_api<Client, Pool, Network>(
 where
     Client: BlockProvider
         + HeaderProvider
+        + ReceiptProvider
         + StateProviderFactory
         + EvmEnvProvider
         + Clone
@@ -1,6 +1,10 @@
+use crate::constants::DEFAULT_MAX_TRACING_REQUESTS;
 use reth_rpc::{
-    eth::cache::{EthStateCache, EthStateCacheConfig},
-    EthApi, EthFilter, EthPubSub,
+    eth::{
+        cache::{EthStateCache, EthStateCacheConfig},
+        gas_oracle::GasPriceOracleConfig,
+    },
+    EthApi, EthFilter, EthPubSub, JsTracerLimits, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
 };
 use serde::{Deserialize, Serialize};
 
@@ -14,12 +18,35 @@ pub struct EthHandlers<Client, Pool, Network, Events> {
     /// Polling based filter handler available on all transports
     pub filter: EthFilter<Client, Pool>,
     /// Handler for subscriptions only available for transports that support it (ws, ipc)
-    pub pubsub: Option<EthPubSub<Client, Pool, Events>>,
+    pub pubsub: EthPubSub<Client, Pool, Events, Network>,
 }
 
 /// Additional config values for the eth namespace
-#[derive(Debug, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct EthConfig {
     /// Settings for the caching layer
     pub cache: EthStateCacheConfig,
+    /// Settings for the gas price oracle
+    pub gas_oracle: GasPriceOracleConfig,
+    /// The maximum number of tracing calls that can be executed concurrently.
+    ///
+    /// This is shared by all handlers that replay transactions, i.e. the `trace_` and `debug_`
+    /// namespaces.
+    pub max_tracing_requests: u32,
+    /// The maximum number of blocks a `trace_filter` request can cover.
+    pub max_trace_filter_blocks: u64,
+    /// Limits of the javascript tracers of the `debug_` namespace.
+    pub js_tracer_limits: JsTracerLimits,
+}
+
+impl Default for EthConfig {
+    fn default() -> Self {
+        Self {
+            cache: EthStateCacheConfig::default(),
+            gas_oracle: GasPriceOracleConfig::default(),
+            max_tracing_requests: DEFAULT_MAX_TRACING_REQUESTS,
+            max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            js_tracer_limits: JsTracerLimits::default(),
+        }
+    }
 }
@@ -16,6 +16,9 @@
 //! transaction pool. [RpcModuleBuilder::build] returns a [TransportRpcModules] which contains the
 //! transport specific config (what APIs are available via this transport).
 //!
+//! The `eth_subscribe` handlers are only installed on the transports that support subscriptions
+//! (ws, ipc).
+//!
 //! The [RpcServerConfig] is used to configure the [RpcServer] type which contains all transport
 //! implementations (http server, ws server, ipc server). [RpcServer::start] requires the
 //! [TransportRpcModules] so it can start the servers with the configured modules.
@@ -25,16 +28,18 @@
 //! Configure only a http server with a selection of [RethRpcModule]s
 //!
 //! ```
+//! use reth_interfaces::{events::ChainEventSubscriptions, sync::SyncStateSubscriptions};
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{BlockProvider, HeaderProvider, StateProviderFactory, EvmEnvProvider};
+//! use reth_provider::{BlockProvider, HeaderProvider, ReceiptProvider, StateProviderFactory, EvmEnvProvider};
 //! use reth_rpc_builder::{RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig};
 //! use reth_tasks::TokioTaskExecutor;
 //! use reth_transaction_pool::TransactionPool;
-//! pub async fn launch<Client, Pool, Network>(client: Client, pool: Pool, network: Network)
+//! pub async fn launch<Client, Pool, Network, Events>(client: Client, pool: Pool, network: Network, events: Events)
 //! where
-//!     Client: BlockProvider + HeaderProvider + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
+//!     Client: BlockProvider + HeaderProvider + ReceiptProvider + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
 //!     Pool: TransactionPool + Clone + 'static,
-//!     Network: NetworkInfo + Peers + Clone + 'static,
+//!     Network: NetworkInfo + Peers + SyncStateSubscriptions + Clone + 'static,
+//!     Events: ChainEventSubscriptions + Clone + 'static,
 //! {
 //!     // configure the rpc module per transport
 //!     let transports = TransportRpcModuleConfig::default().with_http(vec![
@@ -43,7 +48,7 @@
 //!         RethRpcModule::Eth,
 //!         RethRpcModule::Web3,
 //!     ]);
-//!     let transport_modules = RpcModuleBuilder::new(client, pool, network, TokioTaskExecutor::default()).build(transports);
+//!     let transport_modules = RpcModuleBuilder::new(client, pool, network, TokioTaskExecutor::default(), events).build(transports);
 //!     let handle = RpcServerConfig::default()
 //!         .with_http(ServerBuilder::default())
 //!         .start(transport_modules)
@@ -61,10 +66,16 @@ use jsonrpsee::{
     server::{Server, ServerHandle},
     RpcModule,
 };
+use reth_interfaces::{events::ChainEventSubscriptions, sync::SyncStateSubscriptions};
 use reth_ipc::server::IpcServer;
 use reth_network_api::{NetworkInfo, Peers};
-use reth_provider::{BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory};
-use reth_rpc::{AdminApi, DebugApi, EthApi, EthFilter, NetApi, TraceApi, Web3Api};
+use reth_provider::{
+    BadBlocks, BlockProvider, EvmEnvProvider, HeaderProvider, ReceiptProvider, StateProviderFactory,
+};
+use reth_rpc::{
+    AdminApi, DebugApi, EthApi, EthFilter, EthPubSub, NetApi, TraceApi, TracingCallGuard,
+    TxPoolApi, Web3Api,
+};
 use reth_rpc_api::servers::*;
 use reth_transaction_pool::TransactionPool;
 use serde::{Deserialize, Serialize, Serializer};
@@ -73,6 +84,7 @@ use std::{
     fmt,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     str::FromStr,
+    sync::Arc,
 };
 use strum::{AsRefStr, EnumString, EnumVariantNames, ParseError, VariantNames};
 use tower::layer::util::{Identity, Stack};
@@ -91,36 +103,39 @@ mod eth;
 pub mod constants;
 pub use crate::eth::{EthConfig, EthHandlers};
 use constants::*;
-use reth_rpc::eth::cache::EthStateCache;
+use reth_rpc::eth::{cache::EthStateCache, gas_oracle::GasPriceOracle, signer::EthSigner};
 use reth_tasks::TaskSpawner;
 
 /// Cors utilities.
 mod cors;
 
 /// Convenience function for starting a server in one step.
-pub async fn launch<Client, Pool, Network, Tasks>(
+pub async fn launch<Client, Pool, Network, Tasks, Events>(
     client: Client,
     pool: Pool,
     network: Network,
     module_config: impl Into<TransportRpcModuleConfig>,
     server_config: impl Into<RpcServerConfig>,
     executor: Tasks,
+    events: Events,
 ) -> Result<RpcServerHandle, RpcError>
 where
     Client: BlockProvider
         + HeaderProvider
+        + ReceiptProvider
         + StateProviderFactory
         + EvmEnvProvider
         + Clone
         + Unpin
         + 'static,
     Pool: TransactionPool + Clone + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + SyncStateSubscriptions + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
+    Events: ChainEventSubscriptions + Clone + 'static,
 {
     let module_config = module_config.into();
     let server_config = server_config.into();
-    RpcModuleBuilder::new(client, pool, network, executor)
+    RpcModuleBuilder::new(client, pool, network, executor, events)
         .build(module_config)
         .start_server(server_config)
         .await
@@ -130,7 +145,7 @@ where
 ///
 /// This is the main entrypoint for up RPC servers.
 #[derive(Debug)]
-pub struct RpcModuleBuilder<Client, Pool, Network, Tasks> {
+pub struct RpcModuleBuilder<Client, Pool, Network, Tasks, Events> {
     /// The Client type to when creating all rpc handlers
     client: Client,
     /// The Pool type to when creating all rpc handlers
@@ -139,65 +154,109 @@ pub struct RpcModuleBuilder<Client, Pool, Network, Tasks> {
     network: Network,
     /// How additional tasks are spawned, for example in the eth pubsub namespace
     executor: Tasks,
+    /// Provides the chain events for the eth pubsub namespace
+    events: Events,
+    /// The signers whose accounts are available in the eth namespace
+    signers: Vec<Arc<dyn EthSigner>>,
+    /// Record of the rejected blocks served by the debug namespace
+    bad_blocks: BadBlocks,
 }
 
 // === impl RpcBuilder ===
 
-impl<Client, Pool, Network, Tasks> RpcModuleBuilder<Client, Pool, Network, Tasks> {
+impl<Client, Pool, Network, Tasks, Events> RpcModuleBuilder<Client, Pool, Network, Tasks, Events> {
     /// Create a new instance of the builder
-    pub fn new(client: Client, pool: Pool, network: Network, executor: Tasks) -> Self {
-        Self { client, pool, network, executor }
+    pub fn new(
+        client: Client,
+        pool: Pool,
+        network: Network,
+        executor: Tasks,
+        events: Events,
+    ) -> Self {
+        Self {
+            client,
+            pool,
+            network,
+            executor,
+            events,
+            signers: Vec::new(),
+            bad_blocks: Default::default(),
+        }
     }
 
     /// Configure the client instance.
-    pub fn with_client<C>(self, client: C) -> RpcModuleBuilder<C, Pool, Network, Tasks>
+    pub fn with_client<C>(self, client: C) -> RpcModuleBuilder<C, Pool, Network, Tasks, Events>
     where
         C: BlockProvider + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, .. } = self;
-        RpcModuleBuilder { client, network, pool, executor }
+        let Self { pool, network, executor, events, signers, bad_blocks, .. } = self;
+        RpcModuleBuilder { client, network, pool, executor, events, signers, bad_blocks }
     }
 
     /// Configure the transaction pool instance.
-    pub fn with_pool<P>(self, pool: P) -> RpcModuleBuilder<Client, P, Network, Tasks>
+    pub fn with_pool<P>(self, pool: P) -> RpcModuleBuilder<Client, P, Network, Tasks, Events>
     where
         P: TransactionPool + 'static,
     {
-        let Self { client, network, executor, .. } = self;
-        RpcModuleBuilder { client, network, pool, executor }
+        let Self { client, network, executor, events, signers, bad_blocks, .. } = self;
+        RpcModuleBuilder { client, network, pool, executor, events, signers, bad_blocks }
     }
 
     /// Configure the network instance.
-    pub fn with_network<N>(self, network: N) -> RpcModuleBuilder<Client, Pool, N, Tasks>
+    pub fn with_network<N>(self, network: N) -> RpcModuleBuilder<Client, Pool, N, Tasks, Events>
     where
-        N: NetworkInfo + Peers + 'static,
+        N: NetworkInfo + Peers + SyncStateSubscriptions + 'static,
     {
-        let Self { client, pool, executor, .. } = self;
-        RpcModuleBuilder { client, network, pool, executor }
+        let Self { client, pool, executor, events, signers, bad_blocks, .. } = self;
+        RpcModuleBuilder { client, network, pool, executor, events, signers, bad_blocks }
     }
 
     /// Configure the task executor to use for additional tasks.
-    pub fn with_executor<T>(self, executor: T) -> RpcModuleBuilder<Client, Pool, Network, T>
+    pub fn with_executor<T>(self, executor: T) -> RpcModuleBuilder<Client, Pool, Network, T, Events>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, client, .. } = self;
-        RpcModuleBuilder { client, network, pool, executor }
+        let Self { pool, network, client, events, signers, bad_blocks, .. } = self;
+        RpcModuleBuilder { client, network, pool, executor, events, signers, bad_blocks }
+    }
+
+    /// Configure the chain event subscriptions for the eth pubsub namespace.
+    pub fn with_events<E>(self, events: E) -> RpcModuleBuilder<Client, Pool, Network, Tasks, E>
+    where
+        E: ChainEventSubscriptions + 'static,
+    {
+        let Self { pool, network, client, executor, signers, bad_blocks, .. } = self;
+        RpcModuleBuilder { client, network, pool, executor, events, signers, bad_blocks }
+    }
+
+    /// Configure the signers whose accounts can be used by the `eth_sign*` and
+    /// `eth_sendTransaction` endpoints.
+    pub fn with_signers(mut self, signers: Vec<Arc<dyn EthSigner>>) -> Self {
+        self.signers = signers;
+        self
+    }
+
+    /// Configure the record of rejected blocks that is served by `debug_getBadBlocks`.
+    pub fn with_bad_blocks(mut self, bad_blocks: BadBlocks) -> Self {
+        self.bad_blocks = bad_blocks;
+        self
     }
 }
 
-impl<Client, Pool, Network, Tasks> RpcModuleBuilder<Client, Pool, Network, Tasks>
+impl<Client, Pool, Network, Tasks, Events> RpcModuleBuilder<Client, Pool, Network, Tasks, Events>
 where
     Client: BlockProvider
         + HeaderProvider
+        + ReceiptProvider
         + StateProviderFactory
         + EvmEnvProvider
         + Clone
         + Unpin
         + 'static,
     Pool: TransactionPool + Clone + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + SyncStateSubscriptions + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
+    Events: ChainEventSubscriptions + Clone + 'static,
 {
     /// Configures all [RpcModule]s specific to the given [TransportRpcModuleConfig] which can be
     /// used to start the transport server(s).
@@ -206,7 +265,7 @@ where
     pub fn build(self, module_config: TransportRpcModuleConfig) -> TransportRpcModules<()> {
         let mut modules = TransportRpcModules::default();
 
-        let Self { client, pool, network, executor } = self;
+        let Self { client, pool, network, executor, events, signers, bad_blocks } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config;
@@ -216,21 +275,24 @@ where
                 pool,
                 network,
                 executor,
+                events,
                 config.unwrap_or_default(),
-            );
+            )
+            .with_signers(signers)
+            .with_bad_blocks(bad_blocks);
 
             modules.http = registry.maybe_module(http.as_ref());
-            modules.ws = registry.maybe_module(ws.as_ref());
-            modules.ipc = registry.maybe_module(ipc.as_ref());
+            modules.ws = registry.maybe_module_with_pubsub(ws.as_ref());
+            modules.ipc = registry.maybe_module_with_pubsub(ipc.as_ref());
         }
 
         modules
     }
 }
 
-impl Default for RpcModuleBuilder<(), (), (), ()> {
+impl Default for RpcModuleBuilder<(), (), (), (), ()> {
     fn default() -> Self {
-        RpcModuleBuilder::new((), (), (), ())
+        RpcModuleBuilder::new((), (), (), (), ())
     }
 }
 
@@ -334,27 +396,30 @@ impl RpcModuleSelection {
     /// Note: This will always create new instance of the module handlers and is therefor only
     /// recommended for launching standalone transports. If multiple transports need to be
     /// configured it's recommended to use the [RpcModuleBuilder].
-    pub fn standalone_module<Client, Pool, Network, Tasks>(
+    pub fn standalone_module<Client, Pool, Network, Tasks, Events>(
         &self,
         client: Client,
         pool: Pool,
         network: Network,
         executor: Tasks,
+        events: Events,
         config: RpcModuleConfig,
     ) -> RpcModule<()>
     where
         Client: BlockProvider
             + HeaderProvider
+            + ReceiptProvider
             + StateProviderFactory
             + EvmEnvProvider
             + Clone
             + Unpin
             + 'static,
         Pool: TransactionPool + Clone + 'static,
-        Network: NetworkInfo + Peers + Clone + 'static,
+        Network: NetworkInfo + Peers + SyncStateSubscriptions + Clone + 'static,
         Tasks: TaskSpawner + Clone + 'static,
+        Events: ChainEventSubscriptions + Clone + 'static,
     {
-        let mut registry = RethModuleRegistry::new(client, pool, network, executor, config);
+        let mut registry = RethModuleRegistry::new(client, pool, network, executor, events, config);
         registry.module_for(self)
     }
 
@@ -414,6 +479,8 @@ pub enum RethRpcModule {
     Net,
     /// `trace_` module
     Trace,
+    /// `txpool_` module
+    Txpool,
     /// `web3_` module
     Web3,
 }
@@ -434,31 +501,65 @@ impl Serialize for RethRpcModule {
 }
 
 /// A Helper type the holds instances of the configured modules.
-pub struct RethModuleRegistry<Client, Pool, Network, Tasks> {
+pub struct RethModuleRegistry<Client, Pool, Network, Tasks, Events> {
     client: Client,
     pool: Pool,
     network: Network,
     executor: Tasks,
+    events: Events,
+    /// The signers whose accounts are available in the eth namespace
+    signers: Vec<Arc<dyn EthSigner>>,
+    /// Record of the rejected blocks served by the debug namespace
+    bad_blocks: BadBlocks,
     /// Additional settings for handlers.
     config: RpcModuleConfig,
     /// Holds a clone of all the eth namespace handlers
-    eth: Option<EthHandlers<Client, Pool, Network, ()>>,
+    eth: Option<EthHandlers<Client, Pool, Network, Events>>,
+    /// Restricts the number of concurrent tracing calls of all tracing handlers
+    tracing_call_guard: TracingCallGuard,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
 }
 
 // === impl RethModuleRegistry ===
 
-impl<Client, Pool, Network, Tasks> RethModuleRegistry<Client, Pool, Network, Tasks> {
+impl<Client, Pool, Network, Tasks, Events>
+    RethModuleRegistry<Client, Pool, Network, Tasks, Events>
+{
     /// Creates a new, empty instance.
     pub fn new(
         client: Client,
         pool: Pool,
         network: Network,
         executor: Tasks,
+        events: Events,
         config: RpcModuleConfig,
     ) -> Self {
-        Self { client, pool, network, eth: None, executor, modules: Default::default(), config }
+        Self {
+            client,
+            pool,
+            network,
+            eth: None,
+            executor,
+            events,
+            signers: Vec::new(),
+            bad_blocks: Default::default(),
+            tracing_call_guard: TracingCallGuard::new(config.eth.max_tracing_requests),
+            modules: Default::default(),
+            config,
+        }
+    }
+
+    /// Configures the signers whose accounts are available in the eth namespace.
+    pub fn with_signers(mut self, signers: Vec<Arc<dyn EthSigner>>) -> Self {
+        self.signers = signers;
+        self
+    }
+
+    /// Configures the record of rejected blocks that is served by `debug_getBadBlocks`.
+    pub fn with_bad_blocks(mut self, bad_blocks: BadBlocks) -> Self {
+        self.bad_blocks = bad_blocks;
+        self
     }
 
     /// Returns all installed methods
@@ -476,7 +577,7 @@ impl<Client, Pool, Network, Tasks> RethModuleRegistry<Client, Pool, Network, Tas
     }
 }
 
-impl<Client, Pool, Network, Tasks> RethModuleRegistry<Client, Pool, Network, Tasks>
+impl<Client, Pool, Network, Tasks, Events> RethModuleRegistry<Client, Pool, Network, Tasks, Events>
 where
     Network: NetworkInfo + Peers + Clone + 'static,
 {
@@ -495,30 +596,47 @@ where
     }
 }
 
-impl<Client, Pool, Network, Tasks> RethModuleRegistry<Client, Pool, Network, Tasks>
+impl<Client, Pool, Network, Tasks, Events> RethModuleRegistry<Client, Pool, Network, Tasks, Events>
 where
     Client: BlockProvider
         + HeaderProvider
+        + ReceiptProvider
         + StateProviderFactory
         + EvmEnvProvider
         + Clone
         + Unpin
         + 'static,
     Pool: TransactionPool + Clone + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + SyncStateSubscriptions + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
+    Events: ChainEventSubscriptions + Clone + 'static,
 {
     /// Register Eth Namespace
+    ///
+    /// This includes the polling based filter handlers
     pub fn register_eth(&mut self) -> &mut Self {
-        let eth_api = self.eth_api();
-        self.modules.insert(RethRpcModule::Eth, eth_api.into_rpc().into());
+        let eth_methods = self.eth_methods();
+        self.modules.insert(RethRpcModule::Eth, eth_methods);
         self
     }
 
     /// Register Debug Namespace
     pub fn register_debug(&mut self) -> &mut Self {
         let eth_api = self.eth_api();
-        self.modules.insert(RethRpcModule::Debug, DebugApi::new(eth_api).into_rpc().into());
+        let eth_cache = self.eth_cache();
+        self.modules.insert(
+            RethRpcModule::Debug,
+            DebugApi::new(
+                self.client.clone(),
+                eth_api,
+                eth_cache,
+                self.bad_blocks.clone(),
+                self.tracing_call_guard.clone(),
+            )
+            .with_js_tracer_limits(self.config.eth.js_tracer_limits)
+            .into_rpc()
+            .into(),
+        );
         self
     }
 
@@ -532,6 +650,21 @@ where
         self
     }
 
+    /// Register Trace Namespace
+    ///
+    /// The trace handlers share the [EthStateCache] with the eth namespace.
+    pub fn register_trace(&mut self) -> &mut Self {
+        let eth_cache = self.eth_cache();
+        self.modules.insert(
+            RethRpcModule::Trace,
+            TraceApi::new(self.client.clone(), eth_cache, self.tracing_call_guard.clone())
+                .with_max_trace_filter_blocks(self.config.eth.max_trace_filter_blocks)
+                .into_rpc()
+                .into(),
+        );
+        self
+    }
+
     /// Helper function to create a [RpcModule] if it's not `None`
     fn maybe_module(&mut self, config: Option<&RpcModuleSelection>) -> Option<RpcModule<()>> {
         let config = config?;
@@ -539,6 +672,23 @@ where
         Some(module)
     }
 
+    /// Helper function to create a [RpcModule] for a transport that supports subscriptions if
+    /// it's not `None`.
+    ///
+    /// This also installs the [EthPubSub] handlers if the `eth` namespace is selected.
+    fn maybe_module_with_pubsub(
+        &mut self,
+        config: Option<&RpcModuleSelection>,
+    ) -> Option<RpcModule<()>> {
+        let config = config?;
+        let mut module = self.module_for(config);
+        if config.iter_selection().any(|namespace| namespace == RethRpcModule::Eth) {
+            let pubsub = self.with_eth(|handlers| handlers.pubsub.clone());
+            module.merge(pubsub.into_rpc()).expect("No conflicts");
+        }
+        Some(module)
+    }
+
     /// Populates a new [RpcModule] based on the selected [RethRpcModule]s in the given
     /// [RpcModuleSelection]
     pub fn module_for(&mut self, config: &RpcModuleSelection) -> RpcModule<()> {
@@ -560,6 +710,7 @@ where
     ) -> Vec<Methods> {
         let eth_api = self.eth_api();
         let eth_cache = self.eth_cache();
+        let eth_methods = self.eth_methods();
         namespaces
             .map(|namespace| {
                 self.modules
@@ -568,13 +719,30 @@ where
                         RethRpcModule::Admin => {
                             AdminApi::new(self.network.clone()).into_rpc().into()
                         }
-                        RethRpcModule::Debug => DebugApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Eth => eth_api.clone().into_rpc().into(),
+                        RethRpcModule::Debug => DebugApi::new(
+                            self.client.clone(),
+                            eth_api.clone(),
+                            eth_cache.clone(),
+                            self.bad_blocks.clone(),
+                            self.tracing_call_guard.clone(),
+                        )
+                        .with_js_tracer_limits(self.config.eth.js_tracer_limits)
+                        .into_rpc()
+                        .into(),
+                        RethRpcModule::Eth => eth_methods.clone(),
                         RethRpcModule::Net => {
                             NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
                         }
-                        RethRpcModule::Trace => {
-                            TraceApi::new(self.client.clone(), eth_cache.clone()).into_rpc().into()
+                        RethRpcModule::Trace => TraceApi::new(
+                            self.client.clone(),
+                            eth_cache.clone(),
+                            self.tracing_call_guard.clone(),
+                        )
+                        .with_max_trace_filter_blocks(self.config.eth.max_trace_filter_blocks)
+                        .into_rpc()
+                        .into(),
+                        RethRpcModule::Txpool => {
+                            TxPoolApi::new(self.pool.clone()).into_rpc().into()
                         }
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
                     })
@@ -594,7 +762,7 @@ where
     /// Creates the [EthHandlers] type the first time this is called.
     fn with_eth<F, R>(&mut self, f: F) -> R
     where
-        F: FnOnce(&EthHandlers<Client, Pool, Network, ()>) -> R,
+        F: FnOnce(&EthHandlers<Client, Pool, Network, Events>) -> R,
     {
         if self.eth.is_none() {
             let eth_cache = EthStateCache::spawn_with(
@@ -602,17 +770,31 @@ where
                 self.config.eth.cache.clone(),
                 self.executor.clone(),
             );
+            let gas_oracle = GasPriceOracle::new(
+                self.client.clone(),
+                self.config.eth.gas_oracle.clone(),
+                eth_cache.clone(),
+            );
             let api = EthApi::new(
                 self.client.clone(),
                 self.pool.clone(),
                 self.network.clone(),
                 eth_cache.clone(),
+                gas_oracle,
             );
+            for signer in &self.signers {
+                api.add_signer(signer.clone());
+            }
             let filter = EthFilter::new(self.client.clone(), self.pool.clone());
+            let pubsub = EthPubSub::with_spawner(
+                self.client.clone(),
+                self.pool.clone(),
+                self.events.clone(),
+                self.network.clone(),
+                Box::new(self.executor.clone()),
+            );
 
-            // TODO: install pubsub
-
-            let eth = EthHandlers { api, eth_cache, filter, pubsub: None };
+            let eth = EthHandlers { api, eth_cache, filter, pubsub };
             self.eth = Some(eth);
         }
         f(self.eth.as_ref().expect("exists; qed"))
@@ -622,6 +804,16 @@ where
     fn eth_api(&mut self) -> EthApi<Client, Pool, Network> {
         self.with_eth(|handlers| handlers.api.clone())
     }
+
+    /// Returns the [Methods] of the `eth` namespace: the [EthApi] merged with the [EthFilter]
+    /// handlers.
+    fn eth_methods(&mut self) -> Methods {
+        self.with_eth(|handlers| {
+            let mut module = handlers.api.clone().into_rpc();
+            module.merge(handlers.filter.clone().into_rpc()).expect("No conflicts");
+            module.into()
+        })
+    }
 }
 
 /// A builder type for configuring and launching the servers that will handle RPC requests.
@@ -1097,6 +1289,7 @@ mod tests {
                 "eth" =>  RethRpcModule::Eth,
                 "net" =>  RethRpcModule::Net,
                 "trace" =>  RethRpcModule::Trace,
+                "txpool" =>  RethRpcModule::Txpool,
                 "web3" =>  RethRpcModule::Web3,
             );
     }
@@ -3,9 +3,11 @@
 use crate::utils::{launch_http, launch_http_ws, launch_ws};
 use jsonrpsee::{
     core::{
-        client::{ClientT, SubscriptionClientT},
+        client::{ClientT, Subscription, SubscriptionClientT},
         error::Error,
+        JsonValue,
     },
+    rpc_params,
     types::error::{CallError, ErrorCode},
 };
 use reth_primitives::{
@@ -13,7 +15,7 @@ use reth_primitives::{
 };
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, NetApiClient, TraceApiClient, Web3ApiClient,
+    DebugApiClient, NetApiClient, TraceApiClient, TxPoolApiClient, Web3ApiClient,
 };
 use reth_rpc_builder::RethRpcModule;
 use reth_rpc_types::{trace::filter::TraceFilter, CallRequest, Index, TransactionRequest};
@@ -29,6 +31,13 @@ fn is_unimplemented(err: Error) -> bool {
     }
 }
 
+fn is_unknown_account(err: Error) -> bool {
+    match err {
+        Error::Call(CallError::Custom(error_obj)) => error_obj.message() == "unknown account",
+        _ => false,
+    }
+}
+
 async fn test_basic_admin_calls<C>(client: &C)
 where
     C: ClientT + SubscriptionClientT + Sync,
@@ -67,6 +76,7 @@ where
     EthApiClient::balance(client, address, None).await.unwrap();
     EthApiClient::transaction_count(client, address, None).await.unwrap();
     EthApiClient::storage_at(client, address, U256::default(), None).await.unwrap();
+    EthApiClient::get_proof(client, address, vec![], None).await.unwrap();
     EthApiClient::block_by_hash(client, hash, false).await.unwrap();
     EthApiClient::block_by_number(client, block_number, false).await.unwrap();
     EthApiClient::block_transaction_count_by_number(client, block_number).await.unwrap();
@@ -76,6 +86,12 @@ where
     EthApiClient::uncle_by_block_hash_and_index(client, hash, index).await.unwrap();
     EthApiClient::uncle_by_block_number_and_index(client, block_number, index).await.unwrap();
     EthApiClient::create_access_list(client, call_request.clone(), None).await.unwrap();
+    EthApiClient::call(client, call_request.clone(), None, None, None).await.unwrap();
+    EthApiClient::estimate_gas(client, call_request.clone(), None, None, None).await.unwrap();
+    EthApiClient::gas_price(client).await.unwrap();
+    EthApiClient::max_priority_fee_per_gas(client).await.unwrap();
+    EthApiClient::transaction_receipt(client, hash).await.unwrap();
+    EthApiClient::block_receipts(client, block_number.into()).await.unwrap();
 
     // Unimplemented
     assert!(is_unimplemented(EthApiClient::syncing(client).await.err().unwrap()));
@@ -90,15 +106,6 @@ where
             .err()
             .unwrap()
     ));
-    assert!(is_unimplemented(EthApiClient::transaction_receipt(client, hash).await.err().unwrap()));
-    assert!(is_unimplemented(
-        EthApiClient::call(client, call_request.clone(), None, None).await.err().unwrap()
-    ));
-    assert!(is_unimplemented(
-        EthApiClient::estimate_gas(client, call_request.clone(), None).await.err().unwrap()
-    ));
-    assert!(is_unimplemented(EthApiClient::gas_price(client).await.err().unwrap()));
-    assert!(is_unimplemented(EthApiClient::max_priority_fee_per_gas(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::is_mining(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::hashrate(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::get_work(client).await.err().unwrap()));
@@ -114,24 +121,23 @@ where
             .err()
             .unwrap()
     ));
-    assert!(is_unimplemented(
+
+    // No signers configured
+    assert!(is_unknown_account(
         EthApiClient::send_transaction(client, transaction_request).await.err().unwrap()
     ));
-    assert!(is_unimplemented(
+    assert!(is_unknown_account(
         EthApiClient::sign(client, address, bytes.clone()).await.err().unwrap()
     ));
-    assert!(is_unimplemented(
+    assert!(is_unknown_account(
         EthApiClient::sign_transaction(client, call_request.clone()).await.err().unwrap()
     ));
-    assert!(is_unimplemented(
+    assert!(is_unknown_account(
         EthApiClient::sign_typed_data(client, address, jsonrpsee::core::JsonValue::Null)
             .await
             .err()
             .unwrap()
     ));
-    assert!(is_unimplemented(
-        EthApiClient::get_proof(client, address, vec![], None).await.err().unwrap()
-    ));
 }
 
 async fn test_basic_debug_calls<C>(client: &C)
@@ -140,13 +146,12 @@ where
 {
     let block_id = BlockId::Number(BlockNumberOrTag::default());
 
-    assert!(is_unimplemented(DebugApiClient::raw_header(client, block_id).await.err().unwrap()));
-    assert!(is_unimplemented(DebugApiClient::raw_block(client, block_id).await.err().unwrap()));
-    assert!(is_unimplemented(
-        DebugApiClient::raw_transaction(client, H256::default()).await.err().unwrap()
-    ));
-    assert!(is_unimplemented(DebugApiClient::raw_receipts(client, block_id).await.err().unwrap()));
-    assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
+    // there are no blocks, but these are implemented
+    assert!(!is_unimplemented(DebugApiClient::raw_header(client, block_id).await.err().unwrap()));
+    assert!(!is_unimplemented(DebugApiClient::raw_block(client, block_id).await.err().unwrap()));
+    DebugApiClient::raw_transaction(client, H256::default()).await.unwrap();
+    assert!(!is_unimplemented(DebugApiClient::raw_receipts(client, block_id).await.err().unwrap()));
+    assert!(DebugApiClient::bad_blocks(client).await.unwrap().is_empty());
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -172,39 +177,44 @@ where
         count: None,
     };
 
-    assert!(is_unimplemented(
+    TraceApiClient::replay_block_transactions(client, block_id, HashSet::default()).await.unwrap();
+    TraceApiClient::block(client, block_id).await.unwrap();
+    TraceApiClient::trace(client, H256::default(), vec![]).await.unwrap();
+    TraceApiClient::transaction_traces(client, H256::default()).await.unwrap();
+
+    // there's no state to execute on, but these are implemented
+    assert!(!is_unimplemented(
         TraceApiClient::call(client, CallRequest::default(), HashSet::default(), None)
             .await
             .err()
             .unwrap()
     ));
-    assert!(is_unimplemented(TraceApiClient::call_many(client, vec![], None).await.err().unwrap()));
-    assert!(is_unimplemented(
-        TraceApiClient::raw_transaction(client, Bytes::default(), HashSet::default(), None)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        TraceApiClient::replay_block_transactions(client, block_id, HashSet::default())
+    assert!(!is_unimplemented(
+        TraceApiClient::replay_transaction(client, H256::default(), HashSet::default())
             .await
             .err()
             .unwrap()
     ));
+
+    assert!(is_unimplemented(TraceApiClient::call_many(client, vec![], None).await.err().unwrap()));
     assert!(is_unimplemented(
-        TraceApiClient::replay_transaction(client, H256::default(), HashSet::default())
+        TraceApiClient::raw_transaction(client, Bytes::default(), HashSet::default(), None)
             .await
             .err()
             .unwrap()
     ));
-    assert!(is_unimplemented(TraceApiClient::block(client, block_id).await.err().unwrap()));
-    assert!(is_unimplemented(TraceApiClient::filter(client, trace_filter).await.err().unwrap()));
-    assert!(is_unimplemented(
-        TraceApiClient::trace(client, H256::default(), vec![]).await.err().unwrap()
-    ));
-    assert!(is_unimplemented(
-        TraceApiClient::transaction_traces(client, H256::default()).await.err().unwrap()
-    ));
+    // there are no blocks to trace, but this is implemented
+    assert!(!is_unimplemented(TraceApiClient::filter(client, trace_filter).await.err().unwrap()));
+}
+
+async fn test_basic_txpool_calls<C>(client: &C)
+where
+    C: ClientT + SubscriptionClientT + Sync,
+{
+    TxPoolApiClient::txpool_status(client).await.unwrap();
+    TxPoolApiClient::txpool_inspect(client).await.unwrap();
+    TxPoolApiClient::txpool_content_from(client, Address::default()).await.unwrap();
+    TxPoolApiClient::txpool_content(client).await.unwrap();
 }
 
 async fn test_basic_web3_calls<C>(client: &C)
@@ -269,6 +279,26 @@ async fn test_call_eth_functions_http_and_ws() {
     test_basic_eth_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_eth_subscribe_ws_only() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http_ws(vec![RethRpcModule::Eth]).await;
+
+    let client = handle.ws_client().await.unwrap();
+    for kind in ["newHeads", "logs", "newPendingTransactions", "syncing"] {
+        let subscription: Subscription<JsonValue> =
+            client.subscribe("eth_subscribe", rpc_params![kind], "eth_unsubscribe").await.unwrap();
+        subscription.unsubscribe().await.unwrap();
+    }
+
+    // subscriptions are not supported over http
+    let client = handle.http_client().unwrap();
+    let response: Result<JsonValue, _> =
+        client.request("eth_subscribe", rpc_params!["newHeads"]).await;
+    assert!(response.is_err());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_debug_functions_http() {
     reth_tracing::init_test_tracing();
@@ -350,6 +380,33 @@ async fn test_call_trace_functions_http_and_ws() {
     test_basic_trace_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_txpool_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::Txpool]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_txpool_calls(&client).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_txpool_functions_ws() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_ws(vec![RethRpcModule::Txpool]).await;
+    let client = handle.ws_client().await.unwrap();
+    test_basic_txpool_calls(&client).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_txpool_functions_http_and_ws() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http_ws(vec![RethRpcModule::Txpool]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_txpool_calls(&client).await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_web3_functions_http() {
     reth_tracing::init_test_tracing();
@@ -1,3 +1,4 @@
+use reth_interfaces::test_utils::TestChainEventSubscriptions;
 use reth_network_api::test_utils::NoopNetwork;
 use reth_provider::test_utils::NoopProvider;
 use reth_rpc_builder::{
@@ -51,11 +52,17 @@ pub async fn launch_http_ws(modules: impl Into<RpcModuleSelection>) -> RpcServer
 }
 
 /// Returns an [RpcModuleBuilder] with testing components.
-pub fn test_rpc_builder() -> RpcModuleBuilder<NoopProvider, TestPool, NoopNetwork, TokioTaskExecutor>
-{
+pub fn test_rpc_builder() -> RpcModuleBuilder<
+    NoopProvider,
+    TestPool,
+    NoopNetwork,
+    TokioTaskExecutor,
+    TestChainEventSubscriptions,
+> {
     RpcModuleBuilder::default()
         .with_client(NoopProvider::default())
         .with_pool(testing_pool())
         .with_network(NoopNetwork::default())
         .with_executor(TokioTaskExecutor::default())
+        .with_events(TestChainEventSubscriptions::default())
 }
@@ -1,14 +1,17 @@
 use crate::{message::EngineApiMessageVersion, EngineApiError, EngineApiMessage, EngineApiResult};
-use futures::StreamExt;
-use reth_interfaces::consensus::ForkchoiceState;
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
+use reth_interfaces::{
+    blockchain_tree::BlockchainTreeEngine, consensus::ForkchoiceState,
+    executor::Error as ExecError, Error,
+};
+use reth_payload_builder::{PayloadBuilderAttributes, PayloadBuilderHandle};
 use reth_primitives::{
     proofs::{self, EMPTY_LIST_HASH},
-    BlockHash, BlockId, BlockNumber, ChainSpec, Hardfork, Header, SealedBlock, TransactionSigned,
-    H256, H64, U256,
+    Block, BlockHash, BlockId, BlockNumber, ChainSpec, Hardfork, Header, SealedBlock,
+    TransactionSigned, H256, H64, U256,
 };
 use reth_provider::{
-    BlockExecutor, BlockProvider, EvmEnvProvider, ExecutorFactory, HeaderProvider,
-    StateProviderFactory,
+    BadBlocks, BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory,
 };
 use reth_rlp::Decodable;
 use reth_rpc_types::engine::{
@@ -36,39 +39,64 @@ const MAX_PAYLOAD_BODIES_LIMIT: u64 = 1024;
 /// The Engine API implementation that grants the Consensus layer access to data and
 /// functions in the Execution layer that are crucial for the consensus process.
 #[must_use = "EngineApi does nothing unless polled."]
-pub struct EngineApi<Client> {
+pub struct EngineApi<Client, Tree> {
     client: Client,
+    /// The blockchain tree the payloads are inserted into and made canonical by.
+    tree: Tree,
     /// Consensus configuration
     chain_spec: Arc<ChainSpec>,
     message_rx: UnboundedReceiverStream<EngineApiMessage>,
     forkchoice_state_tx: watch::Sender<ForkchoiceState>,
-    // TODO: Placeholder for storing future blocks. Make cache bounded. Use lru
-    // local_store: HashMap<H64, ExecutionPayload>,
-    // remote_store: HashMap<H64, ExecutionPayload>,
+    /// Handle to the payload builder that builds the payloads requested by the Consensus layer.
+    payload_builder: PayloadBuilderHandle,
+    /// `engine_getPayload` requests waiting for the payload builder.
+    pending_payloads: FuturesUnordered<BoxFuture<'static, ()>>,
+    /// Record of the payloads that were rejected as invalid.
+    bad_blocks: BadBlocks,
 }
 
-impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvider>
-    EngineApi<Client>
+impl<Client, Tree> EngineApi<Client, Tree>
+where
+    Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvider,
+    Tree: BlockchainTreeEngine,
 {
     /// Create new instance of [EngineApi].
     pub fn new(
         client: Client,
+        tree: Tree,
         chain_spec: ChainSpec,
         message_rx: mpsc::UnboundedReceiver<EngineApiMessage>,
         forkchoice_state_tx: watch::Sender<ForkchoiceState>,
+        payload_builder: PayloadBuilderHandle,
     ) -> Self {
         Self {
             client,
+            tree,
             chain_spec: Arc::new(chain_spec),
             message_rx: UnboundedReceiverStream::new(message_rx),
             forkchoice_state_tx,
+            payload_builder,
+            pending_payloads: Default::default(),
+            bad_blocks: Default::default(),
         }
     }
 
+    /// Configures the store the payloads that are rejected as invalid are recorded in.
+    pub fn with_bad_blocks(mut self, bad_blocks: BadBlocks) -> Self {
+        self.bad_blocks = bad_blocks;
+        self
+    }
+
     fn on_message(&mut self, msg: EngineApiMessage) {
         match msg {
             EngineApiMessage::GetPayload(payload_id, tx) => {
-                let _ = tx.send(self.get_payload(payload_id).ok_or(EngineApiError::PayloadUnknown));
+                let payload = self.get_payload(payload_id);
+                self.pending_payloads.push(
+                    async move {
+                        let _ = tx.send(payload.await);
+                    }
+                    .boxed(),
+                );
             }
             EngineApiMessage::GetPayloadBodiesByHash(hashes, tx) => {
                 let _ = tx.send(self.get_payload_bodies_by_hash(hashes));
@@ -182,6 +210,8 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             ommers_hash: EMPTY_LIST_HASH,
             difficulty: Default::default(),
             nonce: Default::default(),
+            blob_gas_used: None,
+            excess_blob_gas: None,
         }
         .seal_slow();
 
@@ -203,10 +233,17 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
     /// Called to retrieve the latest state of the network, validate new blocks, and maintain
     /// consistency between the Consensus and Execution layers.
     ///
-    /// NOTE: Will always result in `PayloadUnknown` since we don't support block
-    /// building for now.
-    pub fn get_payload(&self, _payload_id: H64) -> Option<ExecutionPayload> {
-        None
+    /// Stops the payload build job started by `engine_forkchoiceUpdated` and resolves to the best
+    /// payload built so far, or `PayloadUnknown` if there is no such job.
+    pub fn get_payload(
+        &self,
+        payload_id: H64,
+    ) -> impl Future<Output = EngineApiResult<ExecutionPayload>> {
+        let payload = self.payload_builder.resolve(payload_id);
+        async move {
+            let payload = payload.await.ok_or(EngineApiError::PayloadUnknown)?;
+            Ok(payload.block().clone().into())
+        }
     }
 
     /// Called to retrieve execution payload bodies by range.
@@ -277,17 +314,51 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             return Ok(PayloadStatus::new(PayloadStatusEnum::Valid, block_hash))
         }
 
-        let Some(parent) = self.client.block_by_hash(parent_hash)? else {
-             // TODO: cache block for storing later
-             return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
-        };
+        // The terminal block conditions and the timestamp are checked against canonical parents,
+        // payloads extending side chains were checked when their ancestors were inserted.
+        if let Some(parent) = self.client.block_by_hash(parent_hash)? {
+            if let Some(status) = self.validate_against_canonical_parent(&block, &parent)? {
+                return Ok(status)
+            }
+        }
+
+        match self.tree.insert_block(block.clone()) {
+            Ok(()) => Ok(PayloadStatus::new(PayloadStatusEnum::Valid, block_hash)),
+            // The parent is neither canonical nor in the tree, or the payload is too far ahead of
+            // the canonical tip, the pipeline syncs up to it.
+            Err(Error::Execution(
+                ExecError::BlockParentUnknown { .. } | ExecError::PendingBlockIsInFuture { .. },
+            )) => Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing)),
+            Err(err @ (Error::Execution(_) | Error::Consensus(_))) => {
+                let validation_error = err.to_string();
+                self.bad_blocks.insert(block_hash, block.unseal(), &validation_error);
+                Ok(PayloadStatus::new(
+                    PayloadStatusEnum::Invalid { validation_error },
+                    parent_hash, // The parent was validated before it was inserted
+                ))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
 
+    /// Check the terminal block conditions and the timestamp of the payload against its canonical
+    /// parent.
+    ///
+    /// Returns the `Invalid` status if the payload fails the checks.
+    fn validate_against_canonical_parent(
+        &self,
+        block: &SealedBlock,
+        parent: &Block,
+    ) -> EngineApiResult<Option<PayloadStatus>> {
+        let block_hash = block.hash();
         let parent_td = if let Some(parent_td) = self.client.header_td(&block.parent_hash)? {
             parent_td
         } else {
-            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
-                validation_error: EngineApiError::PayloadPreMerge.to_string(),
-            }))
+            let validation_error = EngineApiError::PayloadPreMerge.to_string();
+            self.bad_blocks.insert(block_hash, block.clone().unseal(), &validation_error);
+            return Ok(Some(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                validation_error,
+            })))
         };
 
         // Short circuit the check by passing parent total difficulty.
@@ -304,34 +375,27 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             // }
             //
             // if terminal block conditions are not satisfied
-            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
-                validation_error: EngineApiError::PayloadPreMerge.to_string(),
-            })
-            .with_latest_valid_hash(H256::zero()))
+            let validation_error = EngineApiError::PayloadPreMerge.to_string();
+            self.bad_blocks.insert(block_hash, block.clone().unseal(), &validation_error);
+            return Ok(Some(
+                PayloadStatus::from_status(PayloadStatusEnum::Invalid { validation_error })
+                    .with_latest_valid_hash(H256::zero()),
+            ))
         }
 
         if block.timestamp <= parent.timestamp {
-            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
-                validation_error: EngineApiError::PayloadTimestamp {
-                    invalid: block.timestamp,
-                    latest: parent.timestamp,
-                }
-                .to_string(),
-            }))
+            let validation_error = EngineApiError::PayloadTimestamp {
+                invalid: block.timestamp,
+                latest: parent.timestamp,
+            }
+            .to_string();
+            self.bad_blocks.insert(block_hash, block.clone().unseal(), &validation_error);
+            return Ok(Some(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                validation_error,
+            })))
         }
 
-        let state_provider = self.client.latest()?;
-        let total_difficulty = parent_td + block.header.difficulty;
-
-        let factory = reth_executor::Factory::new(self.chain_spec.clone());
-        let mut executor = factory.with_sp(&state_provider);
-        match executor.execute_and_verify_receipt(&block.unseal(), total_difficulty, None) {
-            Ok(_) => Ok(PayloadStatus::new(PayloadStatusEnum::Valid, block_hash)),
-            Err(err) => Ok(PayloadStatus::new(
-                PayloadStatusEnum::Invalid { validation_error: err.to_string() },
-                parent_hash, // The parent hash is already in our database hence it is valid
-            )),
-        }
+        Ok(None)
     }
 
     /// Called to resolve chain forks and ensure that the Execution layer is working with the latest
@@ -352,6 +416,18 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             }))
         }
 
+        // Make the head canonical if it was inserted into the tree by `engine_newPayload`. Heads
+        // unknown to the tree are either canonical already or synced by the pipeline.
+        match self.tree.make_canonical(&head_block_hash) {
+            Ok(()) | Err(Error::Execution(ExecError::BlockHashNotFoundInChain { .. })) => {}
+            Err(Error::Execution(ExecError::CanonicalCommit { inner })) => {
+                return Ok(ForkchoiceUpdated::from_status(PayloadStatusEnum::Invalid {
+                    validation_error: inner,
+                }))
+            }
+            Err(err) => return Err(err.into()),
+        }
+
         let head = if let Some(head) = self.client.header(&head_block_hash)? {
             head
         } else {
@@ -418,13 +494,27 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             tracing::error!(target: "rpc::engine_api", ?error, "Failed to update forkchoice state");
         }
 
-        if let Some(_attr) = payload_attributes {
-            // TODO: optionally build the block
+        // The blocks below the finalized block can't be reorged anymore.
+        if !finalized_block_hash.is_zero() {
+            if let Some(finalized) = self.client.header(&finalized_block_hash)? {
+                self.tree.finalize_block(finalized.number);
+            }
         }
 
         let chain_info = self.client.chain_info()?;
-        Ok(ForkchoiceUpdated::from_status(PayloadStatusEnum::Valid)
-            .with_latest_valid_hash(chain_info.best_hash))
+        let mut response = ForkchoiceUpdated::from_status(PayloadStatusEnum::Valid)
+            .with_latest_valid_hash(chain_info.best_hash);
+
+        if let Some(attributes) = payload_attributes {
+            // Start building a payload on top of the new head, the Consensus layer fetches it
+            // with `engine_getPayload`.
+            let payload_id = self
+                .payload_builder
+                .new_payload(PayloadBuilderAttributes::new(head_block_hash, attributes));
+            response = response.with_payload_id(payload_id);
+        }
+
+        Ok(response)
     }
 
     /// Called to verify network configuration parameters and ensure that Consensus and Execution
@@ -479,15 +569,19 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
     }
 }
 
-impl<Client> Future for EngineApi<Client>
+impl<Client, Tree> Future for EngineApi<Client, Tree>
 where
     Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvider + Unpin,
+    Tree: BlockchainTreeEngine + Unpin,
 {
     type Output = ();
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
         loop {
+            // drive the pending `engine_getPayload` requests
+            while let Poll::Ready(Some(())) = this.pending_payloads.poll_next_unpin(cx) {}
+
             match ready!(this.message_rx.poll_next_unpin(cx)) {
                 Some(msg) => this.on_message(msg),
                 None => {
@@ -504,34 +598,105 @@ mod tests {
     use super::*;
     use assert_matches::assert_matches;
     use reth_interfaces::test_utils::generators::random_block;
+    use reth_payload_builder::{BuiltPayload, PayloadServiceCommand};
     use reth_primitives::{H256, MAINNET};
     use reth_provider::test_utils::MockEthProvider;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
     use tokio::sync::{
-        mpsc::{unbounded_channel, UnboundedSender},
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
         watch::Receiver as WatchReceiver,
     };
 
-    fn setup_engine_api() -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>>) {
+    /// Blockchain tree that records the calls of the engine API.
+    #[derive(Clone, Default)]
+    struct TestTree(Arc<Mutex<TestTreeInner>>);
+
+    #[derive(Default)]
+    struct TestTreeInner {
+        /// The error returned by the next insertion.
+        insert_error: Option<Error>,
+        inserted: Vec<SealedBlock>,
+        canonical: Vec<BlockHash>,
+        finalized: Option<BlockNumber>,
+    }
+
+    impl TestTree {
+        fn fail_next_insert(&self, error: impl Into<Error>) {
+            self.0.lock().unwrap().insert_error = Some(error.into());
+        }
+
+        fn inserted(&self) -> Vec<SealedBlock> {
+            self.0.lock().unwrap().inserted.clone()
+        }
+
+        fn canonical(&self) -> Vec<BlockHash> {
+            self.0.lock().unwrap().canonical.clone()
+        }
+
+        fn finalized(&self) -> Option<BlockNumber> {
+            self.0.lock().unwrap().finalized
+        }
+    }
+
+    impl BlockchainTreeEngine for TestTree {
+        fn insert_block(&self, block: SealedBlock) -> reth_interfaces::Result<()> {
+            let mut inner = self.0.lock().unwrap();
+            if let Some(error) = inner.insert_error.take() {
+                return Err(error)
+            }
+            inner.inserted.push(block);
+            Ok(())
+        }
+
+        fn make_canonical(&self, block_hash: &BlockHash) -> reth_interfaces::Result<()> {
+            let mut inner = self.0.lock().unwrap();
+            if !inner.inserted.iter().any(|block| block.hash() == *block_hash) {
+                return Err(ExecError::BlockHashNotFoundInChain { block_hash: *block_hash }.into())
+            }
+            inner.canonical.push(*block_hash);
+            Ok(())
+        }
+
+        fn finalize_block(&self, finalized_block: BlockNumber) {
+            self.0.lock().unwrap().finalized = Some(finalized_block);
+        }
+    }
+
+    fn setup_engine_api() -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>, TestTree>) {
         let chain_spec = Arc::new(MAINNET.clone());
         let client = Arc::new(MockEthProvider::default());
+        let tree = TestTree::default();
         let (msg_tx, msg_rx) = unbounded_channel();
         let (forkchoice_state_tx, forkchoice_state_rx) = watch::channel(ForkchoiceState::default());
+        let (payload_builder_tx, payload_builder_rx) = unbounded_channel();
         let api = EngineApi {
             client: client.clone(),
+            tree: tree.clone(),
             chain_spec: chain_spec.clone(),
             message_rx: UnboundedReceiverStream::new(msg_rx),
             forkchoice_state_tx,
+            payload_builder: PayloadBuilderHandle::new(payload_builder_tx),
+            pending_payloads: Default::default(),
+            bad_blocks: Default::default(),
+        };
+        let handle = EngineApiTestHandle {
+            chain_spec,
+            client,
+            tree,
+            msg_tx,
+            forkchoice_state_rx,
+            payload_builder_rx,
         };
-        let handle = EngineApiTestHandle { chain_spec, client, msg_tx, forkchoice_state_rx };
         (handle, api)
     }
 
     struct EngineApiTestHandle {
         chain_spec: Arc<ChainSpec>,
         client: Arc<MockEthProvider>,
+        tree: TestTree,
         msg_tx: UnboundedSender<EngineApiMessage>,
         forkchoice_state_rx: WatchReceiver<ForkchoiceState>,
+        payload_builder_rx: UnboundedReceiver<PayloadServiceCommand>,
     }
 
     impl EngineApiTestHandle {
@@ -684,6 +849,11 @@ mod tests {
 
             let (result_tx, result_rx) = oneshot::channel();
             let block = random_block(100, Some(H256::random()), None, Some(0)); // payload must have no ommers
+            handle.tree.fail_next_insert(ExecError::BlockParentUnknown {
+                block_hash: block.hash(),
+                block_number: block.number,
+                parent_hash: block.parent_hash,
+            });
             handle.send_message(EngineApiMessage::NewPayload(
                 EngineApiMessageVersion::V1,
                 block.into(),
@@ -725,7 +895,8 @@ mod tests {
         #[tokio::test]
         async fn invalid_payload_timestamp() {
             let (handle, api) = setup_engine_api();
-            tokio::spawn(api);
+            let bad_blocks = BadBlocks::default();
+            tokio::spawn(api.with_bad_blocks(bad_blocks.clone()));
 
             let block_timestamp = 100;
             let parent_timestamp = block_timestamp + 10;
@@ -758,27 +929,120 @@ mod tests {
                 .to_string(),
             });
             assert_matches!( result_rx.await, Ok(Ok(result)) => assert_eq!(result, expected_result));
+
+            // the rejected payload is recorded
+            let bad_block = bad_blocks.get(block.hash()).expect("bad block is recorded");
+            assert_eq!(
+                bad_block.error,
+                EngineApiError::PayloadTimestamp {
+                    invalid: block_timestamp,
+                    latest: parent_timestamp
+                }
+                .to_string()
+            );
+        }
+
+        #[tokio::test]
+        async fn payload_inserted() {
+            let (handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let parent = transform_block(random_block(100, None, None, Some(0)), |mut b| {
+                b.header.difficulty = handle.chain_spec.fork(Hardfork::Paris).ttd().unwrap();
+                b
+            });
+            let block =
+                transform_block(random_block(101, Some(parent.hash()), None, Some(0)), |mut b| {
+                    b.header.timestamp = parent.timestamp + 12;
+                    b
+                });
+
+            handle.client.add_block(parent.hash(), parent.clone().unseal());
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::NewPayload(
+                EngineApiMessageVersion::V1,
+                block.clone().into(),
+                result_tx,
+            ));
+
+            let expected_result = PayloadStatus::new(PayloadStatusEnum::Valid, block.hash());
+            assert_matches!(result_rx.await, Ok(Ok(result)) => assert_eq!(result, expected_result));
+            assert_eq!(handle.tree.inserted(), vec![block]);
         }
 
-        // TODO: add execution tests
+        #[tokio::test]
+        async fn payload_execution_failed() {
+            let (handle, api) = setup_engine_api();
+            let bad_blocks = BadBlocks::default();
+            tokio::spawn(api.with_bad_blocks(bad_blocks.clone()));
+
+            // the parent is part of a side chain in the tree
+            let block = random_block(101, Some(H256::random()), None, Some(0));
+            let error = ExecError::BlockGasUsed { got: 1, expected: 2 };
+            handle.tree.fail_next_insert(error.clone());
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::NewPayload(
+                EngineApiMessageVersion::V1,
+                block.clone().into(),
+                result_tx,
+            ));
+
+            let expected_result = PayloadStatus::new(
+                PayloadStatusEnum::Invalid { validation_error: error.to_string() },
+                block.parent_hash,
+            );
+            assert_matches!(result_rx.await, Ok(Ok(result)) => assert_eq!(result, expected_result));
+
+            // the rejected payload is recorded
+            let bad_block = bad_blocks.get(block.hash()).expect("bad block is recorded");
+            assert_eq!(bad_block.error, error.to_string());
+        }
     }
 
     // non exhaustive tests for engine_getPayload
-    // TODO: amend when block building is implemented
     mod get_payload {
         use super::*;
 
         #[tokio::test]
         async fn payload_unknown() {
-            let (handle, api) = setup_engine_api();
+            let (mut handle, api) = setup_engine_api();
             tokio::spawn(api);
 
             let payload_id = H64::random();
             let (result_tx, result_rx) = oneshot::channel();
             handle.send_message(EngineApiMessage::GetPayload(payload_id, result_tx));
 
+            assert_matches!(
+                handle.payload_builder_rx.recv().await,
+                Some(PayloadServiceCommand::Resolve(id, tx)) if id == payload_id => {
+                    tx.send(None).unwrap();
+                }
+            );
             assert_matches!(result_rx.await, Ok(Err(EngineApiError::PayloadUnknown)));
         }
+
+        #[tokio::test]
+        async fn payload_built() {
+            let (mut handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let payload_id = H64::random();
+            let block = random_block(100, None, Some(3), Some(0));
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::GetPayload(payload_id, result_tx));
+
+            assert_matches!(
+                handle.payload_builder_rx.recv().await,
+                Some(PayloadServiceCommand::Resolve(id, tx)) if id == payload_id => {
+                    let payload = BuiltPayload::new(payload_id, block.clone(), U256::ZERO);
+                    tx.send(Some(Arc::new(payload))).unwrap();
+                }
+            );
+            let expected = ExecutionPayload::from(block);
+            assert_matches!(result_rx.await, Ok(Ok(payload)) => assert_eq!(payload, expected));
+        }
     }
 
     // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
@@ -1013,6 +1277,38 @@ mod tests {
 
             assert!(handle.forkchoice_state_has_changed());
             assert_eq!(handle.forkchoice_state(), state);
+            assert_eq!(handle.tree.finalized(), Some(finalized.number));
+        }
+
+        #[tokio::test]
+        async fn head_is_made_canonical() {
+            let (handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let ttd = handle.chain_spec.fork(Hardfork::Paris).ttd().unwrap();
+            let mut head = random_header(100, None).unseal();
+            head.difficulty = ttd;
+            let head = SealedBlock { header: head.seal_slow(), ..Default::default() };
+
+            // the head was inserted by `engine_newPayload` and is committed by the tree
+            handle.tree.insert_block(head.clone()).unwrap();
+            handle.client.add_block(head.hash(), head.clone().unseal());
+
+            let state = ForkchoiceState { head_block_hash: head.hash(), ..Default::default() };
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::ForkchoiceUpdated(
+                EngineApiMessageVersion::V1,
+                state,
+                None,
+                result_tx,
+            ));
+
+            assert_matches!(
+                result_rx.await,
+                Ok(Ok(result)) => assert_eq!(result.payload_status.status, PayloadStatusEnum::Valid)
+            );
+            assert_eq!(handle.tree.canonical(), vec![head.hash()]);
+            assert_eq!(handle.tree.finalized(), None);
         }
 
         #[tokio::test]
@@ -10,7 +10,7 @@ use std::{collections::BTreeMap, ops::Deref};
 
 /// Block Transactions depending on the boolean attribute of `eth_getBlockBy*`,
 /// or if used by `eth_getUncle*`
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
 #[serde(untagged)]
 pub enum BlockTransactions {
     /// Only hashes
@@ -27,6 +27,20 @@ impl BlockTransactions {
         matches!(self, Self::Uncle)
     }
 }
+
+impl Serialize for BlockTransactions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            BlockTransactions::Hashes(hashes) => hashes.serialize(serializer),
+            BlockTransactions::Full(transactions) => transactions.serialize(serializer),
+            // uncles are returned with an empty transaction list, like geth does
+            BlockTransactions::Uncle => serializer.collect_seq(std::iter::empty::<H256>()),
+        }
+    }
+}
 /// Determines how the `transactions` field of [Block] should be filled.
 ///
 /// This essentially represents the `full:bool` argument in RPC calls that determine whether the
@@ -71,7 +85,6 @@ pub struct Block {
     /// Uncles' hashes
     pub uncles: Vec<H256>,
     /// Transactions
-    #[serde(skip_serializing_if = "BlockTransactions::is_uncle")]
     pub transactions: BlockTransactions,
     /// Integer the size of this block in bytes.
     pub size: Option<U256>,
@@ -183,6 +196,7 @@ impl Block {
     /// an Uncle from its header.
     pub fn uncle_block_from_header(header: PrimitiveHeader) -> Self {
         let hash = header.hash_slow();
+        let base_fee_per_gas = header.base_fee_per_gas.map(U256::from);
         let rpc_header = Header::from_primitive_with_hash(header.clone(), hash);
         let uncle_block = PrimitiveBlock { header, ..Default::default() };
         let size = Some(U256::from(uncle_block.length()));
@@ -190,7 +204,7 @@ impl Block {
             uncles: vec![],
             header: rpc_header,
             transactions: BlockTransactions::Uncle,
-            base_fee_per_gas: None,
+            base_fee_per_gas,
             withdrawals: None,
             size,
             total_difficulty: None,
@@ -240,6 +254,12 @@ pub struct Header {
     pub mix_hash: H256,
     /// Nonce
     pub nonce: Option<H64>,
+    /// Blob gas used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob_gas_used: Option<U256>,
+    /// Excess blob gas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excess_blob_gas: Option<U256>,
 }
 
 // === impl Header ===
@@ -267,6 +287,8 @@ impl Header {
             base_fee_per_gas: _,
             extra_data,
             withdrawals_root,
+            blob_gas_used,
+            excess_blob_gas,
         } = primitive_header;
 
         Header {
@@ -288,6 +310,8 @@ impl Header {
             difficulty,
             mix_hash,
             nonce: Some(nonce.to_be_bytes().into()),
+            blob_gas_used: blob_gas_used.map(U256::from),
+            excess_blob_gas: excess_blob_gas.map(U256::from),
         }
     }
 }
@@ -385,6 +409,8 @@ mod tests {
                 difficulty: U256::from(13),
                 mix_hash: H256::from_low_u64_be(14),
                 nonce: Some(H64::from_low_u64_be(15)),
+                blob_gas_used: None,
+                excess_blob_gas: None,
             },
             total_difficulty: Some(U256::from(100000)),
             uncles: vec![H256::from_low_u64_be(17)],
@@ -401,4 +427,17 @@ mod tests {
         let deserialized: Block = serde_json::from_str(&serialized).unwrap();
         assert_eq!(block, deserialized);
     }
+
+    #[test]
+    fn serde_uncle_block() {
+        let header = PrimitiveHeader { number: 1, base_fee_per_gas: Some(7), ..Default::default() };
+        let uncle = Block::uncle_block_from_header(header.clone());
+        assert_eq!(uncle.header.hash, Some(header.hash_slow()));
+        assert_eq!(uncle.base_fee_per_gas, Some(U256::from(7)));
+
+        let serialized = serde_json::to_value(&uncle).unwrap();
+        assert_eq!(serialized["transactions"], serde_json::json!([]));
+        assert_eq!(serialized["uncles"], serde_json::json!([]));
+        assert!(serialized.get("totalDifficulty").is_none());
+    }
 }
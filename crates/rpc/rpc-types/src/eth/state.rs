@@ -1,6 +1,6 @@
-//! bindings for state overrides in eth_call
+//! bindings for state and block overrides in eth_call
 
-use reth_primitives::{Address, Bytes, H256, U256};
+use reth_primitives::{Address, Bytes, H256, U256, U64};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -12,9 +12,81 @@ pub type StateOverride = HashMap<Address, AccountOverride>;
 #[serde(default, rename_all = "camelCase", deny_unknown_fields)]
 #[allow(missing_docs)]
 pub struct AccountOverride {
-    pub nonce: Option<u64>,
+    pub nonce: Option<U64>,
     pub code: Option<Bytes>,
     pub balance: Option<U256>,
     pub state: Option<HashMap<H256, H256>>,
     pub state_diff: Option<HashMap<H256, H256>>,
 }
+
+/// Helper type that bundles various overrides for EVM Execution.
+///
+/// By `Default`, no overrides are included.
+///
+/// See also <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-eth#eth-call>
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct BlockOverrides {
+    /// Overrides the block number.
+    pub number: Option<U256>,
+    /// Overrides the difficulty of the block.
+    pub difficulty: Option<U256>,
+    /// Overrides the timestamp of the block, `time` in geth.
+    #[serde(alias = "timestamp")]
+    pub time: Option<U64>,
+    /// Overrides the gas limit of the block.
+    pub gas_limit: Option<U64>,
+    /// Overrides the coinbase address of the block.
+    pub coinbase: Option<Address>,
+    /// Overrides the prevrandao of the block.
+    pub random: Option<H256>,
+    /// Overrides the basefee of the block.
+    #[serde(alias = "baseFeePerGas")]
+    pub base_fee: Option<U256>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_state_override() {
+        let s = r#"{
+            "0x0000000000000000000000000000000000000124": {
+                "balance": "0x1",
+                "nonce": "0x2",
+                "code": "0x6080",
+                "stateDiff": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002"
+                }
+            }
+        }"#;
+        let state_override: StateOverride = serde_json::from_str(s).unwrap();
+        let account_override =
+            state_override.get(&Address::from_low_u64_be(0x124)).cloned().unwrap();
+        assert_eq!(account_override.balance, Some(U256::from(1)));
+        assert_eq!(account_override.nonce, Some(U64::from(2)));
+        assert!(account_override.state.is_none());
+        assert_eq!(account_override.state_diff.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_block_overrides() {
+        let s = r#"{
+            "number": "0x10",
+            "time": "0x64",
+            "baseFee": "0x3b9aca00",
+            "coinbase": "0x0000000000000000000000000000000000000124"
+        }"#;
+        let overrides: BlockOverrides = serde_json::from_str(s).unwrap();
+        assert_eq!(overrides.number, Some(U256::from(16)));
+        assert_eq!(overrides.time, Some(U64::from(100)));
+        assert_eq!(overrides.base_fee, Some(U256::from(1_000_000_000u64)));
+        assert_eq!(overrides.coinbase, Some(Address::from_low_u64_be(0x124)));
+        assert!(overrides.gas_limit.is_none());
+
+        let s = r#"{"timestamp": "0x64"}"#;
+        let overrides: BlockOverrides = serde_json::from_str(s).unwrap();
+        assert_eq!(overrides.time, Some(U64::from(100)));
+    }
+}
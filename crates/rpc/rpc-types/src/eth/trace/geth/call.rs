@@ -0,0 +1,73 @@
+use reth_primitives::{Address, Bytes, H256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The response object for `debug_traceTransaction` with `"tracer": "callTracer"`
+///
+/// <https://github.com/ethereum/go-ethereum/blob/91cb6f863a965481e51d5d9c0e5ccd54796fd967/eth/tracers/native/call.go#L44>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub from: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    pub gas: U256,
+    pub gas_used: U256,
+    pub input: Bytes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logs: Vec<CallLogFrame>,
+}
+
+/// Represents a recorded call
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallLogFrame {
+    pub address: Address,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+}
+
+/// The config for the call tracer
+///
+/// <https://github.com/ethereum/go-ethereum/blob/91cb6f863a965481e51d5d9c0e5ccd54796fd967/eth/tracers/native/call.go#L106>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallConfig {
+    /// When set to true, this will only trace the primary (top-level) call and not any sub-calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub only_top_call: Option<bool>,
+    /// When set to true, this will include the logs emitted by the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_log: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_call_frame() {
+        let frame = CallFrame {
+            typ: "STATICCALL".to_string(),
+            gas: U256::from(100),
+            gas_used: U256::from(50),
+            ..Default::default()
+        };
+        let s = serde_json::to_string(&frame).unwrap();
+        assert_eq!(
+            s,
+            r#"{"type":"STATICCALL","from":"0x0000000000000000000000000000000000000000","gas":"0x64","gasUsed":"0x32","input":"0x"}"#
+        );
+    }
+}
@@ -0,0 +1,225 @@
+#![allow(missing_docs)]
+/// Geth tracing types
+use reth_primitives::{Bytes, JsonU256, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// re-exports
+pub use self::{
+    call::{CallConfig, CallFrame, CallLogFrame},
+    pre_state::{AccountState, DiffMode, PreStateConfig, PreStateFrame, PreStateMode},
+};
+
+mod call;
+mod pre_state;
+
+/// Result type for geth style transaction trace
+pub type TraceResult = crate::trace::common::TraceResult<GethTraceFrame, String>;
+
+/// blockTraceResult represents the results of tracing a single block when an entire chain is being
+/// traced. ref <https://github.com/ethereum/go-ethereum/blob/ee530c0d5aa70d2c00ab5691a89ab431b73f8165/eth/tracers/api.go#L218-L222>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockTraceResult {
+    /// Block number corresponding to the trace task
+    pub block: U256,
+    /// Block hash corresponding to the trace task
+    pub hash: H256,
+    /// Trace results produced by the trace task
+    pub traces: Vec<TraceResult>,
+}
+
+/// Geth Default trace frame
+///
+/// <https://github.com/ethereum/go-ethereum/blob/a9ef135e2dd53682d106c6a2aede9187026cc1de/eth/tracers/logger/logger.go#L406-L411>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultFrame {
+    pub failed: bool,
+    pub gas: JsonU256,
+    pub return_value: Bytes,
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// Represents a struct log entry in a trace
+///
+/// <https://github.com/ethereum/go-ethereum/blob/366d2169fbc0e0f803b68c042b77b6b480836dbc/eth/tracers/logger/logger.go#L413-L426>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructLog {
+    pub depth: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub gas: u64,
+    #[serde(rename = "gasCost")]
+    pub gas_cost: u64,
+    /// ref <https://github.com/ethereum/go-ethereum/blob/366d2169fbc0e0f803b68c042b77b6b480836dbc/eth/tracers/logger/logger.go#L450-L452>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<String>>,
+    pub op: String,
+    pub pc: u64,
+    #[serde(default, rename = "refund", skip_serializing_if = "Option::is_none")]
+    pub refund_counter: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<U256>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Tracing response
+///
+/// See <https://github.com/ethereum/go-ethereum/blob/a9ef135e2dd53682d106c6a2aede9187026cc1de/eth/tracers/native/call.go#L44-L57>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GethTraceFrame {
+    Default(DefaultFrame),
+    CallTracer(CallFrame),
+    PreStateTracer(PreStateFrame),
+    /// The result of a javascript tracer
+    JS(serde_json::Value),
+}
+
+impl From<DefaultFrame> for GethTraceFrame {
+    fn from(value: DefaultFrame) -> Self {
+        GethTraceFrame::Default(value)
+    }
+}
+
+impl From<CallFrame> for GethTraceFrame {
+    fn from(value: CallFrame) -> Self {
+        GethTraceFrame::CallTracer(value)
+    }
+}
+
+impl From<PreStateFrame> for GethTraceFrame {
+    fn from(value: PreStateFrame) -> Self {
+        GethTraceFrame::PreStateTracer(value)
+    }
+}
+
+impl From<serde_json::Value> for GethTraceFrame {
+    fn from(value: serde_json::Value) -> Self {
+        GethTraceFrame::JS(value)
+    }
+}
+
+/// Available built-in tracers
+///
+/// See <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GethDebugBuiltInTracerType {
+    /// The call tracer tracks all the call frames executed during a transaction
+    #[serde(rename = "callTracer")]
+    CallTracer,
+    /// The prestate tracer returns the accounts necessary to execute the transaction, or the
+    /// changes of the transaction if configured in diff mode
+    #[serde(rename = "prestateTracer")]
+    PreStateTracer,
+}
+
+/// Tracer to use when debugging a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GethDebugTracerType {
+    /// built-in tracer
+    BuiltInTracer(GethDebugBuiltInTracerType),
+    /// The code of a custom javascript tracer
+    JsTracer(String),
+}
+
+/// Configuration of the tracer
+///
+/// This is a simple wrapper around serde_json::Value.
+/// with helpers for deserializing tracer configs.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct GethDebugTracerConfig(pub serde_json::Value);
+
+// === impl GethDebugTracerConfig ===
+
+impl GethDebugTracerConfig {
+    /// Returns if this is a null object
+    pub fn is_null(&self) -> bool {
+        self.0.is_null()
+    }
+
+    /// Consumes the config and tries to deserialize it into the given type.
+    pub fn from_value<T: serde::de::DeserializeOwned>(self) -> Result<T, serde_json::Error> {
+        serde_json::from_value(self.0)
+    }
+
+    /// Returns the [CallConfig] if it is a call config, or the default config if it is null.
+    pub fn into_call_config(self) -> Result<CallConfig, serde_json::Error> {
+        if self.is_null() {
+            return Ok(Default::default())
+        }
+        self.from_value()
+    }
+
+    /// Returns the [PreStateConfig] if it is a prestate config, or the default config if it is
+    /// null.
+    pub fn into_pre_state_config(self) -> Result<PreStateConfig, serde_json::Error> {
+        if self.is_null() {
+            return Ok(Default::default())
+        }
+        self.from_value()
+    }
+}
+
+/// Bindings for additional `debug_traceTransaction` options
+///
+/// See <https://geth.ethereum.org/docs/rpc/ns-debug#debug_tracetransaction>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GethDebugTracingOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_storage: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_stack: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_memory: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_return_data: Option<bool>,
+    /// The custom tracer to use.
+    ///
+    /// If `None` then the default structlog tracer is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracer: Option<GethDebugTracerType>,
+    /// Config specific to the given custom tracer.
+    #[serde(default, skip_serializing_if = "GethDebugTracerConfig::is_null")]
+    pub tracer_config: GethDebugTracerConfig,
+    /// A string of decimal integers that overrides the JavaScript-based tracing calls default
+    /// timeout of 5 seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracer_options() {
+        let s =
+            r#"{"tracer": "callTracer", "tracerConfig": {"onlyTopCall": true, "withLog": true}}"#;
+        let opts = serde_json::from_str::<GethDebugTracingOptions>(s).unwrap();
+        assert_eq!(
+            opts.tracer,
+            Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer))
+        );
+        let config = opts.tracer_config.into_call_config().unwrap();
+        assert_eq!(config, CallConfig { only_top_call: Some(true), with_log: Some(true) });
+
+        let s = r#"{"tracer": "prestateTracer", "tracerConfig": {"diffMode": true}}"#;
+        let opts = serde_json::from_str::<GethDebugTracingOptions>(s).unwrap();
+        assert!(opts.tracer_config.into_pre_state_config().unwrap().is_diff_mode());
+
+        let s = r#"{"disableStorage": true, "enableMemory": true}"#;
+        let opts = serde_json::from_str::<GethDebugTracingOptions>(s).unwrap();
+        assert_eq!(opts.disable_storage, Some(true));
+        assert_eq!(opts.tracer, None);
+        assert!(opts.tracer_config.is_null());
+
+        let s = r#"{"tracer": "{step: function() {}, fault: function() {}, result: function() { return 1; }}"}"#;
+        let opts = serde_json::from_str::<GethDebugTracingOptions>(s).unwrap();
+        assert!(matches!(opts.tracer, Some(GethDebugTracerType::JsTracer(_))));
+    }
+}
@@ -0,0 +1,77 @@
+use reth_primitives::{Address, Bytes, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The response object for `debug_traceTransaction` with `"tracer": "prestateTracer"`
+///
+/// <https://github.com/ethereum/go-ethereum/blob/91cb6f863a965481e51d5d9c0e5ccd54796fd967/eth/tracers/native/prestate.go#L38>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PreStateFrame {
+    Default(PreStateMode),
+    Diff(DiffMode),
+}
+
+/// The accounts that are touched by the transaction and their state before the transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreStateMode(pub BTreeMap<Address, AccountState>);
+
+/// The state of the accounts that were changed by the transaction before and after the
+/// transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffMode {
+    pub pre: BTreeMap<Address, AccountState>,
+    pub post: BTreeMap<Address, AccountState>,
+}
+
+/// The state of an account
+///
+/// <https://github.com/ethereum/go-ethereum/blob/91cb6f863a965481e51d5d9c0e5ccd54796fd967/eth/tracers/native/prestate.go#L46>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// The config for the prestate tracer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreStateConfig {
+    /// When set to true, the tracer returns the changes of the transaction: the changed state
+    /// before and after the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_mode: Option<bool>,
+}
+
+impl PreStateConfig {
+    /// Returns true if the tracer is configured in diff mode
+    pub fn is_diff_mode(&self) -> bool {
+        self.diff_mode.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_diff_mode() {
+        let mut diff = DiffMode::default();
+        diff.pre.insert(
+            Address::zero(),
+            AccountState { balance: Some(U256::from(1)), nonce: Some(1), ..Default::default() },
+        );
+        diff.post.insert(Address::zero(), AccountState { nonce: Some(2), ..Default::default() });
+        let s = serde_json::to_string(&PreStateFrame::Diff(diff)).unwrap();
+        assert_eq!(
+            s,
+            r#"{"pre":{"0x0000000000000000000000000000000000000000":{"balance":"0x1","nonce":1}},"post":{"0x0000000000000000000000000000000000000000":{"nonce":2}}}"#
+        );
+    }
+}
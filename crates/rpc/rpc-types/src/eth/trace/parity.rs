@@ -74,6 +74,7 @@ pub struct StateDiff(pub BTreeMap<Address, AccountDiff>);
 pub enum Action {
     Call(CallAction),
     Create(CreateAction),
+    #[serde(rename = "suicide", alias = "selfdestruct")]
     Selfdestruct(SelfdestructAction),
     Reward(RewardAction),
 }
@@ -87,6 +88,7 @@ pub enum ActionType {
     /// Contract creation.
     Create,
     /// Contract suicide/selfdestruct.
+    #[serde(rename = "suicide", alias = "selfdestruct")]
     Selfdestruct,
     /// A block reward.
     Reward,
@@ -216,7 +218,7 @@ pub struct VmInstruction {
 #[serde(rename_all = "camelCase")]
 pub struct VmExecutedOperation {
     pub used: u64,
-    pub push: Option<H256>,
+    pub push: Vec<U256>,
     pub mem: Option<MemoryDelta>,
     pub store: Option<StorageDelta>,
 }
@@ -3,7 +3,9 @@
 //! transaction deserialized from the json input of an RPC call. Depending on what fields are set,
 //! it can be converted into the container type [`TypedTransactionRequest`].
 
-use reth_primitives::{AccessList, Address, Bytes, U128, U256};
+use reth_primitives::{
+    AccessList, Address, Bytes, Transaction, TxEip1559, TxEip2930, TxLegacy, U128, U256,
+};
 use reth_rlp::{BufMut, Decodable, DecodeError, Encodable, RlpDecodable, RlpEncodable};
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +22,50 @@ pub enum TypedTransactionRequest {
     EIP1559(EIP1559TransactionRequest),
 }
 
+// == impl TypedTransactionRequest ==
+
+impl TypedTransactionRequest {
+    /// Converts the request into the unsigned [Transaction].
+    ///
+    /// Returns `None` if a numeric field of the request exceeds the range of its counterpart in
+    /// the [Transaction].
+    pub fn into_transaction(self) -> Option<Transaction> {
+        let transaction = match self {
+            TypedTransactionRequest::Legacy(tx) => Transaction::Legacy(TxLegacy {
+                chain_id: tx.chain_id,
+                nonce: u64::try_from(tx.nonce).ok()?,
+                gas_price: u128::try_from(tx.gas_price).ok()?,
+                gas_limit: u64::try_from(tx.gas_limit).ok()?,
+                to: tx.kind.into(),
+                value: u128::try_from(tx.value).ok()?,
+                input: tx.input,
+            }),
+            TypedTransactionRequest::EIP2930(tx) => Transaction::Eip2930(TxEip2930 {
+                chain_id: tx.chain_id,
+                nonce: u64::try_from(tx.nonce).ok()?,
+                gas_price: u128::try_from(tx.gas_price).ok()?,
+                gas_limit: u64::try_from(tx.gas_limit).ok()?,
+                to: tx.kind.into(),
+                value: u128::try_from(tx.value).ok()?,
+                access_list: tx.access_list,
+                input: tx.input,
+            }),
+            TypedTransactionRequest::EIP1559(tx) => Transaction::Eip1559(TxEip1559 {
+                chain_id: tx.chain_id,
+                nonce: u64::try_from(tx.nonce).ok()?,
+                gas_limit: u64::try_from(tx.gas_limit).ok()?,
+                max_fee_per_gas: u128::try_from(tx.max_fee_per_gas).ok()?,
+                max_priority_fee_per_gas: u128::try_from(tx.max_priority_fee_per_gas).ok()?,
+                to: tx.kind.into(),
+                value: u128::try_from(tx.value).ok()?,
+                access_list: tx.access_list,
+                input: tx.input,
+            }),
+        };
+        Some(transaction)
+    }
+}
+
 /// Represents a legacy transaction request
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LegacyTransactionRequest {
@@ -82,6 +128,15 @@ impl TransactionKind {
     }
 }
 
+impl From<TransactionKind> for reth_primitives::TransactionKind {
+    fn from(kind: TransactionKind) -> Self {
+        match kind {
+            TransactionKind::Call(to) => reth_primitives::TransactionKind::Call(to),
+            TransactionKind::Create => reth_primitives::TransactionKind::Create,
+        }
+    }
+}
+
 impl Encodable for TransactionKind {
     fn length(&self) -> usize {
         match self {
@@ -0,0 +1,157 @@
+//! Types for the `txpool` namespace: <https://geth.ethereum.org/docs/rpc/ns-txpool>
+
+use crate::Transaction;
+use reth_primitives::{Address, U128, U256, U64};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// Transaction summary as found in the txpool inspection property.
+///
+/// Serialized in the same format as geth: `"<to>: <value> wei + <gas> gas × <gas_price> wei"`,
+/// with `contract creation` instead of the recipient for transactions that create a contract.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TxpoolInspectSummary {
+    /// Recipient, `None` for contract creation
+    pub to: Option<Address>,
+    /// Transferred value
+    pub value: U256,
+    /// Gas amount
+    pub gas: U256,
+    /// Gas price, the fee cap for EIP-1559 transactions
+    pub gas_price: U128,
+}
+
+impl fmt::Display for TxpoolInspectSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to {
+            Some(to) => write!(f, "{to:?}")?,
+            None => f.write_str("contract creation")?,
+        }
+        write!(f, ": {} wei + {} gas × {} wei", self.value, self.gas, self.gas_price)
+    }
+}
+
+impl FromStr for TxpoolInspectSummary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid txpool inspect summary: {s}");
+
+        let (to, rest) = s.split_once(": ").ok_or_else(invalid)?;
+        let to = match to {
+            "contract creation" => None,
+            to => Some(to.parse().map_err(|_| invalid())?),
+        };
+        let (value, rest) = rest.split_once(" wei + ").ok_or_else(invalid)?;
+        let (gas, gas_price) = rest.split_once(" gas × ").ok_or_else(invalid)?;
+        let gas_price = gas_price.strip_suffix(" wei").ok_or_else(invalid)?;
+
+        Ok(Self {
+            to,
+            value: value.parse().map_err(|_| invalid())?,
+            gas: gas.parse().map_err(|_| invalid())?,
+            gas_price: gas_price.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl Serialize for TxpoolInspectSummary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TxpoolInspectSummary {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Transactions of the pool grouped by sender and nonce, as returned by `txpool_content`.
+///
+/// The nonces are decimal strings.
+///
+/// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolContent {
+    /// Transactions that are ready to be included in the next block
+    pub pending: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// Transactions that are scheduled for future execution
+    pub queued: BTreeMap<Address, BTreeMap<String, Transaction>>,
+}
+
+/// Transactions of a single sender grouped by nonce, as returned by `txpool_contentFrom`.
+///
+/// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_contentfrom) for more details
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolContentFrom {
+    /// Transactions that are ready to be included in the next block
+    pub pending: BTreeMap<String, Transaction>,
+    /// Transactions that are scheduled for future execution
+    pub queued: BTreeMap<String, Transaction>,
+}
+
+/// Summaries of the transactions of the pool grouped by sender and nonce, as returned by
+/// `txpool_inspect`.
+///
+/// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect) for more details
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolInspect {
+    /// Transactions that are ready to be included in the next block
+    pub pending: BTreeMap<Address, BTreeMap<String, TxpoolInspectSummary>>,
+    /// Transactions that are scheduled for future execution
+    pub queued: BTreeMap<Address, BTreeMap<String, TxpoolInspectSummary>>,
+}
+
+/// The number of transactions in the pool, as returned by `txpool_status`.
+///
+/// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_status) for more details
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolStatus {
+    /// Number of transactions that are ready to be included in the next block
+    pub pending: U64,
+    /// Number of transactions that are scheduled for future execution
+    pub queued: U64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_txpool_inspect_summary() {
+        let s =
+            r#""0x0216d5032f356960cd3749c31ab34eeff21b3395: 0 wei + 21000 gas × 20000000000 wei""#;
+        let summary: TxpoolInspectSummary = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            summary,
+            TxpoolInspectSummary {
+                to: Some("0x0216d5032f356960cd3749c31ab34eeff21b3395".parse().unwrap()),
+                value: U256::ZERO,
+                gas: U256::from(21000),
+                gas_price: U128::from(20000000000u64),
+            }
+        );
+        assert_eq!(serde_json::to_string(&summary).unwrap(), s);
+
+        let s = r#""contract creation: 1 wei + 100000 gas × 1 wei""#;
+        let summary: TxpoolInspectSummary = serde_json::from_str(s).unwrap();
+        assert_eq!(summary.to, None);
+        assert_eq!(serde_json::to_string(&summary).unwrap(), s);
+    }
+
+    #[test]
+    fn serde_txpool_status() {
+        let s = r#"{"pending":"0xa","queued":"0x7"}"#;
+        let status: TxpoolStatus = serde_json::from_str(s).unwrap();
+        assert_eq!(status, TxpoolStatus { pending: U64::from(10), queued: U64::from(7) });
+        assert_eq!(serde_json::to_string(&status).unwrap(), s);
+    }
+}
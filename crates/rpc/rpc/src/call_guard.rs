@@ -0,0 +1,93 @@
+use crate::eth::error::{EthApiError, EthResult};
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// A gate that limits the number of concurrently executing tracing calls.
+///
+/// Tracing requests replay entire blocks and can be expensive, this ensures a burst of them can't
+/// occupy all resources of the node. The guard is cheap to clone and all clones share the same
+/// limit.
+///
+/// The tracing itself runs on tokio's blocking thread pool, see
+/// [`TracingCallGuard::spawn_blocking`], so at most as many blocking threads as there are permits
+/// are busy with tracing.
+#[derive(Debug, Clone)]
+pub struct TracingCallGuard(Arc<Semaphore>);
+
+impl TracingCallGuard {
+    /// Creates a new guard that allows at most `max_tracing_requests` concurrent calls.
+    pub fn new(max_tracing_requests: u32) -> Self {
+        Self(Arc::new(Semaphore::new(max_tracing_requests as usize)))
+    }
+
+    /// Waits until a tracing call is allowed to execute.
+    ///
+    /// The returned permit must be held for the entire duration of the call.
+    pub async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.0.clone().acquire_owned().await.expect("semaphore is never closed")
+    }
+
+    /// Waits for a permit and then executes the tracing work on tokio's blocking thread pool.
+    ///
+    /// Tracing executes transactions synchronously, running it on the blocking pool ensures it
+    /// doesn't stall the async workers that serve all other requests. The permit is held until the
+    /// work is done.
+    pub(crate) async fn spawn_blocking<F, R>(&self, f: F) -> EthResult<R>
+    where
+        F: FnOnce() -> EthResult<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let permit = self.acquire().await;
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            f()
+        })
+        .await
+        .map_err(|_| EthApiError::InternalTracingError)?
+    }
+
+    /// Returns the number of tracing calls that can currently be started without waiting.
+    pub fn available_permits(&self) -> usize {
+        self.0.available_permits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn limits_concurrent_calls() {
+        let guard = TracingCallGuard::new(2);
+        let first = guard.acquire().await;
+        let _second = guard.clone().acquire().await;
+        assert_eq!(guard.available_permits(), 0);
+
+        drop(first);
+        assert_eq!(guard.available_permits(), 1);
+        let _third = guard.acquire().await;
+        assert_eq!(guard.available_permits(), 0);
+    }
+
+    #[tokio::test]
+    async fn spawn_blocking_holds_permit() {
+        let guard = TracingCallGuard::new(1);
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+        let task = tokio::spawn({
+            let guard = guard.clone();
+            async move { guard.spawn_blocking(move || Ok(rx.recv().is_ok())).await }
+        });
+
+        // the permit is taken while the blocking work runs
+        while guard.available_permits() != 0 {
+            tokio::task::yield_now().await;
+        }
+        tx.send(()).unwrap();
+        assert!(task.await.unwrap().unwrap());
+        assert_eq!(guard.available_permits(), 1);
+
+        let res = guard.spawn_blocking(|| -> EthResult<()> { panic!("tracing failed") }).await;
+        assert!(matches!(res, Err(EthApiError::InternalTracingError)));
+        assert_eq!(guard.available_permits(), 1);
+    }
+}
@@ -1,115 +1,854 @@
-use crate::{result::internal_rpc_err, EthApiSpec};
+use crate::{
+    call_guard::TracingCallGuard,
+    eth::{
+        cache::EthStateCache,
+        error::{decode_revert_reason, EthApiError, EthResult},
+        revm_utils::{
+            account_code, build_call_evm_env, fill_tx_env_with_recovered, get_precompiles, inspect,
+            is_empty_account, replay_transactions, transact,
+        },
+    },
+    result::ToRpcResult,
+    EthApiSpec,
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_primitives::{BlockId, BlockNumberOrTag, Bytes, H256};
+use reth_primitives::{Block, BlockId, BlockNumberOrTag, Bytes, H256, U256};
+use reth_provider::{
+    BadBlocks, BlockProvider, EvmEnvProvider, HeaderProvider, ReceiptProvider, StateProvider,
+    StateProviderFactory,
+};
+use reth_revm::{
+    database::{State, SubState},
+    tracing::{
+        js::{JsInspector, JsTracerLimits, TransactionContext},
+        TracingInspector,
+    },
+};
+use reth_rlp::{Decodable, Encodable};
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_types::{
-    trace::geth::{BlockTraceResult, GethDebugTracingOptions, GethTraceFrame, TraceResult},
-    CallRequest, RichBlock,
+    trace::geth::{
+        AccountState, BlockTraceResult, CallFrame, DiffMode, GethDebugBuiltInTracerType,
+        GethDebugTracerType, GethDebugTracingOptions, GethTraceFrame, PreStateFrame, PreStateMode,
+        TraceResult,
+    },
+    CallRequest, Rich, RichBlock,
+};
+use revm::{
+    primitives::{AccountInfo, BlockEnv, CfgEnv, Env, State as EvmState, TxEnv},
+    Database, DatabaseCommit,
 };
+use std::{collections::BTreeMap, time::Duration};
 
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
 #[non_exhaustive]
-pub struct DebugApi<Eth> {
+pub struct DebugApi<Client, Eth> {
+    /// The client that can interact with the chain.
+    client: Client,
     /// The implementation of `eth` API
     eth: Eth,
+    /// The async cache frontend for eth related data
+    eth_cache: EthStateCache,
+    /// Record of the blocks that were rejected as invalid
+    bad_blocks: BadBlocks,
+    /// Limits of the javascript tracers
+    js_tracer_limits: JsTracerLimits,
+    /// Restricts the number of concurrent tracing calls
+    tracing_call_guard: TracingCallGuard,
 }
 
 // === impl DebugApi ===
 
-impl<Eth> DebugApi<Eth> {
+impl<Client, Eth> DebugApi<Client, Eth> {
     /// Create a new instance of the [DebugApi]
-    pub fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub fn new(
+        client: Client,
+        eth: Eth,
+        eth_cache: EthStateCache,
+        bad_blocks: BadBlocks,
+        tracing_call_guard: TracingCallGuard,
+    ) -> Self {
+        Self {
+            client,
+            eth,
+            eth_cache,
+            bad_blocks,
+            js_tracer_limits: Default::default(),
+            tracing_call_guard,
+        }
+    }
+
+    /// Configures the limits of the javascript tracers.
+    ///
+    /// The `timeout` of a request can't exceed the configured timeout.
+    pub fn with_js_tracer_limits(mut self, js_tracer_limits: JsTracerLimits) -> Self {
+        self.js_tracer_limits = js_tracer_limits;
+        self
+    }
+}
+
+impl<Client, Eth> DebugApi<Client, Eth>
+where
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + Clone + 'static,
+{
+    /// Replays all transactions of the given block on top of the state of its parent and traces
+    /// every transaction.
+    async fn trace_block_with_env(
+        &self,
+        block: Block,
+        block_hash: H256,
+        cfg: CfgEnv,
+        block_env: BlockEnv,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let client = self.client.clone();
+        let js_tracer_limits = self.js_tracer_limits;
+        self.tracing_call_guard
+            .spawn_blocking(move || {
+                let state = client.history_by_block_hash(block.parent_hash)?;
+                let mut db = SubState::new(State::new(state));
+                let mut env = Env { cfg, block: block_env, tx: TxEnv::default() };
+
+                let mut results = Vec::with_capacity(block.body.len());
+                for (idx, tx) in block.body.iter().enumerate() {
+                    let tx_ctx = TransactionContext {
+                        block_hash: Some(block_hash),
+                        tx_index: Some(idx),
+                        tx_hash: Some(tx.hash()),
+                    };
+                    fill_tx_env_with_recovered(&mut env.tx, tx)?;
+                    let (result, state) =
+                        trace_env(opts.clone(), env.clone(), &mut db, tx_ctx, js_tracer_limits)?;
+                    db.commit(state);
+                    results.push(TraceResult::Success { result });
+                }
+
+                Ok(results)
+            })
+            .await
+    }
+
+    /// Traces all transactions of the rlp encoded block.
+    ///
+    /// The parent of the block must be known.
+    pub(crate) async fn trace_raw_block(
+        &self,
+        rlp_block: Bytes,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let block =
+            Block::decode(&mut rlp_block.as_ref()).map_err(|_| EthApiError::FailedToDecodeBlock)?;
+
+        let mut cfg = CfgEnv::default();
+        let mut block_env = BlockEnv::default();
+        self.client.fill_env_with_header(&mut cfg, &mut block_env, &block.header)?;
+
+        let block_hash = block.header.hash_slow();
+        self.trace_block_with_env(block, block_hash, cfg, block_env, opts).await
+    }
+
+    /// Traces all transactions of the block with the given hash.
+    pub(crate) async fn trace_block_by_hash(
+        &self,
+        block_hash: H256,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let block =
+            self.eth_cache.get_block(block_hash).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let (cfg, block_env) = self.eth_cache.get_evm_env(block_hash).await?;
+
+        self.trace_block_with_env(block, block_hash, cfg, block_env, opts).await
+    }
+
+    /// Traces all transactions of the given block.
+    pub(crate) async fn trace_block(
+        &self,
+        block_id: BlockId,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let block_hash =
+            self.client.block_hash_for_id(block_id)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        self.trace_block_by_hash(block_hash, opts).await
+    }
+
+    /// Traces all blocks in the range `(start_exclusive, end_inclusive]`.
+    pub(crate) async fn trace_chain(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<BlockTraceResult>> {
+        let start = self
+            .client
+            .convert_block_number(start_exclusive)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let end = self
+            .client
+            .convert_block_number(end_inclusive)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        if start >= end {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        let mut results = Vec::with_capacity((end - start) as usize);
+        for number in start + 1..=end {
+            let hash = self
+                .client
+                .block_hash(U256::from(number))?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+            let traces = self.trace_block_by_hash(hash, opts.clone()).await?;
+            results.push(BlockTraceResult { block: U256::from(number), hash, traces });
+        }
+
+        Ok(results)
+    }
+
+    /// Replays the transaction with the given hash and traces it.
+    ///
+    /// All transactions of the block that precede the transaction are replayed first on top of the
+    /// state of the parent block.
+    pub(crate) async fn trace_transaction(
+        &self,
+        tx_hash: H256,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<GethTraceFrame> {
+        let (transaction, meta) = self
+            .client
+            .transaction_by_hash_with_meta(tx_hash)?
+            .ok_or(EthApiError::TransactionNotFound)?;
+        let block = self
+            .eth_cache
+            .get_block(meta.block_hash)
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let (cfg, block_env) = self.eth_cache.get_evm_env(meta.block_hash).await?;
+
+        let client = self.client.clone();
+        let js_tracer_limits = self.js_tracer_limits;
+        self.tracing_call_guard
+            .spawn_blocking(move || {
+                let state = client.history_by_block_hash(block.parent_hash)?;
+                let mut db = SubState::new(State::new(state));
+                let mut env = Env { cfg, block: block_env, tx: TxEnv::default() };
+
+                // replay all transactions prior to the targeted transaction
+                replay_transactions(
+                    &mut db,
+                    &mut env,
+                    block.body.iter().take(meta.index as usize),
+                )?;
+
+                let tx_ctx = TransactionContext {
+                    block_hash: Some(meta.block_hash),
+                    tx_index: Some(meta.index as usize),
+                    tx_hash: Some(meta.tx_hash),
+                };
+                fill_tx_env_with_recovered(&mut env.tx, &transaction)?;
+                let (frame, _) = trace_env(opts, env, &mut db, tx_ctx, js_tracer_limits)?;
+                Ok(frame)
+            })
+            .await
+    }
+
+    /// Executes the given call on top of the state of the given block and traces it.
+    pub(crate) async fn trace_call(
+        &self,
+        call: CallRequest,
+        block_id: Option<BlockId>,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<GethTraceFrame> {
+        let at = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+        let block_hash =
+            self.client.block_hash_for_id(at)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let (mut cfg, block_env) = self.eth_cache.get_evm_env(block_hash).await?;
+
+        // we want to disable this in eth_call, since this is common practice used by other node
+        // impls and providers <https://github.com/foundry-rs/foundry/issues/4388>
+        cfg.disable_block_gas_limit = true;
+
+        let env = build_call_evm_env(cfg, block_env, call)?;
+        let client = self.client.clone();
+        let js_tracer_limits = self.js_tracer_limits;
+        self.tracing_call_guard
+            .spawn_blocking(move || {
+                let state = client.history_by_block_hash(block_hash)?;
+                let mut db = SubState::new(State::new(state));
+
+                let (frame, _) =
+                    trace_env(opts, env, &mut db, Default::default(), js_tracer_limits)?;
+                Ok(frame)
+            })
+            .await
     }
 }
 
 #[async_trait]
-impl<Eth> DebugApiServer for DebugApi<Eth>
+impl<Client, Eth> DebugApiServer for DebugApi<Client, Eth>
 where
+    Client:
+        BlockProvider + ReceiptProvider + StateProviderFactory + EvmEnvProvider + Clone + 'static,
     Eth: EthApiSpec + 'static,
 {
-    async fn raw_header(&self, _block_id: BlockId) -> RpcResult<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    /// Handler for `debug_getRawHeader`
+    async fn raw_header(&self, block_id: BlockId) -> RpcResult<Bytes> {
+        let header = match self.client.block_hash_for_id(block_id).to_rpc_result()? {
+            Some(hash) => self.client.header(&hash).to_rpc_result()?,
+            None => None,
+        }
+        .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let mut res = Vec::new();
+        header.encode(&mut res);
+        Ok(res.into())
     }
 
-    async fn raw_block(&self, _block_id: BlockId) -> RpcResult<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    /// Handler for `debug_getRawBlock`
+    async fn raw_block(&self, block_id: BlockId) -> RpcResult<Bytes> {
+        let block =
+            self.client.block(block_id).to_rpc_result()?.ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let mut res = Vec::new();
+        block.encode(&mut res);
+        Ok(res.into())
     }
 
-    /// Returns the bytes of the transaction for the given hash.
-    async fn raw_transaction(&self, _hash: H256) -> RpcResult<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    /// Handler for `debug_getRawTransaction`
+    ///
+    /// Returns the bytes of the transaction for the given hash, or empty bytes if the transaction
+    /// is unknown.
+    async fn raw_transaction(&self, hash: H256) -> RpcResult<Bytes> {
+        let tx = self.client.transaction_by_hash(hash).to_rpc_result()?;
+        Ok(tx.map(|tx| tx.envelope_encoded().into()).unwrap_or_default())
     }
 
-    async fn raw_receipts(&self, _block_id: BlockId) -> RpcResult<Vec<Bytes>> {
-        Err(internal_rpc_err("unimplemented"))
+    /// Handler for `debug_getRawReceipts`
+    async fn raw_receipts(&self, block_id: BlockId) -> RpcResult<Vec<Bytes>> {
+        let receipts = self
+            .client
+            .receipts_by_block(block_id)
+            .to_rpc_result()?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        Ok(receipts
+            .into_iter()
+            .map(|receipt| {
+                let mut buf = Vec::new();
+                receipt.encode_inner(&mut buf, false);
+                buf.into()
+            })
+            .collect())
     }
 
+    /// Handler for `debug_getBadBlocks`
+    ///
+    /// Returns the recently rejected blocks, oldest first. Next to the block itself, every entry
+    /// contains the `validationError` it was rejected with, the time it was `receivedAt` and the
+    /// `rlp` encoded block.
     async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self
+            .bad_blocks
+            .blocks()
+            .into_iter()
+            .map(|bad| {
+                let mut rlp = Vec::new();
+                bad.block.encode(&mut rlp);
+
+                let extra_info = BTreeMap::from([
+                    ("validationError".to_string(), bad.error.into()),
+                    ("receivedAt".to_string(), bad.received_at.into()),
+                    ("rlp".to_string(), Bytes::from(rlp).to_string().into()),
+                ]);
+                // the transactions of a bad block may not be recoverable, so only their hashes are
+                // included, the full block is part of the rlp.
+                let inner = reth_rpc_types::Block::from_block_with_tx_hashes(
+                    bad.block,
+                    U256::ZERO,
+                    Some(bad.hash),
+                );
+                Rich { inner, extra_info }
+            })
+            .collect())
     }
 
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self.trace_chain(start_exclusive, end_inclusive, opts.unwrap_or_default()).await?)
     }
 
     /// Handler for `debug_traceBlock`
     async fn debug_trace_block(
         &self,
-        _rlp_block: Bytes,
-        _opts: GethDebugTracingOptions,
+        rlp_block: Bytes,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self.trace_raw_block(rlp_block, opts.unwrap_or_default()).await?)
     }
 
     /// Handler for `debug_traceBlockByHash`
     async fn debug_trace_block_by_hash(
         &self,
-        _block: H256,
-        _opts: GethDebugTracingOptions,
+        block: H256,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self.trace_block_by_hash(block, opts.unwrap_or_default()).await?)
     }
 
     /// Handler for `debug_traceBlockByNumber`
     async fn debug_trace_block_by_number(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: GethDebugTracingOptions,
+        block: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self.trace_block(BlockId::Number(block), opts.unwrap_or_default()).await?)
     }
 
     /// Handler for `debug_traceTransaction`
     async fn debug_trace_transaction(
         &self,
-        _tx_hash: H256,
-        _opts: GethDebugTracingOptions,
+        tx_hash: H256,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<GethTraceFrame> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self.trace_transaction(tx_hash, opts.unwrap_or_default()).await?)
     }
 
     /// Handler for `debug_traceCall`
     async fn debug_trace_call(
         &self,
-        _request: CallRequest,
-        _block_number: Option<BlockId>,
-        _opts: GethDebugTracingOptions,
+        request: CallRequest,
+        block_number: Option<BlockId>,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<GethTraceFrame> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self.trace_call(request, block_number, opts.unwrap_or_default()).await?)
     }
 }
 
-impl<Eth> std::fmt::Debug for DebugApi<Eth> {
+impl<Client, Eth> std::fmt::Debug for DebugApi<Client, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
     }
 }
+
+/// Executes the [Env] with the tracer that is configured in the [GethDebugTracingOptions] and
+/// returns the resulting trace together with the changed state.
+///
+/// Javascript tracers run with the given limits, their timeout can be lowered by the options.
+///
+/// Does not commit any changes to the database.
+fn trace_env<S>(
+    opts: GethDebugTracingOptions,
+    env: Env,
+    db: &mut SubState<S>,
+    tx_ctx: TransactionContext,
+    js_tracer_limits: JsTracerLimits,
+) -> EthResult<(GethTraceFrame, EvmState)>
+where
+    S: StateProvider,
+{
+    let Some(tracer) = opts.tracer.clone() else {
+        // default structlog tracer
+        let mut inspector = TracingInspector::default().with_steps_recording();
+        let (res, _) = inspect(&mut *db, env, &mut inspector)?;
+        let gas_used = res.result.gas_used();
+        let frame = inspector.finalize().geth_traces(U256::from(gas_used), opts);
+        return Ok((frame.into(), res.state))
+    };
+
+    match tracer {
+        GethDebugTracerType::BuiltInTracer(tracer) => match tracer {
+            GethDebugBuiltInTracerType::CallTracer => {
+                let config = opts
+                    .tracer_config
+                    .into_call_config()
+                    .map_err(|err| EthApiError::InvalidTracerConfig(err.to_string()))?;
+
+                let gas_limit = env.tx.gas_limit;
+                let mut inspector = TracingInspector::default();
+                let (res, _) = inspect(&mut *db, env, &mut inspector)?;
+
+                let mut frame = inspector.finalize().geth_call_traces(config);
+                // the top level frame reports the gas of the transaction
+                frame.gas = U256::from(gas_limit);
+                frame.gas_used = U256::from(res.result.gas_used());
+                fill_revert_reasons(&mut frame);
+
+                Ok((frame.into(), res.state))
+            }
+            GethDebugBuiltInTracerType::PreStateTracer => {
+                let config = opts
+                    .tracer_config
+                    .into_pre_state_config()
+                    .map_err(|err| EthApiError::InvalidTracerConfig(err.to_string()))?;
+
+                let (res, _) = transact(&mut *db, env)?;
+                let frame = pre_state_frame(&res.state, db, config.is_diff_mode())?;
+
+                Ok((frame.into(), res.state))
+            }
+        },
+        GethDebugTracerType::JsTracer(code) => {
+            let mut limits = js_tracer_limits;
+            if let Some(timeout) = opts.timeout.as_deref() {
+                let timeout = parse_duration(timeout).ok_or_else(|| {
+                    EthApiError::InvalidTracerConfig(format!("invalid timeout {timeout}"))
+                })?;
+                limits.timeout = limits.timeout.min(timeout);
+            }
+            let precompiles = get_precompiles(&env.cfg.spec_id);
+            let mut inspector = JsInspector::with_limits(code, opts.tracer_config.0, limits)?
+                .with_transaction_context(tx_ctx)
+                .with_precompiles(precompiles)?;
+
+            let (res, env) = inspect(&mut *db, env, &mut inspector)?;
+            let state = res.state.clone();
+            let result = inspector.json_result(res, &env, db)?;
+
+            Ok((result.into(), state))
+        }
+    }
+}
+
+/// Parses a duration in the format of go's `time.ParseDuration`, e.g. `5s` or `1m30s`.
+///
+/// Units other than `ns`, `us`, `ms`, `s`, `m` and `h` are not supported.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let mut rest = s.trim();
+    if rest.is_empty() {
+        return None
+    }
+
+    let mut total = Duration::ZERO;
+    while !rest.is_empty() {
+        let num_len = rest.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        let (num, tail) = rest.split_at(num_len);
+        let num: f64 = num.parse().ok()?;
+        let unit_len = tail.find(|c: char| c.is_ascii_digit() || c == '.').unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let secs = match unit {
+            "ns" => num / 1e9,
+            "us" | "µs" => num / 1e6,
+            "ms" => num / 1e3,
+            "s" => num,
+            "m" => num * 60.0,
+            "h" => num * 3600.0,
+            _ => return None,
+        };
+        total += Duration::try_from_secs_f64(secs).ok()?;
+        rest = tail;
+    }
+    Some(total)
+}
+
+/// Decodes the revert reason of all reverted calls.
+fn fill_revert_reasons(frame: &mut CallFrame) {
+    if frame.error.as_deref() == Some("execution reverted") {
+        frame.revert_reason = frame.output.as_ref().and_then(decode_revert_reason);
+    }
+    frame.calls.iter_mut().for_each(fill_revert_reasons);
+}
+
+/// Returns the geth prestate of all accounts touched by the transaction by comparing the changes
+/// with their state in the database.
+///
+/// In diff mode only the state of the modified accounts before and after the transaction is
+/// returned.
+///
+/// This expects that the changes have not been committed to the database yet.
+fn pre_state_frame<S>(
+    changes: &EvmState,
+    db: &mut SubState<S>,
+    diff_mode: bool,
+) -> EthResult<PreStateFrame>
+where
+    S: StateProvider,
+{
+    let mut pre = BTreeMap::new();
+    let mut post = BTreeMap::new();
+
+    for (address, account) in changes {
+        let pre_info = db.basic(*address)?.filter(|info| !is_empty_account(info));
+        let post_info =
+            Some(&account.info).filter(|info| !account.is_destroyed && !is_empty_account(info));
+
+        if pre_info.is_none() && post_info.is_none() {
+            // account was only touched
+            continue
+        }
+
+        let pre_code = match &pre_info {
+            Some(info) => account_code(info, db)?,
+            None => Bytes::default(),
+        };
+
+        if !diff_mode {
+            let storage = account
+                .storage
+                .iter()
+                .map(|(slot, value)| {
+                    (H256(slot.to_be_bytes()), H256(value.original_value().to_be_bytes()))
+                })
+                .collect();
+            let info = pre_info.unwrap_or_default();
+            pre.insert(*address, account_state(&info, pre_code, storage));
+            continue
+        }
+
+        match (pre_info, post_info) {
+            (None, Some(post_info)) => {
+                // created accounts are only part of the post state
+                let post_code = account_code(post_info, db)?;
+                let storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, value)| value.present_value() != U256::ZERO)
+                    .map(|(slot, value)| {
+                        (H256(slot.to_be_bytes()), H256(value.present_value().to_be_bytes()))
+                    })
+                    .collect();
+                post.insert(*address, account_state(post_info, post_code, storage));
+            }
+            (Some(pre_info), None) => {
+                // destroyed accounts are only part of the pre state
+                let storage = account
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| {
+                        (H256(slot.to_be_bytes()), H256(value.original_value().to_be_bytes()))
+                    })
+                    .collect();
+                pre.insert(*address, account_state(&pre_info, pre_code, storage));
+            }
+            (Some(pre_info), Some(post_info)) => {
+                let mut pre_storage = BTreeMap::new();
+                let mut post_storage = BTreeMap::new();
+                for (slot, value) in account.storage.iter() {
+                    let original = value.original_value();
+                    let present = value.present_value();
+                    if original == present {
+                        continue
+                    }
+                    let slot = H256(slot.to_be_bytes());
+                    pre_storage.insert(slot, H256(original.to_be_bytes()));
+                    if present != U256::ZERO {
+                        post_storage.insert(slot, H256(present.to_be_bytes()));
+                    }
+                }
+
+                let post_state = AccountState {
+                    balance: (pre_info.balance != post_info.balance).then_some(post_info.balance),
+                    nonce: (pre_info.nonce != post_info.nonce).then_some(post_info.nonce),
+                    code: if pre_info.code_hash != post_info.code_hash {
+                        Some(account_code(post_info, db)?)
+                    } else {
+                        None
+                    },
+                    storage: (!post_storage.is_empty()).then_some(post_storage),
+                };
+
+                if post_state == AccountState::default() && pre_storage.is_empty() {
+                    // account was not modified
+                    continue
+                }
+
+                pre.insert(*address, account_state(&pre_info, pre_code, pre_storage));
+                post.insert(*address, post_state);
+            }
+            (None, None) => unreachable!("untouched accounts are skipped"),
+        }
+    }
+
+    if diff_mode {
+        Ok(PreStateFrame::Diff(DiffMode { pre, post }))
+    } else {
+        Ok(PreStateFrame::Default(PreStateMode(pre)))
+    }
+}
+
+/// Returns the geth [AccountState] of the account, omitting empty fields.
+fn account_state(info: &AccountInfo, code: Bytes, storage: BTreeMap<H256, H256>) -> AccountState {
+    AccountState {
+        balance: Some(info.balance),
+        nonce: (info.nonce != 0).then_some(info.nonce),
+        code: (!code.is_empty()).then_some(code),
+        storage: (!storage.is_empty()).then_some(storage),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
+        EthApi, TracingCallGuard,
+    };
+    use reth_interfaces::test_utils::generators::sign_message;
+    use reth_network_api::test_utils::NoopNetwork;
+    use reth_primitives::{
+        Address, Header, Log, Receipt, Transaction, TransactionKind, TransactionSigned, TxEip1559,
+        TxLegacy, TxType,
+    };
+    use reth_provider::{test_utils::MockEthProvider, BadBlocks};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    type TestDebugApi = DebugApi<MockEthProvider, EthApi<MockEthProvider, TestPool, NoopNetwork>>;
+
+    fn debug_api(provider: MockEthProvider) -> TestDebugApi {
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork,
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache.clone()),
+        );
+        DebugApi::new(provider, eth, cache, BadBlocks::default(), TracingCallGuard::new(1))
+    }
+
+    fn sign(transaction: Transaction) -> TransactionSigned {
+        let signature =
+            sign_message(H256::from_low_u64_be(1), transaction.signature_hash()).unwrap();
+        TransactionSigned::from_transaction_and_signature(transaction, signature)
+    }
+
+    /// Returns a block with a legacy and an EIP-1559 transaction, and its hash.
+    fn block() -> (H256, Block) {
+        let legacy = sign(Transaction::Legacy(TxLegacy {
+            chain_id: Some(1),
+            nonce: 0,
+            gas_price: 10,
+            gas_limit: 21_000,
+            to: TransactionKind::Call(Address::random()),
+            ..Default::default()
+        }));
+        let eip1559 = sign(Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            nonce: 1,
+            gas_limit: 50_000,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 1,
+            to: TransactionKind::Call(Address::random()),
+            ..Default::default()
+        }));
+        let header = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            gas_used: 71_000,
+            base_fee_per_gas: Some(7),
+            ..Default::default()
+        };
+        (header.hash_slow(), Block { header, body: vec![legacy, eip1559], ..Default::default() })
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("5s"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_duration("1m30s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("300ms"), Some(Duration::from_millis(300)));
+        assert_eq!(parse_duration("1.5h"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("5"), None);
+        assert_eq!(parse_duration("5d"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn test_account_state_omits_empty_fields() {
+        let info = AccountInfo { balance: U256::from(1), ..Default::default() };
+        let state = account_state(&info, Bytes::default(), BTreeMap::new());
+        assert_eq!(
+            state,
+            AccountState { balance: Some(U256::from(1)), code: None, nonce: None, storage: None }
+        );
+    }
+
+    #[tokio::test]
+    async fn raw_header_and_block_roundtrip() {
+        let provider = MockEthProvider::default();
+        let (hash, block) = block();
+        provider.add_block(hash, block.clone());
+        let api = debug_api(provider);
+
+        for id in [BlockId::from(hash), BlockId::from(BlockNumberOrTag::Number(1))] {
+            let raw = DebugApiServer::raw_header(&api, id).await.unwrap();
+            assert_eq!(Header::decode(&mut raw.as_ref()).unwrap(), block.header);
+
+            let raw = DebugApiServer::raw_block(&api, id).await.unwrap();
+            assert_eq!(Block::decode(&mut raw.as_ref()).unwrap(), block);
+        }
+
+        let unknown = BlockId::from(BlockNumberOrTag::Number(2));
+        assert!(DebugApiServer::raw_header(&api, unknown).await.is_err());
+        assert!(DebugApiServer::raw_block(&api, unknown).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn raw_transaction_is_enveloped() {
+        let provider = MockEthProvider::default();
+        let (hash, block) = block();
+        provider.add_block(hash, block.clone());
+        let api = debug_api(provider);
+
+        for tx in &block.body {
+            let raw = DebugApiServer::raw_transaction(&api, tx.hash).await.unwrap();
+            assert_eq!(raw, Bytes::from(tx.envelope_encoded()));
+            assert_eq!(&TransactionSigned::decode_enveloped(raw).unwrap(), tx);
+        }
+        // legacy transactions are a list, typed transactions start with their type
+        let raw = DebugApiServer::raw_transaction(&api, block.body[0].hash).await.unwrap();
+        assert!(raw[0] >= 0xc0);
+        let raw = DebugApiServer::raw_transaction(&api, block.body[1].hash).await.unwrap();
+        assert_eq!(raw[0], 0x02);
+
+        let unknown = DebugApiServer::raw_transaction(&api, H256::random()).await.unwrap();
+        assert!(unknown.is_empty());
+    }
+
+    #[tokio::test]
+    async fn raw_receipts_are_typed() {
+        let provider = MockEthProvider::default();
+        let (hash, block) = block();
+        let log = Log {
+            address: Address::random(),
+            topics: vec![H256::random()],
+            data: Default::default(),
+        };
+        let receipts = vec![
+            Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: 21_000,
+                bloom: Default::default(),
+                logs: vec![log.clone()],
+            },
+            Receipt {
+                tx_type: TxType::EIP1559,
+                success: false,
+                cumulative_gas_used: 71_000,
+                bloom: Default::default(),
+                logs: vec![],
+            },
+        ];
+        provider.add_block(hash, block);
+        provider.add_receipts(hash, receipts.clone());
+        let api = debug_api(provider);
+
+        let raw = DebugApiServer::raw_receipts(&api, hash.into()).await.unwrap();
+        assert_eq!(raw.len(), 2);
+        // the legacy receipt is the plain rlp list, the typed receipt is `type || rlp(receipt)`
+        // without a string header
+        assert!(raw[0][0] >= 0xc0);
+        assert_eq!(raw[1][0], 0x02);
+        for (raw, receipt) in raw.iter().zip(&receipts) {
+            assert_eq!(&Receipt::decode(&mut raw.as_ref()).unwrap(), receipt);
+        }
+
+        let unknown = BlockId::from(BlockNumberOrTag::Number(2));
+        assert!(DebugApiServer::raw_receipts(&api, unknown).await.is_err());
+    }
+}
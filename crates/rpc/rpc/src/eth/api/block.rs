@@ -4,45 +4,60 @@ use crate::{
     eth::error::{EthApiError, EthResult},
     EthApi,
 };
-use reth_primitives::BlockId;
+use reth_primitives::{BlockId, BlockNumberOrTag};
 use reth_provider::{BlockProvider, EvmEnvProvider, StateProviderFactory};
 use reth_rpc_types::{Block, Index, RichBlock};
+use reth_transaction_pool::TransactionPool;
 
 impl<Client, Pool, Network> EthApi<Client, Pool, Network>
 where
-    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + Clone + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Returns the uncle headers of the given block
     ///
     /// Returns an empty vec if there are none.
-    pub(crate) fn ommers(
+    pub(crate) async fn ommers(
         &self,
         block_id: impl Into<BlockId>,
     ) -> EthResult<Option<Vec<reth_primitives::Header>>> {
         let block_id = block_id.into();
-        Ok(self.client().ommers(block_id)?)
+
+        if matches!(block_id, BlockId::Number(BlockNumberOrTag::Pending)) {
+            return Ok(Some(self.pending_block().await?.block.ommers.clone()))
+        }
+
+        let block_hash = match self.client().block_hash_for_id(block_id)? {
+            Some(block_hash) => block_hash,
+            None => return Ok(None),
+        };
+        Ok(self.cache().get_block(block_hash).await?.map(|block| block.ommers))
     }
 
+    /// Returns the uncle at the given index of the given block, formatted as an uncle block
+    /// without transactions.
     pub(crate) async fn ommer_by_block_and_index(
         &self,
         block_id: impl Into<BlockId>,
         index: Index,
     ) -> EthResult<Option<RichBlock>> {
-        let block_id = block_id.into();
-        let index = usize::from(index);
-        let uncles = self.client().ommers(block_id)?.unwrap_or_default();
+        let uncles = self.ommers(block_id).await?.unwrap_or_default();
         let uncle = uncles
             .into_iter()
-            .nth(index)
+            .nth(usize::from(index))
             .map(|header| Block::uncle_block_from_header(header).into());
         Ok(uncle)
     }
+
     pub(crate) async fn block_transaction_count(
         &self,
         block_id: impl Into<BlockId>,
     ) -> EthResult<Option<usize>> {
         let block_id = block_id.into();
-        // TODO support pending block
+
+        if matches!(block_id, BlockId::Number(BlockNumberOrTag::Pending)) {
+            return Ok(Some(self.pending_block().await?.block.body.len()))
+        }
 
         if let Some(txs) = self.client().transactions_by_block(block_id)? {
             Ok(Some(txs.len()))
@@ -57,7 +72,17 @@ where
         full: bool,
     ) -> EthResult<Option<RichBlock>> {
         let block_id = block_id.into();
-        // TODO support pending block
+
+        if matches!(block_id, BlockId::Number(BlockNumberOrTag::Pending)) {
+            let pending = self.pending_block().await?;
+            let block = Block::from_block(
+                pending.block.clone(),
+                pending.total_difficulty,
+                full.into(),
+                None,
+            )?;
+            return Ok(Some(block.into()))
+        }
 
         if let Some(block) = self.client().block(block_id)? {
             let block_hash = self
@@ -0,0 +1,144 @@
+//! Contains RPC handler implementations for fee history and gas price estimation.
+
+use crate::eth::{
+    api::EthApi,
+    error::{EthApiError, EthResult},
+};
+use reth_primitives::{Header, Receipt, TransactionSigned, U256};
+use reth_provider::{BlockProvider, EvmEnvProvider, StateProviderFactory};
+use reth_transaction_pool::TransactionPool;
+
+impl<Client, Pool, Network> EthApi<Client, Pool, Network>
+where
+    Pool: TransactionPool + 'static,
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + 'static,
+{
+    /// Returns a suggestion for a gas price for legacy transactions.
+    ///
+    /// This is the suggested priority fee plus the base fee of the latest block.
+    ///
+    /// See also: <https://github.com/ethereum/pm/issues/328#issuecomment-853234014>
+    pub(crate) async fn gas_price(&self) -> EthResult<U256> {
+        let chain_info = self.client().chain_info()?;
+        let base_fee = self
+            .client()
+            .header_by_number(chain_info.best_number)?
+            .and_then(|header| header.base_fee_per_gas)
+            .unwrap_or_default();
+        let suggested_tip = self.suggested_priority_fee().await?;
+        Ok(suggested_tip + U256::from(base_fee))
+    }
+
+    /// Returns a suggestion for the priority fee (the tip)
+    pub(crate) async fn suggested_priority_fee(&self) -> EthResult<U256> {
+        self.gas_oracle().suggest_tip_cap().await
+    }
+
+    /// Returns the `reward` percentiles of `eth_feeHistory` for the given block.
+    ///
+    /// The block and its receipts are read through the [EthStateCache](crate::eth::cache).
+    pub(crate) async fn fee_history_rewards(
+        &self,
+        block_number: u64,
+        percentiles: &[f64],
+    ) -> EthResult<Vec<U256>> {
+        let block_hash = self
+            .client()
+            .block_hash(U256::from(block_number))?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let block =
+            self.cache().get_block(block_hash).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let receipts =
+            self.cache().get_receipts(block_hash).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+
+        Ok(calculate_reward_percentiles(percentiles, &block.header, &block.body, &receipts))
+    }
+}
+
+/// Calculates the reward percentiles of a block's transactions.
+///
+/// The transactions are sorted by their effective tip, the reward of a percentile is the tip of
+/// the transaction at which the cumulative gas used of the sorted transactions reaches the
+/// percentile of the block's gas used. This mirrors geth's fee history.
+///
+/// Returns zeroes if the block has no transactions.
+fn calculate_reward_percentiles(
+    percentiles: &[f64],
+    header: &Header,
+    transactions: &[TransactionSigned],
+    receipts: &[Receipt],
+) -> Vec<U256> {
+    // the receipts only track the cumulative gas used of the block
+    let mut transactions = transactions
+        .iter()
+        .zip(receipts)
+        .scan(0u64, |previous_gas, (tx, receipt)| {
+            let gas_used = receipt.cumulative_gas_used - *previous_gas;
+            *previous_gas = receipt.cumulative_gas_used;
+            let reward = tx.effective_tip_per_gas(header.base_fee_per_gas).unwrap_or_default();
+            Some((gas_used, reward))
+        })
+        .collect::<Vec<_>>();
+
+    if transactions.is_empty() {
+        return vec![U256::ZERO; percentiles.len()]
+    }
+
+    transactions.sort_by_key(|(_, reward)| *reward);
+
+    let mut rewards = Vec::with_capacity(percentiles.len());
+    let mut tx_index = 0;
+    let mut cumulative_gas_used = transactions[0].0;
+    for percentile in percentiles {
+        let threshold_gas_used = (header.gas_used as f64 * percentile / 100.) as u64;
+        while cumulative_gas_used < threshold_gas_used && tx_index < transactions.len() - 1 {
+            tx_index += 1;
+            cumulative_gas_used += transactions[tx_index].0;
+        }
+        rewards.push(U256::from(transactions[tx_index].1));
+    }
+
+    rewards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Transaction, TxLegacy};
+
+    fn legacy_tx(gas_price: u128) -> TransactionSigned {
+        TransactionSigned {
+            transaction: Transaction::Legacy(TxLegacy { gas_price, ..Default::default() }),
+            ..Default::default()
+        }
+    }
+
+    fn receipt(cumulative_gas_used: u64) -> Receipt {
+        Receipt { cumulative_gas_used, ..Default::default() }
+    }
+
+    #[test]
+    fn reward_percentiles_of_empty_block() {
+        let rewards = calculate_reward_percentiles(&[10., 50., 90.], &Header::default(), &[], &[]);
+        assert_eq!(rewards, vec![U256::ZERO; 3]);
+    }
+
+    #[test]
+    fn reward_percentiles_weighted_by_gas() {
+        let header = Header { gas_used: 100_000, base_fee_per_gas: Some(10), ..Default::default() };
+        // tips: 20, 5, 1 with gas used: 50_000, 30_000, 20_000
+        let transactions = vec![legacy_tx(30), legacy_tx(15), legacy_tx(11)];
+        let receipts = vec![receipt(50_000), receipt(80_000), receipt(100_000)];
+
+        let rewards = calculate_reward_percentiles(
+            &[0., 20., 30., 50., 100.],
+            &header,
+            &transactions,
+            &receipts,
+        );
+        assert_eq!(
+            rewards,
+            vec![U256::from(1), U256::from(1), U256::from(5), U256::from(5), U256::from(20)]
+        );
+    }
+}
@@ -3,25 +3,34 @@
 //! The entire implementation of the namespace is quite large, hence it is divided across several
 //! files.
 
-use crate::eth::signer::EthSigner;
 use async_trait::async_trait;
 use reth_interfaces::Result;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
     Address, BlockId, BlockNumberOrTag, ChainInfo, TransactionSigned, H256, U64,
 };
-use reth_provider::{BlockProvider, EvmEnvProvider, StateProviderFactory};
+use reth_provider::{BlockProvider, EvmEnvProvider, StateProvider, StateProviderFactory};
 use std::num::NonZeroUsize;
 
-use crate::eth::{cache::EthStateCache, error::EthResult};
-use reth_provider::providers::ChainState;
+use crate::eth::{
+    cache::EthStateCache,
+    error::{EthApiError, EthResult},
+    gas_oracle::GasPriceOracle,
+    signer::{EthSigner, SignError},
+};
+use parking_lot::RwLock;
+use pending_block::{PendingBlock, PendingStateProvider};
 use reth_rpc_types::FeeHistoryCache;
 use reth_transaction_pool::TransactionPool;
 use std::sync::Arc;
+use tokio::sync::Mutex;
 
 mod block;
 mod call;
+mod fees;
+mod pending_block;
 mod server;
+mod sign;
 mod state;
 mod transactions;
 
@@ -66,8 +75,22 @@ pub struct EthApi<Client, Pool, Network> {
 
 impl<Client, Pool, Network> EthApi<Client, Pool, Network> {
     /// Creates a new, shareable instance.
-    pub fn new(client: Client, pool: Pool, network: Network, eth_cache: EthStateCache) -> Self {
-        let inner = EthApiInner { client, pool, network, signers: Default::default(), eth_cache };
+    pub fn new(
+        client: Client,
+        pool: Pool,
+        network: Network,
+        eth_cache: EthStateCache,
+        gas_oracle: GasPriceOracle<Client>,
+    ) -> Self {
+        let inner = EthApiInner {
+            client,
+            pool,
+            network,
+            signers: Default::default(),
+            eth_cache,
+            gas_oracle,
+            pending_block: Default::default(),
+        };
         Self {
             inner: Arc::new(inner),
             fee_history_cache: FeeHistoryCache::new(
@@ -81,6 +104,11 @@ impl<Client, Pool, Network> EthApi<Client, Pool, Network> {
         &self.inner.eth_cache
     }
 
+    /// Returns the gas oracle frontend
+    pub(crate) fn gas_oracle(&self) -> &GasPriceOracle<Client> {
+        &self.inner.gas_oracle
+    }
+
     /// Returns the inner `Client`
     pub(crate) fn client(&self) -> &Client {
         &self.inner.client
@@ -95,36 +123,55 @@ impl<Client, Pool, Network> EthApi<Client, Pool, Network> {
     pub(crate) fn pool(&self) -> &Pool {
         &self.inner.pool
     }
+
+    /// Adds a signer whose accounts can be used by the `eth_sign*` and `eth_sendTransaction`
+    /// endpoints.
+    pub fn add_signer(&self, signer: Arc<dyn EthSigner>) {
+        self.inner.signers.write().push(signer);
+    }
+
+    /// Returns the signer for the given account.
+    pub(crate) fn find_signer(
+        &self,
+        account: &Address,
+    ) -> std::result::Result<Arc<dyn EthSigner>, SignError> {
+        self.inner
+            .signers
+            .read()
+            .iter()
+            .find(|signer| signer.is_signer_for(account))
+            .cloned()
+            .ok_or(SignError::NoAccount)
+    }
 }
 
 // === State access helpers ===
 
 impl<Client, Pool, Network> EthApi<Client, Pool, Network>
 where
-    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + Clone + 'static,
+    Pool: TransactionPool + 'static,
 {
     fn convert_block_number(&self, num: BlockNumberOrTag) -> Result<Option<u64>> {
         self.client().convert_block_number(num)
     }
 
-    /// Helper function to execute a closure with the database at a specific block.
-    pub(crate) fn with_state_at<F, T>(&self, _at: BlockId, _f: F) -> EthResult<T>
+    /// Helper function to execute a closure with the state at a specific block.
+    ///
+    /// The state of the [BlockNumberOrTag::Pending] block is the latest state with the changes
+    /// of the pending block applied on top.
+    pub(crate) async fn with_state_at<F, T>(&self, at: BlockId, f: F) -> EthResult<T>
     where
-        F: FnOnce(ChainState<'_>) -> T,
+        F: FnOnce(&dyn StateProvider) -> EthResult<T>,
     {
-        unimplemented!()
-    }
-
-    /// Returns the state at the given [BlockId] enum or the latest.
-    pub(crate) fn state_at_block_id_or_latest(
-        &self,
-        block_id: Option<BlockId>,
-    ) -> Result<Option<<Client as StateProviderFactory>::HistorySP<'_>>> {
-        if let Some(block_id) = block_id {
-            self.state_at_block_id(block_id)
-        } else {
-            self.latest_state()
+        if matches!(at, BlockId::Number(BlockNumberOrTag::Pending)) {
+            let pending = self.pending_block().await?;
+            let latest = self.client().latest()?;
+            return f(&PendingStateProvider::new(&pending.state, latest))
         }
+
+        let state = self.state_at_block_id(at)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        f(&state)
     }
 
     /// Returns the state at the given [BlockId] enum.
@@ -167,13 +214,6 @@ where
     ) -> Result<<Client as StateProviderFactory>::HistorySP<'_>> {
         self.client().history_by_block_number(block_number)
     }
-
-    /// Returns the _latest_ state
-    pub(crate) fn latest_state(
-        &self,
-    ) -> Result<Option<<Client as StateProviderFactory>::HistorySP<'_>>> {
-        self.state_at_block_number(BlockNumberOrTag::Latest)
-    }
 }
 
 impl<Client, Pool, Events> std::fmt::Debug for EthApi<Client, Pool, Events> {
@@ -208,7 +248,7 @@ where
     }
 
     fn accounts(&self) -> Vec<Address> {
-        self.inner.signers.iter().flat_map(|s| s.accounts()).collect()
+        self.inner.signers.read().iter().flat_map(|s| s.accounts()).collect()
     }
 
     async fn transaction_by_hash(&self, hash: H256) -> Result<Option<TransactionSigned>> {
@@ -225,7 +265,11 @@ struct EthApiInner<Client, Pool, Network> {
     /// An interface to interact with the network
     network: Network,
     /// All configured Signers
-    signers: Vec<Box<dyn EthSigner>>,
+    signers: RwLock<Vec<Arc<dyn EthSigner>>>,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
+    /// The gas oracle used to suggest gas prices
+    gas_oracle: GasPriceOracle<Client>,
+    /// The most recently built pending block
+    pending_block: Mutex<Option<Arc<PendingBlock>>>,
 }
@@ -0,0 +1,392 @@
+//! Support for the `pending` block tag.
+
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult},
+        revm_utils::transact,
+    },
+    EthApi,
+};
+use reth_interfaces::Result;
+use reth_primitives::{
+    bloom::logs_bloom,
+    constants::MAX_BLOB_GAS_PER_BLOCK,
+    proofs::{self, EMPTY_LIST_HASH, EMPTY_ROOT},
+    Account, Address, Block, Bloom, Bytecode, Header, IntoRecoveredTransaction, Log, Receipt,
+    SealedHeader, StorageKey, StorageValue, TxHash, H256, U256,
+};
+use reth_provider::{
+    trie::AccountProof, AccountProvider, BlockHashProvider, BlockProvider, EvmEnvProvider,
+    ProviderError, StateProvider, StateProviderFactory,
+};
+use reth_revm::{
+    database::{State, SubState},
+    env::fill_tx_env,
+    into_reth_log, to_reth_acc,
+};
+use reth_transaction_pool::{BestTransactions, TransactionPool};
+use revm::{
+    db::{AccountState, CacheDB},
+    primitives::{BlockEnv, CfgEnv, Env},
+    DatabaseCommit,
+};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::trace;
+
+/// The pending block: the best transactions of the pool executed on top of the latest block.
+#[derive(Debug)]
+pub(crate) struct PendingBlock {
+    /// Hash of the latest block the pending block was built on.
+    parent_hash: H256,
+    /// The sorted hashes of all pooled transactions at the time the block was built.
+    pool_hashes: Vec<TxHash>,
+    /// The evm config of the pending block.
+    pub(crate) cfg: CfgEnv,
+    /// The evm block env of the pending block.
+    pub(crate) block_env: BlockEnv,
+    /// The pending block.
+    ///
+    /// NOTE: The state root of the pending block is not calculated and left empty.
+    pub(crate) block: Block,
+    /// The total difficulty of the pending block.
+    pub(crate) total_difficulty: U256,
+    /// The state changes of the pending block on top of the latest state.
+    pub(crate) state: PendingState,
+}
+
+impl PendingBlock {
+    /// Builds the pending block on top of the `latest` block.
+    ///
+    /// Transactions are executed in the order of [TransactionPool::best_transactions] against the
+    /// `state` of the latest block until the block is full. Transactions that fail to execute are
+    /// skipped together with their descendants.
+    fn build<SP, Pool>(
+        state: SP,
+        pool: &Pool,
+        latest: SealedHeader,
+        latest_td: U256,
+        cfg: CfgEnv,
+        mut block_env: BlockEnv,
+        pool_hashes: Vec<TxHash>,
+    ) -> EthResult<Self>
+    where
+        SP: StateProvider,
+        Pool: TransactionPool,
+    {
+        let parent_hash = latest.hash();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let mut header = Header {
+            parent_hash,
+            ommers_hash: EMPTY_LIST_HASH,
+            beneficiary: latest.beneficiary,
+            // Withdrawals of the pending block are not known, but the header needs a root from
+            // Shanghai on.
+            withdrawals_root: latest.withdrawals_root.map(|_| EMPTY_ROOT),
+            difficulty: latest.difficulty,
+            number: latest.number + 1,
+            gas_limit: latest.gas_limit,
+            timestamp: now.max(latest.timestamp + 1),
+            mix_hash: latest.mix_hash,
+            base_fee_per_gas: latest.next_block_base_fee(),
+            // Like the withdrawals root, the blob gas fields are carried over from Cancun on.
+            excess_blob_gas: latest.next_block_excess_blob_gas(),
+            ..Default::default()
+        };
+        let blob_gasprice = header.blob_gasprice();
+
+        // The env of the latest block only needs its block specific settings updated.
+        block_env.number = U256::from(header.number);
+        block_env.timestamp = U256::from(header.timestamp);
+        block_env.basefee = U256::from(header.base_fee_per_gas.unwrap_or_default());
+
+        let mut db = SubState::new(State::new(state));
+        let mut env = Env { cfg: cfg.clone(), block: block_env.clone(), ..Default::default() };
+
+        let mut cumulative_gas_used = 0;
+        let mut cumulative_blob_gas_used = 0;
+        let mut body = Vec::new();
+        let mut receipts = Vec::new();
+        let mut best_txs = pool.best_transactions();
+        while let Some(pool_tx) = best_txs.next() {
+            // Skip the transaction and its descendants if it does not fit into the block.
+            if cumulative_gas_used + pool_tx.gas_limit() > header.gas_limit {
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+
+            let transaction = pool_tx.transaction.to_recovered_transaction();
+
+            // Skip blob transactions before Cancun, if they don't pay the blob gas price or if
+            // their blobs don't fit into the block.
+            if let Some(max_fee_per_blob_gas) = transaction.max_fee_per_blob_gas() {
+                let fits = blob_gasprice.map_or(false, |blob_gasprice| {
+                    max_fee_per_blob_gas >= blob_gasprice &&
+                        cumulative_blob_gas_used + transaction.blob_gas() <=
+                            MAX_BLOB_GAS_PER_BLOCK
+                });
+                if !fits {
+                    best_txs.mark_invalid(&pool_tx);
+                    continue
+                }
+            }
+
+            fill_tx_env(&mut env.tx, &transaction, transaction.signer());
+            let result = match transact(&mut db, env.clone()) {
+                Ok((res, _)) => res,
+                Err(error) => {
+                    trace!(target: "rpc::eth", ?error, hash = ?transaction.hash(), "Skipping invalid pending transaction");
+                    best_txs.mark_invalid(&pool_tx);
+                    continue
+                }
+            };
+            db.commit(result.state);
+
+            cumulative_gas_used += result.result.gas_used();
+            cumulative_blob_gas_used += transaction.blob_gas();
+            let logs: Vec<Log> = result.result.logs().into_iter().map(into_reth_log).collect();
+            receipts.push(Receipt {
+                tx_type: transaction.tx_type(),
+                success: result.result.is_success(),
+                cumulative_gas_used,
+                bloom: logs_bloom(logs.iter()),
+                logs,
+            });
+            body.push(transaction.into_signed());
+        }
+
+        header.gas_used = cumulative_gas_used;
+        header.blob_gas_used = header.excess_blob_gas.map(|_| cumulative_blob_gas_used);
+        header.receipts_root = proofs::calculate_receipt_root(receipts.iter());
+        header.logs_bloom =
+            receipts.iter().fold(Bloom::zero(), |bloom, receipt| bloom | receipt.bloom);
+        header.transactions_root = proofs::calculate_transaction_root(body.iter());
+
+        let total_difficulty = latest_td + header.difficulty;
+        let withdrawals = header.withdrawals_root.map(|_| Vec::new());
+        Ok(Self {
+            parent_hash,
+            pool_hashes,
+            cfg,
+            block_env,
+            block: Block { header, body, ommers: Vec::new(), withdrawals },
+            total_difficulty,
+            state: PendingState::from_cache_db(&db),
+        })
+    }
+}
+
+/// The state changes of the pending block.
+#[derive(Debug, Default)]
+pub(crate) struct PendingState {
+    /// Changed accounts, `None` if the account does not exist.
+    accounts: HashMap<Address, Option<Account>>,
+    /// Changed storage of accounts.
+    storages: HashMap<Address, PendingStorage>,
+    /// Bytecodes loaded or created by the pending block.
+    bytecodes: HashMap<H256, Bytecode>,
+}
+
+/// Changed storage of an account.
+#[derive(Debug, Default)]
+struct PendingStorage {
+    /// Whether the storage was wiped, in which case slots that are not in `slots` are zero.
+    wiped: bool,
+    /// Changed storage slots.
+    slots: HashMap<U256, U256>,
+}
+
+impl PendingState {
+    /// Collects the changes that were committed to the [CacheDB].
+    fn from_cache_db<DB>(db: &CacheDB<DB>) -> Self {
+        let mut state = Self::default();
+        for (address, account) in db.accounts.iter() {
+            let (info, wiped) = match account.account_state {
+                // only loaded, not changed
+                AccountState::None => continue,
+                AccountState::NotExisting => (None, true),
+                AccountState::StorageCleared => (Some(to_reth_acc(&account.info)), true),
+                AccountState::Touched => (Some(to_reth_acc(&account.info)), false),
+            };
+            state.accounts.insert(*address, info);
+            state.storages.insert(
+                *address,
+                PendingStorage {
+                    wiped,
+                    slots: account.storage.iter().map(|(key, value)| (*key, *value)).collect(),
+                },
+            );
+        }
+        state.bytecodes = db
+            .contracts
+            .iter()
+            .map(|(hash, bytecode)| (H256(hash.0), Bytecode(bytecode.clone())))
+            .collect();
+        state
+    }
+
+    /// Returns the changed account, `Some(None)` if it does not exist and `None` if it was not
+    /// touched.
+    fn account(&self, address: &Address) -> Option<Option<Account>> {
+        self.accounts.get(address).copied()
+    }
+
+    /// Returns the changed storage value, `None` if the slot was not touched.
+    fn storage(&self, address: &Address, key: &U256) -> Option<U256> {
+        let storage = self.storages.get(address)?;
+        storage.slots.get(key).copied().or(storage.wiped.then_some(U256::ZERO))
+    }
+}
+
+/// A [StateProvider] that serves the [PendingState] on top of the latest state.
+pub(crate) struct PendingStateProvider<'a, SP> {
+    /// The state changes of the pending block.
+    pending: &'a PendingState,
+    /// The latest state.
+    provider: SP,
+}
+
+impl<'a, SP> PendingStateProvider<'a, SP> {
+    /// Create a new provider for the pending state on top of the latest state.
+    pub(crate) fn new(pending: &'a PendingState, provider: SP) -> Self {
+        Self { pending, provider }
+    }
+}
+
+impl<'a, SP: StateProvider> BlockHashProvider for PendingStateProvider<'a, SP> {
+    fn block_hash(&self, number: U256) -> Result<Option<H256>> {
+        self.provider.block_hash(number)
+    }
+}
+
+impl<'a, SP: StateProvider> AccountProvider for PendingStateProvider<'a, SP> {
+    fn basic_account(&self, address: Address) -> Result<Option<Account>> {
+        if let Some(account) = self.pending.account(&address) {
+            return Ok(account)
+        }
+        self.provider.basic_account(address)
+    }
+}
+
+impl<'a, SP: StateProvider> StateProvider for PendingStateProvider<'a, SP> {
+    fn storage(&self, account: Address, storage_key: StorageKey) -> Result<Option<StorageValue>> {
+        let key = U256::from_be_bytes(storage_key.0);
+        if let Some(value) = self.pending.storage(&account, &key) {
+            return Ok(Some(value))
+        }
+        self.provider.storage(account, storage_key)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: H256) -> Result<Option<Bytecode>> {
+        if let Some(bytecode) = self.pending.bytecodes.get(&code_hash) {
+            return Ok(Some(bytecode.clone()))
+        }
+        self.provider.bytecode_by_hash(code_hash)
+    }
+
+    /// Proofs can not be generated for state that is not committed to the database.
+    fn proof(&self, _address: Address, _keys: &[H256]) -> Result<AccountProof> {
+        Err(ProviderError::StateProofUnsupported.into())
+    }
+}
+
+impl<Client, Pool, Network> EthApi<Client, Pool, Network>
+where
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + Clone + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Returns the pending block.
+    ///
+    /// The pending block is rebuilt on the blocking pool if a new block was imported or the pool
+    /// changed since it was last built, otherwise the cached block is returned.
+    pub(crate) async fn pending_block(&self) -> EthResult<Arc<PendingBlock>> {
+        let chain_info = self.client().chain_info()?;
+        let mut pool_hashes = self.pool().pooled_transaction_hashes();
+        pool_hashes.sort_unstable();
+
+        // hold the lock while building, so concurrent requests wait for the block instead of
+        // building it again
+        let mut cached = self.inner.pending_block.lock().await;
+        if let Some(pending) = cached.as_ref() {
+            if pending.parent_hash == chain_info.best_hash && pending.pool_hashes == pool_hashes {
+                return Ok(Arc::clone(pending))
+            }
+        }
+
+        let client = self.client().clone();
+        let pool = self.pool().clone();
+        let pending = tokio::task::spawn_blocking(move || -> EthResult<PendingBlock> {
+            let latest =
+                client.header(&chain_info.best_hash)?.ok_or(EthApiError::UnknownBlockNumber)?;
+            let latest_td =
+                client.header_td(&chain_info.best_hash)?.ok_or(EthApiError::UnknownBlockNumber)?;
+            let mut cfg = CfgEnv::default();
+            let mut block_env = BlockEnv::default();
+            client.fill_env_with_header(&mut cfg, &mut block_env, &latest)?;
+
+            PendingBlock::build(
+                client.latest()?,
+                &pool,
+                latest.seal(chain_info.best_hash),
+                latest_td,
+                cfg,
+                block_env,
+                pool_hashes,
+            )
+        })
+        .await
+        .map_err(|_| EthApiError::InternalPendingBlockError)??;
+
+        let pending = Arc::new(pending);
+        *cached = Some(Arc::clone(&pending));
+        Ok(pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::NoopProvider;
+    use revm::{db::DbAccount, primitives::AccountInfo};
+
+    #[test]
+    fn pending_state_overrides_latest() {
+        let address = Address::random();
+        let key = U256::from(1);
+        let account = Account { nonce: 2, balance: U256::from(10), bytecode_hash: None };
+
+        let mut db = CacheDB::new(State::new(NoopProvider::default()));
+        db.accounts.insert(
+            address,
+            DbAccount {
+                info: AccountInfo {
+                    nonce: account.nonce,
+                    balance: account.balance,
+                    ..Default::default()
+                },
+                account_state: AccountState::Touched,
+                storage: [(key, U256::from(3))].into_iter().collect(),
+            },
+        );
+        // loaded but not changed
+        let untouched = Address::random();
+        db.accounts.insert(
+            untouched,
+            DbAccount { account_state: AccountState::None, ..Default::default() },
+        );
+
+        let pending = PendingState::from_cache_db(&db);
+        let provider = PendingStateProvider::new(&pending, NoopProvider::default());
+
+        assert_eq!(provider.basic_account(address).unwrap(), Some(account));
+        assert_eq!(
+            provider.storage(address, H256(key.to_be_bytes())).unwrap(),
+            Some(U256::from(3))
+        );
+        assert_eq!(provider.storage(address, H256::random()).unwrap(), None);
+        assert_eq!(pending.account(&untouched), None);
+    }
+}
@@ -7,15 +7,18 @@ use crate::{
     result::{internal_rpc_err, ToRpcResult},
 };
 use jsonrpsee::core::RpcResult as Result;
+use reth_network_api::NetworkInfo;
 use reth_primitives::{
     AccessListWithGasUsed, Address, BlockId, BlockNumberOrTag, Bytes, Header, H256, H64, U256, U64,
 };
-use reth_provider::{BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory};
+use reth_provider::{
+    BlockProvider, EvmEnvProvider, HeaderProvider, ReceiptProvider, StateProviderFactory,
+};
 use reth_rpc_api::EthApiServer;
 use reth_rpc_types::{
-    state::StateOverride, CallRequest, EIP1186AccountProofResponse, FeeHistory,
-    FeeHistoryCacheItem, Index, RichBlock, SyncStatus, TransactionReceipt, TransactionRequest,
-    Work,
+    state::{BlockOverrides, StateOverride},
+    CallRequest, EIP1186AccountProofResponse, FeeHistory, FeeHistoryCacheItem, Index, RichBlock,
+    SyncStatus, TransactionReceipt, TransactionRequest, Work,
 };
 use reth_transaction_pool::TransactionPool;
 use serde_json::Value;
@@ -26,8 +29,14 @@ impl<Client, Pool, Network> EthApiServer for EthApi<Client, Pool, Network>
 where
     Self: EthApiSpec,
     Pool: TransactionPool + 'static,
-    Client: BlockProvider + HeaderProvider + StateProviderFactory + EvmEnvProvider + 'static,
-    Network: 'static,
+    Client: BlockProvider
+        + HeaderProvider
+        + ReceiptProvider
+        + StateProviderFactory
+        + EvmEnvProvider
+        + Clone
+        + 'static,
+    Network: NetworkInfo + 'static,
 {
     /// Handler for: `eth_protocolVersion`
     async fn protocol_version(&self) -> Result<U64> {
@@ -90,12 +99,12 @@ where
 
     /// Handler for: `eth_getUncleCountByBlockHash`
     async fn block_uncles_count_by_hash(&self, hash: H256) -> Result<Option<U256>> {
-        Ok(EthApi::ommers(self, hash)?.map(|ommers| U256::from(ommers.len())))
+        Ok(EthApi::ommers(self, hash).await?.map(|ommers| U256::from(ommers.len())))
     }
 
     /// Handler for: `eth_getUncleCountByBlockNumber`
     async fn block_uncles_count_by_number(&self, number: BlockNumberOrTag) -> Result<Option<U256>> {
-        Ok(EthApi::ommers(self, number)?.map(|ommers| U256::from(ommers.len())))
+        Ok(EthApi::ommers(self, number).await?.map(|ommers| U256::from(ommers.len())))
     }
 
     /// Handler for: `eth_getUncleByBlockHashAndIndex`
@@ -143,13 +152,18 @@ where
     }
 
     /// Handler for: `eth_getTransactionReceipt`
-    async fn transaction_receipt(&self, _hash: H256) -> Result<Option<TransactionReceipt>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>> {
+        Ok(EthApi::transaction_receipt(self, hash).await?)
+    }
+
+    /// Handler for: `eth_getBlockReceipts`
+    async fn block_receipts(&self, block_id: BlockId) -> Result<Option<Vec<TransactionReceipt>>> {
+        Ok(EthApi::block_receipts(self, block_id).await?)
     }
 
     /// Handler for: `eth_getBalance`
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> Result<U256> {
-        Ok(EthApi::balance(self, address, block_number)?)
+        Ok(EthApi::balance(self, address, block_number).await?)
     }
 
     /// Handler for: `eth_getStorageAt`
@@ -159,7 +173,7 @@ where
         index: U256,
         block_number: Option<BlockId>,
     ) -> Result<H256> {
-        Ok(EthApi::storage_at(self, address, index, block_number)?)
+        Ok(EthApi::storage_at(self, address, index, block_number).await?)
     }
 
     /// Handler for: `eth_getTransactionCount`
@@ -168,22 +182,23 @@ where
         address: Address,
         block_number: Option<BlockId>,
     ) -> Result<U256> {
-        Ok(EthApi::get_transaction_count(self, address, block_number)?)
+        Ok(EthApi::get_transaction_count(self, address, block_number).await?)
     }
 
     /// Handler for: `eth_getCode`
     async fn get_code(&self, address: Address, block_number: Option<BlockId>) -> Result<Bytes> {
-        Ok(EthApi::get_code(self, address, block_number)?)
+        Ok(EthApi::get_code(self, address, block_number).await?)
     }
 
     /// Handler for: `eth_call`
     async fn call(
         &self,
-        _request: CallRequest,
-        _block_number: Option<BlockId>,
-        _state_overrides: Option<StateOverride>,
+        request: CallRequest,
+        block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(EthApi::call(self, request, block_number, state_overrides, block_overrides).await?)
     }
 
     /// Handler for: `eth_createAccessList`
@@ -195,22 +210,31 @@ where
         let block_id = block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
         let access_list = self.create_access_list_at(request.clone(), block_number).await?;
         request.access_list = Some(access_list.clone());
-        let gas_used = self.estimate_gas_at(request, block_id).await?;
+        let gas_used = self.estimate_gas_at(request, block_id, None, None).await?;
         Ok(AccessListWithGasUsed { access_list, gas_used })
     }
 
     /// Handler for: `eth_estimateGas`
     async fn estimate_gas(
         &self,
-        _request: CallRequest,
-        _block_number: Option<BlockId>,
+        request: CallRequest,
+        block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<U256> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self
+            .estimate_gas_at(
+                request,
+                block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+                state_overrides,
+                block_overrides,
+            )
+            .await?)
     }
 
     /// Handler for: `eth_gasPrice`
     async fn gas_price(&self) -> Result<U256> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(EthApi::gas_price(self).await?)
     }
 
     // FeeHistory is calculated based on lazy evaluation of fees for historical blocks, and further
@@ -226,7 +250,7 @@ where
         &self,
         block_count: U64,
         newest_block: BlockId,
-        _reward_percentiles: Option<Vec<f64>>,
+        reward_percentiles: Option<Vec<f64>>,
     ) -> Result<FeeHistory> {
         let block_count = block_count.as_u64();
 
@@ -234,7 +258,20 @@ where
             return Ok(FeeHistory::default())
         }
 
-        let Some(end_block) = self.inner.client.block_number_for_id(newest_block).to_rpc_result()? else { return Err(EthApiError::UnknownBlockNumber.into())};
+        // percentiles must be in ascending order and within [0, 100]
+        if let Some(percentiles) = &reward_percentiles {
+            if percentiles.iter().any(|p| !(0.0..=100.0).contains(p)) ||
+                percentiles.windows(2).any(|w| w[0] > w[1])
+            {
+                return Err(EthApiError::InvalidRewardPercentiles.into())
+            }
+        }
+
+        let Some(end_block) =
+            self.inner.client.block_number_for_id(newest_block).to_rpc_result()?
+        else {
+            return Err(EthApiError::UnknownBlockNumber.into())
+        };
 
         if end_block < block_count {
             return Err(EthApiError::InvalidBlockRange.into())
@@ -287,7 +324,9 @@ where
                     hash: None,
                     base_fee_per_gas,
                     gas_used_ratio,
-                    reward: None, // TODO: calculate rewards per transaction
+                    // rewards depend on the requested percentiles and are calculated per
+                    // request from the cached blocks and receipts
+                    reward: None,
                 };
 
                 // Insert missing cache entries in the map for further response composition from it
@@ -303,6 +342,19 @@ where
         fee_history_cache_items.get_mut(&start_block).unwrap().hash = Some(oldest_block_hash);
         fee_history_cache.get_mut(&start_block).unwrap().hash = Some(oldest_block_hash);
 
+        // release the lock before fetching blocks and receipts for the rewards
+        drop(fee_history_cache);
+
+        let reward = if let Some(percentiles) = reward_percentiles {
+            let mut rewards = Vec::with_capacity(fee_history_cache_items.len());
+            for block_number in fee_history_cache_items.keys() {
+                rewards.push(self.fee_history_rewards(*block_number, &percentiles).await?);
+            }
+            Some(rewards)
+        } else {
+            None
+        };
+
         // `fee_history_cache_items` now contains full requested block range (populated from both
         // cache and database), so we can iterate over it in order and populate the response fields
         Ok(FeeHistory {
@@ -315,13 +367,13 @@ where
                 .map(|item| item.gas_used_ratio)
                 .collect(),
             oldest_block: U256::from_be_bytes(oldest_block_hash.0),
-            reward: None,
+            reward,
         })
     }
 
     /// Handler for: `eth_maxPriorityFeePerGas`
     async fn max_priority_fee_per_gas(&self) -> Result<U256> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(EthApi::suggested_priority_fee(self).await?)
     }
 
     /// Handler for: `eth_mining`
@@ -350,8 +402,8 @@ where
     }
 
     /// Handler for: `eth_sendTransaction`
-    async fn send_transaction(&self, _request: TransactionRequest) -> Result<H256> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn send_transaction(&self, request: TransactionRequest) -> Result<H256> {
+        Ok(EthApi::send_transaction(self, request).await?)
     }
 
     /// Handler for: `eth_sendRawTransaction`
@@ -359,35 +411,40 @@ where
         Ok(EthApi::send_raw_transaction(self, tx).await?)
     }
 
+    /// Handler for: `eth_sendPrivateRawTransaction`
+    async fn send_private_raw_transaction(&self, tx: Bytes) -> Result<H256> {
+        Ok(EthApi::send_private_raw_transaction(self, tx).await?)
+    }
+
     /// Handler for: `eth_sign`
-    async fn sign(&self, _address: Address, _message: Bytes) -> Result<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn sign(&self, address: Address, message: Bytes) -> Result<Bytes> {
+        Ok(EthApi::sign(self, address, message).await?)
     }
 
     /// Handler for: `eth_signTransaction`
-    async fn sign_transaction(&self, _transaction: CallRequest) -> Result<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn sign_transaction(&self, transaction: CallRequest) -> Result<Bytes> {
+        Ok(EthApi::sign_transaction(self, transaction).await?)
     }
 
     /// Handler for: `eth_signTypedData`
-    async fn sign_typed_data(&self, _address: Address, _data: Value) -> Result<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn sign_typed_data(&self, address: Address, data: Value) -> Result<Bytes> {
+        Ok(EthApi::sign_typed_data(self, address, data)?)
     }
 
     /// Handler for: `eth_getProof`
     async fn get_proof(
         &self,
-        _address: Address,
-        _keys: Vec<H256>,
-        _block_number: Option<BlockId>,
+        address: Address,
+        keys: Vec<H256>,
+        block_number: Option<BlockId>,
     ) -> Result<EIP1186AccountProofResponse> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(EthApi::get_proof(self, address, keys, block_number).await?)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::eth::cache::EthStateCache;
+    use crate::eth::{cache::EthStateCache, gas_oracle::GasPriceOracle};
     use jsonrpsee::{
         core::{error::Error as RpcError, RpcResult},
         types::error::{CallError, INVALID_PARAMS_CODE},
@@ -404,11 +461,13 @@ mod tests {
     #[tokio::test]
     /// Handler for: `eth_test_fee_history`
     async fn test_fee_history() {
+        let cache = EthStateCache::spawn(NoopProvider::default(), Default::default());
         let eth_api = EthApi::new(
             NoopProvider::default(),
             testing_pool(),
             NoopNetwork::default(),
-            EthStateCache::spawn(NoopProvider::default(), Default::default()),
+            cache.clone(),
+            GasPriceOracle::new(NoopProvider::default(), Default::default(), cache),
         );
 
         let response = eth_api.fee_history(1.into(), BlockNumberOrTag::Latest.into(), None).await;
@@ -449,11 +508,13 @@ mod tests {
                 .push(base_fee_per_gas.map(|fee| U256::try_from(fee).unwrap()).unwrap_or_default());
         }
 
+        let cache = EthStateCache::spawn(NoopProvider::default(), Default::default());
         let eth_api = EthApi::new(
-            mock_provider,
+            mock_provider.clone(),
             testing_pool(),
             NoopNetwork::default(),
-            EthStateCache::spawn(NoopProvider::default(), Default::default()),
+            cache.clone(),
+            GasPriceOracle::new(mock_provider, Default::default(), cache),
         );
 
         let response =
@@ -462,6 +523,13 @@ mod tests {
         let Err(RpcError::Call(CallError::Custom(error_object))) = response else { unreachable!() };
         assert_eq!(error_object.code(), INVALID_PARAMS_CODE);
 
+        // percentiles must be in ascending order
+        let response = eth_api
+            .fee_history(block_count.into(), newest_block.into(), Some(vec![50., 10.]))
+            .await;
+        let Err(RpcError::Call(CallError::Custom(error_object))) = response else { unreachable!() };
+        assert_eq!(error_object.code(), INVALID_PARAMS_CODE);
+
         let fee_history =
             eth_api.fee_history(block_count.into(), newest_block.into(), None).await.unwrap();
 
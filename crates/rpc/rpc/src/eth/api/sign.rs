@@ -0,0 +1,76 @@
+//! Contains RPC handler implementations specific to signing with the accounts of the node.
+
+use crate::{
+    eth::{error::EthResult, signer::SignError},
+    EthApi,
+};
+use ethers_core::types::transaction::eip712::TypedData;
+use reth_network_api::NetworkInfo;
+use reth_primitives::{Address, Bytes};
+use reth_provider::{BlockProvider, EvmEnvProvider, ReceiptProvider, StateProviderFactory};
+use reth_rpc_types::{CallRequest, TransactionRequest};
+use reth_transaction_pool::TransactionPool;
+use serde_json::Value;
+
+impl<Client, Pool, Network> EthApi<Client, Pool, Network>
+where
+    Pool: TransactionPool + 'static,
+    Client: BlockProvider + ReceiptProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + 'static,
+{
+    /// Signs the [EIP-191](https://eips.ethereum.org/EIPS/eip-191) prefixed message with the
+    /// account.
+    ///
+    /// Returns the 65 bytes signature.
+    pub(crate) async fn sign(&self, account: Address, message: Bytes) -> EthResult<Bytes> {
+        let signature = self.find_signer(&account)?.sign(account, &message).await?;
+        Ok(signature.to_bytes().into())
+    }
+
+    /// Signs the [EIP-712](https://eips.ethereum.org/EIPS/eip-712) typed data with the account.
+    ///
+    /// Returns the 65 bytes signature.
+    pub(crate) fn sign_typed_data(&self, account: Address, data: Value) -> EthResult<Bytes> {
+        let signer = self.find_signer(&account)?;
+        let data: TypedData =
+            serde_json::from_value(data).map_err(|_| SignError::InvalidTypedData)?;
+        let signature = signer.sign_typed_data(account, &data)?;
+        Ok(signature.to_bytes().into())
+    }
+
+    /// Fills the missing fields of the request and signs it with the `from` account, without
+    /// submitting it.
+    ///
+    /// Returns the EIP-2718 encoded signed transaction.
+    pub(crate) async fn sign_transaction(&self, request: CallRequest) -> EthResult<Bytes> {
+        let CallRequest {
+            from,
+            to,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            gas,
+            value,
+            data,
+            nonce,
+            access_list,
+            ..
+        } = request;
+        let request = TransactionRequest {
+            from,
+            to,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            gas,
+            value,
+            data,
+            nonce,
+            access_list,
+            transaction_type: None,
+        };
+
+        let transaction = self.sign_request(request).await?;
+        Ok(transaction.envelope_encoded().into())
+    }
+}
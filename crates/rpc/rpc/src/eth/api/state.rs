@@ -1,51 +1,92 @@
 //! Contains RPC handler implementations specific to state.
 
-use crate::{
-    eth::error::{EthApiError, EthResult},
-    EthApi,
-};
-use reth_primitives::{Address, BlockId, Bytes, H256, U256};
+use crate::{eth::error::EthResult, EthApi};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, H256, KECCAK_EMPTY, U256};
 use reth_provider::{BlockProvider, EvmEnvProvider, StateProvider, StateProviderFactory};
+use reth_rpc_types::{EIP1186AccountProofResponse, StorageProof};
+use reth_transaction_pool::TransactionPool;
 
 impl<Client, Pool, Network> EthApi<Client, Pool, Network>
 where
-    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + Clone + 'static,
+    Pool: TransactionPool + 'static,
 {
-    pub(crate) fn get_code(&self, address: Address, block_id: Option<BlockId>) -> EthResult<Bytes> {
-        let state =
-            self.state_at_block_id_or_latest(block_id)?.ok_or(EthApiError::UnknownBlockNumber)?;
-        let code = state.account_code(address)?.unwrap_or_default();
-        Ok(code.original_bytes().into())
+    pub(crate) async fn get_code(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> EthResult<Bytes> {
+        self.with_state_at(block_id.unwrap_or(BlockNumberOrTag::Latest.into()), |state| {
+            let code = state.account_code(address)?.unwrap_or_default();
+            Ok(code.original_bytes().into())
+        })
+        .await
     }
 
-    pub(crate) fn balance(&self, address: Address, block_id: Option<BlockId>) -> EthResult<U256> {
-        let state =
-            self.state_at_block_id_or_latest(block_id)?.ok_or(EthApiError::UnknownBlockNumber)?;
-        let balance = state.account_balance(address)?.unwrap_or_default();
-        Ok(balance)
+    pub(crate) async fn balance(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> EthResult<U256> {
+        self.with_state_at(block_id.unwrap_or(BlockNumberOrTag::Latest.into()), |state| {
+            let balance = state.account_balance(address)?.unwrap_or_default();
+            Ok(balance)
+        })
+        .await
     }
 
-    pub(crate) fn get_transaction_count(
+    pub(crate) async fn get_transaction_count(
         &self,
         address: Address,
         block_id: Option<BlockId>,
     ) -> EthResult<U256> {
-        let state =
-            self.state_at_block_id_or_latest(block_id)?.ok_or(EthApiError::UnknownBlockNumber)?;
-        let nonce = U256::from(state.account_nonce(address)?.unwrap_or_default());
-        Ok(nonce)
+        self.with_state_at(block_id.unwrap_or(BlockNumberOrTag::Latest.into()), |state| {
+            let nonce = U256::from(state.account_nonce(address)?.unwrap_or_default());
+            Ok(nonce)
+        })
+        .await
     }
 
-    pub(crate) fn storage_at(
+    pub(crate) async fn storage_at(
         &self,
         address: Address,
         index: U256,
         block_id: Option<BlockId>,
     ) -> EthResult<H256> {
-        let state =
-            self.state_at_block_id_or_latest(block_id)?.ok_or(EthApiError::UnknownBlockNumber)?;
-        let storage_key = H256(index.to_be_bytes());
-        let value = state.storage(address, storage_key)?.unwrap_or_default();
-        Ok(H256(value.to_be_bytes()))
+        self.with_state_at(block_id.unwrap_or(BlockNumberOrTag::Latest.into()), |state| {
+            let storage_key = H256(index.to_be_bytes());
+            let value = state.storage(address, storage_key)?.unwrap_or_default();
+            Ok(H256(value.to_be_bytes()))
+        })
+        .await
+    }
+
+    pub(crate) async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block_id: Option<BlockId>,
+    ) -> EthResult<EIP1186AccountProofResponse> {
+        self.with_state_at(block_id.unwrap_or(BlockNumberOrTag::Latest.into()), |state| {
+            let proof = state.proof(address, &keys)?;
+            let account = state.basic_account(address)?.unwrap_or_default();
+
+            let mut storage_proof = Vec::with_capacity(keys.len());
+            for (key, proof) in keys.into_iter().zip(proof.storage_proofs) {
+                let value = state.storage(address, key)?.unwrap_or_default();
+                storage_proof.push(StorageProof { key: U256::from_be_bytes(key.0), value, proof });
+            }
+
+            Ok(EIP1186AccountProofResponse {
+                address,
+                balance: account.balance,
+                code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+                nonce: account.nonce.into(),
+                storage_hash: proof.storage_root,
+                account_proof: proof.proof,
+                storage_proof,
+            })
+        })
+        .await
     }
 }
@@ -1,69 +1,468 @@
 //! Contains RPC handler implementations specific to transactions
 
 use crate::{
-    eth::error::{EthApiError, EthResult},
+    eth::{
+        error::{EthApiError, EthResult},
+        signer::SignError,
+    },
     EthApi,
 };
-use reth_primitives::{Bytes, FromRecoveredTransaction, TransactionSigned, H256};
-use reth_provider::{BlockProvider, EvmEnvProvider, StateProviderFactory};
+use reth_network_api::NetworkInfo;
+use reth_primitives::{
+    contract::create_address, Address, BlobTransactionSidecar, BlockId, BlockNumberOrTag, Bytes,
+    FromRecoveredTransaction, PooledTransactionsElement, Receipt, TransactionKind, TransactionMeta,
+    TransactionSigned, H256, U128, U256, U64,
+};
+use reth_provider::{
+    BlockProvider, EvmEnvProvider, ReceiptProvider, StateProvider, StateProviderFactory,
+};
 use reth_rlp::Decodable;
-use reth_rpc_types::TransactionRequest;
+use reth_rpc_types::{
+    CallRequest, Log, TransactionReceipt, TransactionRequest, TypedTransactionRequest,
+};
 use reth_transaction_pool::{TransactionOrigin, TransactionPool};
 
 impl<Client, Pool, Network> EthApi<Client, Pool, Network>
 where
     Pool: TransactionPool + 'static,
-    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Client: BlockProvider + ReceiptProvider + StateProviderFactory + EvmEnvProvider + 'static,
     Network: 'static,
 {
-    pub(crate) async fn send_transaction(&self, _request: TransactionRequest) -> EthResult<H256> {
-        unimplemented!()
+    /// Returns the transaction receipt for the given hash.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub(crate) async fn transaction_receipt(
+        &self,
+        hash: H256,
+    ) -> EthResult<Option<TransactionReceipt>> {
+        let (tx, meta) = match self.client().transaction_by_hash_with_meta(hash)? {
+            Some((tx, meta)) => (tx, meta),
+            None => return Ok(None),
+        };
+
+        let receipts = match self.client().receipts_by_block(meta.block_number.into())? {
+            Some(receipts) => receipts,
+            None => return Ok(None),
+        };
+
+        build_transaction_receipt_with_block_receipts(tx, meta, &receipts).map(Some)
+    }
+
+    /// Returns the receipts of all transactions in the given block.
+    ///
+    /// Returns `None` if the block or its receipts are not found.
+    pub(crate) async fn block_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<TransactionReceipt>>> {
+        let block_hash = match self.client().block_hash_for_id(block_id)? {
+            Some(block_hash) => block_hash,
+            None => return Ok(None),
+        };
+
+        let (block, receipts) = futures::try_join!(
+            self.cache().get_block(block_hash),
+            self.cache().get_receipts(block_hash)
+        )?;
+        let (block, receipts) = match (block, receipts) {
+            (Some(block), Some(receipts)) => (block, receipts),
+            _ => return Ok(None),
+        };
+        if block.body.len() != receipts.len() {
+            return Err(EthApiError::UnknownBlockOrTxIndex)
+        }
+
+        let block_number = block.header.number;
+        let base_fee = block.header.base_fee_per_gas;
+
+        // the gas used and the log indices are derived from the preceding receipts
+        let mut prev_cumulative_gas_used = 0;
+        let mut num_logs_before = 0;
+        block
+            .body
+            .into_iter()
+            .zip(receipts)
+            .enumerate()
+            .map(|(index, (tx, receipt))| {
+                let meta = TransactionMeta {
+                    tx_hash: tx.hash,
+                    index: index as u64,
+                    block_hash,
+                    block_number,
+                    base_fee,
+                };
+                let gas_used = receipt.cumulative_gas_used - prev_cumulative_gas_used;
+                prev_cumulative_gas_used = receipt.cumulative_gas_used;
+                let log_index_offset = num_logs_before;
+                num_logs_before += receipt.logs.len();
+                build_transaction_receipt(tx, meta, receipt, gas_used, log_index_offset)
+            })
+            .collect::<EthResult<Vec<_>>>()
+            .map(Some)
     }
 
     /// Decodes and recovers the transaction and submits it to the pool.
     ///
+    /// Blob transactions must be submitted in their network form, which includes the sidecar.
+    ///
     /// Returns the hash of the transaction.
     pub(crate) async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<H256> {
-        let mut data = tx.as_ref();
-        if data.is_empty() {
-            return Err(EthApiError::EmptyRawTransactionData)
-        }
+        let (transaction, sidecar) = decode_raw_transaction(tx)?.into_parts();
+        self.submit_transaction(transaction, sidecar, TransactionOrigin::Local).await
+    }
 
-        let transaction = TransactionSigned::decode(&mut data)
-            .map_err(|_| EthApiError::FailedToDecodeSignedTransaction)?;
+    /// Decodes and recovers the transaction and submits it to the pool as a private transaction,
+    /// which is never propagated to the network.
+    ///
+    /// Returns the hash of the transaction.
+    pub(crate) async fn send_private_raw_transaction(&self, tx: Bytes) -> EthResult<H256> {
+        let (transaction, sidecar) = decode_raw_transaction(tx)?.into_parts();
+        self.submit_transaction(transaction, sidecar, TransactionOrigin::Private).await
+    }
 
+    /// Recovers the signer of the transaction and submits it to the pool with the given origin.
+    ///
+    /// Blob transactions are submitted together with their sidecar.
+    ///
+    /// Returns the hash of the transaction.
+    async fn submit_transaction(
+        &self,
+        transaction: TransactionSigned,
+        sidecar: Option<BlobTransactionSidecar>,
+        origin: TransactionOrigin,
+    ) -> EthResult<H256> {
         let recovered =
             transaction.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
 
         let pool_transaction = <Pool::Transaction>::from_recovered_transaction(recovered);
 
-        // submit the transaction to the pool with a `Local` origin
-        let hash = self.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await?;
+        let hash = match sidecar {
+            Some(sidecar) => {
+                self.pool().add_blob_transaction(origin, pool_transaction, sidecar).await?
+            }
+            None => self.pool().add_transaction(origin, pool_transaction).await?,
+        };
 
         Ok(hash)
     }
 }
 
+impl<Client, Pool, Network> EthApi<Client, Pool, Network>
+where
+    Pool: TransactionPool + 'static,
+    Client: BlockProvider + ReceiptProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + 'static,
+{
+    /// Fills the missing fields of the request, signs it with the `from` account and submits it
+    /// to the pool.
+    ///
+    /// Returns the hash of the transaction.
+    pub(crate) async fn send_transaction(&self, request: TransactionRequest) -> EthResult<H256> {
+        let transaction = self.sign_request(request).await?;
+        self.submit_transaction(transaction, None, TransactionOrigin::Local).await
+    }
+
+    /// Fills the missing fields of the request and signs it with the `from` account.
+    pub(crate) async fn sign_request(
+        &self,
+        request: TransactionRequest,
+    ) -> EthResult<TransactionSigned> {
+        let from = request.from.ok_or(SignError::NoAccount)?;
+        let signer = self.find_signer(&from)?;
+
+        let request = self.fill_transaction_request(from, request).await?;
+        let mut request =
+            request.into_typed_request().ok_or(SignError::InvalidTransactionRequest)?;
+
+        let chain_id = self.network().chain_id();
+        match &mut request {
+            TypedTransactionRequest::Legacy(tx) => tx.chain_id = Some(chain_id),
+            TypedTransactionRequest::EIP2930(tx) => tx.chain_id = chain_id,
+            TypedTransactionRequest::EIP1559(tx) => tx.chain_id = chain_id,
+        }
+
+        Ok(signer.sign_transaction(request, &from)?)
+    }
+
+    /// Fills the nonce, fee and gas fields of the request that are not set.
+    ///
+    /// The nonce is the next nonce of the account, including its transactions in the pool. If no
+    /// fee is set, the request becomes an EIP-1559 transaction with a suggested tip and a fee cap
+    /// of twice the latest base fee plus the tip, or a legacy transaction with the suggested gas
+    /// price before London. The gas limit is estimated against the pending block.
+    async fn fill_transaction_request(
+        &self,
+        from: Address,
+        mut request: TransactionRequest,
+    ) -> EthResult<TransactionRequest> {
+        match (request.gas_price, request.max_fee_per_gas, request.max_priority_fee_per_gas) {
+            (Some(gas_price), Some(max_fee_per_gas), _) => {
+                return Err(EthApiError::ConflictingRequestGasPrice { gas_price, max_fee_per_gas })
+            }
+            (Some(gas_price), None, Some(max_priority_fee_per_gas)) => {
+                return Err(EthApiError::RequestLegacyGasPriceAndTipSet {
+                    gas_price,
+                    max_priority_fee_per_gas,
+                })
+            }
+            (Some(_), None, None) => {}
+            (None, max_fee_per_gas, max_priority_fee_per_gas) => {
+                let chain_info = self.client().chain_info()?;
+                let base_fee = self
+                    .client()
+                    .header_by_number(chain_info.best_number)?
+                    .and_then(|header| header.base_fee_per_gas);
+
+                match base_fee {
+                    Some(base_fee) => {
+                        let tip = match max_priority_fee_per_gas {
+                            Some(tip) => tip,
+                            None => to_u128(self.suggested_priority_fee().await?),
+                        };
+                        let max_fee = max_fee_per_gas
+                            .unwrap_or_else(|| U128::from(base_fee) * U128::from(2) + tip);
+                        request.max_fee_per_gas = Some(max_fee);
+                        request.max_priority_fee_per_gas = Some(tip.min(max_fee));
+                    }
+                    None if max_fee_per_gas.is_none() && max_priority_fee_per_gas.is_none() => {
+                        request.gas_price = Some(to_u128(self.gas_price().await?));
+                    }
+                    // an EIP-1559 request before London is rejected by the pool
+                    None => {}
+                }
+            }
+        }
+
+        if request.nonce.is_none() {
+            request.nonce = Some(U256::from(self.next_nonce(from)?));
+        }
+
+        if request.gas.is_none() {
+            let call = CallRequest {
+                from: Some(from),
+                to: request.to,
+                gas_price: request.gas_price,
+                max_fee_per_gas: request.max_fee_per_gas,
+                max_priority_fee_per_gas: request.max_priority_fee_per_gas,
+                gas: None,
+                value: request.value,
+                data: request.data.clone(),
+                // the pending nonce of the account can be ahead of its nonce in the state
+                nonce: None,
+                chain_id: None,
+                access_list: request.access_list.clone(),
+            };
+            let gas = self
+                .estimate_gas_at(call, BlockId::Number(BlockNumberOrTag::Pending), None, None)
+                .await?;
+            request.gas = Some(gas);
+        }
+
+        Ok(request)
+    }
+
+    /// Returns the next nonce of the account, which is the nonce after its last transaction in
+    /// the pool or its nonce in the latest state.
+    fn next_nonce(&self, address: Address) -> EthResult<u64> {
+        let state_nonce = self.client().latest()?.account_nonce(address)?.unwrap_or_default();
+        let pool_nonce =
+            self.pool().get_transactions_by_sender(address).last().map(|tx| tx.nonce() + 1);
+        Ok(pool_nonce.map_or(state_nonce, |nonce| nonce.max(state_nonce)))
+    }
+}
+
+/// Decodes the raw bytes of a signed transaction.
+///
+/// Blob transactions are decoded from their network form, which includes the sidecar.
+fn decode_raw_transaction(tx: Bytes) -> EthResult<PooledTransactionsElement> {
+    let mut data = tx.as_ref();
+    if data.is_empty() {
+        return Err(EthApiError::EmptyRawTransactionData)
+    }
+
+    PooledTransactionsElement::decode(&mut data)
+        .map_err(|_| EthApiError::FailedToDecodeSignedTransaction)
+}
+
+/// Converts a suggested fee into the fee type of the request, saturating at `u128::MAX`.
+fn to_u128(fee: U256) -> U128 {
+    U128::from(u128::try_from(fee).unwrap_or(u128::MAX))
+}
+
+/// Helper function to construct a transaction receipt
+///
+/// `all_receipts` are all receipts of the block the transaction was included in, ordered by their
+/// transaction index. These are required to derive the gas used by this transaction alone and the
+/// block-wide log indices.
+pub(crate) fn build_transaction_receipt_with_block_receipts(
+    tx: TransactionSigned,
+    meta: TransactionMeta,
+    all_receipts: &[Receipt],
+) -> EthResult<TransactionReceipt> {
+    let index = meta.index as usize;
+    let receipt = all_receipts.get(index).cloned().ok_or(EthApiError::UnknownBlockOrTxIndex)?;
+
+    // get the previous transaction cumulative gas used
+    let gas_used = if index == 0 {
+        receipt.cumulative_gas_used
+    } else {
+        let prev_receipt = &all_receipts[index - 1];
+        receipt.cumulative_gas_used - prev_receipt.cumulative_gas_used
+    };
+
+    // the log index is counted across all receipts of the block
+    let num_logs_before: usize = all_receipts.iter().take(index).map(|r| r.logs.len()).sum();
+
+    build_transaction_receipt(tx, meta, receipt, gas_used, num_logs_before)
+}
+
+/// Helper function to construct a transaction receipt out of its own receipt.
+///
+/// `gas_used` is the gas used by this transaction alone and `num_logs_before` is the number of
+/// logs of the preceding transactions in the block, from which the block-wide log indices of its
+/// logs are counted.
+pub(crate) fn build_transaction_receipt(
+    tx: TransactionSigned,
+    meta: TransactionMeta,
+    receipt: Receipt,
+    gas_used: u64,
+    num_logs_before: usize,
+) -> EthResult<TransactionReceipt> {
+    let transaction = tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
+
+    let mut res_receipt = TransactionReceipt {
+        transaction_hash: Some(meta.tx_hash),
+        transaction_index: Some(U256::from(meta.index)),
+        block_hash: Some(meta.block_hash),
+        block_number: Some(U256::from(meta.block_number)),
+        from: transaction.signer(),
+        to: None,
+        cumulative_gas_used: U256::from(receipt.cumulative_gas_used),
+        gas_used: Some(U256::from(gas_used)),
+        contract_address: None,
+        logs: Vec::with_capacity(receipt.logs.len()),
+        state_root: None,
+        logs_bloom: receipt.bloom,
+        status_code: Some(U64::from(receipt.success as u8)),
+        effective_gas_price: U128::from(transaction.effective_gas_price(meta.base_fee)),
+        transaction_type: U256::from(transaction.tx_type() as u8),
+    };
+
+    match transaction.kind() {
+        TransactionKind::Create => {
+            res_receipt.contract_address =
+                Some(create_address(transaction.signer(), transaction.nonce()));
+        }
+        TransactionKind::Call(addr) => {
+            res_receipt.to = Some(*addr);
+        }
+    }
+
+    for (tx_log_idx, log) in receipt.logs.into_iter().enumerate() {
+        let rpclog = Log {
+            address: log.address,
+            topics: log.topics,
+            data: log.data,
+            block_hash: Some(meta.block_hash),
+            block_number: Some(U256::from(meta.block_number)),
+            transaction_hash: Some(meta.tx_hash),
+            transaction_index: Some(U256::from(meta.index)),
+            log_index: Some(U256::from(num_logs_before + tx_log_idx)),
+            transaction_log_index: Some(U256::from(tx_log_idx)),
+            removed: false,
+        };
+        res_receipt.logs.push(rpclog);
+    }
+
+    Ok(res_receipt)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::eth::cache::EthStateCache;
-    use reth_primitives::{hex_literal::hex, Bytes};
-    use reth_provider::test_utils::NoopProvider;
-    use reth_transaction_pool::{test_utils::testing_pool, TransactionPool};
+    use super::build_transaction_receipt_with_block_receipts;
+    use crate::eth::{cache::EthStateCache, gas_oracle::GasPriceOracle};
+    use reth_interfaces::test_utils::generators::sign_message;
+    use reth_primitives::{
+        constants::BYTES_PER_BLOB, contract::create_address, eip4844::MAINNET_KZG_TRUSTED_SETUP,
+        hex_literal::hex, Address, BlobTransactionSidecar, Block, Bytes, Header, Log,
+        PooledTransactionsElement, Receipt, Transaction, TransactionKind, TransactionMeta,
+        TransactionSigned, TxEip1559, TxEip4844, TxLegacy, TxType, H256, U128, U256,
+    };
+    use reth_provider::test_utils::{MockEthProvider, NoopProvider};
+    use reth_rlp::{Decodable, Encodable, Header as RlpHeader};
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, NoopTransactionValidator},
+        CostOrdering, Pool, PooledTransaction, TransactionOrigin, TransactionPool,
+    };
+    use std::sync::Arc;
 
     use crate::EthApi;
 
+    fn sign(transaction: Transaction) -> TransactionSigned {
+        let signature =
+            sign_message(H256::from_low_u64_be(1), transaction.signature_hash()).unwrap();
+        TransactionSigned::from_transaction_and_signature(transaction, signature)
+    }
+
+    #[test]
+    fn build_receipt_with_block_receipts() {
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let raw = hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3");
+        let tx = TransactionSigned::decode(&mut &raw[..]).unwrap();
+
+        let log = Log { address: Address::random(), topics: vec![], data: Default::default() };
+        let receipt = |cumulative_gas_used, logs| Receipt {
+            tx_type: TxType::EIP1559,
+            success: true,
+            cumulative_gas_used,
+            bloom: Default::default(),
+            logs,
+        };
+        let receipts = vec![
+            receipt(50_000, vec![log.clone(), log.clone()]),
+            receipt(71_000, vec![log.clone()]),
+        ];
+
+        let meta = TransactionMeta {
+            tx_hash: tx.hash,
+            index: 1,
+            block_hash: H256::random(),
+            block_number: 100,
+            base_fee: Some(1_000_000_000),
+        };
+
+        let res =
+            build_transaction_receipt_with_block_receipts(tx.clone(), meta, &receipts).unwrap();
+
+        assert_eq!(res.transaction_hash, Some(tx.hash));
+        assert_eq!(res.transaction_index, Some(U256::from(1)));
+        assert_eq!(res.block_hash, Some(meta.block_hash));
+        assert_eq!(res.from, tx.recover_signer().unwrap());
+        assert_eq!(res.to, Some(hex!("eee27662c2b8eba3cd936a23f039f3189633e4c8").into()));
+        assert_eq!(res.contract_address, None);
+        assert_eq!(res.cumulative_gas_used, U256::from(71_000));
+        assert_eq!(res.gas_used, Some(U256::from(21_000)));
+        // no priority fee, so the effective price is the base fee
+        assert_eq!(res.effective_gas_price, U128::from(1_000_000_000u64));
+        assert_eq!(res.transaction_type, U256::from(2));
+        assert_eq!(res.logs.len(), 1);
+        assert_eq!(res.logs[0].log_index, Some(U256::from(2)));
+        assert_eq!(res.logs[0].transaction_log_index, Some(U256::from(0)));
+    }
+
     #[tokio::test]
     async fn send_raw_transaction() {
         let noop_provider = NoopProvider::default();
 
         let pool = testing_pool();
 
+        let cache = EthStateCache::spawn(NoopProvider::default(), Default::default());
         let eth_api = EthApi::new(
             noop_provider,
             pool.clone(),
             (),
-            EthStateCache::spawn(NoopProvider::default(), Default::default()),
+            cache.clone(),
+            GasPriceOracle::new(NoopProvider::default(), Default::default(), cache),
         );
 
         // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
@@ -91,4 +490,194 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[tokio::test]
+    async fn send_private_raw_transaction() {
+        let pool = testing_pool();
+        let cache = EthStateCache::spawn(NoopProvider::default(), Default::default());
+        let eth_api = EthApi::new(
+            NoopProvider::default(),
+            pool.clone(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(NoopProvider::default(), Default::default(), cache),
+        );
+
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let tx = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+
+        let hash = eth_api.send_private_raw_transaction(tx).await.unwrap();
+        let pooled = pool.get(&hash).expect("tx not found in the pool");
+        assert_eq!(pooled.origin, TransactionOrigin::Private);
+        assert!(pooled.is_local());
+    }
+
+    #[tokio::test]
+    async fn send_raw_blob_transaction() {
+        let pool: Pool<
+            NoopTransactionValidator<PooledTransaction>,
+            CostOrdering<PooledTransaction>,
+        > = Pool::new(
+            Arc::new(NoopTransactionValidator::default()),
+            Arc::new(CostOrdering::default()),
+            Default::default(),
+        );
+        let cache = EthStateCache::spawn(NoopProvider::default(), Default::default());
+        let eth_api = EthApi::new(
+            NoopProvider::default(),
+            pool.clone(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(NoopProvider::default(), Default::default(), cache),
+        );
+
+        // the first byte of each field element is kept zero so it's canonical
+        let mut blob = vec![0u8; BYTES_PER_BLOB];
+        blob.iter_mut().skip(1).step_by(32).for_each(|b| *b = 1);
+        let sidecar =
+            BlobTransactionSidecar::from_blobs(vec![blob.into()], &MAINNET_KZG_TRUSTED_SETUP)
+                .unwrap();
+        let transaction = Transaction::Eip4844(TxEip4844 {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 21_000,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 1,
+            to: TransactionKind::Call(Address::random()),
+            value: 0,
+            access_list: Default::default(),
+            blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+            max_fee_per_blob_gas: 5,
+            input: Default::default(),
+        });
+        let transaction = sign(transaction);
+
+        // the canonical encoding doesn't carry the sidecar and is rejected
+        let mut canonical = Vec::new();
+        transaction.encode(&mut canonical);
+        assert!(eth_api.send_raw_transaction(canonical.into()).await.is_err());
+        assert_eq!(pool.len(), 0);
+
+        // raw transactions are `type || rlp([tx_payload_body, blobs, commitments, proofs])`,
+        // without the string header of the network encoding
+        let element =
+            PooledTransactionsElement::blob_transaction(transaction.clone(), sidecar.clone());
+        let mut encoded = Vec::new();
+        element.encode(&mut encoded);
+        let mut raw = &encoded[..];
+        RlpHeader::decode(&mut raw).unwrap();
+
+        let hash = eth_api.send_raw_transaction(Bytes::from(raw.to_vec())).await.unwrap();
+        assert_eq!(hash, transaction.hash);
+        assert!(pool.get(&hash).is_some(), "blob tx not found in the pool");
+        assert_eq!(pool.get_blob_sidecar(&hash), Some(sidecar));
+    }
+
+    #[tokio::test]
+    async fn block_receipts_derive_gas_used_and_log_indices() {
+        let transactions = vec![
+            sign(Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                nonce: 0,
+                gas_price: 10,
+                gas_limit: 21_000,
+                to: TransactionKind::Call(Address::random()),
+                ..Default::default()
+            })),
+            sign(Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                nonce: 1,
+                gas_limit: 100_000,
+                max_fee_per_gas: 10,
+                max_priority_fee_per_gas: 1,
+                to: TransactionKind::Create,
+                ..Default::default()
+            })),
+            sign(Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                nonce: 2,
+                gas_limit: 50_000,
+                max_fee_per_gas: 10,
+                max_priority_fee_per_gas: 1,
+                to: TransactionKind::Call(Address::random()),
+                ..Default::default()
+            })),
+        ];
+        let log = Log { address: Address::random(), topics: vec![], data: Default::default() };
+        let receipt = |tx_type, cumulative_gas_used, logs| Receipt {
+            tx_type,
+            success: true,
+            cumulative_gas_used,
+            bloom: Default::default(),
+            logs,
+        };
+        let receipts = vec![
+            receipt(TxType::Legacy, 21_000, vec![log.clone(), log.clone()]),
+            receipt(TxType::EIP1559, 71_000, vec![]),
+            receipt(TxType::EIP1559, 100_000, vec![log]),
+        ];
+        let header = Header { number: 1, base_fee_per_gas: Some(7), ..Default::default() };
+        let block_hash = header.hash_slow();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            block_hash,
+            Block { header, body: transactions.clone(), ..Default::default() },
+        );
+        provider.add_receipts(block_hash, receipts);
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+        );
+
+        let res = eth_api.block_receipts(block_hash.into()).await.unwrap().unwrap();
+        assert_eq!(res.len(), 3);
+
+        // the gas used is the difference of the cumulative gas used of consecutive receipts
+        let gas_used = res.iter().map(|receipt| receipt.gas_used).collect::<Vec<_>>();
+        assert_eq!(
+            gas_used,
+            vec![Some(U256::from(21_000)), Some(U256::from(50_000)), Some(U256::from(29_000))]
+        );
+        assert_eq!(res[2].cumulative_gas_used, U256::from(100_000));
+
+        // the log index counts the logs of the whole block
+        let log_indices = res
+            .iter()
+            .flat_map(|receipt| &receipt.logs)
+            .map(|log| (log.log_index, log.transaction_log_index))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            log_indices,
+            vec![
+                (Some(U256::from(0)), Some(U256::from(0))),
+                (Some(U256::from(1)), Some(U256::from(1))),
+                (Some(U256::from(2)), Some(U256::from(0))),
+            ]
+        );
+
+        assert_eq!(res[0].transaction_type, U256::from(0));
+        assert_eq!(res[0].effective_gas_price, U128::from(10));
+        assert_eq!(res[1].transaction_type, U256::from(2));
+        // the tip is capped by the max fee: min(10, 7 + 1)
+        assert_eq!(res[1].effective_gas_price, U128::from(8));
+        assert_eq!(res[1].to, None);
+        assert_eq!(res[1].contract_address, Some(create_address(res[1].from, 1)));
+        for (index, (receipt, tx)) in res.iter().zip(&transactions).enumerate() {
+            assert_eq!(receipt.transaction_hash, Some(tx.hash));
+            assert_eq!(receipt.transaction_index, Some(U256::from(index)));
+            assert_eq!(receipt.block_hash, Some(block_hash));
+            assert_eq!(receipt.from, tx.recover_signer().unwrap());
+        }
+
+        // a single receipt is built from the same block receipts
+        for (receipt, tx) in res.iter().zip(&transactions) {
+            assert_eq!(eth_api.transaction_receipt(tx.hash).await.unwrap().as_ref(), Some(receipt));
+        }
+    }
 }
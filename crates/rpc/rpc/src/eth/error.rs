@@ -1,11 +1,17 @@
 //! Implementation specific Errors for the `eth_` namespace.
 
-use crate::result::{internal_rpc_err, rpc_err};
+use crate::{
+    eth::signer::SignError,
+    result::{internal_rpc_err, rpc_err},
+};
 use jsonrpsee::{core::Error as RpcError, types::error::INVALID_PARAMS_CODE};
-use reth_primitives::{constants::SELECTOR_LEN, Address, U128, U256};
+use reth_primitives::{
+    constants::SELECTOR_LEN, Address, Bytes, InvalidBlobSidecarError, U128, U256,
+};
+use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::{error::EthRpcErrorCode, BlockError};
 use reth_transaction_pool::error::{InvalidPoolTransactionError, PoolError};
-use revm::primitives::{EVMError, Halt};
+use revm::primitives::{EVMError, ExecutionResult, Halt, Output};
 
 /// Result alias
 pub(crate) type EthResult<T> = Result<T, EthApiError>;
@@ -25,8 +31,19 @@ pub(crate) enum EthApiError {
     PoolError(RpcPoolError),
     #[error("Unknown block number")]
     UnknownBlockNumber,
+    /// Thrown when the requested transaction is not known
+    #[error("transaction not found")]
+    TransactionNotFound,
+    /// Thrown when querying for a transaction or receipt by index that does not exist in the
+    /// block
+    #[error("Unknown block or tx index")]
+    UnknownBlockOrTxIndex,
     #[error("Invalid block range")]
     InvalidBlockRange,
+    /// Thrown when the reward percentiles of `eth_feeHistory` are not in ascending order or out
+    /// of the `[0, 100]` range
+    #[error("invalid reward percentiles")]
+    InvalidRewardPercentiles,
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("Prevrandao not in th EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -49,6 +66,31 @@ pub(crate) enum EthApiError {
     /// conflicting `state` and `stateDiff` fields
     #[error("account {0:?} has both 'state' and 'stateDiff'")]
     BothStateAndStateDiffInOverride(Address),
+    /// Thrown when the `tracerConfig` of the tracing options does not match the selected tracer
+    #[error("invalid tracer config: {0}")]
+    InvalidTracerConfig(String),
+    /// Thrown when decoding a raw block failed
+    #[error("Failed to decode block")]
+    FailedToDecodeBlock,
+    /// Thrown when `trace_filter` filters by address but the call address index does not cover
+    /// the requested blocks
+    #[error("call address index not available for the requested block range")]
+    CallAddressIndexUnavailable,
+    /// Thrown when the block range of `trace_filter` is larger than the configured maximum
+    #[error("block range too large, at most {0} blocks can be traced")]
+    BlockRangeTooLarge(u64),
+    /// Error thrown when running a javascript tracer
+    #[error(transparent)]
+    JsTracer(#[from] JsInspectorError),
+    /// Thrown when the blocking task that executes a tracing call failed
+    #[error("internal error while tracing")]
+    InternalTracingError,
+    /// Thrown when the blocking task that builds the pending block failed
+    #[error("internal error while building the pending block")]
+    InternalPendingBlockError,
+    /// Error thrown when signing with an account of the node
+    #[error(transparent)]
+    Signing(#[from] SignError),
     /// Other internal error
     #[error(transparent)]
     Internal(#[from] reth_interfaces::Error),
@@ -61,17 +103,29 @@ impl From<EthApiError> for RpcError {
             EthApiError::InvalidTransactionSignature |
             EthApiError::EmptyRawTransactionData |
             EthApiError::UnknownBlockNumber |
+            EthApiError::TransactionNotFound |
+            EthApiError::UnknownBlockOrTxIndex |
             EthApiError::InvalidBlockRange |
+            EthApiError::BlockRangeTooLarge(_) |
+            EthApiError::InvalidRewardPercentiles |
             EthApiError::ConflictingRequestGasPrice { .. } |
             EthApiError::ConflictingRequestGasPriceAndTipSet { .. } |
             EthApiError::RequestLegacyGasPriceAndTipSet { .. } |
-            EthApiError::BothStateAndStateDiffInOverride(_) => {
+            EthApiError::BothStateAndStateDiffInOverride(_) |
+            EthApiError::InvalidTracerConfig(_) |
+            EthApiError::FailedToDecodeBlock => {
                 rpc_err(INVALID_PARAMS_CODE, error.to_string(), None)
             }
             EthApiError::InvalidTransaction(err) => err.into(),
+            EthApiError::Signing(_) | EthApiError::CallAddressIndexUnavailable => {
+                rpc_err(EthRpcErrorCode::InvalidInput.code(), error.to_string(), None)
+            }
             EthApiError::PoolError(_) |
             EthApiError::PrevrandaoNotSet |
             EthApiError::InvalidBlockData(_) |
+            EthApiError::JsTracer(_) |
+            EthApiError::InternalTracingError |
+            EthApiError::InternalPendingBlockError |
             EthApiError::Internal(_) => internal_rpc_err(error.to_string()),
         }
     }
@@ -153,7 +207,7 @@ pub enum InvalidTransactionError {
     #[error("sender not an eoa")]
     SenderNoEOA,
     /// Thrown during estimate if caller has insufficient funds to cover the tx.
-    #[error("Out of gas: gas required exceeds allowance: {0:?}")]
+    #[error("gas required exceeds allowance ({0})")]
     OutOfGas(U256),
     /// Thrown if executing a transaction failed during estimate/call
     #[error("{0}")]
@@ -166,6 +220,18 @@ pub enum InvalidTransactionError {
 }
 
 impl InvalidTransactionError {
+    /// Converts the halt reason of an execution into the matching error.
+    ///
+    /// Takes the configured gas limit of the transaction, which is reported if the execution ran
+    /// out of gas.
+    pub(crate) fn halt(reason: Halt, gas_limit: u64) -> Self {
+        match reason {
+            Halt::OutOfGas(_) => InvalidTransactionError::OutOfGas(U256::from(gas_limit)),
+            Halt::NonceOverflow => InvalidTransactionError::NonceMaxValue,
+            err => InvalidTransactionError::EvmHalt(err),
+        }
+    }
+
     /// Returns the rpc error code for this error.
     fn error_code(&self) -> i32 {
         match self {
@@ -288,6 +354,8 @@ pub(crate) enum RpcPoolError {
     #[error(transparent)]
     Invalid(#[from] InvalidPoolTransactionError),
     #[error(transparent)]
+    InvalidBlobSidecar(#[from] InvalidBlobSidecarError),
+    #[error(transparent)]
     Other(Box<dyn std::error::Error + Send + Sync>),
 }
 
@@ -299,6 +367,7 @@ impl From<PoolError> for RpcPoolError {
             PoolError::SpammerExceededCapacity(_, _) => RpcPoolError::TxPoolOverflow,
             PoolError::DiscardedOnInsert(_) => RpcPoolError::TxPoolOverflow,
             PoolError::InvalidTransaction(_, err) => err.into(),
+            PoolError::InvalidBlobSidecar(_, err) => err.into(),
             PoolError::Other(_, err) => RpcPoolError::Other(err),
         }
     }
@@ -310,14 +379,106 @@ impl From<PoolError> for EthApiError {
     }
 }
 
-/// Returns the revert reason from the `revm::TransactOut` data, if it's an abi encoded String.
+/// Returns the output of a successful execution, or the error of a reverted or halted one.
+///
+/// The `gas_limit` is the gas limit the execution was configured with.
+pub(crate) fn ensure_success(result: ExecutionResult, gas_limit: u64) -> EthResult<Bytes> {
+    match result {
+        ExecutionResult::Success { output, .. } => match output {
+            Output::Call(data) | Output::Create(data, _) => Ok(data.into()),
+        },
+        ExecutionResult::Revert { output, .. } => {
+            Err(InvalidTransactionError::Revert(RevertError::new(output)).into())
+        }
+        ExecutionResult::Halt { reason, .. } => {
+            Err(InvalidTransactionError::halt(reason, gas_limit).into())
+        }
+    }
+}
+
+/// Selector of solidity's `Error(string)`, which is used by `revert(string)` and `require`
+const REVERT_SELECTOR: [u8; SELECTOR_LEN] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of solidity's `Panic(uint256)`, which is used by `assert` and for checked arithmetic
+const PANIC_SELECTOR: [u8; SELECTOR_LEN] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Returns the revert reason from the `revm::TransactOut` data, if it's an abi encoded
+/// `Error(string)` or `Panic(uint256)`.
+///
+/// This mirrors geth's `abi.UnpackRevert`.
 ///
 /// **Note:** it's assumed the `out` buffer starts with the call's signature
 pub(crate) fn decode_revert_reason(out: impl AsRef<[u8]>) -> Option<String> {
-    use ethers_core::abi::AbiDecode;
+    use ethers_core::{abi::AbiDecode, types::U256};
     let out = out.as_ref();
     if out.len() < SELECTOR_LEN {
         return None
     }
-    String::decode(&out[SELECTOR_LEN..]).ok()
+    let (selector, data) = out.split_at(SELECTOR_LEN);
+    if selector == REVERT_SELECTOR {
+        String::decode(data).ok()
+    } else if selector == PANIC_SELECTOR {
+        let code = U256::decode(data).ok()?;
+        let reason = match code.low_u64() {
+            _ if code > U256::from(u64::MAX) => None,
+            0x00 => Some("generic panic"),
+            0x01 => Some("assert(false)"),
+            0x11 => Some("arithmetic underflow or overflow"),
+            0x12 => Some("division or modulo by zero"),
+            0x21 => Some("enum overflow"),
+            0x22 => Some("invalid encoded storage byte array accessed"),
+            0x31 => Some("out-of-bounds array access; popping on an empty array"),
+            0x32 => Some("out-of-bounds access of an array or bytesSlice"),
+            0x41 => Some("out of memory"),
+            0x51 => Some("tried to call a zero-initialized variable of internal function type"),
+            _ => None,
+        };
+        Some(reason.map(str::to_string).unwrap_or_else(|| format!("unknown panic code: {code:#x}")))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::hex;
+
+    #[test]
+    fn decode_revert_reason_string() {
+        // `Error("not enough balance")`
+        let out = hex::decode("08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000126e6f7420656e6f7567682062616c616e63650000000000000000000000000000").unwrap();
+        assert_eq!(decode_revert_reason(&out), Some("not enough balance".to_string()));
+
+        let err = RevertError::new(out.into());
+        assert_eq!(err.to_string(), "execution reverted: not enough balance");
+    }
+
+    #[test]
+    fn decode_revert_reason_panic() {
+        // `Panic(0x11)`
+        let out =
+            hex::decode("4e487b710000000000000000000000000000000000000000000000000000000000000011")
+                .unwrap();
+        assert_eq!(
+            decode_revert_reason(&out),
+            Some("arithmetic underflow or overflow".to_string())
+        );
+
+        // `Panic(0x99)`
+        let out =
+            hex::decode("4e487b710000000000000000000000000000000000000000000000000000000000000099")
+                .unwrap();
+        assert_eq!(decode_revert_reason(out), Some("unknown panic code: 0x99".to_string()));
+    }
+
+    #[test]
+    fn decode_revert_reason_unknown_selector() {
+        // custom errors can't be decoded without the abi
+        let out = hex::decode("deadbeef").unwrap();
+        assert_eq!(decode_revert_reason(&out), None);
+
+        let err = RevertError::new(out.into());
+        assert_eq!(err.to_string(), "execution reverted");
+    }
 }
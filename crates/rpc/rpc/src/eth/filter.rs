@@ -8,10 +8,10 @@ use jsonrpsee::{
     server::{IdProvider, RandomIntegerIdProvider},
 };
 use reth_primitives::{
-    filter::{Filter, FilterBlockOption, FilteredParams},
-    Block, U256,
+    filter::{Filter, FilterBlockOption, FilteredParams, ValueOrArray},
+    Address, BlockNumber, Receipt, TxHash, H256, U256,
 };
-use reth_provider::{BlockProvider, EvmEnvProvider};
+use reth_provider::{BlockProvider, EvmEnvProvider, ReceiptProvider};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{FilterChanges, FilterId, Log};
 use reth_transaction_pool::TransactionPool;
@@ -51,7 +51,7 @@ impl<Client, Pool> EthFilter<Client, Pool> {
 #[async_trait]
 impl<Client, Pool> EthFilterApiServer for EthFilter<Client, Pool>
 where
-    Client: BlockProvider + EvmEnvProvider + 'static,
+    Client: BlockProvider + EvmEnvProvider + ReceiptProvider + 'static,
     Pool: TransactionPool + 'static,
 {
     async fn new_filter(&self, filter: Filter) -> RpcResult<FilterId> {
@@ -136,8 +136,25 @@ where
         }
     }
 
-    async fn filter_logs(&self, _id: FilterId) -> RpcResult<Vec<Log>> {
-        todo!()
+    /// Returns an array of all logs matching filter with given id.
+    ///
+    /// Returns an error if no matching log filter exists.
+    ///
+    /// Handler for `eth_getFilterLogs`
+    async fn filter_logs(&self, id: FilterId) -> RpcResult<Vec<Log>> {
+        let filter = {
+            let filters = self.inner.active_filters.inner.lock().await;
+            if let FilterKind::Log(ref filter) =
+                filters.get(&id).ok_or_else(|| FilterError::FilterNotFound(id.clone()))?.kind
+            {
+                *filter.clone()
+            } else {
+                // Not a log filter
+                return Err(FilterError::FilterNotFound(id).into())
+            }
+        };
+
+        self.inner.logs_for_filter(filter)
     }
 
     async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool> {
@@ -150,8 +167,12 @@ where
         }
     }
 
-    async fn logs(&self, _filter: Filter) -> RpcResult<Vec<Log>> {
-        todo!()
+    /// Returns logs matching given filter object.
+    ///
+    /// Handler for `eth_getLogs`
+    async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
+        trace!(target: "rpc::eth::filter", ?filter, "serving eth_getLogs");
+        self.inner.logs_for_filter(filter)
     }
 }
 
@@ -172,7 +193,7 @@ struct EthFilterInner<Client, Pool> {
 
 impl<Client, Pool> EthFilterInner<Client, Pool>
 where
-    Client: BlockProvider + EvmEnvProvider + 'static,
+    Client: BlockProvider + EvmEnvProvider + ReceiptProvider + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Installs a new filter and returns the new identifier.
@@ -191,16 +212,74 @@ where
         Ok(id)
     }
 
+    /// Returns logs matching given filter object.
+    fn logs_for_filter(&self, filter: Filter) -> RpcResult<Vec<Log>> {
+        match filter.block_option {
+            FilterBlockOption::AtBlockHash(block_hash) => {
+                // blockHash is equivalent to fromBlock = toBlock = the block number with
+                // hash blockHash
+                let block_number = self
+                    .client
+                    .block_number(block_hash)
+                    .to_rpc_result()?
+                    .ok_or(EthApiError::UnknownBlockNumber)?;
+
+                self.filter_logs(&filter, block_number, block_number)
+            }
+            FilterBlockOption::Range { from_block, to_block } => {
+                let info = self.client.chain_info().to_rpc_result()?;
+
+                // we start at the most recent block if unset in filter
+                let from_block_number = from_block
+                    .and_then(|num| info.convert_block_number(num.into()))
+                    .unwrap_or(info.best_number);
+
+                // and the to block is capped at the best number
+                let to_block_number = to_block
+                    .and_then(|num| info.convert_block_number(num.into()))
+                    .unwrap_or(info.best_number)
+                    .min(info.best_number);
+
+                if from_block_number > to_block_number {
+                    return Err(EthApiError::InvalidBlockRange.into())
+                }
+
+                self.filter_logs(&filter, from_block_number, to_block_number)
+            }
+        }
+    }
+
     /// Returns all logs in the given range that match the filter
     ///
     /// Returns an error if:
     ///  - underlying database error
     ///  - amount of matches exceeds configured limit
-    #[allow(dead_code)]
     fn filter_logs(&self, filter: &Filter, from_block: u64, to_block: u64) -> RpcResult<Vec<Log>> {
-        let mut logs = Vec::new();
+        let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
 
+        // use the log history index if it is available, it only returns candidate blocks
+        let (addresses, topics) = log_index_keys(filter);
+        if let Some(blocks) = self
+            .client
+            .blocks_by_log_index(&addresses, &topics, from_block..=to_block)
+            .to_rpc_result()?
+        {
+            for block_number in blocks {
+                let Some(header) = self.client.header_by_number(block_number).to_rpc_result()?
+                else {
+                    continue
+                };
+                self.append_block_logs(
+                    &mut all_logs,
+                    &filter_params,
+                    block_number,
+                    header.hash_slow(),
+                )?;
+            }
+            return Ok(all_logs)
+        }
+
         let topics =
             if filter.has_topics() { Some(filter_params.flat_topics.clone()) } else { None };
 
@@ -209,40 +288,86 @@ where
         let topics_filter = FilteredParams::topics_filter(&topics);
 
         for block_number in from_block..=to_block {
-            if let Some(block) = self.client.block_by_number(block_number).to_rpc_result()? {
-                // only if filter matches
-                if FilteredParams::matches_address(block.header.logs_bloom, &address_filter) &&
-                    FilteredParams::matches_topics(block.header.logs_bloom, &topics_filter)
-                {
-                    self.append_matching_block_logs(&mut logs, &filter_params, block);
-
-                    // TODO size check
-                }
+            let Some(header) = self.client.header_by_number(block_number).to_rpc_result()? else {
+                continue
+            };
+
+            // only if filter matches
+            if FilteredParams::matches_address(header.logs_bloom, &address_filter) &&
+                FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+            {
+                self.append_block_logs(
+                    &mut all_logs,
+                    &filter_params,
+                    block_number,
+                    header.hash_slow(),
+                )?;
             }
         }
 
-        Ok(logs)
+        Ok(all_logs)
     }
 
-    /// Appends all logs emitted in the `block` that match the `filter` to the `logs` vector.
-    #[allow(clippy::ptr_arg)]
-    fn append_matching_block_logs(
+    /// Appends the logs of the block that match the filter.
+    ///
+    /// Returns an error if the amount of matches exceeds the configured limit.
+    fn append_block_logs(
         &self,
-        _logs: &mut Vec<Log>,
-        _filter: &FilteredParams,
-        block: Block,
-    ) {
-        let _block_log_index: u32 = 0;
-        let _block_hash = block.hash_slow();
-
-        // loop over all transactions in the block
-        for tx in block.body {
-            let _transaction_log_index: u32 = 0;
-            let _transaction_hash = tx.hash;
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        block_number: u64,
+        block_hash: H256,
+    ) -> RpcResult<()> {
+        let block_id = block_number.into();
+
+        let (Some(transactions), Some(receipts)) = (
+            self.client.transactions_by_block(block_id).to_rpc_result()?,
+            self.client.receipts_by_block(block_id).to_rpc_result()?,
+        ) else {
+            return Ok(())
+        };
+
+        append_matching_block_logs(
+            all_logs,
+            filter_params,
+            (block_number, block_hash),
+            transactions.into_iter().map(|tx| tx.hash).zip(receipts),
+            false,
+        );
+
+        // size check
+        if all_logs.len() > self.max_logs_in_response {
+            return Err(FilterError::QueryExceedsMaxResults(self.max_logs_in_response).into())
         }
+        Ok(())
     }
 }
 
+/// Returns the addresses and the topics of every position of the filter to look up in the log
+/// history index.
+///
+/// A position that matches any topic is returned as an empty list.
+fn log_index_keys(filter: &Filter) -> (Vec<Address>, Vec<Vec<H256>>) {
+    let addresses = match &filter.address {
+        Some(ValueOrArray::Value(address)) => vec![*address],
+        Some(ValueOrArray::Array(addresses)) => addresses.clone(),
+        None => Vec::new(),
+    };
+    let topics = filter
+        .topics
+        .iter()
+        .map(|topic| match topic {
+            Some(ValueOrArray::Value(Some(topic))) => vec![*topic],
+            Some(ValueOrArray::Array(topics)) => {
+                // a wildcard in the list matches any topic
+                topics.iter().copied().collect::<Option<Vec<_>>>().unwrap_or_default()
+            }
+            _ => Vec::new(),
+        })
+        .collect();
+    (addresses, topics)
+}
+
 /// All active filters
 #[derive(Debug, Clone, Default)]
 pub struct ActiveFilters {
@@ -273,6 +398,8 @@ enum FilterKind {
 pub enum FilterError {
     #[error("filter not found")]
     FilterNotFound(FilterId),
+    #[error("Query exceeds max results {0}")]
+    QueryExceedsMaxResults(usize),
 }
 
 // convert the error
@@ -283,6 +410,116 @@ impl From<FilterError> for jsonrpsee::core::Error {
                 jsonrpsee::types::error::CALL_EXECUTION_FAILED_CODE,
                 "filter not found",
             ),
+            err @ FilterError::QueryExceedsMaxResults(_) => {
+                rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
+            }
+        }
+    }
+}
+
+/// Appends all logs emitted in the block that match the `filter` to the `all_logs` vector.
+///
+/// The receipts must be in the order of the block's transactions, since the log index is tracked
+/// across all receipts of the block. `removed` marks logs of blocks that were removed from the
+/// canonical chain.
+pub(crate) fn append_matching_block_logs(
+    all_logs: &mut Vec<Log>,
+    filter: &FilteredParams,
+    (block_number, block_hash): (BlockNumber, H256),
+    tx_and_receipts: impl IntoIterator<Item = (TxHash, Receipt)>,
+    removed: bool,
+) {
+    // tracks the index of a log in the entire block
+    let mut log_index: u32 = 0;
+
+    for (transaction_idx, (transaction_hash, receipt)) in tx_and_receipts.into_iter().enumerate() {
+        for (transaction_log_idx, log) in receipt.logs.into_iter().enumerate() {
+            if filter.filter_address(&log) && filter.filter_topics(&log) {
+                all_logs.push(Log {
+                    address: log.address,
+                    topics: log.topics,
+                    data: log.data,
+                    block_hash: Some(block_hash),
+                    block_number: Some(U256::from(block_number)),
+                    transaction_hash: Some(transaction_hash),
+                    transaction_index: Some(U256::from(transaction_idx)),
+                    log_index: Some(U256::from(log_index)),
+                    transaction_log_index: Some(U256::from(transaction_log_idx)),
+                    removed,
+                });
+            }
+            log_index += 1;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Log as PrimitiveLog, TxType};
+
+    fn receipt_with_logs(logs: Vec<PrimitiveLog>) -> Receipt {
+        Receipt {
+            tx_type: TxType::EIP1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            bloom: Default::default(),
+            logs,
+        }
+    }
+
+    #[test]
+    fn append_matching_logs_tracks_indices() {
+        let address = Address::random();
+        let other = Address::random();
+        let log = |address| PrimitiveLog { address, topics: vec![], data: Default::default() };
+
+        let filter = FilteredParams::new(Some(Filter::new().address(ValueOrArray::Value(address))));
+        let block_hash = H256::random();
+        let tx_and_receipts = vec![
+            (H256::random(), receipt_with_logs(vec![log(other), log(address)])),
+            (H256::random(), receipt_with_logs(vec![])),
+            (H256::random(), receipt_with_logs(vec![log(address), log(other)])),
+        ];
+        let tx_hashes = tx_and_receipts.iter().map(|(hash, _)| *hash).collect::<Vec<_>>();
+
+        let mut logs = Vec::new();
+        append_matching_block_logs(&mut logs, &filter, (10, block_hash), tx_and_receipts, false);
+
+        assert_eq!(logs.len(), 2);
+
+        assert_eq!(logs[0].transaction_hash, Some(tx_hashes[0]));
+        assert_eq!(logs[0].transaction_index, Some(U256::from(0)));
+        assert_eq!(logs[0].log_index, Some(U256::from(1)));
+        assert_eq!(logs[0].transaction_log_index, Some(U256::from(1)));
+
+        assert_eq!(logs[1].transaction_hash, Some(tx_hashes[2]));
+        assert_eq!(logs[1].transaction_index, Some(U256::from(2)));
+        assert_eq!(logs[1].log_index, Some(U256::from(2)));
+        assert_eq!(logs[1].transaction_log_index, Some(U256::from(0)));
+
+        assert!(logs.iter().all(|log| log.block_hash == Some(block_hash) &&
+            log.block_number == Some(U256::from(10)) &&
+            !log.removed));
+    }
+
+    #[test]
+    fn log_index_keys_skip_wildcards() {
+        let address = Address::random();
+        let topic = H256::random();
+        let other = H256::random();
+
+        let filter = Filter::new()
+            .address(address)
+            .topic0(topic)
+            .topic1(ValueOrArray::Array(vec![Some(topic), None]))
+            .topic2(vec![topic, other]);
+        let (addresses, topics) = log_index_keys(&filter);
+        assert_eq!(addresses, vec![address]);
+        assert_eq!(topics, vec![vec![topic], vec![], vec![topic, other], vec![]]);
+
+        let (addresses, topics) = log_index_keys(&Filter::new());
+        assert!(addresses.is_empty());
+        assert!(topics.iter().all(|topics| topics.is_empty()));
+    }
+}
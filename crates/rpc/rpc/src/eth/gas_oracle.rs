@@ -0,0 +1,365 @@
+//! An implementation of the eth gas price oracle, used for providing gas price estimates based on
+//! previous blocks.
+
+use crate::eth::{
+    cache::EthStateCache,
+    error::{EthApiError, EthResult},
+};
+use reth_primitives::{H256, U256};
+use reth_provider::BlockProvider;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// The number of transactions sampled in a block
+pub const SAMPLE_NUMBER: u32 = 3;
+
+/// The default maximum gas price to use for the estimate
+pub const DEFAULT_MAX_PRICE: U256 = U256::from_limbs([500_000_000_000u64, 0, 0, 0]);
+
+/// The default minimum gas price, under which the sample will be ignored
+pub const DEFAULT_IGNORE_PRICE: U256 = U256::from_limbs([2u64, 0, 0, 0]);
+
+/// Settings for the [GasPriceOracle]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasPriceOracleConfig {
+    /// The number of populated blocks to produce the gas price estimate
+    pub blocks: u32,
+
+    /// The percentile of gas prices to use for the estimate
+    pub percentile: u32,
+
+    /// The default gas price to use if there are no blocks to use
+    pub default: Option<U256>,
+
+    /// The maximum gas price to use for the estimate
+    pub max_price: Option<U256>,
+
+    /// The minimum gas price, under which the sample will be ignored
+    pub ignore_price: Option<U256>,
+}
+
+impl Default for GasPriceOracleConfig {
+    fn default() -> Self {
+        GasPriceOracleConfig {
+            blocks: 20,
+            percentile: 60,
+            default: None,
+            max_price: Some(DEFAULT_MAX_PRICE),
+            ignore_price: Some(DEFAULT_IGNORE_PRICE),
+        }
+    }
+}
+
+/// Calculates a gas price depending on recent blocks.
+///
+/// This mirrors geth's `gasprice.Oracle`: it samples the lowest effective priority fees of the
+/// most recent blocks and returns the configured percentile of all samples.
+#[derive(Debug)]
+pub struct GasPriceOracle<Client> {
+    /// The type used to get block and tx info
+    client: Client,
+    /// The cache for blocks
+    cache: EthStateCache,
+    /// The config for the oracle
+    oracle_config: GasPriceOracleConfig,
+    /// The latest calculated price and its block hash
+    last_price: Mutex<GasPriceOracleResult>,
+}
+
+impl<Client> GasPriceOracle<Client>
+where
+    Client: BlockProvider + 'static,
+{
+    /// Creates and returns the [GasPriceOracle].
+    pub fn new(
+        client: Client,
+        mut oracle_config: GasPriceOracleConfig,
+        cache: EthStateCache,
+    ) -> Self {
+        // sanitize the percentile to be less than 100
+        if oracle_config.percentile > 100 {
+            warn!(
+                target: "rpc::eth",
+                prev_percentile = ?oracle_config.percentile,
+                "Invalid configured gas price percentile, assuming 100."
+            );
+            oracle_config.percentile = 100;
+        }
+
+        let last_price = GasPriceOracleResult {
+            block_hash: H256::zero(),
+            price: oracle_config.default.unwrap_or_default(),
+        };
+
+        Self { client, oracle_config, last_price: Mutex::new(last_price), cache }
+    }
+
+    /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
+    pub async fn suggest_tip_cap(&self) -> EthResult<U256> {
+        let chain_info = self.client.chain_info()?;
+
+        // only one request computes a new price at a time, concurrent requests for the same head
+        // are served by the cached result
+        let mut last_price = self.last_price.lock().await;
+
+        // if we have stored a last price, then we check whether or not it was for the same head
+        if last_price.block_hash == chain_info.best_hash {
+            return Ok(last_price.price)
+        }
+
+        let max_blocks = self.oracle_config.blocks as u64 * 2;
+        // the number of blocks we want to check, this is extended for blocks that don't provide
+        // enough samples, up to `max_blocks`
+        let mut blocks_to_check = self.oracle_config.blocks as u64;
+        let mut checked_blocks = 0u64;
+
+        let mut results = Vec::new();
+        let mut current_hash = chain_info.best_hash;
+
+        while checked_blocks < blocks_to_check {
+            let Some((parent_hash, block_values)) =
+                self.get_block_values(current_hash, SAMPLE_NUMBER as usize).await?
+            else {
+                // the block is unknown, nothing more to sample
+                break
+            };
+            checked_blocks += 1;
+
+            // if the block is empty or all transactions are below the ignore price, we use the
+            // last calculated price as sample
+            let block_values =
+                if block_values.is_empty() { vec![last_price.price] } else { block_values };
+
+            // in order to collect enough data for sampling, try to query more blocks if the block
+            // provided only one sample
+            if block_values.len() <= 1 && blocks_to_check < max_blocks {
+                blocks_to_check += 1;
+            }
+
+            results.extend(block_values);
+
+            if current_hash == parent_hash || parent_hash.is_zero() {
+                // reached genesis
+                break
+            }
+            current_hash = parent_hash;
+        }
+
+        // sort results then take the configured percentile result
+        let mut price = last_price.price;
+        if !results.is_empty() {
+            results.sort_unstable();
+            price = results[(results.len() - 1) * self.oracle_config.percentile as usize / 100];
+        }
+
+        // constrain to the max price
+        if let Some(max_price) = self.oracle_config.max_price {
+            if price > max_price {
+                price = max_price;
+            }
+        }
+
+        *last_price = GasPriceOracleResult { block_hash: chain_info.best_hash, price };
+
+        Ok(price)
+    }
+
+    /// Get the `limit` lowest effective tip values for the given block, ignoring transactions that
+    /// are sent by the block's beneficiary or have a tip below the configured ignore price.
+    ///
+    /// Returns the parent hash of the block and the sampled tips, or `None` if the block is
+    /// unknown.
+    async fn get_block_values(
+        &self,
+        block_hash: H256,
+        limit: usize,
+    ) -> EthResult<Option<(H256, Vec<U256>)>> {
+        let Some(block) = self.cache.get_block(block_hash).await? else { return Ok(None) };
+
+        let base_fee = block.header.base_fee_per_gas;
+        let parent_hash = block.header.parent_hash;
+
+        // sort the transactions by their effective tip, transactions that can't pay the base fee
+        // are skipped
+        let mut txs = block
+            .body
+            .iter()
+            .filter_map(|tx| Some((tx.effective_tip_per_gas(base_fee)?, tx)))
+            .collect::<Vec<_>>();
+        txs.sort_unstable_by_key(|(tip, _)| *tip);
+
+        let ignore_price = self.oracle_config.ignore_price.unwrap_or_default();
+        let mut prices = Vec::with_capacity(limit);
+
+        for (tip, tx) in txs {
+            if prices.len() >= limit {
+                break
+            }
+
+            let tip = U256::from(tip);
+            if tip < ignore_price {
+                continue
+            }
+
+            // transactions of the block's beneficiary are not representative
+            let sender = tx.recover_signer().ok_or(EthApiError::InvalidTransactionSignature)?;
+            if sender == block.header.beneficiary {
+                continue
+            }
+
+            prices.push(tip);
+        }
+
+        Ok(Some((parent_hash, prices)))
+    }
+}
+
+/// Stores the last result that the oracle returned
+#[derive(Debug, Clone)]
+pub struct GasPriceOracleResult {
+    /// The block hash that the oracle used to calculate the price
+    pub block_hash: H256,
+    /// The price that the oracle calculated
+    pub price: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_interfaces::test_utils::generators::sign_message;
+    use reth_primitives::{
+        Address, Block, Header, Transaction, TransactionKind, TransactionSigned, TxEip1559,
+    };
+    use reth_provider::test_utils::MockEthProvider;
+
+    /// Signs an EIP-1559 transaction with the given key that pays the given tip on top of a zero
+    /// base fee.
+    fn transaction(key: u64, nonce: u64, tip: u128) -> TransactionSigned {
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: tip,
+            max_priority_fee_per_gas: tip,
+            to: TransactionKind::Call(Address::random()),
+            ..Default::default()
+        });
+        let signature =
+            sign_message(H256::from_low_u64_be(key), transaction.signature_hash()).unwrap();
+        TransactionSigned::from_transaction_and_signature(transaction, signature)
+    }
+
+    /// Returns a transaction of the same sender for every tip.
+    fn transactions(tips: &[u128]) -> Vec<TransactionSigned> {
+        tips.iter().enumerate().map(|(nonce, tip)| transaction(1, nonce as u64, *tip)).collect()
+    }
+
+    /// Appends a block on top of the chain of the provider and returns its hash.
+    fn add_block(
+        provider: &MockEthProvider,
+        beneficiary: Address,
+        body: Vec<TransactionSigned>,
+    ) -> H256 {
+        let parent = provider
+            .headers
+            .lock()
+            .iter()
+            .max_by_key(|(_, header)| header.number)
+            .map(|(hash, header)| (*hash, header.number));
+        let header = Header {
+            parent_hash: parent.map(|(hash, _)| hash).unwrap_or_default(),
+            number: parent.map(|(_, number)| number + 1).unwrap_or_default(),
+            beneficiary,
+            base_fee_per_gas: Some(0),
+            ..Default::default()
+        };
+        let hash = header.hash_slow();
+        provider.add_block(hash, Block { header, body, ..Default::default() });
+        hash
+    }
+
+    fn oracle(
+        provider: &MockEthProvider,
+        oracle_config: GasPriceOracleConfig,
+    ) -> GasPriceOracle<MockEthProvider> {
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        GasPriceOracle::new(provider.clone(), oracle_config, cache)
+    }
+
+    #[tokio::test]
+    async fn suggest_tip_cap_percentile() {
+        let provider = MockEthProvider::default();
+        // only the 3 lowest tips of every block are sampled
+        add_block(&provider, Address::random(), transactions(&[10, 20, 30, 40]));
+        add_block(&provider, Address::random(), transactions(&[50, 60, 70]));
+
+        // the samples are [10, 20, 30, 50, 60, 70]
+        for (percentile, expected) in [(0, 10u64), (60, 50), (100, 70)] {
+            let config = GasPriceOracleConfig { blocks: 2, percentile, ..Default::default() };
+            let price = oracle(&provider, config).suggest_tip_cap().await.unwrap();
+            assert_eq!(price, U256::from(expected), "percentile {percentile}");
+        }
+
+        // the price is capped by the max price
+        let config = GasPriceOracleConfig {
+            blocks: 2,
+            percentile: 100,
+            max_price: Some(U256::from(55)),
+            ..Default::default()
+        };
+        assert_eq!(oracle(&provider, config).suggest_tip_cap().await.unwrap(), U256::from(55));
+    }
+
+    #[tokio::test]
+    async fn suggest_tip_cap_skips_cheap_and_beneficiary_transactions() {
+        let provider = MockEthProvider::default();
+        let beneficiary = transaction(2, 0, 0).recover_signer().unwrap();
+        let mut body = transactions(&[1, 5, 7]);
+        body.push(transaction(2, 0, 3));
+        let hash = add_block(&provider, beneficiary, body);
+
+        let default_oracle = oracle(&provider, GasPriceOracleConfig::default());
+        // the tip of 1 is below the default ignore price and the tip of 3 is paid by the
+        // beneficiary
+        let (_, values) =
+            default_oracle.get_block_values(hash, SAMPLE_NUMBER as usize).await.unwrap().unwrap();
+        assert_eq!(values, vec![U256::from(5), U256::from(7)]);
+
+        let config = GasPriceOracleConfig { blocks: 1, percentile: 0, ..Default::default() };
+        let oracle = oracle(&provider, config);
+        assert_eq!(oracle.suggest_tip_cap().await.unwrap(), U256::from(5));
+    }
+
+    #[tokio::test]
+    async fn suggest_tip_cap_extends_blocks_with_few_samples() {
+        let provider = MockEthProvider::default();
+        add_block(&provider, Address::random(), transactions(&[100]));
+        add_block(&provider, Address::random(), transactions(&[10]));
+
+        // the head only provides a single sample, so its parent is sampled as well
+        let config = GasPriceOracleConfig { blocks: 1, percentile: 100, ..Default::default() };
+        assert_eq!(oracle(&provider, config).suggest_tip_cap().await.unwrap(), U256::from(100));
+    }
+
+    #[tokio::test]
+    async fn suggest_tip_cap_caches_per_head() {
+        let provider = MockEthProvider::default();
+        let first = add_block(&provider, Address::random(), transactions(&[10]));
+        let config = GasPriceOracleConfig { blocks: 1, percentile: 100, ..Default::default() };
+        let oracle = oracle(&provider, config);
+
+        assert_eq!(oracle.suggest_tip_cap().await.unwrap(), U256::from(10));
+        assert_eq!(oracle.last_price.lock().await.block_hash, first);
+
+        // a new head is sampled again
+        let second = add_block(&provider, Address::random(), transactions(&[50]));
+        assert_eq!(oracle.suggest_tip_cap().await.unwrap(), U256::from(50));
+        assert_eq!(oracle.last_price.lock().await.block_hash, second);
+
+        // the price of the same head is served from the cache
+        oracle.last_price.lock().await.price = U256::from(1);
+        assert_eq!(oracle.suggest_tip_cap().await.unwrap(), U256::from(1));
+    }
+}
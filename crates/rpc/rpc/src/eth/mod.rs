@@ -4,8 +4,10 @@ mod api;
 pub mod cache;
 pub(crate) mod error;
 mod filter;
+pub mod gas_oracle;
 mod pubsub;
-mod signer;
+pub(crate) mod revm_utils;
+pub mod signer;
 
 pub use api::{EthApi, EthApiSpec};
 pub use filter::EthFilter;
@@ -1,18 +1,25 @@
 //! `eth_` PubSub RPC handler implementation
 
+use crate::eth::filter::append_matching_block_logs;
 use jsonrpsee::{types::SubscriptionResult, SubscriptionSink};
-use reth_interfaces::events::ChainEventSubscriptions;
-use reth_primitives::{rpc::FilteredParams, TxHash};
+use reth_interfaces::{
+    events::{CanonicalBlock, ChainEventSubscriptions},
+    sync::{SyncState, SyncStateSubscriptions},
+};
+use reth_primitives::{filter::FilteredParams, TxHash};
 use reth_provider::{BlockProvider, EvmEnvProvider};
 use reth_rpc_api::EthPubSubApiServer;
 use reth_rpc_types::{
-    pubsub::{Params, SubscriptionKind, SubscriptionResult as EthSubscriptionResult},
-    Header,
+    pubsub::{
+        Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
+        SyncStatusMetadata,
+    },
+    Header, Log,
 };
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::TransactionPool;
 use tokio_stream::{
-    wrappers::{ReceiverStream, UnboundedReceiverStream},
+    wrappers::{ReceiverStream, UnboundedReceiverStream, WatchStream},
     Stream, StreamExt,
 };
 
@@ -20,21 +27,21 @@ use tokio_stream::{
 ///
 /// This handles `eth_subscribe` RPC calls.
 #[derive(Clone)]
-pub struct EthPubSub<Client, Pool, Events> {
+pub struct EthPubSub<Client, Pool, Events, Network> {
     /// All nested fields bundled together.
-    inner: EthPubSubInner<Client, Pool, Events>,
+    inner: EthPubSubInner<Client, Pool, Events, Network>,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
 }
 
 // === impl EthPubSub ===
 
-impl<Client, Pool, Events> EthPubSub<Client, Pool, Events> {
+impl<Client, Pool, Events, Network> EthPubSub<Client, Pool, Events, Network> {
     /// Creates a new, shareable instance.
     ///
     /// Subscription tasks are spawned via [tokio::task::spawn]
-    pub fn new(client: Client, pool: Pool, chain_events: Events) -> Self {
-        Self::with_spawner(client, pool, chain_events, Box::<TokioTaskExecutor>::default())
+    pub fn new(client: Client, pool: Pool, chain_events: Events, network: Network) -> Self {
+        Self::with_spawner(client, pool, chain_events, network, Box::<TokioTaskExecutor>::default())
     }
 
     /// Creates a new, shareable instance.
@@ -42,18 +49,20 @@ impl<Client, Pool, Events> EthPubSub<Client, Pool, Events> {
         client: Client,
         pool: Pool,
         chain_events: Events,
+        network: Network,
         subscription_task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
-        let inner = EthPubSubInner { client, pool, chain_events };
+        let inner = EthPubSubInner { client, pool, chain_events, network };
         Self { inner, subscription_task_spawner }
     }
 }
 
-impl<Client, Pool, Events> EthPubSubApiServer for EthPubSub<Client, Pool, Events>
+impl<Client, Pool, Events, Network> EthPubSubApiServer for EthPubSub<Client, Pool, Events, Network>
 where
     Client: BlockProvider + EvmEnvProvider + Clone + 'static,
     Pool: TransactionPool + 'static,
     Events: ChainEventSubscriptions + Clone + 'static,
+    Network: SyncStateSubscriptions + Clone + 'static,
 {
     fn subscribe(
         &self,
@@ -73,8 +82,8 @@ where
 }
 
 /// The actual handler for and accepted [`EthPubSub::subscribe`] call.
-async fn handle_accepted<Client, Pool, Events>(
-    pubsub: EthPubSubInner<Client, Pool, Events>,
+async fn handle_accepted<Client, Pool, Events, Network>(
+    pubsub: EthPubSubInner<Client, Pool, Events, Network>,
     mut accepted_sink: SubscriptionSink,
     kind: SubscriptionKind,
     params: Option<Params>,
@@ -82,13 +91,8 @@ async fn handle_accepted<Client, Pool, Events>(
     Client: BlockProvider + EvmEnvProvider + 'static,
     Pool: TransactionPool + 'static,
     Events: ChainEventSubscriptions + 'static,
+    Network: SyncStateSubscriptions + 'static,
 {
-    // if no params are provided, used default filter params
-    let _params = match params {
-        Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
-        _ => FilteredParams::default(),
-    };
-
     match kind {
         SubscriptionKind::NewHeads => {
             let stream = pubsub
@@ -97,7 +101,14 @@ async fn handle_accepted<Client, Pool, Events>(
             accepted_sink.pipe_from_stream(stream).await;
         }
         SubscriptionKind::Logs => {
-            // TODO subscribe new blocks -> fetch logs via bloom
+            // if no params are provided, used default filter params
+            let filter = match params {
+                Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
+                _ => FilteredParams::default(),
+            };
+            let stream =
+                pubsub.into_log_stream(filter).map(|log| EthSubscriptionResult::Log(Box::new(log)));
+            accepted_sink.pipe_from_stream(stream).await;
         }
         SubscriptionKind::NewPendingTransactions => {
             let stream = pubsub
@@ -106,12 +117,13 @@ async fn handle_accepted<Client, Pool, Events>(
             accepted_sink.pipe_from_stream(stream).await;
         }
         SubscriptionKind::Syncing => {
-            // TODO subscribe new blocks -> read is_syncing from network
+            let stream = pubsub.into_sync_status_stream().map(EthSubscriptionResult::SyncState);
+            accepted_sink.pipe_from_stream(stream).await;
         }
     }
 }
 
-impl<Client, Pool, Events> std::fmt::Debug for EthPubSub<Client, Pool, Events> {
+impl<Client, Pool, Events, Network> std::fmt::Debug for EthPubSub<Client, Pool, Events, Network> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("EthPubSub").finish_non_exhaustive()
     }
@@ -119,18 +131,20 @@ impl<Client, Pool, Events> std::fmt::Debug for EthPubSub<Client, Pool, Events> {
 
 /// Container type `EthPubSub`
 #[derive(Clone)]
-struct EthPubSubInner<Client, Pool, Events> {
+struct EthPubSubInner<Client, Pool, Events, Network> {
     /// The transaction pool.
     pool: Pool,
     /// The client that can interact with the chain.
     client: Client,
     /// A type that allows to create new event subscriptions,
     chain_events: Events,
+    /// The network that publishes the sync state.
+    network: Network,
 }
 
 // == impl EthPubSubInner ===
 
-impl<Client, Pool, Events> EthPubSubInner<Client, Pool, Events>
+impl<Client, Pool, Events, Network> EthPubSubInner<Client, Pool, Events, Network>
 where
     Pool: TransactionPool + 'static,
 {
@@ -140,7 +154,7 @@ where
     }
 }
 
-impl<Client, Pool, Events> EthPubSubInner<Client, Pool, Events>
+impl<Client, Pool, Events, Network> EthPubSubInner<Client, Pool, Events, Network>
 where
     Client: BlockProvider + EvmEnvProvider + 'static,
     Events: ChainEventSubscriptions + 'static,
@@ -151,4 +165,176 @@ where
             Header::from_primitive_with_hash(new_block.header.as_ref().clone(), new_block.hash)
         })
     }
+
+    /// Returns a stream that yields all logs of new canonical blocks that match the filter.
+    ///
+    /// If the canonical chain is reorged, the matching logs of the blocks that are no longer
+    /// canonical are yielded first with `removed` set, starting with the highest block.
+    fn into_log_stream(self, filter: FilteredParams) -> impl Stream<Item = Log> {
+        let notifications = UnboundedReceiverStream::new(self.chain_events.subscribe_canon_state())
+            .map(move |notification| {
+                let mut logs = Vec::new();
+                for block in notification.reverted().iter().rev() {
+                    append_canonical_block_logs(&mut logs, &filter, block, true);
+                }
+                for block in notification.committed() {
+                    append_canonical_block_logs(&mut logs, &filter, block, false);
+                }
+                logs
+            });
+        futures::StreamExt::flat_map(notifications, futures::stream::iter)
+    }
+}
+
+impl<Client, Pool, Events, Network> EthPubSubInner<Client, Pool, Events, Network>
+where
+    Client: BlockProvider + EvmEnvProvider + 'static,
+    Network: SyncStateSubscriptions + 'static,
+{
+    /// Returns a stream that yields the current sync status and every time the node starts or
+    /// stops syncing.
+    fn into_sync_status_stream(self) -> impl Stream<Item = PubSubSyncStatus> {
+        let client = self.client;
+        let mut is_syncing = None;
+        WatchStream::new(self.network.subscribe_sync_state()).filter_map(move |state| {
+            // only changes between syncing and idle are reported
+            let syncing = state.is_syncing();
+            if is_syncing.replace(syncing) == Some(syncing) {
+                return None
+            }
+            let target_block = match state {
+                SyncState::Idle => return Some(PubSubSyncStatus::Simple(false)),
+                SyncState::Downloading { target_block } | SyncState::Executing { target_block } => {
+                    target_block
+                }
+            };
+            let current_block =
+                client.chain_info().map(|info| info.best_number).unwrap_or_default();
+            Some(PubSubSyncStatus::Detailed(SyncStatusMetadata {
+                syncing: true,
+                starting_block: current_block,
+                current_block,
+                highest_block: Some(target_block),
+            }))
+        })
+    }
+}
+
+/// Appends the logs of the canonical block that match the filter.
+fn append_canonical_block_logs(
+    logs: &mut Vec<Log>,
+    filter: &FilteredParams,
+    block: &CanonicalBlock,
+    removed: bool,
+) {
+    append_matching_block_logs(
+        logs,
+        filter,
+        (block.block.number, block.block.hash()),
+        block.block.body.iter().map(|tx| tx.hash).zip(block.receipts.iter().cloned()),
+        removed,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_interfaces::{
+        events::CanonStateNotification, sync::SyncStateNotifications,
+        test_utils::TestChainEventSubscriptions,
+    };
+    use reth_network_api::test_utils::NoopNetwork;
+    use reth_primitives::{
+        filter::{Filter, ValueOrArray},
+        Address, Log as PrimitiveLog, Receipt, SealedBlock, TransactionSigned,
+    };
+    use reth_provider::test_utils::NoopProvider;
+    use reth_transaction_pool::test_utils::testing_pool;
+    use std::sync::Arc;
+
+    fn block_with_logs(number: u64, address: Address) -> CanonicalBlock {
+        let mut block = SealedBlock::default();
+        block.header.number = number;
+        block.header = block.header.unseal().seal_slow();
+        block.body = vec![TransactionSigned::default()];
+        let log = PrimitiveLog { address, topics: vec![], data: Default::default() };
+        let receipt = Receipt { logs: vec![log], ..Default::default() };
+        CanonicalBlock { block, receipts: vec![receipt], changed_accounts: Vec::new() }
+    }
+
+    #[tokio::test]
+    async fn log_stream_reports_removed_logs() {
+        let events = TestChainEventSubscriptions::default();
+        let pubsub = EthPubSubInner {
+            pool: testing_pool(),
+            client: NoopProvider::default(),
+            chain_events: events.clone(),
+            network: NoopNetwork,
+        };
+        let address = Address::random();
+        let filter = FilteredParams::new(Some(Filter::new().address(ValueOrArray::Value(address))));
+        let mut logs = Box::pin(pubsub.into_log_stream(filter));
+
+        let old = block_with_logs(1, address);
+        let new = block_with_logs(2, address);
+        let other = block_with_logs(2, Address::random());
+
+        events.notify_canon_state(CanonStateNotification::Commit {
+            new: Arc::new(vec![old.clone(), other]),
+        });
+        events.notify_canon_state(CanonStateNotification::Reorg {
+            old: Arc::new(vec![old.clone()]),
+            new: Arc::new(vec![new.clone()]),
+        });
+
+        let log = logs.next().await.unwrap();
+        assert_eq!(log.block_hash, Some(old.block.hash()));
+        assert!(!log.removed);
+
+        let log = logs.next().await.unwrap();
+        assert_eq!(log.block_hash, Some(old.block.hash()));
+        assert!(log.removed);
+
+        let log = logs.next().await.unwrap();
+        assert_eq!(log.block_hash, Some(new.block.hash()));
+        assert!(!log.removed);
+    }
+
+    #[tokio::test]
+    async fn sync_status_stream_reports_changes() {
+        #[derive(Clone)]
+        struct TestNetwork(Arc<tokio::sync::watch::Sender<SyncState>>);
+
+        impl SyncStateSubscriptions for TestNetwork {
+            fn subscribe_sync_state(&self) -> SyncStateNotifications {
+                self.0.subscribe()
+            }
+        }
+
+        let network = TestNetwork(Arc::new(tokio::sync::watch::channel(SyncState::Idle).0));
+        let pubsub = EthPubSubInner {
+            pool: testing_pool(),
+            client: NoopProvider::default(),
+            chain_events: TestChainEventSubscriptions::default(),
+            network: network.clone(),
+        };
+        let mut statuses = Box::pin(pubsub.into_sync_status_stream());
+        assert_eq!(statuses.next().await, Some(PubSubSyncStatus::Simple(false)));
+
+        network.0.send_replace(SyncState::Downloading { target_block: 100 });
+        assert_eq!(
+            statuses.next().await,
+            Some(PubSubSyncStatus::Detailed(SyncStatusMetadata {
+                syncing: true,
+                starting_block: 0,
+                current_block: 0,
+                highest_block: Some(100),
+            }))
+        );
+
+        // switching between syncing stages is not reported
+        network.0.send_replace(SyncState::Executing { target_block: 100 });
+        network.0.send_replace(SyncState::Idle);
+        assert_eq!(statuses.next().await, Some(PubSubSyncStatus::Simple(false)));
+    }
 }
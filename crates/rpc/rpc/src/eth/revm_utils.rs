@@ -0,0 +1,334 @@
+//! utilities for working with revm
+
+use crate::eth::error::{EthApiError, EthResult, InvalidTransactionError};
+use reth_primitives::{AccessList, Address, Bytes, TransactionSigned, KECCAK_EMPTY, U128, U256};
+use reth_revm::env::fill_tx_env;
+use reth_rpc_types::{
+    state::{AccountOverride, BlockOverrides, StateOverride},
+    CallRequest,
+};
+use revm::{
+    db::{CacheDB, DatabaseRef},
+    precompile::{Precompiles, SpecId as PrecompilesSpecId},
+    primitives::{
+        AccountInfo, BlockEnv, Bytecode, CfgEnv, Env, ResultAndState, SpecId, TransactTo, TxEnv,
+    },
+    Database, DatabaseCommit, Inspector,
+};
+
+/// Executes the [Env] against the given [Database] without committing state changes.
+pub(crate) fn transact<S>(db: S, env: Env) -> EthResult<(ResultAndState, Env)>
+where
+    S: Database,
+    <S as Database>::Error: Into<EthApiError>,
+{
+    let mut evm = revm::EVM::with_env(env);
+    evm.database(db);
+    let res = evm.transact()?;
+    Ok((res, evm.env))
+}
+
+/// Executes the [Env] against the given [Database] without committing state changes.
+pub(crate) fn inspect<S, I>(db: S, env: Env, inspector: I) -> EthResult<(ResultAndState, Env)>
+where
+    S: Database,
+    <S as Database>::Error: Into<EthApiError>,
+    I: Inspector<S>,
+{
+    let mut evm = revm::EVM::with_env(env);
+    evm.database(db);
+    let res = evm.inspect(inspector)?;
+    Ok((res, evm.env))
+}
+
+/// Recovers the signer of the transaction and fills the [TxEnv] with it.
+pub(crate) fn fill_tx_env_with_recovered(
+    tx_env: &mut TxEnv,
+    tx: &TransactionSigned,
+) -> EthResult<()> {
+    let sender = tx.recover_signer().ok_or(EthApiError::InvalidTransactionSignature)?;
+    fill_tx_env(tx_env, tx, sender);
+    Ok(())
+}
+
+/// Executes the given transactions with the [Env] and commits their changes to the _runtime_ db
+/// ([CacheDB]).
+///
+/// This is used to reach the state of a block right before one of its transactions.
+pub(crate) fn replay_transactions<'a, DB, I>(
+    db: &mut CacheDB<DB>,
+    env: &mut Env,
+    transactions: I,
+) -> EthResult<()>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+    I: IntoIterator<Item = &'a TransactionSigned>,
+{
+    for tx in transactions {
+        fill_tx_env_with_recovered(&mut env.tx, tx)?;
+        let (res, _) = transact(&mut *db, env.clone())?;
+        db.commit(res.state);
+    }
+    Ok(())
+}
+
+/// Returns `true` if the account is empty as defined by EIP-161.
+pub(crate) fn is_empty_account(info: &AccountInfo) -> bool {
+    info.balance == U256::ZERO && info.nonce == 0 && info.code_hash == KECCAK_EMPTY
+}
+
+/// Returns the bytecode of the account, loading it from the database if it is not set.
+pub(crate) fn account_code<DB>(info: &AccountInfo, db: &mut CacheDB<DB>) -> EthResult<Bytes>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    if info.code_hash == KECCAK_EMPTY {
+        return Ok(Bytes::default())
+    }
+    let code = match &info.code {
+        Some(code) => code.clone(),
+        None => db.code_by_hash(info.code_hash)?,
+    };
+    Ok(code.bytes()[..code.len()].to_vec().into())
+}
+
+/// Creates a new [Env] to be used for executing the [CallRequest] in `eth_call`
+pub(crate) fn build_call_evm_env(
+    mut cfg: CfgEnv,
+    block: BlockEnv,
+    request: CallRequest,
+) -> EthResult<Env> {
+    let tx = create_txn_env(&block, request)?;
+    Ok(Env { cfg, block, tx })
+}
+
+/// Configures a new [TxEnv]  for the [CallRequest]
+fn create_txn_env(block_env: &BlockEnv, request: CallRequest) -> EthResult<TxEnv> {
+    let CallRequest {
+        from,
+        to,
+        gas_price,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        gas,
+        value,
+        data,
+        nonce,
+        access_list,
+        chain_id,
+    } = request;
+
+    let CallFees { max_priority_fee_per_gas, gas_price } =
+        CallFees::ensure_fees(gas_price, max_fee_per_gas, max_priority_fee_per_gas)?;
+
+    let gas_limit = gas.unwrap_or(block_env.gas_limit.min(U256::from(u64::MAX)));
+
+    let env = TxEnv {
+        gas_limit: gas_limit.try_into().map_err(|_| InvalidTransactionError::GasUintOverflow)?,
+        nonce: nonce
+            .map(|n| n.try_into().map_err(|n| InvalidTransactionError::NonceTooHigh))
+            .transpose()?,
+        caller: from.unwrap_or_default(),
+        gas_price,
+        gas_priority_fee: max_priority_fee_per_gas,
+        transact_to: to.map(TransactTo::Call).unwrap_or_else(TransactTo::create),
+        value: value.unwrap_or_default(),
+        data: data.map(|data| data.0).unwrap_or_default(),
+        chain_id: chain_id.map(|c| c.as_u64()),
+        access_list: access_list.map(AccessList::flattened).unwrap_or_default(),
+    };
+
+    Ok(env)
+}
+
+/// Helper type for representing the fees of a [CallRequest]
+struct CallFees {
+    /// EIP-1559 priority fee
+    max_priority_fee_per_gas: Option<U256>,
+    /// Unified gas price setting
+    ///
+    /// Will be `0` if unset in request
+    ///
+    /// `gasPrice` for legacy,
+    /// `maxFeePerGas` for EIP-1559
+    gas_price: U256,
+}
+
+// === impl CallFees ===
+
+impl CallFees {
+    /// Ensures the fields of a [CallRequest] are not conflicting
+    fn ensure_fees(
+        call_gas_price: Option<U128>,
+        call_max_fee: Option<U128>,
+        call_priority_fee: Option<U128>,
+    ) -> EthResult<CallFees> {
+        match (call_gas_price, call_max_fee, call_priority_fee) {
+            (gas_price, None, None) => {
+                // request for a legacy transaction
+                // set everything to zero
+                let gas_price = gas_price.unwrap_or_default();
+                Ok(CallFees { gas_price: U256::from(gas_price), max_priority_fee_per_gas: None })
+            }
+            (None, max_fee_per_gas, max_priority_fee_per_gas) => {
+                // request for eip-1559 transaction
+                let max_fee = max_fee_per_gas.unwrap_or_default();
+
+                if let Some(max_priority) = max_priority_fee_per_gas {
+                    if max_priority > max_fee {
+                        // Fail early
+                        return Err(
+                            // `max_priority_fee_per_gas` is greater than the `max_fee_per_gas`
+                            InvalidTransactionError::TipAboveFeeCap.into(),
+                        )
+                    }
+                }
+                Ok(CallFees {
+                    gas_price: U256::from(max_fee),
+                    max_priority_fee_per_gas: max_priority_fee_per_gas.map(U256::from),
+                })
+            }
+            (Some(gas_price), Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => {
+                Err(EthApiError::ConflictingRequestGasPriceAndTipSet {
+                    gas_price,
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                })
+            }
+            (Some(gas_price), Some(max_fee_per_gas), None) => {
+                Err(EthApiError::ConflictingRequestGasPrice { gas_price, max_fee_per_gas })
+            }
+            (Some(gas_price), None, Some(max_priority_fee_per_gas)) => {
+                Err(EthApiError::RequestLegacyGasPriceAndTipSet {
+                    gas_price,
+                    max_priority_fee_per_gas,
+                })
+            }
+        }
+    }
+}
+
+/// Applies the given block overrides to the [BlockEnv].
+pub(crate) fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
+    let BlockOverrides { number, difficulty, time, gas_limit, coinbase, random, base_fee } =
+        overrides;
+
+    if let Some(number) = number {
+        env.number = number;
+    }
+    if let Some(difficulty) = difficulty {
+        env.difficulty = difficulty;
+    }
+    if let Some(time) = time {
+        env.timestamp = U256::from(time.as_u64());
+    }
+    if let Some(gas_limit) = gas_limit {
+        env.gas_limit = U256::from(gas_limit.as_u64());
+    }
+    if let Some(coinbase) = coinbase {
+        env.coinbase = coinbase;
+    }
+    if let Some(random) = random {
+        env.prevrandao = Some(random);
+    }
+    if let Some(base_fee) = base_fee {
+        env.basefee = base_fee;
+    }
+}
+
+/// Applies the given state overrides (a set of [AccountOverride]) to the [CacheDB].
+pub(crate) fn apply_state_overrides<DB>(
+    overrides: StateOverride,
+    db: &mut CacheDB<DB>,
+) -> EthResult<()>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    for (account, account_overrides) in overrides {
+        apply_account_override(account, account_overrides, db)?;
+    }
+    Ok(())
+}
+
+/// Applies a single [AccountOverride] to the [CacheDB].
+fn apply_account_override<DB>(
+    account: Address,
+    account_override: AccountOverride,
+    db: &mut CacheDB<DB>,
+) -> EthResult<()>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    let mut account_info = db.basic(account)?.unwrap_or_default();
+
+    if let Some(nonce) = account_override.nonce {
+        account_info.nonce = nonce.as_u64();
+    }
+    if let Some(code) = account_override.code {
+        account_info.code = Some(Bytecode::new_raw(code.0));
+    }
+    if let Some(balance) = account_override.balance {
+        account_info.balance = balance;
+    }
+
+    db.insert_account_info(account, account_info);
+
+    // We ensure that not both state and state_diff are set.
+    // If state is set, we must mark the account as "NewlyCreated", so that the old storage
+    // isn't read from
+    match (account_override.state, account_override.state_diff) {
+        (Some(_), Some(_)) => return Err(EthApiError::BothStateAndStateDiffInOverride(account)),
+        (None, None) => {
+            // nothing to do
+        }
+        (Some(new_account_state), None) => {
+            db.replace_account_storage(
+                account,
+                new_account_state
+                    .into_iter()
+                    .map(|(slot, value)| {
+                        (U256::from_be_bytes(slot.0), U256::from_be_bytes(value.0))
+                    })
+                    .collect(),
+            )?;
+        }
+        (None, Some(account_state_diff)) => {
+            for (slot, value) in account_state_diff {
+                db.insert_account_storage(
+                    account,
+                    U256::from_be_bytes(slot.0),
+                    U256::from_be_bytes(value.0),
+                )?;
+            }
+        }
+    };
+
+    Ok(())
+}
+
+/// Returns the addresses of the precompiles corresponding to the SpecId.
+pub(crate) fn get_precompiles(spec_id: &SpecId) -> Vec<Address> {
+    let spec = match spec_id {
+        SpecId::FRONTIER | SpecId::FRONTIER_THAWING => return vec![],
+        SpecId::HOMESTEAD | SpecId::DAO_FORK | SpecId::TANGERINE | SpecId::SPURIOUS_DRAGON => {
+            PrecompilesSpecId::HOMESTEAD
+        }
+        SpecId::BYZANTIUM | SpecId::CONSTANTINOPLE | SpecId::PETERSBURG => {
+            PrecompilesSpecId::BYZANTIUM
+        }
+        SpecId::ISTANBUL | SpecId::MUIR_GLACIER => PrecompilesSpecId::ISTANBUL,
+        SpecId::BERLIN |
+        SpecId::LONDON |
+        SpecId::ARROW_GLACIER |
+        SpecId::GRAY_GLACIER |
+        SpecId::MERGE |
+        SpecId::SHANGHAI |
+        SpecId::CANCUN => PrecompilesSpecId::BERLIN,
+        SpecId::LATEST => PrecompilesSpecId::LATEST,
+    };
+    Precompiles::new(spec).addresses().into_iter().map(Address::from).collect()
+}
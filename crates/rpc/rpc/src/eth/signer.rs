@@ -1,14 +1,23 @@
 //! An abstraction over ethereum signers.
 
-use jsonrpsee::core::RpcResult as Result;
-use reth_primitives::{Address, Signature, TransactionSigned};
+use ethers_core::types::transaction::eip712::{Eip712, TypedData};
+use parking_lot::RwLock;
+use reth_primitives::{keccak256, Address, Signature, TransactionSigned, H256, U256};
 use reth_rpc_types::TypedTransactionRequest;
-use secp256k1::SecretKey;
-use std::collections::HashMap;
+use secp256k1::{Message, PublicKey, SecretKey, SECP256K1};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+type Result<T> = std::result::Result<T, SignError>;
 
 /// An Ethereum Signer used via RPC.
 #[async_trait::async_trait]
-pub(crate) trait EthSigner: Send + Sync {
+pub trait EthSigner: fmt::Debug + Send + Sync {
     /// Returns the available accounts for this signer.
     fn accounts(&self) -> Vec<Address>;
 
@@ -17,7 +26,8 @@ pub(crate) trait EthSigner: Send + Sync {
         self.accounts().contains(addr)
     }
 
-    /// Returns the signature
+    /// Returns the signature of the [EIP-191](https://eips.ethereum.org/EIPS/eip-191) prefixed
+    /// message.
     async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature>;
 
     /// signs a transaction request using the given account in request
@@ -26,14 +36,69 @@ pub(crate) trait EthSigner: Send + Sync {
         request: TypedTransactionRequest,
         address: &Address,
     ) -> Result<TransactionSigned>;
+
+    /// Returns the signature of the [EIP-712](https://eips.ethereum.org/EIPS/eip-712) hash of the
+    /// typed data.
+    fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature>;
+}
+
+/// Errors that can occur when signing.
+#[derive(Debug, thiserror::Error)]
+pub enum SignError {
+    /// Error occurred while trying to sign data.
+    #[error("could not sign")]
+    CouldNotSign,
+    /// No signer for the requested account.
+    #[error("unknown account")]
+    NoAccount,
+    /// The account is known but its key has not been unlocked.
+    #[error("authentication needed: password or unlock")]
+    Locked,
+    /// The typed data could not be hashed.
+    #[error("given typed data is not valid")]
+    InvalidTypedData,
+    /// The transaction request does not fit into a transaction.
+    #[error("invalid transaction request")]
+    InvalidTransactionRequest,
+    /// The key of a keystore file does not belong to the account it is stored for.
+    #[error("keystore file of account {0:?} contains a different key")]
+    KeystoreKeyMismatch(Address),
+    /// Error reading a keystore directory.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Error decrypting a keystore file.
+    #[error(transparent)]
+    Keystore(#[from] eth_keystore::KeystoreError),
 }
 
 /// Holds developer keys
-pub(crate) struct DevSigner {
+#[derive(Debug, Clone)]
+pub struct DevSigner {
     addresses: Vec<Address>,
     accounts: HashMap<Address, SecretKey>,
 }
 
+// === impl DevSigner ===
+
+impl DevSigner {
+    /// Creates a new signer for the given secret keys.
+    pub fn new(keys: impl IntoIterator<Item = SecretKey>) -> Self {
+        let mut addresses = Vec::new();
+        let mut accounts = HashMap::new();
+        for key in keys {
+            let address = secret_key_to_address(&key);
+            if accounts.insert(address, key).is_none() {
+                addresses.push(address);
+            }
+        }
+        Self { addresses, accounts }
+    }
+
+    fn get_key(&self, address: &Address) -> Result<&SecretKey> {
+        self.accounts.get(address).ok_or(SignError::NoAccount)
+    }
+}
+
 #[async_trait::async_trait]
 impl EthSigner for DevSigner {
     fn accounts(&self) -> Vec<Address> {
@@ -44,15 +109,347 @@ impl EthSigner for DevSigner {
         self.accounts.contains_key(addr)
     }
 
-    async fn sign(&self, _address: Address, _message: &[u8]) -> Result<Signature> {
-        todo!()
+    async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature> {
+        sign_hash(self.get_key(&address)?, eip191_hash_message(message))
+    }
+
+    fn sign_transaction(
+        &self,
+        request: TypedTransactionRequest,
+        address: &Address,
+    ) -> Result<TransactionSigned> {
+        sign_transaction_request(self.get_key(address)?, request)
+    }
+
+    fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
+        sign_hash(self.get_key(&address)?, eip712_hash(payload)?)
+    }
+}
+
+/// A signer for the accounts of a keystore directory, holding geth compatible encrypted
+/// [V3 keystore](https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/)
+/// files.
+///
+/// All accounts of the directory are listed, but an account can only sign after it has been
+/// unlocked with its password via [KeystoreSigner::unlock].
+#[derive(Debug)]
+pub struct KeystoreSigner {
+    /// All accounts of the keystore, in the order of their files
+    addresses: Vec<Address>,
+    /// The keystore file of each account
+    files: HashMap<Address, PathBuf>,
+    /// The decrypted keys of unlocked accounts
+    unlocked: RwLock<HashMap<Address, SecretKey>>,
+}
+
+// === impl KeystoreSigner ===
+
+impl KeystoreSigner {
+    /// Opens the keystore directory and collects the accounts of all keystore files in it.
+    ///
+    /// Files that are not keystore files are skipped. A missing directory is treated as an empty
+    /// keystore.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let mut paths = match std::fs::read_dir(dir) {
+            Ok(entries) => entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<std::io::Result<Vec<_>>>()?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+        // geth's key file names start with the creation time, so this keeps the accounts in
+        // creation order
+        paths.sort();
+
+        let mut addresses = Vec::new();
+        let mut files = HashMap::new();
+        for path in paths.into_iter().filter(|path| path.is_file()) {
+            let Some(address) = read_keystore_address(&path) else {
+                debug!(target: "rpc::eth", ?path, "Skipping non keystore file");
+                continue
+            };
+            if files.insert(address, path).is_none() {
+                addresses.push(address);
+            }
+        }
+
+        Ok(Self { addresses, files, unlocked: Default::default() })
+    }
+
+    /// Decrypts the key of the account with the given password, so the account can sign.
+    pub fn unlock(&self, address: Address, password: impl AsRef<[u8]>) -> Result<()> {
+        let path = self.files.get(&address).ok_or(SignError::NoAccount)?;
+        let key = eth_keystore::decrypt_key(path, password)?;
+        let key =
+            SecretKey::from_slice(&key).map_err(|_| SignError::KeystoreKeyMismatch(address))?;
+        if secret_key_to_address(&key) != address {
+            return Err(SignError::KeystoreKeyMismatch(address))
+        }
+        self.unlocked.write().insert(address, key);
+        Ok(())
+    }
+
+    /// Removes the decrypted key of the account.
+    pub fn lock(&self, address: &Address) {
+        self.unlocked.write().remove(address);
+    }
+
+    /// Returns `true` if the account is unlocked.
+    pub fn is_unlocked(&self, address: &Address) -> bool {
+        self.unlocked.read().contains_key(address)
+    }
+
+    /// Returns the decrypted key of the account.
+    fn get_key(&self, address: &Address) -> Result<SecretKey> {
+        if let Some(key) = self.unlocked.read().get(address) {
+            return Ok(*key)
+        }
+        if self.files.contains_key(address) {
+            Err(SignError::Locked)
+        } else {
+            Err(SignError::NoAccount)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthSigner for KeystoreSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.addresses.clone()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.files.contains_key(addr)
+    }
+
+    async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature> {
+        sign_hash(&self.get_key(&address)?, eip191_hash_message(message))
     }
 
     fn sign_transaction(
         &self,
-        _request: TypedTransactionRequest,
-        _address: &Address,
+        request: TypedTransactionRequest,
+        address: &Address,
     ) -> Result<TransactionSigned> {
-        todo!()
+        sign_transaction_request(&self.get_key(address)?, request)
+    }
+
+    fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
+        sign_hash(&self.get_key(&address)?, eip712_hash(payload)?)
+    }
+}
+
+/// The fields of a keystore file that are read without decrypting it.
+#[derive(Deserialize)]
+struct KeystoreFile {
+    address: String,
+}
+
+/// Reads the unencrypted address of a keystore file.
+///
+/// Returns `None` if the file is not a keystore file.
+fn read_keystore_address(path: &Path) -> Option<Address> {
+    let content = std::fs::read(path).ok()?;
+    let file: KeystoreFile = serde_json::from_slice(&content).ok()?;
+    let address = hex::decode(file.address.trim_start_matches("0x")).ok()?;
+    (address.len() == 20).then(|| Address::from_slice(&address))
+}
+
+/// Returns the address of the given secret key.
+fn secret_key_to_address(secret: &SecretKey) -> Address {
+    let public = PublicKey::from_secret_key(SECP256K1, secret);
+    let hash = keccak256(&public.serialize_uncompressed()[1..]);
+    Address::from_slice(&hash[12..])
+}
+
+/// Hashes the message according to [EIP-191](https://eips.ethereum.org/EIPS/eip-191):
+/// `keccak256("\x19Ethereum Signed Message:\n" + len(message) + message)`
+fn eip191_hash_message(message: &[u8]) -> H256 {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    keccak256(prefixed)
+}
+
+/// Returns the [EIP-712](https://eips.ethereum.org/EIPS/eip-712) signing hash of the typed data.
+fn eip712_hash(payload: &TypedData) -> Result<H256> {
+    payload.encode_eip712().map(H256::from).map_err(|_| SignError::InvalidTypedData)
+}
+
+/// Signs the hash with the given key.
+fn sign_hash(secret: &SecretKey, hash: H256) -> Result<Signature> {
+    let message = Message::from_slice(&hash[..]).map_err(|_| SignError::CouldNotSign)?;
+    let (rec_id, data) = SECP256K1.sign_ecdsa_recoverable(&message, secret).serialize_compact();
+    Ok(Signature {
+        r: U256::try_from_be_slice(&data[..32]).expect("is 32 bytes"),
+        s: U256::try_from_be_slice(&data[32..64]).expect("is 32 bytes"),
+        odd_y_parity: rec_id.to_i32() != 0,
+    })
+}
+
+/// Converts the request into a transaction and signs it with the given key.
+fn sign_transaction_request(
+    secret: &SecretKey,
+    request: TypedTransactionRequest,
+) -> Result<TransactionSigned> {
+    let transaction = request.into_transaction().ok_or(SignError::InvalidTransactionRequest)?;
+    let signature = sign_hash(secret, transaction.signature_hash())?;
+    Ok(TransactionSigned::from_transaction_and_signature(transaction, signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{hex_literal::hex, U128};
+    use reth_rpc_types::{EIP1559TransactionRequest, TransactionKind};
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+    use std::str::FromStr;
+
+    fn recover(signature: Signature, hash: H256) -> Address {
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&signature.r.to_be_bytes::<32>());
+        sig[32..].copy_from_slice(&signature.s.to_be_bytes::<32>());
+        let rec_id = RecoveryId::from_i32(signature.odd_y_parity as i32).unwrap();
+        let sig = RecoverableSignature::from_compact(&sig, rec_id).unwrap();
+        let public =
+            SECP256K1.recover_ecdsa(&Message::from_slice(&hash[..]).unwrap(), &sig).unwrap();
+        let hash = keccak256(&public.serialize_uncompressed()[1..]);
+        Address::from_slice(&hash[12..])
+    }
+
+    fn dev_signer() -> DevSigner {
+        let key = SecretKey::from_slice(&hex!(
+            "4646464646464646464646464646464646464646464646464646464646464646"
+        ))
+        .unwrap();
+        DevSigner::new([key])
+    }
+
+    #[test]
+    fn dev_signer_address() {
+        let signer = dev_signer();
+        let address = Address::from_str("9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F").unwrap();
+        assert_eq!(signer.accounts(), vec![address]);
+        assert!(signer.is_signer_for(&address));
+    }
+
+    #[tokio::test]
+    async fn sign_message_recovers_signer() {
+        let signer = dev_signer();
+        let address = signer.accounts()[0];
+        let message = b"Hello world";
+
+        let hash = eip191_hash_message(message);
+        assert_eq!(hash, H256::from(ethers_core::utils::hash_message(message).0));
+
+        let signature = signer.sign(address, message).await.unwrap();
+        assert_eq!(recover(signature, hash), address);
+
+        assert!(matches!(signer.sign(Address::random(), message).await, Err(SignError::NoAccount)));
+    }
+
+    #[test]
+    fn sign_transaction_recovers_signer() {
+        let signer = dev_signer();
+        let address = signer.accounts()[0];
+        let request = TypedTransactionRequest::EIP1559(EIP1559TransactionRequest {
+            chain_id: 1,
+            nonce: U256::from(3),
+            max_priority_fee_per_gas: U128::from(1_000_000_000u64),
+            max_fee_per_gas: U128::from(20_000_000_000u64),
+            gas_limit: U256::from(21_000),
+            kind: TransactionKind::Call(Address::random()),
+            value: U256::from(1),
+            input: Default::default(),
+            access_list: Default::default(),
+        });
+
+        let tx = signer.sign_transaction(request, &address).unwrap();
+        assert_eq!(tx.recover_signer(), Some(address));
+        assert_eq!(tx.nonce(), 3);
+    }
+
+    #[test]
+    fn sign_typed_data() {
+        let signer = dev_signer();
+        let address = signer.accounts()[0];
+        let json = serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": {
+                    "name": "Cow",
+                    "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+                },
+                "to": {
+                    "name": "Bob",
+                    "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+                },
+                "contents": "Hello, Bob!"
+            }
+        });
+        let typed_data: TypedData = serde_json::from_value(json).unwrap();
+
+        // the hash of the example of the EIP
+        assert_eq!(
+            eip712_hash(&typed_data).unwrap(),
+            H256::from(hex!("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"))
+        );
+        let signature = signer.sign_typed_data(address, &typed_data).unwrap();
+        assert_eq!(recover(signature, eip712_hash(&typed_data).unwrap()), address);
+    }
+
+    #[tokio::test]
+    async fn unlock_keystore_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = hex!("4646464646464646464646464646464646464646464646464646464646464646");
+        eth_keystore::encrypt_key(dir.path(), &mut rand::thread_rng(), key, "password", None)
+            .unwrap();
+        std::fs::write(dir.path().join("README"), "not a keystore file").unwrap();
+
+        let signer = KeystoreSigner::open(dir.path()).unwrap();
+        let address = dev_signer().accounts()[0];
+        assert_eq!(signer.accounts(), vec![address]);
+        assert!(matches!(signer.sign(address, b"message").await, Err(SignError::Locked)));
+
+        assert!(signer.unlock(address, "wrong password").is_err());
+        assert!(!signer.is_unlocked(&address));
+
+        signer.unlock(address, "password").unwrap();
+        assert!(signer.is_unlocked(&address));
+        let signature = signer.sign(address, b"message").await.unwrap();
+        assert_eq!(recover(signature, eip191_hash_message(b"message")), address);
+
+        signer.lock(&address);
+        assert!(!signer.is_unlocked(&address));
+    }
+
+    #[test]
+    fn open_missing_keystore_is_empty() {
+        let signer = KeystoreSigner::open("/does/not/exist").unwrap();
+        assert!(signer.accounts().is_empty());
+        assert!(matches!(signer.unlock(Address::random(), "pw"), Err(SignError::NoAccount)));
     }
 }
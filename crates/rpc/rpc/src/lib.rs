@@ -12,21 +12,26 @@
 //! Provides the implementation of all RPC interfaces.
 
 mod admin;
+mod call_guard;
 mod debug;
 mod engine;
 pub mod eth;
 mod layers;
 mod net;
 mod trace;
+mod txpool;
 mod web3;
 
 pub use admin::AdminApi;
+pub use call_guard::TracingCallGuard;
 pub use debug::DebugApi;
 pub use engine::EngineApi;
 pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub};
 pub use layers::{AuthLayer, AuthValidator, JwtAuthValidator, JwtError, JwtSecret};
 pub use net::NetApi;
-pub use trace::TraceApi;
+pub use reth_revm::tracing::js::JsTracerLimits;
+pub use trace::{TraceApi, DEFAULT_MAX_TRACE_FILTER_BLOCKS};
+pub use txpool::TxPoolApi;
 pub use web3::Web3Api;
 
 pub(crate) mod result;
@@ -1,14 +1,39 @@
-use crate::{eth::cache::EthStateCache, result::internal_rpc_err};
+use crate::{
+    call_guard::TracingCallGuard,
+    eth::{
+        cache::EthStateCache,
+        error::{EthApiError, EthResult},
+        revm_utils::{
+            account_code, build_call_evm_env, fill_tx_env_with_recovered, inspect,
+            is_empty_account, replay_transactions,
+        },
+    },
+    result::internal_rpc_err,
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult as Result;
-use reth_primitives::{BlockId, Bytes, H256};
-use reth_provider::{BlockProvider, EvmEnvProvider, StateProviderFactory};
+use reth_primitives::{
+    Address, Block, BlockId, BlockNumber, BlockNumberOrTag, Bytes, TransactionMeta, H256, U256, U64,
+};
+use reth_provider::{BlockProvider, EvmEnvProvider, StateProvider, StateProviderFactory};
+use reth_revm::{
+    config::{base_block_reward, block_reward, ommer_reward},
+    database::{State, SubState},
+    tracing::TracingInspector,
+};
 use reth_rpc_api::TraceApiServer;
 use reth_rpc_types::{
     trace::{filter::TraceFilter, parity::*},
     CallRequest, Index,
 };
-use std::collections::HashSet;
+use revm::{
+    primitives::{Env, ExecutionResult, Output, ResultAndState, SpecId, State as EvmState, TxEnv},
+    Database, DatabaseCommit,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// The default maximum number of blocks a `trace_filter` request can cover.
+pub const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 100;
 
 /// `trace` API implementation.
 ///
@@ -19,29 +44,347 @@ pub struct TraceApi<Client> {
     client: Client,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
+    /// Restricts the number of concurrent tracing calls
+    tracing_call_guard: TracingCallGuard,
+    /// The maximum number of blocks a `trace_filter` request can cover
+    max_trace_filter_blocks: u64,
 }
 
 // === impl TraceApi ===
 
 impl<Client> TraceApi<Client> {
     /// Create a new instance of the [TraceApi]
-    pub fn new(client: Client, eth_cache: EthStateCache) -> Self {
-        Self { client, eth_cache }
+    pub fn new(
+        client: Client,
+        eth_cache: EthStateCache,
+        tracing_call_guard: TracingCallGuard,
+    ) -> Self {
+        Self {
+            client,
+            eth_cache,
+            tracing_call_guard,
+            max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+        }
+    }
+
+    /// Configures the maximum number of blocks a `trace_filter` request can cover.
+    pub fn with_max_trace_filter_blocks(mut self, max_trace_filter_blocks: u64) -> Self {
+        self.max_trace_filter_blocks = max_trace_filter_blocks;
+        self
+    }
+}
+
+impl<Client> TraceApi<Client>
+where
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + Clone + 'static,
+{
+    /// Executes the given call on top of the state of the given block and returns the requested
+    /// traces for it.
+    pub(crate) async fn trace_call(
+        &self,
+        call: CallRequest,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
+    ) -> EthResult<TraceResults> {
+        let at = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+        let block_hash =
+            self.client.block_hash_for_id(at)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let (mut cfg, block_env) = self.eth_cache.get_evm_env(block_hash).await?;
+
+        // we want to disable this in eth_call, since this is common practice used by other node
+        // impls and providers <https://github.com/foundry-rs/foundry/issues/4388>
+        cfg.disable_block_gas_limit = true;
+
+        let env = build_call_evm_env(cfg, block_env, call)?;
+        let client = self.client.clone();
+        self.tracing_call_guard
+            .spawn_blocking(move || {
+                let state = client.history_by_block_hash(block_hash)?;
+                let mut db = SubState::new(State::new(state));
+
+                let (trace_results, _) = trace_env(&mut db, env, &trace_types)?;
+                Ok(trace_results)
+            })
+            .await
+    }
+
+    /// Replays the transaction with the given hash and returns the requested traces for it.
+    ///
+    /// All transactions of the block that precede the transaction are replayed first on top of the
+    /// state of the parent block.
+    ///
+    /// Returns `None` if the transaction is not known.
+    pub(crate) async fn replay_transaction_with_meta(
+        &self,
+        hash: H256,
+        trace_types: HashSet<TraceType>,
+    ) -> EthResult<Option<(TransactionMeta, TraceResults)>> {
+        let Some((transaction, meta)) = self.client.transaction_by_hash_with_meta(hash)? else {
+            return Ok(None)
+        };
+        let block = self
+            .eth_cache
+            .get_block(meta.block_hash)
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let (cfg, block_env) = self.eth_cache.get_evm_env(meta.block_hash).await?;
+
+        let client = self.client.clone();
+        let trace_results = self
+            .tracing_call_guard
+            .spawn_blocking(move || {
+                let state = client.history_by_block_number(meta.block_number.saturating_sub(1))?;
+                let mut db = SubState::new(State::new(state));
+                let mut env = Env { cfg, block: block_env, tx: TxEnv::default() };
+
+                // replay all transactions prior to the targeted transaction
+                replay_transactions(
+                    &mut db,
+                    &mut env,
+                    block.body.iter().take(meta.index as usize),
+                )?;
+
+                fill_tx_env_with_recovered(&mut env.tx, &transaction)?;
+                let (trace_results, _) = trace_env(&mut db, env, &trace_types)?;
+                Ok(trace_results)
+            })
+            .await?;
+        Ok(Some((meta, trace_results)))
+    }
+
+    /// Replays all transactions of the given block on top of the state of the parent block and
+    /// returns the requested traces for every transaction.
+    ///
+    /// Returns `None` if the block is not known.
+    pub(crate) async fn replay_block_with_meta(
+        &self,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
+    ) -> EthResult<Option<Vec<(TransactionMeta, TraceResults)>>> {
+        let Some(block_hash) = self.client.block_hash_for_id(block_id)? else { return Ok(None) };
+        let Some(block) = self.eth_cache.get_block(block_hash).await? else { return Ok(None) };
+        let (cfg, block_env) = self.eth_cache.get_evm_env(block_hash).await?;
+
+        let client = self.client.clone();
+        let results = self
+            .tracing_call_guard
+            .spawn_blocking(move || {
+                let state = client.history_by_block_number(block.number.saturating_sub(1))?;
+                let mut db = SubState::new(State::new(state));
+                let mut env = Env { cfg, block: block_env, tx: TxEnv::default() };
+
+                let mut results = Vec::with_capacity(block.body.len());
+                for (idx, tx) in block.body.iter().enumerate() {
+                    let meta = TransactionMeta {
+                        tx_hash: tx.hash(),
+                        index: idx as u64,
+                        block_hash,
+                        block_number: block.number,
+                        base_fee: block.base_fee_per_gas,
+                    };
+
+                    fill_tx_env_with_recovered(&mut env.tx, tx)?;
+                    let (trace_results, res) = trace_env(&mut db, env.clone(), &trace_types)?;
+                    db.commit(res.state);
+
+                    results.push((meta, trace_results));
+                }
+                Ok(results)
+            })
+            .await?;
+
+        Ok(Some(results))
+    }
+
+    /// Returns all traces of the given block.
+    ///
+    /// The traces of pre-merge blocks end with the rewards of the block and its ommers.
+    pub(crate) async fn trace_block(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let Some(block_hash) = self.client.block_hash_for_id(block_id)? else { return Ok(None) };
+        let Some(traces) = self
+            .replay_block_with_meta(block_hash.into(), HashSet::from([TraceType::Trace]))
+            .await?
+        else {
+            return Ok(None)
+        };
+        let block =
+            self.eth_cache.get_block(block_hash).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let (cfg, _) = self.eth_cache.get_evm_env(block_hash).await?;
+
+        let mut traces = traces
+            .into_iter()
+            .flat_map(|(meta, trace_results)| localized_traces(meta, trace_results))
+            .collect::<Vec<_>>();
+        traces.extend(reward_traces(&block, block_hash, cfg.spec_id));
+        Ok(Some(traces))
+    }
+
+    /// Returns the traces of the block range of the filter that match its addresses.
+    ///
+    /// If the filter restricts the addresses, only the transactions that the call address index
+    /// returns for them are traced, which requires the `IndexCallAddressHistory` stage. Otherwise
+    /// all transactions of the range are traced.
+    pub(crate) async fn trace_filter(
+        &self,
+        filter: TraceFilter,
+    ) -> EthResult<Vec<LocalizedTransactionTrace>> {
+        let TraceFilter { from_block, to_block, from_address, to_address, after, count } = filter;
+        let from_address = from_address.unwrap_or_default();
+        let to_address = to_address.unwrap_or_default();
+
+        let best_number = self.client.chain_info()?.best_number;
+        let from_block = from_block.unwrap_or_default();
+        let to_block = to_block.unwrap_or(best_number);
+        if from_block > to_block || to_block > best_number {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to_block - from_block >= self.max_trace_filter_blocks {
+            return Err(EthApiError::BlockRangeTooLarge(self.max_trace_filter_blocks))
+        }
+
+        // the transactions to trace grouped by block, `None` traces all transactions of the block
+        let mut transactions = BTreeMap::<BlockNumber, Option<BTreeSet<u64>>>::new();
+        if from_address.is_empty() && to_address.is_empty() {
+            transactions.extend((from_block..=to_block).map(|number| (number, None)));
+        } else {
+            // a matching trace has a sender and a recipient of the filter, so its transaction is
+            // in the results of both lookups
+            let mut candidates: Option<BTreeSet<(BlockNumber, u64)>> = None;
+            for addresses in [&from_address, &to_address] {
+                if addresses.is_empty() {
+                    continue
+                }
+                let found = self
+                    .client
+                    .transactions_by_call_addresses(addresses, from_block..=to_block)?
+                    .ok_or(EthApiError::CallAddressIndexUnavailable)?
+                    .into_iter()
+                    .collect::<BTreeSet<_>>();
+                candidates = Some(match candidates {
+                    Some(candidates) => candidates.intersection(&found).copied().collect(),
+                    None => found,
+                });
+            }
+            for (number, index) in candidates.unwrap_or_default() {
+                transactions
+                    .entry(number)
+                    .or_default()
+                    .get_or_insert_with(Default::default)
+                    .insert(index);
+            }
+        }
+
+        // blocks after the last requested trace are not executed
+        let after = after.unwrap_or_default();
+        let count = count.unwrap_or(usize::MAX);
+        let limit = after.saturating_add(count);
+
+        let mut traces = Vec::new();
+        for (number, indices) in transactions {
+            if traces.len() >= limit {
+                break
+            }
+            let block_traces = self.trace_block_transactions(number, indices).await?;
+            traces.extend(
+                block_traces.into_iter().filter(|trace| {
+                    matches_address_filter(&trace.trace, &from_address, &to_address)
+                }),
+            );
+        }
+
+        Ok(traces.into_iter().skip(after).take(count).collect())
+    }
+
+    /// Replays the transactions of the block on top of the state of the parent block and returns
+    /// the traces of the transactions at the given indices, or of all transactions and the block
+    /// rewards if `None`.
+    ///
+    /// Transactions after the last requested one are not executed.
+    async fn trace_block_transactions(
+        &self,
+        block_number: BlockNumber,
+        indices: Option<BTreeSet<u64>>,
+    ) -> EthResult<Vec<LocalizedTransactionTrace>> {
+        let block_hash = self
+            .client
+            .block_hash_for_id(BlockId::Number(BlockNumberOrTag::Number(block_number)))?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let block =
+            self.eth_cache.get_block(block_hash).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let (cfg, block_env) = self.eth_cache.get_evm_env(block_hash).await?;
+        let cfg_spec_id = cfg.spec_id;
+
+        let client = self.client.clone();
+        self.tracing_call_guard
+            .spawn_blocking(move || {
+                let state = client.history_by_block_number(block_number.saturating_sub(1))?;
+                let mut db = SubState::new(State::new(state));
+                let mut env = Env { cfg, block: block_env, tx: TxEnv::default() };
+
+                let tx_count = match &indices {
+                    Some(indices) => indices.last().map_or(0, |last| *last as usize + 1),
+                    None => block.body.len(),
+                };
+                let trace_types = HashSet::from([TraceType::Trace]);
+
+                let mut traces = Vec::new();
+                for (idx, tx) in block.body.iter().enumerate().take(tx_count) {
+                    if !indices.as_ref().map_or(true, |indices| indices.contains(&(idx as u64))) {
+                        replay_transactions(&mut db, &mut env, std::iter::once(tx))?;
+                        continue
+                    }
+
+                    let meta = TransactionMeta {
+                        tx_hash: tx.hash(),
+                        index: idx as u64,
+                        block_hash,
+                        block_number,
+                        base_fee: block.base_fee_per_gas,
+                    };
+
+                    fill_tx_env_with_recovered(&mut env.tx, tx)?;
+                    let (trace_results, res) = trace_env(&mut db, env.clone(), &trace_types)?;
+                    db.commit(res.state);
+
+                    traces.extend(localized_traces(meta, trace_results));
+                }
+
+                if indices.is_none() {
+                    traces.extend(reward_traces(&block, block_hash, cfg_spec_id));
+                }
+
+                Ok(traces)
+            })
+            .await
+    }
+
+    /// Returns all traces of the given transaction.
+    pub(crate) async fn trace_transaction(
+        &self,
+        hash: H256,
+    ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let traces =
+            self.replay_transaction_with_meta(hash, HashSet::from([TraceType::Trace])).await?;
+        Ok(traces.map(|(meta, trace_results)| localized_traces(meta, trace_results).collect()))
     }
 }
 
 #[async_trait]
 impl<Client> TraceApiServer for TraceApi<Client>
 where
-    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + Clone + 'static,
 {
+    /// Handler for `trace_call`
     async fn call(
         &self,
-        _call: CallRequest,
-        _trace_types: HashSet<TraceType>,
-        _block_id: Option<BlockId>,
+        call: CallRequest,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
     ) -> Result<TraceResults> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(self.trace_call(call, trace_types, block_id).await?)
     }
 
     async fn call_many(
@@ -61,40 +404,68 @@ where
         Err(internal_rpc_err("unimplemented"))
     }
 
+    /// Handler for `trace_replayBlockTransactions`
     async fn replay_block_transactions(
         &self,
-        _block_id: BlockId,
-        _trace_types: HashSet<TraceType>,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
     ) -> Result<Option<Vec<TraceResultsWithTransactionHash>>> {
-        Err(internal_rpc_err("unimplemented"))
+        let traces = self.replay_block_with_meta(block_id, trace_types).await?;
+        Ok(traces.map(|traces| {
+            traces
+                .into_iter()
+                .map(|(meta, full_trace)| TraceResultsWithTransactionHash {
+                    full_trace,
+                    transaction_hash: meta.tx_hash,
+                })
+                .collect()
+        }))
     }
 
+    /// Handler for `trace_replayTransaction`
     async fn replay_transaction(
         &self,
-        _transaction: H256,
-        _trace_types: HashSet<TraceType>,
+        transaction: H256,
+        trace_types: HashSet<TraceType>,
     ) -> Result<TraceResults> {
-        Err(internal_rpc_err("unimplemented"))
+        let (_, trace_results) = self
+            .replay_transaction_with_meta(transaction, trace_types)
+            .await?
+            .ok_or(EthApiError::TransactionNotFound)?;
+        Ok(trace_results)
     }
 
-    async fn block(&self, _block_id: BlockId) -> Result<Option<Vec<LocalizedTransactionTrace>>> {
-        Err(internal_rpc_err("unimplemented"))
+    /// Handler for `trace_block`
+    async fn block(&self, block_id: BlockId) -> Result<Option<Vec<LocalizedTransactionTrace>>> {
+        Ok(self.trace_block(block_id).await?)
     }
 
-    async fn filter(&self, _filter: TraceFilter) -> Result<Vec<LocalizedTransactionTrace>> {
-        Err(internal_rpc_err("unimplemented"))
+    /// Handler for `trace_filter`
+    async fn filter(&self, filter: TraceFilter) -> Result<Vec<LocalizedTransactionTrace>> {
+        Ok(self.trace_filter(filter).await?)
     }
 
-    fn trace(
+    /// Handler for `trace_get`
+    ///
+    /// Returns the trace of the transaction at the given trace address.
+    async fn trace(
         &self,
-        _hash: H256,
-        _indices: Vec<Index>,
+        hash: H256,
+        indices: Vec<Index>,
     ) -> Result<Option<LocalizedTransactionTrace>> {
-        Err(internal_rpc_err("unimplemented"))
+        let trace_address = indices.into_iter().map(usize::from).collect::<Vec<_>>();
+        let traces = self.trace_transaction(hash).await?;
+        Ok(traces.and_then(|traces| {
+            traces.into_iter().find(|trace| trace.trace.trace_address == trace_address)
+        }))
     }
 
-    fn transaction_traces(&self, _hash: H256) -> Result<Option<Vec<LocalizedTransactionTrace>>> {
-        Err(internal_rpc_err("unimplemented"))
+    /// Handler for `trace_transaction`
+    async fn transaction_traces(
+        &self,
+        hash: H256,
+    ) -> Result<Option<Vec<LocalizedTransactionTrace>>> {
+        Ok(self.trace_transaction(hash).await?)
     }
 }
 
@@ -103,3 +474,323 @@ impl<Client> std::fmt::Debug for TraceApi<Client> {
         f.debug_struct("TraceApi").finish_non_exhaustive()
     }
 }
+
+/// Executes the [Env] with a [TracingInspector] that is configured for the requested
+/// [TraceType]s and returns the [TraceResults] together with the execution result.
+///
+/// Does not commit any changes to the database.
+fn trace_env<S>(
+    db: &mut SubState<S>,
+    env: Env,
+    trace_types: &HashSet<TraceType>,
+) -> EthResult<(TraceResults, ResultAndState)>
+where
+    S: StateProvider,
+{
+    let mut inspector = TracingInspector::default();
+    if trace_types.contains(&TraceType::VmTrace) {
+        inspector = inspector.with_steps_recording();
+    }
+
+    let (res, _) = inspect(&mut *db, env, &mut inspector)?;
+    let traces = inspector.finalize();
+
+    let output = match &res.result {
+        ExecutionResult::Success { output, .. } => match output {
+            Output::Call(data) => data.clone(),
+            Output::Create(data, _) => data.clone(),
+        },
+        ExecutionResult::Revert { output, .. } => output.clone(),
+        ExecutionResult::Halt { .. } => Default::default(),
+    };
+
+    let state_diff = if trace_types.contains(&TraceType::StateDiff) {
+        Some(state_diff(&res.state, db)?)
+    } else {
+        None
+    };
+
+    let trace_results = TraceResults {
+        output: output.into(),
+        trace: trace_types.contains(&TraceType::Trace).then(|| traces.parity_traces()),
+        vm_trace: trace_types.contains(&TraceType::VmTrace).then(|| traces.vm_trace()),
+        state_diff,
+    };
+
+    Ok((trace_results, res))
+}
+
+/// Returns the parity [StateDiff] of the changed accounts by comparing them with their state in
+/// the database.
+///
+/// This expects that the changes have not been committed to the database yet.
+fn state_diff<S>(changes: &EvmState, db: &mut SubState<S>) -> EthResult<StateDiff>
+where
+    S: StateProvider,
+{
+    let mut diff = BTreeMap::new();
+    for (address, account) in changes {
+        let pre = db.basic(*address)?.filter(|info| !is_empty_account(info));
+        let post =
+            Some(&account.info).filter(|info| !account.is_destroyed && !is_empty_account(info));
+
+        if pre.is_none() && post.is_none() {
+            // account was only touched
+            continue
+        }
+
+        let pre_code = match &pre {
+            Some(info) => Some(account_code(info, db)?),
+            None => None,
+        };
+        let post_code = match post {
+            Some(info) if Some(info.code_hash) == pre.as_ref().map(|info| info.code_hash) => {
+                pre_code.clone()
+            }
+            Some(info) => Some(account_code(info, db)?),
+            None => None,
+        };
+
+        let mut storage = BTreeMap::new();
+        for (slot, value) in account.storage.iter() {
+            let original = value.original_value();
+            let present = value.present_value();
+            let slot_delta = match (pre.is_some(), post.is_some()) {
+                (false, true) if present != U256::ZERO => Delta::Added(H256(present.to_be_bytes())),
+                (true, false) if original != U256::ZERO => {
+                    Delta::Removed(H256(original.to_be_bytes()))
+                }
+                (true, true) if original != present => Delta::Changed(ChangedType {
+                    from: H256(original.to_be_bytes()),
+                    to: H256(present.to_be_bytes()),
+                }),
+                _ => continue,
+            };
+            storage.insert(H256(slot.to_be_bytes()), slot_delta);
+        }
+
+        let account_diff = AccountDiff {
+            balance: delta(pre.as_ref().map(|info| info.balance), post.map(|info| info.balance)),
+            nonce: delta(
+                pre.as_ref().map(|info| U64::from(info.nonce)),
+                post.map(|info| U64::from(info.nonce)),
+            ),
+            code: delta(pre_code, post_code),
+            storage,
+        };
+
+        if account_diff != AccountDiff::default() {
+            diff.insert(*address, account_diff);
+        }
+    }
+
+    Ok(StateDiff(diff))
+}
+
+/// Returns the [Delta] between the two values.
+fn delta<T: PartialEq>(from: Option<T>, to: Option<T>) -> Delta<T> {
+    match (from, to) {
+        (Some(from), Some(to)) if from == to => Delta::Unchanged,
+        (Some(from), Some(to)) => Delta::Changed(ChangedType { from, to }),
+        (None, Some(to)) => Delta::Added(to),
+        (Some(from), None) => Delta::Removed(from),
+        (None, None) => Delta::Unchanged,
+    }
+}
+
+/// Returns `true` if the sender of the trace is one of the `from` addresses and its recipient one
+/// of the `to` addresses of a `trace_filter` request. An empty list matches any address.
+///
+/// The recipient of a contract creation is the created contract and that of a selfdestruct the
+/// refund address.
+fn matches_address_filter(trace: &TransactionTrace, from: &[Address], to: &[Address]) -> bool {
+    let (sender, recipient) = match &trace.action {
+        Action::Call(call) => (Some(call.from), Some(call.to)),
+        Action::Create(create) => {
+            let created = match &trace.result {
+                Some(TraceResult::Success { result: TraceOutput::Create(output) }) => {
+                    Some(output.address)
+                }
+                _ => None,
+            };
+            (Some(create.from), created)
+        }
+        Action::Selfdestruct(selfdestruct) => {
+            (Some(selfdestruct.address), Some(selfdestruct.refund_address))
+        }
+        Action::Reward(reward) => (None, Some(reward.author)),
+    };
+    let matches = |addresses: &[Address], address: Option<Address>| {
+        addresses.is_empty() || address.map_or(false, |address| addresses.contains(&address))
+    };
+    matches(from, sender) && matches(to, recipient)
+}
+
+/// Returns the reward traces of a block that is executed with the given spec: the reward of the
+/// beneficiary, followed by the rewards of the ommers. Blocks don't have rewards from the merge on.
+///
+/// This matches the reward traces of OpenEthereum.
+fn reward_traces(
+    block: &Block,
+    block_hash: H256,
+    spec_id: SpecId,
+) -> Vec<LocalizedTransactionTrace> {
+    let Some(base_block_reward) = base_block_reward(spec_id) else { return Vec::new() };
+
+    let reward_trace = |author, value, reward_type| LocalizedTransactionTrace {
+        trace: TransactionTrace {
+            trace_address: vec![],
+            subtraces: 0,
+            action: Action::Reward(RewardAction { author, value: U256::from(value), reward_type }),
+            result: None,
+        },
+        transaction_position: None,
+        transaction_hash: None,
+        block_number: U64::from(block.number),
+        block_hash,
+    };
+
+    let mut traces = Vec::with_capacity(block.ommers.len() + 1);
+    traces.push(reward_trace(
+        block.beneficiary,
+        block_reward(base_block_reward, block.ommers.len()),
+        RewardType::Block,
+    ));
+    traces.extend(block.ommers.iter().map(|ommer| {
+        reward_trace(
+            ommer.beneficiary,
+            ommer_reward(base_block_reward, block.number, ommer.number),
+            RewardType::Uncle,
+        )
+    }));
+    traces
+}
+
+/// Converts the traces of a transaction into [LocalizedTransactionTrace]s.
+fn localized_traces(
+    meta: TransactionMeta,
+    trace_results: TraceResults,
+) -> impl Iterator<Item = LocalizedTransactionTrace> {
+    trace_results.trace.unwrap_or_default().into_iter().map(move |trace| {
+        LocalizedTransactionTrace {
+            trace,
+            transaction_position: Some(meta.index as usize),
+            transaction_hash: Some(meta.tx_hash),
+            block_number: U64::from(meta.block_number),
+            block_hash: meta.block_hash,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Header;
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(delta::<u64>(None, None), Delta::Unchanged);
+        assert_eq!(delta(Some(1u64), Some(1u64)), Delta::Unchanged);
+        assert_eq!(delta(None, Some(1u64)), Delta::Added(1));
+        assert_eq!(delta(Some(1u64), None), Delta::Removed(1));
+        assert_eq!(delta(Some(1u64), Some(2u64)), Delta::Changed(ChangedType { from: 1, to: 2 }));
+    }
+
+    #[test]
+    fn test_matches_address_filter() {
+        let (a, b, c) =
+            (Address::from_low_u64_be(1), Address::from_low_u64_be(2), Address::from_low_u64_be(3));
+        let trace = |action, result| TransactionTrace {
+            trace_address: vec![],
+            subtraces: 0,
+            action,
+            result,
+        };
+
+        let call = trace(
+            Action::Call(CallAction {
+                from: a,
+                to: b,
+                value: U256::ZERO,
+                gas: U64::zero(),
+                input: Bytes::default(),
+                call_type: CallType::Call,
+            }),
+            None,
+        );
+        assert!(matches_address_filter(&call, &[], &[]));
+        assert!(matches_address_filter(&call, &[a], &[]));
+        assert!(matches_address_filter(&call, &[c, a], &[b]));
+        assert!(!matches_address_filter(&call, &[b], &[]));
+        assert!(!matches_address_filter(&call, &[a], &[c]));
+
+        let create = trace(
+            Action::Create(CreateAction {
+                from: a,
+                value: U256::ZERO,
+                gas: U64::zero(),
+                init: Bytes::default(),
+            }),
+            Some(TraceResult::Success {
+                result: TraceOutput::Create(CreateOutput {
+                    gas_used: U64::zero(),
+                    code: Bytes::default(),
+                    address: c,
+                }),
+            }),
+        );
+        assert!(matches_address_filter(&create, &[a], &[c]));
+        assert!(!matches_address_filter(&create, &[], &[b]));
+
+        let selfdestruct = trace(
+            Action::Selfdestruct(SelfdestructAction {
+                address: b,
+                refund_address: c,
+                balance: U256::ZERO,
+            }),
+            None,
+        );
+        assert!(matches_address_filter(&selfdestruct, &[b], &[c]));
+        assert!(!matches_address_filter(&selfdestruct, &[c], &[]));
+    }
+
+    #[test]
+    fn test_reward_traces() {
+        let (a, b) = (Address::from_low_u64_be(1), Address::from_low_u64_be(2));
+        let mut block = Block::default();
+        block.header.number = 10;
+        block.header.beneficiary = a;
+        block.ommers.push(Header { number: 9, beneficiary: b, ..Default::default() });
+        let block_hash = H256::random();
+
+        assert!(reward_traces(&block, block_hash, SpecId::MERGE).is_empty());
+
+        let traces = reward_traces(&block, block_hash, SpecId::LONDON);
+        let rewards = traces
+            .iter()
+            .map(|trace| match &trace.trace.action {
+                Action::Reward(reward) => reward.clone(),
+                action => panic!("unexpected action {action:?}"),
+            })
+            .collect::<Vec<_>>();
+        let base = 2_000_000_000_000_000_000u128;
+        assert_eq!(
+            rewards,
+            vec![
+                RewardAction {
+                    author: a,
+                    value: U256::from(base + base / 32),
+                    reward_type: RewardType::Block,
+                },
+                RewardAction {
+                    author: b,
+                    value: U256::from(base * 7 / 8),
+                    reward_type: RewardType::Uncle,
+                },
+            ]
+        );
+        assert!(traces.iter().all(|trace| trace.transaction_hash.is_none() &&
+            trace.transaction_position.is_none() &&
+            trace.block_hash == block_hash));
+    }
+}
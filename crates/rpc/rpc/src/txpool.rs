@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult as Result;
+use reth_primitives::{Address, IntoRecoveredTransaction, TransactionKind, U128, U256, U64};
+use reth_rpc_api::TxPoolApiServer;
+use reth_rpc_types::{
+    Transaction, TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary,
+    TxpoolStatus,
+};
+use reth_transaction_pool::{PoolTransaction, TransactionPool, ValidPoolTransaction};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Transactions grouped by sender and nonce
+type GroupedBySender<T> = BTreeMap<Address, BTreeMap<String, T>>;
+
+/// `txpool` API implementation.
+///
+/// This type provides the functionality for handling `txpool` related requests.
+///
+/// Transactions of the pending sub-pool are reported as `pending`, the transactions of all other
+/// sub-pools as `queued`.
+#[derive(Clone)]
+pub struct TxPoolApi<Pool> {
+    /// An interface to interact with the pool
+    pool: Pool,
+}
+
+impl<Pool> TxPoolApi<Pool> {
+    /// Creates a new instance of `TxPoolApi`.
+    pub fn new(pool: Pool) -> Self {
+        TxPoolApi { pool }
+    }
+}
+
+impl<Pool> TxPoolApi<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    /// Groups the pending and the queued transactions of the pool by sender and nonce, converting
+    /// them with the given function.
+    fn grouped_by_sender<T>(
+        &self,
+        mut convert: impl FnMut(&Pool::Transaction) -> T,
+    ) -> (GroupedBySender<T>, GroupedBySender<T>) {
+        let mut group = |txs: Vec<Arc<ValidPoolTransaction<Pool::Transaction>>>| {
+            let mut grouped = GroupedBySender::new();
+            for tx in txs {
+                grouped
+                    .entry(tx.sender())
+                    .or_default()
+                    .insert(tx.nonce().to_string(), convert(&tx.transaction));
+            }
+            grouped
+        };
+        let pending = group(self.pool.pending_transactions());
+        let queued = group(self.pool.queued_transactions());
+        (pending, queued)
+    }
+}
+
+#[async_trait]
+impl<Pool> TxPoolApiServer for TxPoolApi<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `txpool_status`
+    async fn txpool_status(&self) -> Result<TxpoolStatus> {
+        let size = self.pool.status();
+        Ok(TxpoolStatus {
+            pending: U64::from(size.pending),
+            queued: U64::from(size.queued + size.basefee),
+        })
+    }
+
+    /// Handler for `txpool_inspect`
+    async fn txpool_inspect(&self) -> Result<TxpoolInspect> {
+        let (pending, queued) = self.grouped_by_sender(|tx| {
+            let tx = tx.to_recovered_transaction();
+            TxpoolInspectSummary {
+                to: match tx.kind() {
+                    TransactionKind::Call(to) => Some(*to),
+                    TransactionKind::Create => None,
+                },
+                value: U256::from(*tx.value()),
+                gas: U256::from(tx.gas_limit()),
+                gas_price: U128::from(tx.max_fee_per_gas()),
+            }
+        });
+        Ok(TxpoolInspect { pending, queued })
+    }
+
+    /// Handler for `txpool_contentFrom`
+    async fn txpool_content_from(&self, from: Address) -> Result<TxpoolContentFrom> {
+        let by_nonce = |txs: Vec<Arc<ValidPoolTransaction<Pool::Transaction>>>| {
+            txs.into_iter()
+                .filter(|tx| tx.sender() == from)
+                .map(|tx| (tx.nonce().to_string(), to_rpc_transaction(&tx.transaction)))
+                .collect()
+        };
+        Ok(TxpoolContentFrom {
+            pending: by_nonce(self.pool.pending_transactions()),
+            queued: by_nonce(self.pool.queued_transactions()),
+        })
+    }
+
+    /// Handler for `txpool_content`
+    async fn txpool_content(&self) -> Result<TxpoolContent> {
+        let (pending, queued) = self.grouped_by_sender(to_rpc_transaction);
+        Ok(TxpoolContent { pending, queued })
+    }
+}
+
+impl<Pool> std::fmt::Debug for TxPoolApi<Pool> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxPoolApi").finish_non_exhaustive()
+    }
+}
+
+/// Converts the pool transaction into a pending rpc transaction.
+fn to_rpc_transaction<T: PoolTransaction>(tx: &T) -> Transaction {
+    Transaction::from_recovered(tx.to_recovered_transaction())
+}
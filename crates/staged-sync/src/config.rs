@@ -50,6 +50,12 @@ pub struct StageConfig {
     pub sender_recovery: SenderRecoveryConfig,
     /// Execution stage configuration.
     pub execution: ExecutionConfig,
+    /// Call address history indexing stage configuration.
+    #[serde(default)]
+    pub index_call_address_history: IndexCallAddressHistoryConfig,
+    /// Log history indexing stage configuration.
+    #[serde(default)]
+    pub index_log_history: IndexLogHistoryConfig,
 }
 
 /// Header stage configuration.
@@ -156,6 +162,40 @@ impl Default for ExecutionConfig {
     }
 }
 
+/// Call address history indexing stage configuration.
+///
+/// The index is used by `trace_filter` to find the transactions of the requested addresses.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+pub struct IndexCallAddressHistoryConfig {
+    /// Whether the stage is part of the pipeline, disabled by default.
+    pub enabled: bool,
+    /// The maximum number of blocks to index before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexCallAddressHistoryConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 10_000 }
+    }
+}
+
+/// Log history indexing stage configuration.
+///
+/// The index is used by `eth_getLogs` and the log filters to find the blocks with matching logs.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+pub struct IndexLogHistoryConfig {
+    /// Whether the stage is part of the pipeline, disabled by default.
+    pub enabled: bool,
+    /// The maximum number of blocks to index before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogHistoryConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Config;
@@ -19,8 +19,7 @@ use reth_provider::Transaction;
 use std::sync::Arc;
 use tracing::*;
 
-/// The [`StageId`] of the bodies downloader stage.
-pub const BODIES: StageId = StageId("Bodies");
+pub use reth_db::models::stage::BODIES;
 
 // TODO(onbjerg): Metrics and events (gradual status for e.g. CLI)
 /// The body stage downloads block bodies.
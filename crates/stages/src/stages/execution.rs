@@ -11,11 +11,12 @@ use reth_db::{
 };
 use reth_interfaces::provider::ProviderError;
 use reth_primitives::{Address, Block, U256};
-use reth_provider::{BlockExecutor, ExecutorFactory, LatestStateProviderRef, Transaction};
+use reth_provider::{
+    BadBlocks, BlockExecutor, ExecutorFactory, LatestStateProviderRef, Transaction,
+};
 use tracing::*;
 
-/// The [`StageId`] of the execution stage.
-pub const EXECUTION: StageId = StageId("Execution");
+pub use reth_db::models::stage::EXECUTION;
 
 /// The execution stage executes all transactions and
 /// update history indexes.
@@ -39,6 +40,7 @@ pub const EXECUTION: StageId = StageId("Execution");
 /// - [tables::Bytecodes]
 /// - [tables::AccountChangeSet]
 /// - [tables::StorageChangeSet]
+/// - [tables::Receipts]
 ///
 /// For unwinds we are accessing:
 /// - [tables::BlockBodies] get tx index to know what needs to be unwinded
@@ -52,18 +54,26 @@ pub struct ExecutionStage<EF: ExecutorFactory> {
     pub executor_factory: EF,
     /// Commit threshold
     pub commit_threshold: u64,
+    /// Record of the blocks that failed execution.
+    pub bad_blocks: BadBlocks,
 }
 
 impl<EF: ExecutorFactory> ExecutionStage<EF> {
     /// Create new execution stage with specified config.
     pub fn new(executor_factory: EF, commit_threshold: u64) -> Self {
-        Self { executor_factory, commit_threshold }
+        Self { executor_factory, commit_threshold, bad_blocks: Default::default() }
     }
 
     /// Create execution stage with executor factory and default commit threshold set to 10_000
     /// blocks
     pub fn new_default_threshold(executor_factory: EF) -> Self {
-        Self { executor_factory, commit_threshold: 10_000 }
+        Self::new(executor_factory, 10_000)
+    }
+
+    /// Set the record the blocks that fail execution are reported to.
+    pub fn with_bad_blocks(mut self, bad_blocks: BadBlocks) -> Self {
+        self.bad_blocks = bad_blocks;
+        self
     }
 
     /// Execute the stage.
@@ -147,13 +157,14 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
             trace!(target: "sync::stages::execution", number = block_number, txs = transactions.len(), "Executing block");
 
             // Configure the executor to use the current state.
-            let changeset = executor
-                .execute_and_verify_receipt(
-                    &Block { header, body: transactions, ommers, withdrawals },
-                    td,
-                    Some(signers),
-                )
-                .map_err(|error| StageError::ExecutionError { block: block_number, error })?;
+            let block = Block { header, body: transactions, ommers, withdrawals };
+            let changeset = match executor.execute_and_verify_receipt(&block, td, Some(signers)) {
+                Ok(changeset) => changeset,
+                Err(error) => {
+                    self.bad_blocks.insert(block.header.hash_slow(), block, &error);
+                    return Err(StageError::ExecutionError { block: block_number, error })
+                }
+            };
             changesets.push(changeset);
         }
 
@@ -208,6 +219,16 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
         let mut account_changeset = tx.cursor_dup_write::<tables::AccountChangeSet>()?;
         let mut storage_changeset = tx.cursor_dup_write::<tables::StorageChangeSet>()?;
 
+        // Discard the receipts of the unwound blocks
+        let first_unwound_tx = tx.get_block_body(input.unwind_to)?.tx_id_range().end;
+        let mut receipts = tx.cursor_write::<tables::Receipts>()?;
+        while let Some((tx_id, _)) = receipts.last()? {
+            if tx_id < first_unwound_tx {
+                break
+            }
+            receipts.delete_current()?;
+        }
+
         let from_transition_rev = tx.get_block_transition(input.unwind_to)?;
         let to_transition_rev = tx.get_block_transition(input.stage_progress)?;
 
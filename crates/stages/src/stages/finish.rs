@@ -4,8 +4,7 @@ use reth_interfaces::p2p::headers::client::StatusUpdater;
 use reth_primitives::{BlockNumber, Head};
 use reth_provider::Transaction;
 
-/// The [`StageId`] of the finish stage.
-pub const FINISH: StageId = StageId("Finish");
+pub use reth_db::models::stage::FINISH;
 
 /// The finish stage.
 ///
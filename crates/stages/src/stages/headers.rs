@@ -16,8 +16,7 @@ use reth_provider::Transaction;
 use std::sync::Arc;
 use tracing::*;
 
-/// The [`StageId`] of the headers downloader stage.
-pub const HEADERS: StageId = StageId("Headers");
+pub use reth_db::models::stage::HEADERS;
 
 /// The headers stage.
 ///
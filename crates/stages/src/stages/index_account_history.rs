@@ -3,6 +3,7 @@ use reth_db::{
     cursor::{DbCursorRO, DbCursorRW},
     database::{Database, DatabaseGAT},
     models::ShardedKey,
+    table::Table,
     tables,
     transaction::{DbTx, DbTxMut, DbTxMutGAT},
     TransitionList,
@@ -13,8 +14,7 @@ use reth_primitives::{Address, TransitionId};
 use std::{collections::BTreeMap, fmt::Debug};
 use tracing::*;
 
-/// The [`StageId`] of the account history indexing stage.
-pub const INDEX_ACCOUNT_HISTORY: StageId = StageId("IndexAccountHistory");
+pub use reth_db::models::stage::INDEX_ACCOUNT_HISTORY;
 
 /// Stage is indexing history the account changesets generated in
 /// [`ExecutionStage`][crate::stages::ExecutionStage]. For more information
@@ -122,11 +122,29 @@ pub fn unwind_account_history_shards<DB: Database>(
     address: Address,
     transition_id: TransitionId,
 ) -> Result<Vec<usize>, StageError> {
-    let mut item = cursor.seek_exact(ShardedKey::new(address, u64::MAX))?;
+    unwind_history_shards::<DB, tables::AccountHistory, _>(cursor, address, transition_id)
+}
+
+/// Unwind all history shards of the key in a table sharded by [`ShardedKey`], see
+/// [`unwind_account_history_shards`].
+///
+/// Removes all indices that are equal or higher than `transition_id`, which is a block number for
+/// the tables that are indexed by block.
+pub fn unwind_history_shards<DB, T, K>(
+    cursor: &mut <<DB as DatabaseGAT<'_>>::TXMut as DbTxMutGAT<'_>>::CursorMut<T>,
+    key: K,
+    transition_id: TransitionId,
+) -> Result<Vec<usize>, StageError>
+where
+    DB: Database,
+    T: Table<Key = ShardedKey<K>, Value = TransitionList>,
+    K: PartialEq + Clone,
+{
+    let mut item = cursor.seek_exact(ShardedKey::new(key.clone(), u64::MAX))?;
 
     while let Some((sharded_key, list)) = item {
-        // there is no more shard for address
-        if sharded_key.key != address {
+        // there is no more shard for the key
+        if sharded_key.key != key {
             break
         }
         cursor.delete_current()?;
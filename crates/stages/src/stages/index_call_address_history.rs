@@ -0,0 +1,306 @@
+use crate::{
+    exec_or_return, stages::unwind_history_shards, ExecAction, ExecInput, ExecOutput, Stage,
+    StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_db::{
+    database::Database, models::ShardedKey, tables, transaction::DbTxMut, TransitionList,
+};
+use reth_primitives::{Address, BlockNumber, TransitionId};
+use reth_provider::{ExecutorFactory, Transaction, TransactionError};
+use std::collections::BTreeMap;
+use tracing::*;
+
+pub use reth_db::models::stage::INDEX_CALL_ADDRESS_HISTORY;
+
+/// Stage is indexing the transitions in which an address was the sender or the recipient of a
+/// call, including internal calls. For more information on index sharding take a look at
+/// [`tables::CallAddressHistory`].
+///
+/// The addresses are collected by re-executing the transactions on top of the historical state,
+/// so the stage needs to run after the
+/// [`IndexAccountHistoryStage`][crate::stages::IndexAccountHistoryStage] and the
+/// [`IndexStorageHistoryStage`][crate::stages::IndexStorageHistoryStage].
+///
+/// This stage is optional and not part of the default stage sets.
+// false positive, we cannot derive it if !DB: Debug.
+#[allow(missing_debug_implementations)]
+pub struct IndexCallAddressHistoryStage<EF: ExecutorFactory> {
+    /// The factory of the executors used to trace the transactions
+    pub executor_factory: EF,
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl<EF: ExecutorFactory> IndexCallAddressHistoryStage<EF> {
+    /// Create new call address history indexing stage with specified config.
+    pub fn new(executor_factory: EF, commit_threshold: u64) -> Self {
+        Self { executor_factory, commit_threshold }
+    }
+
+    /// Collect the transitions in which each address was called or made a call for the given
+    /// range of blocks.
+    pub fn call_address_transitions<DB: Database>(
+        &self,
+        tx: &Transaction<'_, DB>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<BTreeMap<Address, Vec<TransitionId>>, StageError> {
+        tx.get_call_address_transitions(&self.executor_factory, start_block..=end_block).map_err(
+            |error| match error {
+                TransactionError::BlockTracing { block_number, error } => {
+                    StageError::ExecutionError { block: block_number, error }
+                }
+                error => error.into(),
+            },
+        )
+    }
+
+    /// Execute the stage.
+    pub fn execute_inner<DB: Database>(
+        &self,
+        tx: &mut Transaction<'_, DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        let ((start_block, end_block), capped) = exec_or_return!(
+            input,
+            self.commit_threshold,
+            "sync::stages::index_call_address_history"
+        );
+
+        let indices = self.call_address_transitions(tx, start_block, end_block)?;
+        // Insert the call addresses to history index
+        tx.insert_call_address_history_index(indices)?;
+
+        let done = !capped;
+        info!(target: "sync::stages::index_call_address_history", stage_progress = end_block, done, "Stage finished");
+        Ok(ExecOutput { stage_progress: end_block, done })
+    }
+
+    /// Unwind the stage.
+    pub fn unwind_inner<DB: Database>(
+        &self,
+        tx: &mut Transaction<'_, DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        info!(target: "sync::stages::index_call_address_history", to_block = input.unwind_to, "Unwinding");
+        if input.unwind_to >= input.stage_progress {
+            return Ok(UnwindOutput { stage_progress: input.unwind_to })
+        }
+
+        // the blocks are traced again to find the addresses which need to be unwound, this
+        // requires the state of the blocks, which is still present as the stages that remove it
+        // are unwound after this one.
+        let transitions =
+            self.call_address_transitions(tx, input.unwind_to + 1, input.stage_progress)?;
+
+        let mut cursor = tx.cursor_write::<tables::CallAddressHistory>()?;
+        // try to unwind the index
+        for (address, indices) in transitions {
+            // indices are sorted, the first one is the lowest transition to unwind.
+            let rem_index = indices[0];
+            let shard_part = unwind_history_shards::<DB, tables::CallAddressHistory, _>(
+                &mut cursor,
+                address,
+                rem_index,
+            )?;
+
+            // check last shard_part, if present, items needs to be reinserted.
+            if !shard_part.is_empty() {
+                // there are items in list
+                tx.put::<tables::CallAddressHistory>(
+                    ShardedKey::new(address, u64::MAX),
+                    TransitionList::new(shard_part)
+                        .expect("There is at least one element in list and it is sorted."),
+                )?;
+            }
+        }
+
+        Ok(UnwindOutput { stage_progress: input.unwind_to })
+    }
+}
+
+#[async_trait::async_trait]
+impl<EF: ExecutorFactory, DB: Database> Stage<DB> for IndexCallAddressHistoryStage<EF> {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        INDEX_CALL_ADDRESS_HISTORY
+    }
+
+    /// Execute the stage.
+    async fn execute(
+        &mut self,
+        tx: &mut Transaction<'_, DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        // Tracing can take more than the default stack space, see
+        // [`ExecutionStage`][crate::stages::ExecutionStage].
+        std::thread::scope(|scope| {
+            let handle = std::thread::Builder::new()
+                .stack_size(50 * 1024 * 1024)
+                .spawn_scoped(scope, || self.execute_inner(tx, input))
+                .expect("Expects that thread name is not null");
+            handle.join().expect("Expects for thread to not panic")
+        })
+    }
+
+    /// Unwind the stage.
+    async fn unwind(
+        &mut self,
+        tx: &mut Transaction<'_, DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        std::thread::scope(|scope| {
+            let handle = std::thread::Builder::new()
+                .stack_size(50 * 1024 * 1024)
+                .spawn_scoped(scope, || self.unwind_inner(tx, input))
+                .expect("Expects that thread name is not null");
+            handle.join().expect("Expects for thread to not panic")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestTransaction, PREV_STAGE_ID};
+    use reth_db::models::sharded_key::NUM_OF_INDICES_IN_SHARD;
+    use reth_interfaces::test_utils::generators::random_block_range;
+    use reth_primitives::{ChainSpecBuilder, SealedBlock, TransactionKind, H256, U256};
+    use reth_provider::test_utils::TestExecutorFactory;
+    use std::sync::Arc;
+
+    fn stage() -> IndexCallAddressHistoryStage<TestExecutorFactory> {
+        let factory = TestExecutorFactory::new(Arc::new(
+            ChainSpecBuilder::mainnet().berlin_activated().build(),
+        ));
+        IndexCallAddressHistoryStage::new(factory, 100)
+    }
+
+    fn list(list: &[usize]) -> TransitionList {
+        TransitionList::new(list).unwrap()
+    }
+
+    fn cast(
+        table: Vec<(ShardedKey<Address>, TransitionList)>,
+    ) -> BTreeMap<ShardedKey<Address>, Vec<usize>> {
+        table.into_iter().map(|(k, v)| (k, v.iter(0).collect())).collect()
+    }
+
+    /// Inserts the blocks together with their senders and total difficulty.
+    fn setup(tx: &TestTransaction, blocks: &[SealedBlock], tx_offset: u64) {
+        tx.insert_blocks(blocks.iter(), Some(tx_offset)).unwrap();
+        tx.commit(|tx| {
+            let mut tx_id = tx_offset;
+            for block in blocks {
+                tx.put::<tables::HeaderTD>(block.number, U256::ZERO.into())?;
+                for transaction in &block.body {
+                    tx.put::<tables::TxSenders>(tx_id, transaction.recover_signer().unwrap())?;
+                    tx_id += 1;
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    /// The expected index: every sender and recipient of the transactions in the blocks.
+    fn expected(blocks: &[SealedBlock]) -> BTreeMap<ShardedKey<Address>, Vec<usize>> {
+        let mut transitions = BTreeMap::<Address, Vec<usize>>::new();
+        let mut transition = 0;
+        for transaction in blocks.iter().flat_map(|block| block.body.iter()) {
+            transitions.entry(transaction.recover_signer().unwrap()).or_default().push(transition);
+            if let TransactionKind::Call(to) = transaction.kind() {
+                let entry = transitions.entry(*to).or_default();
+                if entry.last() != Some(&transition) {
+                    entry.push(transition);
+                }
+            }
+            transition += 1;
+        }
+        transitions
+            .into_iter()
+            .map(|(address, list)| (ShardedKey::new(address, u64::MAX), list))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn index_and_unwind_call_addresses() {
+        let tx = TestTransaction::default();
+        let blocks = random_block_range(0..4, H256::zero(), 1..3);
+        setup(&tx, &blocks, 0);
+
+        // index the blocks
+        let input = ExecInput { previous_stage: Some((PREV_STAGE_ID, 3)), stage_progress: None };
+        let mut stage = stage();
+        let mut db_tx = tx.inner();
+        let out = stage.execute(&mut db_tx, input).await.unwrap();
+        assert_eq!(out, ExecOutput { stage_progress: 3, done: true });
+        db_tx.commit().unwrap();
+
+        let table = cast(tx.table::<tables::CallAddressHistory>().unwrap());
+        assert_eq!(table, expected(&blocks));
+
+        // unwind the last two blocks
+        let input = UnwindInput { stage_progress: 3, unwind_to: 1, ..Default::default() };
+        let mut db_tx = tx.inner();
+        let out = stage.unwind(&mut db_tx, input).await.unwrap();
+        assert_eq!(out, UnwindOutput { stage_progress: 1 });
+        db_tx.commit().unwrap();
+
+        let table = cast(tx.table::<tables::CallAddressHistory>().unwrap());
+        assert_eq!(table, expected(&blocks[..2]));
+
+        // unwind everything
+        let input = UnwindInput { stage_progress: 1, unwind_to: 0, ..Default::default() };
+        let mut db_tx = tx.inner();
+        stage.unwind(&mut db_tx, input).await.unwrap();
+        db_tx.commit().unwrap();
+
+        let table = cast(tx.table::<tables::CallAddressHistory>().unwrap());
+        assert_eq!(table, expected(&blocks[..1]));
+    }
+
+    #[tokio::test]
+    async fn index_call_addresses_into_full_shard() {
+        let tx = TestTransaction::default();
+        // the transitions of the blocks start after the full shard
+        let blocks = random_block_range(0..2, H256::zero(), 1..2);
+        setup(&tx, &blocks, NUM_OF_INDICES_IN_SHARD as u64);
+
+        let sender = blocks[1].body[0].recover_signer().unwrap();
+        let full_list = (0..NUM_OF_INDICES_IN_SHARD).collect::<Vec<_>>();
+        tx.commit(|tx| {
+            tx.put::<tables::CallAddressHistory>(
+                ShardedKey::new(sender, u64::MAX),
+                list(&full_list),
+            )
+        })
+        .unwrap();
+
+        // only index the second block
+        let input = ExecInput { previous_stage: Some((PREV_STAGE_ID, 1)), stage_progress: Some(0) };
+        let mut stage = stage();
+        let mut db_tx = tx.inner();
+        stage.execute(&mut db_tx, input).await.unwrap();
+        db_tx.commit().unwrap();
+
+        let table = cast(tx.table::<tables::CallAddressHistory>().unwrap());
+        let last_full = NUM_OF_INDICES_IN_SHARD as u64 - 1;
+        assert_eq!(table.get(&ShardedKey::new(sender, last_full)), Some(&full_list));
+        assert_eq!(
+            table.get(&ShardedKey::new(sender, u64::MAX)),
+            Some(&vec![NUM_OF_INDICES_IN_SHARD + 1])
+        );
+
+        // unwind the second block
+        let input = UnwindInput { stage_progress: 1, unwind_to: 0, ..Default::default() };
+        let mut db_tx = tx.inner();
+        stage.unwind(&mut db_tx, input).await.unwrap();
+        db_tx.commit().unwrap();
+
+        let table = cast(tx.table::<tables::CallAddressHistory>().unwrap());
+        assert_eq!(table.get(&ShardedKey::new(sender, u64::MAX)), Some(&full_list));
+        assert_eq!(table.keys().filter(|key| key.key == sender).count(), 1);
+    }
+}
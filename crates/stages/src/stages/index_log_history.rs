@@ -0,0 +1,250 @@
+use crate::{
+    exec_or_return, stages::unwind_history_shards, ExecAction, ExecInput, ExecOutput, Stage,
+    StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_db::{
+    database::Database, models::ShardedKey, table::Table, tables, transaction::DbTxMut,
+    BlockNumberList,
+};
+use reth_primitives::BlockNumber;
+use reth_provider::Transaction;
+use std::collections::BTreeMap;
+use tracing::*;
+
+pub use reth_db::models::stage::INDEX_LOG_HISTORY;
+
+/// Stage is indexing the blocks in which an address emitted a log and in which a topic was part of
+/// a log, read from the stored receipts. For more information on index sharding take a look at
+/// [`tables::LogAddressHistory`] and [`tables::LogTopicHistory`].
+///
+/// This stage is optional and not part of the default stage sets.
+#[derive(Debug)]
+pub struct IndexLogHistoryStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogHistoryStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+impl IndexLogHistoryStage {
+    /// Create new log history indexing stage with specified config.
+    pub fn new(commit_threshold: u64) -> Self {
+        Self { commit_threshold }
+    }
+}
+
+#[async_trait::async_trait]
+impl<DB: Database> Stage<DB> for IndexLogHistoryStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        INDEX_LOG_HISTORY
+    }
+
+    /// Execute the stage.
+    async fn execute(
+        &mut self,
+        tx: &mut Transaction<'_, DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        let ((start_block, end_block), capped) =
+            exec_or_return!(input, self.commit_threshold, "sync::stages::index_log_history");
+
+        let (address_blocks, topic_blocks) = tx.get_log_history_indices(start_block..=end_block)?;
+        // Insert the log addresses and topics to history index
+        tx.insert_log_history_index(address_blocks, topic_blocks)?;
+
+        let done = !capped;
+        info!(target: "sync::stages::index_log_history", stage_progress = end_block, done, "Stage finished");
+        Ok(ExecOutput { stage_progress: end_block, done })
+    }
+
+    /// Unwind the stage.
+    async fn unwind(
+        &mut self,
+        tx: &mut Transaction<'_, DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        info!(target: "sync::stages::index_log_history", to_block = input.unwind_to, "Unwinding");
+        if input.unwind_to >= input.stage_progress {
+            return Ok(UnwindOutput { stage_progress: input.unwind_to })
+        }
+
+        // the receipts of the unwound blocks are still present as the stages that remove them are
+        // unwound after this one.
+        let (address_blocks, topic_blocks) =
+            tx.get_log_history_indices(input.unwind_to + 1..=input.stage_progress)?;
+
+        unwind_log_history::<DB, tables::LogAddressHistory, _>(tx, address_blocks)?;
+        unwind_log_history::<DB, tables::LogTopicHistory, _>(tx, topic_blocks)?;
+
+        Ok(UnwindOutput { stage_progress: input.unwind_to })
+    }
+}
+
+/// Remove the unwound block numbers of every key from the log history table.
+fn unwind_log_history<DB, T, K>(
+    tx: &Transaction<'_, DB>,
+    blocks: BTreeMap<K, Vec<BlockNumber>>,
+) -> Result<(), StageError>
+where
+    DB: Database,
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    K: PartialEq + Clone,
+{
+    let mut cursor = tx.cursor_write::<T>()?;
+    for (key, blocks) in blocks {
+        // block numbers are sorted, the first one is the lowest block to unwind.
+        let rem_index = blocks[0];
+        let shard_part = unwind_history_shards::<DB, T, K>(&mut cursor, key.clone(), rem_index)?;
+
+        // check last shard_part, if present, items needs to be reinserted.
+        if !shard_part.is_empty() {
+            // there are items in list
+            tx.put::<T>(
+                ShardedKey::new(key, u64::MAX),
+                BlockNumberList::new(shard_part)
+                    .expect("There is at least one element in list and it is sorted."),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestTransaction, PREV_STAGE_ID};
+    use reth_db::models::{sharded_key::NUM_OF_INDICES_IN_SHARD, StoredBlockBody};
+    use reth_primitives::{Address, Log, Receipt, H160, H256};
+
+    const ADDRESS: Address = H160([0x11; 20]);
+    const OTHER_ADDRESS: Address = H160([0x22; 20]);
+    const TOPIC: H256 = H256([0x33; 32]);
+
+    fn list(list: &[usize]) -> BlockNumberList {
+        BlockNumberList::new(list).unwrap()
+    }
+
+    fn cast<K: Ord>(
+        table: Vec<(ShardedKey<K>, BlockNumberList)>,
+    ) -> BTreeMap<ShardedKey<K>, Vec<usize>> {
+        table.into_iter().map(|(k, v)| (k, v.iter(0).collect())).collect()
+    }
+
+    fn receipt(logs: Vec<Log>) -> Receipt {
+        Receipt { logs, ..Default::default() }
+    }
+
+    fn log(address: Address, topics: Vec<H256>) -> Log {
+        Log { address, topics, data: Default::default() }
+    }
+
+    /// Inserts a block body with a single transaction for every receipt, starting at
+    /// `first_block`.
+    fn setup(tx: &TestTransaction, first_block: BlockNumber, receipts: Vec<Receipt>) {
+        tx.commit(|tx| {
+            for (index, receipt) in receipts.into_iter().enumerate() {
+                let block = first_block + index as u64;
+                tx.put::<tables::BlockBodies>(
+                    block,
+                    StoredBlockBody { start_tx_id: block, tx_count: 1 },
+                )?;
+                tx.put::<tables::Receipts>(block, receipt)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    async fn run(tx: &TestTransaction, stage_progress: Option<u64>, target: u64) {
+        let input = ExecInput { previous_stage: Some((PREV_STAGE_ID, target)), stage_progress };
+        let mut stage = IndexLogHistoryStage::default();
+        let mut db_tx = tx.inner();
+        let out = stage.execute(&mut db_tx, input).await.unwrap();
+        assert_eq!(out, ExecOutput { stage_progress: target, done: true });
+        db_tx.commit().unwrap();
+    }
+
+    async fn unwind(tx: &TestTransaction, stage_progress: u64, unwind_to: u64) {
+        let input = UnwindInput { stage_progress, unwind_to, ..Default::default() };
+        let mut stage = IndexLogHistoryStage::default();
+        let mut db_tx = tx.inner();
+        let out = stage.unwind(&mut db_tx, input).await.unwrap();
+        assert_eq!(out, UnwindOutput { stage_progress: unwind_to });
+        db_tx.commit().unwrap();
+    }
+
+    #[tokio::test]
+    async fn index_and_unwind_logs() {
+        let tx = TestTransaction::default();
+        setup(
+            &tx,
+            0,
+            vec![
+                receipt(vec![log(ADDRESS, vec![TOPIC])]),
+                receipt(vec![]),
+                receipt(vec![log(ADDRESS, vec![]), log(OTHER_ADDRESS, vec![TOPIC, TOPIC])]),
+                receipt(vec![log(OTHER_ADDRESS, vec![])]),
+            ],
+        );
+
+        run(&tx, None, 3).await;
+
+        let addresses = cast(tx.table::<tables::LogAddressHistory>().unwrap());
+        assert_eq!(
+            addresses,
+            BTreeMap::from([
+                (ShardedKey::new(ADDRESS, u64::MAX), vec![0, 2]),
+                (ShardedKey::new(OTHER_ADDRESS, u64::MAX), vec![2, 3]),
+            ])
+        );
+        let topics = cast(tx.table::<tables::LogTopicHistory>().unwrap());
+        assert_eq!(topics, BTreeMap::from([(ShardedKey::new(TOPIC, u64::MAX), vec![0, 2])]));
+
+        // unwind the last two blocks
+        unwind(&tx, 3, 1).await;
+
+        let addresses = cast(tx.table::<tables::LogAddressHistory>().unwrap());
+        assert_eq!(addresses, BTreeMap::from([(ShardedKey::new(ADDRESS, u64::MAX), vec![0])]));
+        let topics = cast(tx.table::<tables::LogTopicHistory>().unwrap());
+        assert_eq!(topics, BTreeMap::from([(ShardedKey::new(TOPIC, u64::MAX), vec![0])]));
+    }
+
+    #[tokio::test]
+    async fn index_and_unwind_logs_into_full_shard() {
+        let tx = TestTransaction::default();
+        let block = NUM_OF_INDICES_IN_SHARD as u64;
+        setup(&tx, block, vec![receipt(vec![log(ADDRESS, vec![TOPIC])])]);
+
+        let full_list = (0..NUM_OF_INDICES_IN_SHARD).collect::<Vec<_>>();
+        tx.commit(|tx| {
+            tx.put::<tables::LogAddressHistory>(
+                ShardedKey::new(ADDRESS, u64::MAX),
+                list(&full_list),
+            )
+        })
+        .unwrap();
+
+        run(&tx, Some(block - 1), block).await;
+
+        let addresses = cast(tx.table::<tables::LogAddressHistory>().unwrap());
+        assert_eq!(
+            addresses,
+            BTreeMap::from([
+                (ShardedKey::new(ADDRESS, block - 1), full_list.clone()),
+                (ShardedKey::new(ADDRESS, u64::MAX), vec![block as usize]),
+            ])
+        );
+
+        unwind(&tx, block, block - 1).await;
+
+        let addresses = cast(tx.table::<tables::LogAddressHistory>().unwrap());
+        assert_eq!(addresses, BTreeMap::from([(ShardedKey::new(ADDRESS, u64::MAX), full_list)]));
+        assert!(tx.table::<tables::LogTopicHistory>().unwrap().is_empty());
+    }
+}
@@ -12,8 +12,7 @@ use reth_provider::Transaction;
 use std::{collections::BTreeMap, fmt::Debug};
 use tracing::*;
 
-/// The [`StageId`] of the storage history indexing stage.
-pub const INDEX_STORAGE_HISTORY: StageId = StageId("IndexStorageHistory");
+pub use reth_db::models::stage::INDEX_STORAGE_HISTORY;
 
 /// Stage is indexing history the account changesets generated in
 /// [`ExecutionStage`][crate::stages::ExecutionStage]. For more information
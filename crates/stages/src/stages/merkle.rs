@@ -5,14 +5,7 @@ use reth_provider::{trie::DBTrieLoader, Transaction};
 use std::fmt::Debug;
 use tracing::*;
 
-/// The [`StageId`] of the merkle hashing execution stage.
-pub const MERKLE_EXECUTION: StageId = StageId("MerkleExecute");
-
-/// The [`StageId`] of the merkle hashing unwind stage.
-pub const MERKLE_UNWIND: StageId = StageId("MerkleUnwind");
-
-/// The [`StageId`] of the merkle hashing unwind and execution stage.
-pub const MERKLE_BOTH: StageId = StageId("MerkleBoth");
+pub use reth_db::models::stage::{MERKLE_BOTH, MERKLE_EXECUTION, MERKLE_UNWIND};
 
 /// The merkle hashing stage uses input from
 /// [`AccountHashingStage`][crate::stages::AccountHashingStage] and
@@ -17,8 +17,7 @@ use tokio::sync::mpsc;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::*;
 
-/// The [`StageId`] of the sender recovery stage.
-pub const SENDER_RECOVERY: StageId = StageId("SenderRecovery");
+pub use reth_db::models::stage::SENDER_RECOVERY;
 
 /// The sender recovery stage iterates over existing transactions,
 /// recovers the transaction signer and stores them
@@ -13,8 +13,7 @@ use reth_primitives::{ChainSpec, Hardfork, EMPTY_OMMER_ROOT, MAINNET, U256};
 use reth_provider::Transaction;
 use tracing::*;
 
-/// The [`StageId`] of the total difficulty stage.
-pub const TOTAL_DIFFICULTY: StageId = StageId("TotalDifficulty");
+pub use reth_db::models::stage::TOTAL_DIFFICULTY;
 
 /// The total difficulty stage.
 ///
@@ -11,8 +11,7 @@ use reth_db::{
 use reth_provider::Transaction;
 use tracing::*;
 
-/// The [`StageId`] of the transaction lookup stage.
-pub const TRANSACTION_LOOKUP: StageId = StageId("TransactionLookup");
+pub use reth_db::models::stage::TRANSACTION_LOOKUP;
 
 /// The transaction lookup stage.
 ///
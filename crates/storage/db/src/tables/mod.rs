@@ -32,7 +32,7 @@ pub enum TableType {
 }
 
 /// Default tables that should be present inside database.
-pub const TABLES: [(TableType, &str); 26] = [
+pub const TABLES: [(TableType, &str); 29] = [
     (TableType::Table, CanonicalHeaders::const_name()),
     (TableType::Table, HeaderTD::const_name()),
     (TableType::Table, HeaderNumbers::const_name()),
@@ -51,6 +51,9 @@ pub const TABLES: [(TableType, &str); 26] = [
     (TableType::Table, TxTransitionIndex::const_name()),
     (TableType::Table, AccountHistory::const_name()),
     (TableType::Table, StorageHistory::const_name()),
+    (TableType::Table, CallAddressHistory::const_name()),
+    (TableType::Table, LogAddressHistory::const_name()),
+    (TableType::Table, LogTopicHistory::const_name()),
     (TableType::DupSort, AccountChangeSet::const_name()),
     (TableType::DupSort, StorageChangeSet::const_name()),
     (TableType::Table, HashedAccount::const_name()),
@@ -241,6 +244,37 @@ table!(
     ( StorageHistory ) StorageShardedKey | TransitionList
 );
 
+table!(
+    /// Stores pointers to the transitions in which an address was the sender or the recipient of a
+    /// call, including internal calls, contract creations and selfdestructs.
+    ///
+    /// Sharded the same way as [`AccountHistory`], the last shard of an address contains
+    /// `u64::MAX` `TransitionId`.
+    ///
+    /// This table is only populated if the optional `IndexCallAddressHistory` stage is enabled.
+    ( CallAddressHistory ) ShardedKey<Address> | TransitionList
+);
+
+table!(
+    /// Stores pointers to the blocks that contain logs emitted by an address.
+    ///
+    /// Sharded like [`AccountHistory`], but by block number: the shard key holds the highest block
+    /// number of the shard and the last shard of an address contains `u64::MAX`.
+    ///
+    /// This table is only populated if the optional `IndexLogHistory` stage is enabled.
+    ( LogAddressHistory ) ShardedKey<Address> | BlockNumberList
+);
+
+table!(
+    /// Stores pointers to the blocks that contain logs with a topic, regardless of the position of
+    /// the topic in the log.
+    ///
+    /// Sharded the same way as [`LogAddressHistory`].
+    ///
+    /// This table is only populated if the optional `IndexLogHistory` stage is enabled.
+    ( LogTopicHistory ) ShardedKey<H256> | BlockNumberList
+);
+
 dupsort!(
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
@@ -298,6 +332,8 @@ table!(
 
 /// List with transaction numbers.
 pub type TransitionList = IntegerList;
+/// List with block numbers.
+pub type BlockNumberList = IntegerList;
 /// Encoded stage id.
 pub type StageId = Vec<u8>;
 
@@ -4,6 +4,7 @@ pub mod accounts;
 pub mod blocks;
 pub mod integer_list;
 pub mod sharded_key;
+pub mod stage;
 pub mod storage_sharded_key;
 
 pub use accounts::*;
@@ -0,0 +1,107 @@
+//! The ids of the sync stages, which key their progress in [SyncStage].
+
+use crate::{
+    tables::SyncStage,
+    transaction::{DbTx, DbTxMut},
+    Error as DbError,
+};
+use reth_primitives::BlockNumber;
+use std::fmt::Display;
+
+/// The ID of a stage.
+///
+/// Each stage ID must be unique.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StageId(pub &'static str);
+
+impl Display for StageId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StageId {
+    /// Returns a flag indicating if it's a downloading stage
+    pub fn is_downloading_stage(&self) -> bool {
+        *self == HEADERS || *self == BODIES
+    }
+
+    /// Get the last committed progress of this stage.
+    pub fn get_progress<'db>(&self, tx: &impl DbTx<'db>) -> Result<Option<BlockNumber>, DbError> {
+        tx.get::<SyncStage>(self.0.as_bytes().to_vec())
+    }
+
+    /// Save the progress of this stage.
+    pub fn save_progress<'db>(
+        &self,
+        tx: &impl DbTxMut<'db>,
+        block: BlockNumber,
+    ) -> Result<(), DbError> {
+        tx.put::<SyncStage>(self.0.as_bytes().to_vec(), block)
+    }
+}
+
+/// The [`StageId`] of the headers downloader stage.
+pub const HEADERS: StageId = StageId("Headers");
+
+/// The [`StageId`] of the total difficulty stage.
+pub const TOTAL_DIFFICULTY: StageId = StageId("TotalDifficulty");
+
+/// The [`StageId`] of the bodies downloader stage.
+pub const BODIES: StageId = StageId("Bodies");
+
+/// The [`StageId`] of the sender recovery stage.
+pub const SENDER_RECOVERY: StageId = StageId("SenderRecovery");
+
+/// The [`StageId`] of the execution stage.
+pub const EXECUTION: StageId = StageId("Execution");
+
+/// The [`StageId`] of the merkle hashing unwind stage.
+pub const MERKLE_UNWIND: StageId = StageId("MerkleUnwind");
+
+/// The [`StageId`] of the account hashing stage.
+pub const ACCOUNT_HASHING: StageId = StageId("AccountHashing");
+
+/// The [`StageId`] of the storage hashing stage.
+pub const STORAGE_HASHING: StageId = StageId("StorageHashing");
+
+/// The [`StageId`] of the merkle hashing execution stage.
+pub const MERKLE_EXECUTION: StageId = StageId("MerkleExecute");
+
+/// The [`StageId`] of the merkle hashing unwind and execution stage.
+pub const MERKLE_BOTH: StageId = StageId("MerkleBoth");
+
+/// The [`StageId`] of the transaction lookup stage.
+pub const TRANSACTION_LOOKUP: StageId = StageId("TransactionLookup");
+
+/// The [`StageId`] of the storage history indexing stage.
+pub const INDEX_STORAGE_HISTORY: StageId = StageId("IndexStorageHistory");
+
+/// The [`StageId`] of the account history indexing stage.
+pub const INDEX_ACCOUNT_HISTORY: StageId = StageId("IndexAccountHistory");
+
+/// The [`StageId`] of the call address history indexing stage.
+pub const INDEX_CALL_ADDRESS_HISTORY: StageId = StageId("IndexCallAddressHistory");
+
+/// The [`StageId`] of the log history indexing stage.
+pub const INDEX_LOG_HISTORY: StageId = StageId("IndexLogHistory");
+
+/// The [`StageId`] of the finish stage.
+pub const FINISH: StageId = StageId("Finish");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_id_display() {
+        assert_eq!(StageId("foo").to_string(), "foo");
+        assert_eq!(StageId("bar").to_string(), "bar");
+    }
+
+    #[test]
+    fn is_downloading_stage() {
+        assert!(HEADERS.is_downloading_stage());
+        assert!(BODIES.is_downloading_stage());
+    }
+}
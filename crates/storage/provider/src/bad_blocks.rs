@@ -0,0 +1,148 @@
+//! A record of blocks that were rejected as invalid.
+
+use parking_lot::RwLock;
+use reth_primitives::{Block, H256};
+use reth_rlp::Encodable;
+use reth_tracing::tracing::{debug, warn};
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The default number of bad blocks that are kept.
+pub const DEFAULT_BAD_BLOCKS_CAPACITY: usize = 10;
+
+/// A block that was rejected as invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadBlock {
+    /// Hash of the block.
+    pub hash: H256,
+    /// The rejected block.
+    pub block: Block,
+    /// The consensus or execution error the block was rejected with.
+    pub error: String,
+    /// The time the block was received and rejected, in seconds since the unix epoch.
+    pub received_at: u64,
+}
+
+/// A bounded store of the most recently rejected blocks, shared between the components that
+/// validate blocks and the `debug_getBadBlocks` endpoint.
+///
+/// Once the store is full, the oldest block is evicted. If a dump directory is configured, every
+/// bad block is also written RLP encoded to `<number>-<hash>.rlp` in that directory, so it can be
+/// imported and replayed later.
+#[derive(Debug, Clone)]
+pub struct BadBlocks {
+    /// The bad blocks, oldest first.
+    blocks: Arc<RwLock<VecDeque<BadBlock>>>,
+    /// Maximum number of blocks to keep.
+    capacity: usize,
+    /// Directory the bad blocks are dumped to.
+    dump_dir: Option<PathBuf>,
+}
+
+impl BadBlocks {
+    /// Creates a new store that keeps up to `capacity` blocks.
+    pub fn new(capacity: usize) -> Self {
+        Self { blocks: Default::default(), capacity, dump_dir: None }
+    }
+
+    /// Configures the directory every bad block is dumped to.
+    pub fn with_dump_dir(mut self, dump_dir: impl Into<PathBuf>) -> Self {
+        self.dump_dir = Some(dump_dir.into());
+        self
+    }
+
+    /// Records the block with the given hash as rejected with the error.
+    ///
+    /// A block that is already recorded is ignored.
+    pub fn insert(&self, hash: H256, block: Block, error: impl Display) {
+        let error = error.to_string();
+        let mut blocks = self.blocks.write();
+        if blocks.iter().any(|bad| bad.hash == hash) {
+            return
+        }
+
+        debug!(target: "provider::bad_blocks", number = block.number, ?hash, %error, "Recording bad block");
+        if let Some(dump_dir) = &self.dump_dir {
+            dump_block(dump_dir, hash, &block);
+        }
+
+        let received_at =
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        blocks.push_back(BadBlock { hash, block, error, received_at });
+        while blocks.len() > self.capacity {
+            blocks.pop_front();
+        }
+    }
+
+    /// Returns all recorded bad blocks, oldest first.
+    pub fn blocks(&self) -> Vec<BadBlock> {
+        self.blocks.read().iter().cloned().collect()
+    }
+
+    /// Returns the bad block with the given hash.
+    pub fn get(&self, hash: H256) -> Option<BadBlock> {
+        self.blocks.read().iter().find(|bad| bad.hash == hash).cloned()
+    }
+}
+
+impl Default for BadBlocks {
+    fn default() -> Self {
+        Self::new(DEFAULT_BAD_BLOCKS_CAPACITY)
+    }
+}
+
+/// Writes the RLP encoded block to the dump directory.
+fn dump_block(dump_dir: &Path, hash: H256, block: &Block) {
+    let path = dump_dir.join(format!("{}-{hash:?}.rlp", block.number));
+    let mut rlp = Vec::with_capacity(block.length());
+    block.encode(&mut rlp);
+    if let Err(err) = std::fs::create_dir_all(dump_dir).and_then(|_| std::fs::write(&path, rlp)) {
+        warn!(target: "provider::bad_blocks", path = %path.display(), %err, "Failed to dump bad block");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Header;
+    use reth_rlp::Decodable;
+
+    fn bad_block(number: u64) -> (H256, Block) {
+        let header = Header { number, ..Default::default() };
+        (header.hash_slow(), Block { header, ..Default::default() })
+    }
+
+    #[test]
+    fn evicts_oldest_blocks() {
+        let bad_blocks = BadBlocks::new(2);
+        for number in 0..3 {
+            let (hash, block) = bad_block(number);
+            bad_blocks.insert(hash, block, "invalid");
+        }
+        // duplicates are ignored
+        let (hash, block) = bad_block(2);
+        bad_blocks.insert(hash, block, "invalid again");
+
+        let blocks = bad_blocks.blocks();
+        assert_eq!(blocks.iter().map(|bad| bad.block.number).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(blocks[1].error, "invalid");
+        assert!(bad_blocks.get(bad_block(0).0).is_none());
+        assert_eq!(bad_blocks.get(hash), Some(blocks[1].clone()));
+    }
+
+    #[test]
+    fn dumps_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let bad_blocks = BadBlocks::default().with_dump_dir(dir.path());
+        let (hash, block) = bad_block(7);
+        bad_blocks.insert(hash, block.clone(), "invalid");
+
+        let rlp = std::fs::read(dir.path().join(format!("7-{hash:?}.rlp"))).unwrap();
+        assert_eq!(Block::decode(&mut rlp.as_slice()).unwrap(), block);
+    }
+}
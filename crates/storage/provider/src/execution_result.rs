@@ -7,7 +7,7 @@ use std::collections::BTreeMap;
 
 /// Execution Result containing vector of transaction changesets
 /// and block reward if present
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExecutionResult {
     /// Transaction changeset containing [Receipt], changed [Accounts][Account] and Storages.
     pub tx_changesets: Vec<TransactionChangeSet>,
@@ -57,6 +57,17 @@ pub enum AccountInfoChangeSet {
 }
 
 impl AccountInfoChangeSet {
+    /// Create the changeset of an account from its value before and after the change, `None` if
+    /// the account did not exist.
+    pub fn new(old: Option<Account>, new: Option<Account>) -> Self {
+        match (old, new) {
+            (None, Some(new)) => AccountInfoChangeSet::Created { new },
+            (Some(old), None) => AccountInfoChangeSet::Destroyed { old },
+            (Some(old), Some(new)) if old != new => AccountInfoChangeSet::Changed { new, old },
+            _ => AccountInfoChangeSet::NoChange,
+        }
+    }
+
     /// Apply the changes from the changeset to a database transaction.
     pub fn apply_to_db<'a, TX: DbTxMut<'a>>(
         self,
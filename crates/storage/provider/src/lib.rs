@@ -12,8 +12,8 @@
 mod traits;
 pub use traits::{
     AccountProvider, BlockExecutor, BlockHashProvider, BlockIdProvider, BlockProvider,
-    EvmEnvProvider, ExecutorFactory, HeaderProvider, StateProvider, StateProviderFactory,
-    TransactionsProvider, WithdrawalsProvider,
+    EvmEnvProvider, ExecutorFactory, HeaderProvider, ReceiptProvider, StateProvider,
+    StateProviderFactory, TransactionsProvider, WithdrawalsProvider,
 };
 
 /// Provider trait implementations.
@@ -29,6 +29,10 @@ pub mod trie;
 /// Execution result
 pub mod execution_result;
 
+/// Record of rejected blocks
+mod bad_blocks;
+pub use bad_blocks::{BadBlock, BadBlocks, DEFAULT_BAD_BLOCKS_CAPACITY};
+
 /// Helper types for interacting with the database
 mod transaction;
 pub use transaction::{Transaction, TransactionError};
@@ -1,24 +1,35 @@
 use crate::{
     BlockHashProvider, BlockIdProvider, BlockProvider, EvmEnvProvider, HeaderProvider,
-    ProviderError, StateProviderFactory, TransactionsProvider, WithdrawalsProvider,
+    ProviderError, ReceiptProvider, StateProviderFactory, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use reth_db::{
     cursor::DbCursorRO,
     database::{Database, DatabaseGAT},
+    models::{
+        stage::{FINISH, INDEX_CALL_ADDRESS_HISTORY, INDEX_LOG_HISTORY},
+        ShardedKey,
+    },
+    table::Table,
     tables,
     transaction::DbTx,
+    BlockNumberList,
 };
 use reth_interfaces::Result;
 use reth_primitives::{
-    Block, BlockHash, BlockId, BlockNumber, ChainInfo, ChainSpec, Hardfork, Head, Header,
-    TransactionSigned, TxHash, TxNumber, Withdrawal, H256, U256,
+    Address, Block, BlockHash, BlockId, BlockNumber, ChainInfo, ChainSpec, Hardfork, Head, Header,
+    Receipt, TransactionMeta, TransactionSigned, TxHash, TxNumber, Withdrawal, H256, U256,
 };
 use reth_revm_primitives::{
     config::revm_spec,
     env::{fill_block_env, fill_cfg_and_block_env, fill_cfg_env},
 };
 use revm_primitives::{BlockEnv, CfgEnv, SpecId};
-use std::{ops::RangeBounds, sync::Arc};
+use std::{
+    collections::BTreeSet,
+    ops::{RangeBounds, RangeInclusive},
+    sync::Arc,
+};
 
 mod state;
 pub use state::{
@@ -105,7 +116,7 @@ impl<DB: Database> BlockIdProvider for ShareableDatabase<DB> {
     fn chain_info(&self) -> Result<ChainInfo> {
         let best_number = self
             .db
-            .view(|tx| tx.get::<tables::SyncStage>("Finish".as_bytes().to_vec()))?
+            .view(|tx| FINISH.get_progress(tx))?
             .map_err(Into::<reth_interfaces::db::Error>::into)?
             .unwrap_or_default();
         let best_hash = self.block_hash(U256::from(best_number))?.unwrap_or_default();
@@ -170,6 +181,43 @@ impl<DB: Database> TransactionsProvider for ShareableDatabase<DB> {
             .map_err(Into::into)
     }
 
+    fn transaction_by_hash_with_meta(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<Option<(TransactionSigned, TransactionMeta)>> {
+        let tx = self.db.tx()?;
+        let Some(transaction_id) = tx.get::<tables::TxHashNumber>(tx_hash)? else {
+            return Ok(None)
+        };
+        let Some(transaction) = tx.get::<tables::Transactions>(transaction_id)? else {
+            return Ok(None)
+        };
+        let Some(block_number) = transaction_block(&tx, transaction_id)? else { return Ok(None) };
+        let body = tx
+            .get::<tables::BlockBodies>(block_number)?
+            .ok_or(ProviderError::BlockBody { number: block_number })?;
+        let block_hash = tx
+            .get::<tables::CanonicalHeaders>(block_number)?
+            .ok_or(ProviderError::CanonicalHeader { block_number })?;
+        let header =
+            tx.get::<tables::Headers>(block_number)?.ok_or(ProviderError::HeaderNotFound)?;
+
+        let meta = TransactionMeta {
+            tx_hash,
+            index: transaction_id - body.start_tx_id,
+            block_hash,
+            block_number,
+            base_fee: header.base_fee_per_gas,
+        };
+
+        Ok(Some((transaction, meta)))
+    }
+
+    fn transaction_block(&self, id: TxNumber) -> Result<Option<BlockNumber>> {
+        let tx = self.db.tx()?;
+        transaction_block(&tx, id)
+    }
+
     fn transactions_by_block(&self, id: BlockId) -> Result<Option<Vec<TransactionSigned>>> {
         if let Some(number) = self.block_number_for_id(id)? {
             let tx = self.db.tx()?;
@@ -217,6 +265,180 @@ impl<DB: Database> TransactionsProvider for ShareableDatabase<DB> {
         }
         Ok(results)
     }
+
+    fn transactions_by_call_addresses(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<Vec<(BlockNumber, u64)>>> {
+        let tx = self.db.tx()?;
+        let (start, end) = range.into_inner();
+
+        // the index only covers the blocks up to the progress of its stage
+        let progress = INDEX_CALL_ADDRESS_HISTORY.get_progress(&tx)?;
+        if progress.map_or(true, |progress| progress < end) {
+            return Ok(None)
+        }
+        if start > end {
+            return Ok(Some(Vec::new()))
+        }
+
+        let block_transition = |number: BlockNumber| -> Result<u64> {
+            Ok(tx
+                .get::<tables::BlockTransitionIndex>(number)?
+                .ok_or(ProviderError::BlockTransition { block_number: number })?)
+        };
+        let from_transition = if start == 0 { 0 } else { block_transition(start - 1)? };
+        let to_transition = block_transition(end)?;
+
+        // collect the transitions in the range from the shards of every address
+        let mut transitions = BTreeSet::new();
+        let mut cursor = tx.cursor_read::<tables::CallAddressHistory>()?;
+        for address in addresses {
+            let mut walker = cursor.walk(Some(ShardedKey::new(*address, from_transition)))?;
+            while let Some((sharded_key, list)) = walker.next().transpose()? {
+                if sharded_key.key != *address {
+                    break
+                }
+                transitions.extend(
+                    list.iter(0)
+                        .map(|transition| transition as u64)
+                        .skip_while(|transition| *transition < from_transition)
+                        .take_while(|transition| *transition < to_transition),
+                );
+                if sharded_key.highest_transition_id >= to_transition {
+                    break
+                }
+            }
+        }
+
+        // map the transitions to the transactions of the blocks
+        let mut result = Vec::with_capacity(transitions.len());
+        let mut transitions = transitions.into_iter().peekable();
+        let mut block_start = from_transition;
+        for entry in tx.cursor_read::<tables::BlockTransitionIndex>()?.walk_range(start..=end)? {
+            let (number, block_end) = entry?;
+            while let Some(transition) = transitions.next_if(|transition| *transition < block_end) {
+                result.push((number, transition - block_start));
+            }
+            block_start = block_end;
+        }
+        Ok(Some(result))
+    }
+}
+
+impl<DB: Database> ReceiptProvider for ShareableDatabase<DB> {
+    fn receipt(&self, id: TxNumber) -> Result<Option<Receipt>> {
+        self.db.view(|tx| tx.get::<tables::Receipts>(id))?.map_err(Into::into)
+    }
+
+    fn receipt_by_hash(&self, hash: TxHash) -> Result<Option<Receipt>> {
+        self.db
+            .view(|tx| {
+                if let Some(id) = tx.get::<tables::TxHashNumber>(hash)? {
+                    tx.get::<tables::Receipts>(id)
+                } else {
+                    Ok(None)
+                }
+            })?
+            .map_err(Into::into)
+    }
+
+    fn receipts_by_block(&self, block: BlockId) -> Result<Option<Vec<Receipt>>> {
+        if let Some(number) = self.block_number_for_id(block)? {
+            let tx = self.db.tx()?;
+            if let Some(body) = tx.get::<tables::BlockBodies>(number)? {
+                let tx_range = body.tx_id_range();
+                return if tx_range.is_empty() {
+                    Ok(Some(Vec::new()))
+                } else {
+                    let mut tx_cursor = tx.cursor_read::<tables::Receipts>()?;
+                    let receipts = tx_cursor
+                        .walk_range(tx_range)?
+                        .map(|result| result.map(|(_, receipt)| receipt))
+                        .collect::<std::result::Result<Vec<_>, _>>()?;
+                    Ok(Some(receipts))
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn blocks_by_log_index(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<H256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<Vec<BlockNumber>>> {
+        let topics = topics.iter().filter(|topics| !topics.is_empty()).collect::<Vec<_>>();
+        if addresses.is_empty() && topics.is_empty() {
+            return Ok(None)
+        }
+
+        let tx = self.db.tx()?;
+        let (start, end) = range.into_inner();
+
+        // the index only covers the blocks up to the progress of its stage
+        let progress = INDEX_LOG_HISTORY.get_progress(&tx)?;
+        if progress.map_or(true, |progress| progress < end) {
+            return Ok(None)
+        }
+        if start > end {
+            return Ok(Some(Vec::new()))
+        }
+
+        // intersect the blocks of the addresses with the blocks of every topic position
+        let mut blocks = None;
+        if !addresses.is_empty() {
+            blocks = Some(log_history_blocks::<_, tables::LogAddressHistory, _>(
+                &tx, addresses, start, end,
+            )?);
+        }
+        for topics in topics {
+            let topic_blocks =
+                log_history_blocks::<_, tables::LogTopicHistory, _>(&tx, topics, start, end)?;
+            blocks = Some(match blocks {
+                Some(blocks) => blocks.intersection(&topic_blocks).copied().collect(),
+                None => topic_blocks,
+            });
+        }
+        Ok(blocks.map(|blocks| blocks.into_iter().collect()))
+    }
+}
+
+/// Collect the block numbers between `start` and `end` from the shards of any of the keys in a log
+/// history table.
+fn log_history_blocks<'a, TX, T, K>(
+    tx: &TX,
+    keys: &[K],
+    start: BlockNumber,
+    end: BlockNumber,
+) -> Result<BTreeSet<BlockNumber>>
+where
+    TX: DbTx<'a>,
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    K: PartialEq + Clone,
+{
+    let mut blocks = BTreeSet::new();
+    let mut cursor = tx.cursor_read::<T>()?;
+    for key in keys {
+        let mut walker = cursor.walk(Some(ShardedKey::new(key.clone(), start)))?;
+        while let Some((sharded_key, list)) = walker.next().transpose()? {
+            if sharded_key.key != *key {
+                break
+            }
+            blocks.extend(
+                list.iter(0)
+                    .map(|block| block as u64)
+                    .skip_while(|block| *block < start)
+                    .take_while(|block| *block <= end),
+            );
+            if sharded_key.highest_transition_id >= end {
+                break
+            }
+        }
+    }
+    Ok(blocks)
 }
 
 impl<DB: Database> WithdrawalsProvider for ShareableDatabase<DB> {
@@ -331,12 +553,47 @@ impl<DB: Database> StateProviderFactory for ShareableDatabase<DB> {
     }
 }
 
+/// Returns the number of the block that contains the transaction with the given id.
+///
+/// Since the transaction ids of the stored block bodies are strictly ascending, this performs a
+/// binary search over the [tables::BlockBodies] table for the last block whose first transaction
+/// id is not greater than the given id.
+fn transaction_block<'a, TX: DbTx<'a>>(tx: &TX, id: TxNumber) -> Result<Option<BlockNumber>> {
+    let mut cursor = tx.cursor_read::<tables::BlockBodies>()?;
+    let Some((last_block, _)) = cursor.last()? else { return Ok(None) };
+
+    let (mut low, mut high) = (0, last_block);
+    while low <= high {
+        let mid = low + (high - low) / 2;
+        match tx.get::<tables::BlockBodies>(mid)? {
+            Some(body) if body.start_tx_id <= id => {
+                if body.tx_id_range().contains(&id) {
+                    return Ok(Some(mid))
+                }
+                low = mid + 1;
+            }
+            Some(_) if mid > 0 => high = mid - 1,
+            // bodies are contiguous, a gap means this range is not available
+            _ => break,
+        }
+    }
+
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::ShareableDatabase;
-    use crate::{BlockIdProvider, StateProviderFactory};
-    use reth_db::mdbx::{test_utils::create_test_db, EnvKind, WriteMap};
-    use reth_primitives::{ChainSpecBuilder, H256};
+    use crate::{BlockIdProvider, ReceiptProvider, StateProviderFactory, TransactionsProvider};
+    use reth_db::{
+        database::Database,
+        mdbx::{test_utils::create_test_db, EnvKind, WriteMap},
+        models::ShardedKey,
+        tables,
+        transaction::DbTxMut,
+        BlockNumberList, TransitionList,
+    };
+    use reth_primitives::{Address, ChainSpecBuilder, H256};
 
     #[test]
     fn common_history_provider() {
@@ -358,4 +615,104 @@ mod tests {
         assert_eq!(chain_info.last_finalized, None);
         assert_eq!(chain_info.safe_finalized, None);
     }
+
+    #[test]
+    fn transactions_by_call_addresses() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let db = create_test_db::<WriteMap>(EnvKind::RW);
+        let address = Address::from_low_u64_be(1);
+        let other = Address::from_low_u64_be(2);
+        db.update(|tx| {
+            tx.put::<tables::BlockTransitionIndex>(0, 2).unwrap();
+            tx.put::<tables::BlockTransitionIndex>(1, 4).unwrap();
+            tx.put::<tables::BlockTransitionIndex>(2, 7).unwrap();
+            tx.put::<tables::CallAddressHistory>(
+                ShardedKey::new(address, 3),
+                TransitionList::new([1, 3]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::CallAddressHistory>(
+                ShardedKey::new(address, u64::MAX),
+                TransitionList::new([5]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::CallAddressHistory>(
+                ShardedKey::new(other, u64::MAX),
+                TransitionList::new([0, 6]).unwrap(),
+            )
+            .unwrap();
+            INDEX_CALL_ADDRESS_HISTORY.save_progress(&tx, 2).unwrap();
+        })
+        .unwrap();
+        let provider = ShareableDatabase::new(db, chain_spec);
+
+        assert_eq!(
+            provider.transactions_by_call_addresses(&[address], 1..=2).unwrap(),
+            Some(vec![(1, 1), (2, 1)])
+        );
+        assert_eq!(
+            provider.transactions_by_call_addresses(&[address, other], 0..=2).unwrap(),
+            Some(vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 2)])
+        );
+        assert_eq!(provider.transactions_by_call_addresses(&[other], 1..=1).unwrap(), Some(vec![]));
+
+        // not indexed yet
+        assert_eq!(provider.transactions_by_call_addresses(&[address], 2..=3).unwrap(), None);
+    }
+
+    #[test]
+    fn blocks_by_log_index() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let db = create_test_db::<WriteMap>(EnvKind::RW);
+        let address = Address::from_low_u64_be(1);
+        let other = Address::from_low_u64_be(2);
+        let topic = H256::from_low_u64_be(3);
+        db.update(|tx| {
+            tx.put::<tables::LogAddressHistory>(
+                ShardedKey::new(address, 2),
+                BlockNumberList::new([1, 2]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::LogAddressHistory>(
+                ShardedKey::new(address, u64::MAX),
+                BlockNumberList::new([4, 6]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::LogAddressHistory>(
+                ShardedKey::new(other, u64::MAX),
+                BlockNumberList::new([3]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::LogTopicHistory>(
+                ShardedKey::new(topic, u64::MAX),
+                BlockNumberList::new([2, 3, 6]).unwrap(),
+            )
+            .unwrap();
+            INDEX_LOG_HISTORY.save_progress(&tx, 6).unwrap();
+        })
+        .unwrap();
+        let provider = ShareableDatabase::new(db, chain_spec);
+
+        assert_eq!(
+            provider.blocks_by_log_index(&[address], &[], 2..=6).unwrap(),
+            Some(vec![2, 4, 6])
+        );
+        assert_eq!(
+            provider.blocks_by_log_index(&[address, other], &[], 0..=3).unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(
+            provider.blocks_by_log_index(&[address], &[vec![], vec![topic]], 0..=6).unwrap(),
+            Some(vec![2, 6])
+        );
+        assert_eq!(
+            provider.blocks_by_log_index(&[], &[vec![topic]], 3..=5).unwrap(),
+            Some(vec![3])
+        );
+
+        // nothing to look up
+        assert_eq!(provider.blocks_by_log_index(&[], &[vec![]], 0..=6).unwrap(), None);
+        // not indexed yet
+        assert_eq!(provider.blocks_by_log_index(&[address], &[], 0..=7).unwrap(), None);
+    }
 }
@@ -1,6 +1,7 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountProvider, BlockHashProvider,
-    ProviderError, StateProvider,
+    providers::state::{latest::latest_state_root, macros::delegate_provider_impls},
+    trie::{AccountProof, DBTrieLoader},
+    AccountProvider, BlockHashProvider, ProviderError, StateProvider,
 };
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
@@ -119,6 +120,26 @@ impl<'a, 'b, TX: DbTx<'a>> StateProvider for HistoricalStateProviderRef<'a, 'b,
     fn bytecode_by_hash(&self, code_hash: H256) -> Result<Option<Bytecode>> {
         self.tx.get::<tables::Bytecodes>(code_hash).map_err(Into::into)
     }
+
+    /// Get account and storage proofs by rebuilding the state tries at the transition.
+    fn proof(&self, address: Address, keys: &[H256]) -> Result<AccountProof> {
+        let (number, root) = latest_state_root(self.tx)?;
+        let end = self
+            .tx
+            .get::<tables::BlockTransitionIndex>(number)?
+            .ok_or(ProviderError::BlockTransition { block_number: number })?;
+        // the changesets can only revert the tries to transitions before their block
+        if self.transition > end {
+            return Err(ProviderError::StateProof(format!(
+                "state tries are at transition {end} of block #{number}, before transition {}",
+                self.transition
+            ))
+            .into())
+        }
+        DBTrieLoader::default()
+            .generate_historical_proof(self.tx, root, self.transition..end, address, keys)
+            .map_err(|err| ProviderError::StateProof(err.to_string()).into())
+    }
 }
 
 /// State provider for a given transition
@@ -1,10 +1,13 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountProvider, BlockHashProvider,
-    StateProvider,
+    providers::state::macros::delegate_provider_impls,
+    trie::{AccountProof, DBTrieLoader},
+    AccountProvider, BlockHashProvider, ProviderError, StateProvider,
 };
-use reth_db::{cursor::DbDupCursorRO, tables, transaction::DbTx};
+use reth_db::{cursor::DbDupCursorRO, models::stage::MERKLE_EXECUTION, tables, transaction::DbTx};
 use reth_interfaces::Result;
-use reth_primitives::{Account, Address, Bytecode, StorageKey, StorageValue, H256, U256};
+use reth_primitives::{
+    Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, H256, U256,
+};
 use std::marker::PhantomData;
 
 /// State provider over latest state that takes tx reference.
@@ -52,6 +55,25 @@ impl<'a, 'b, TX: DbTx<'a>> StateProvider for LatestStateProviderRef<'a, 'b, TX>
     fn bytecode_by_hash(&self, code_hash: H256) -> Result<Option<Bytecode>> {
         self.db.get::<tables::Bytecodes>(code_hash).map_err(Into::into)
     }
+
+    /// Get account and storage proofs from the state tries.
+    fn proof(&self, address: Address, keys: &[H256]) -> Result<AccountProof> {
+        let (_, root) = latest_state_root(self.db)?;
+        DBTrieLoader::default()
+            .generate_proof(self.db, root, address, keys)
+            .map_err(|err| ProviderError::StateProof(err.to_string()).into())
+    }
+}
+
+/// Returns the number and the state root of the block at the merkle stage checkpoint, which is
+/// the root of the state tries in the database.
+///
+/// The tries can lag behind the canonical tip while the pipeline is syncing, so the root of the
+/// last canonical header is not necessarily present in them.
+pub(crate) fn latest_state_root<'a, TX: DbTx<'a>>(tx: &TX) -> Result<(BlockNumber, H256)> {
+    let number = MERKLE_EXECUTION.get_progress(tx)?.unwrap_or_default();
+    let header = tx.get::<tables::Headers>(number)?.ok_or(ProviderError::Header { number })?;
+    Ok((number, header.state_root))
 }
 
 /// State provider for the latest state.
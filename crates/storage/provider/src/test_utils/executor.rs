@@ -0,0 +1,86 @@
+use crate::{execution_result::ExecutionResult, BlockExecutor, ExecutorFactory, StateProvider};
+use parking_lot::Mutex;
+use reth_interfaces::executor::Error as ExecutionError;
+use reth_primitives::{Address, Block, ChainSpec, TransactionKind, U256};
+use std::{collections::BTreeSet, sync::Arc};
+
+/// Test executor with mocked result.
+#[derive(Debug)]
+pub struct TestExecutor(pub Option<ExecutionResult>);
+
+impl<SP: StateProvider> BlockExecutor<SP> for TestExecutor {
+    fn execute(
+        &mut self,
+        _block: &Block,
+        _total_difficulty: U256,
+        _senders: Option<Vec<Address>>,
+    ) -> Result<ExecutionResult, ExecutionError> {
+        self.0.clone().ok_or(ExecutionError::VerificationFailed)
+    }
+
+    fn execute_and_verify_receipt(
+        &mut self,
+        _block: &Block,
+        _total_difficulty: U256,
+        _senders: Option<Vec<Address>>,
+    ) -> Result<ExecutionResult, ExecutionError> {
+        self.0.clone().ok_or(ExecutionError::VerificationFailed)
+    }
+
+    /// Returns the senders and the recipients of the transactions, without executing them.
+    fn call_addresses(
+        &mut self,
+        block: &Block,
+        _total_difficulty: U256,
+        senders: Option<Vec<Address>>,
+    ) -> Result<Vec<BTreeSet<Address>>, ExecutionError> {
+        let senders = senders.ok_or(ExecutionError::SenderRecoveryError)?;
+        Ok(block
+            .body
+            .iter()
+            .zip(senders)
+            .map(|(transaction, sender)| {
+                let mut addresses = BTreeSet::from([sender]);
+                if let TransactionKind::Call(to) = transaction.kind() {
+                    addresses.insert(*to);
+                }
+                addresses
+            })
+            .collect())
+    }
+}
+
+/// Executor factory with pre-set execution results.
+///
+/// Every executor created by the factory returns the next queued result.
+#[derive(Clone, Debug)]
+pub struct TestExecutorFactory {
+    exec_results: Arc<Mutex<Vec<ExecutionResult>>>,
+    chain_spec: Arc<ChainSpec>,
+}
+
+impl TestExecutorFactory {
+    /// Create new instance of test factory.
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        Self { exec_results: Arc::new(Mutex::new(Vec::new())), chain_spec }
+    }
+
+    /// Extend the mocked execution results
+    pub fn extend(&self, results: Vec<ExecutionResult>) {
+        self.exec_results.lock().extend(results.into_iter());
+    }
+}
+
+impl ExecutorFactory for TestExecutorFactory {
+    type Executor<T: StateProvider> = TestExecutor;
+
+    fn with_sp<SP: StateProvider>(&self, _sp: SP) -> Self::Executor<SP> {
+        let mut results = self.exec_results.lock();
+        let result = (!results.is_empty()).then(|| results.remove(0));
+        TestExecutor(result)
+    }
+
+    fn chain_spec(&self) -> &ChainSpec {
+        self.chain_spec.as_ref()
+    }
+}
@@ -1,16 +1,21 @@
 use crate::{
-    AccountProvider, BlockHashProvider, BlockIdProvider, BlockProvider, EvmEnvProvider,
-    HeaderProvider, StateProvider, StateProviderFactory, TransactionsProvider,
+    trie::AccountProof, AccountProvider, BlockHashProvider, BlockIdProvider, BlockProvider,
+    EvmEnvProvider, HeaderProvider, ReceiptProvider, StateProvider, StateProviderFactory,
+    TransactionsProvider,
 };
 use parking_lot::Mutex;
 use reth_interfaces::Result;
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockId, BlockNumber, BlockNumberOrTag,
-    Bytecode, Bytes, ChainInfo, Header, StorageKey, StorageValue, TransactionSigned, TxHash, H256,
-    U256,
+    Bytecode, Bytes, ChainInfo, Header, Receipt, StorageKey, StorageValue, TransactionMeta,
+    TransactionSigned, TxHash, TxNumber, H256, U256,
 };
 use revm_primitives::{BlockEnv, CfgEnv};
-use std::{collections::HashMap, ops::RangeBounds, sync::Arc};
+use std::{
+    collections::HashMap,
+    ops::{RangeBounds, RangeInclusive},
+    sync::Arc,
+};
 
 /// A mock implementation for Provider interfaces.
 #[derive(Debug, Clone, Default)]
@@ -19,6 +24,8 @@ pub struct MockEthProvider {
     pub blocks: Arc<Mutex<HashMap<H256, Block>>>,
     /// Local header store
     pub headers: Arc<Mutex<HashMap<H256, Header>>>,
+    /// Local receipt store, keyed by block hash
+    pub receipts: Arc<Mutex<HashMap<H256, Vec<Receipt>>>>,
     /// Local account store
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
 }
@@ -77,6 +84,11 @@ impl MockEthProvider {
         }
     }
 
+    /// Add the receipts of a block to local receipt store
+    pub fn add_receipts(&self, hash: H256, receipts: Vec<Receipt>) {
+        self.receipts.lock().insert(hash, receipts);
+    }
+
     /// Add account to local account store
     pub fn add_account(&self, address: Address, account: ExtendedAccount) {
         self.accounts.lock().insert(address, account);
@@ -144,6 +156,32 @@ impl TransactionsProvider for MockEthProvider {
             .find_map(|(_, block)| block.body.iter().find(|tx| tx.hash == hash).cloned()))
     }
 
+    fn transaction_by_hash_with_meta(
+        &self,
+        hash: TxHash,
+    ) -> Result<Option<(TransactionSigned, TransactionMeta)>> {
+        let lock = self.blocks.lock();
+        for (block_hash, block) in lock.iter() {
+            for (index, tx) in block.body.iter().enumerate() {
+                if tx.hash() == hash {
+                    let meta = TransactionMeta {
+                        tx_hash: hash,
+                        index: index as u64,
+                        block_hash: *block_hash,
+                        block_number: block.header.number,
+                        base_fee: block.header.base_fee_per_gas,
+                    };
+                    return Ok(Some((tx.clone(), meta)))
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn transaction_block(&self, _id: TxNumber) -> Result<Option<BlockNumber>> {
+        unimplemented!()
+    }
+
     fn transactions_by_block(&self, id: BlockId) -> Result<Option<Vec<TransactionSigned>>> {
         Ok(self.block(id)?.map(|b| b.body))
     }
@@ -154,6 +192,38 @@ impl TransactionsProvider for MockEthProvider {
     ) -> Result<Vec<Vec<TransactionSigned>>> {
         unimplemented!()
     }
+
+    fn transactions_by_call_addresses(
+        &self,
+        _addresses: &[Address],
+        _range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<Vec<(BlockNumber, u64)>>> {
+        Ok(None)
+    }
+}
+
+impl ReceiptProvider for MockEthProvider {
+    fn receipt(&self, _id: TxNumber) -> Result<Option<Receipt>> {
+        Ok(None)
+    }
+
+    fn receipt_by_hash(&self, _hash: TxHash) -> Result<Option<Receipt>> {
+        Ok(None)
+    }
+
+    fn receipts_by_block(&self, block: BlockId) -> Result<Option<Vec<Receipt>>> {
+        let Some(hash) = self.block_hash_for_id(block)? else { return Ok(None) };
+        Ok(self.receipts.lock().get(&hash).cloned())
+    }
+
+    fn blocks_by_log_index(
+        &self,
+        _addresses: &[Address],
+        _topics: &[Vec<H256>],
+        _range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<Vec<BlockNumber>>> {
+        Ok(None)
+    }
 }
 
 impl BlockHashProvider for MockEthProvider {
@@ -238,6 +308,10 @@ impl StateProvider for MockEthProvider {
             }
         }))
     }
+
+    fn proof(&self, _address: Address, _keys: &[H256]) -> Result<AccountProof> {
+        unimplemented!()
+    }
 }
 
 impl EvmEnvProvider for MockEthProvider {
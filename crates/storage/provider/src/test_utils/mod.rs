@@ -1,5 +1,7 @@
+mod executor;
 mod mock;
 mod noop;
 
+pub use executor::{TestExecutor, TestExecutorFactory};
 pub use mock::{ExtendedAccount, MockEthProvider};
 pub use noop::NoopProvider;
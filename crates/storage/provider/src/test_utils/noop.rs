@@ -1,14 +1,15 @@
 use crate::{
-    AccountProvider, BlockHashProvider, BlockIdProvider, BlockProvider, EvmEnvProvider,
-    HeaderProvider, StateProvider, StateProviderFactory, TransactionsProvider,
+    trie::AccountProof, AccountProvider, BlockHashProvider, BlockIdProvider, BlockProvider,
+    EvmEnvProvider, HeaderProvider, ReceiptProvider, StateProvider, StateProviderFactory,
+    TransactionsProvider,
 };
 use reth_interfaces::Result;
 use reth_primitives::{
-    Account, Address, Block, BlockHash, BlockId, BlockNumber, Bytecode, ChainInfo, Header,
-    StorageKey, StorageValue, TransactionSigned, TxHash, TxNumber, H256, U256,
+    Account, Address, Block, BlockHash, BlockId, BlockNumber, Bytecode, ChainInfo, Header, Receipt,
+    StorageKey, StorageValue, TransactionMeta, TransactionSigned, TxHash, TxNumber, H256, U256,
 };
 use revm_primitives::{BlockEnv, CfgEnv};
-use std::ops::RangeBounds;
+use std::ops::{RangeBounds, RangeInclusive};
 
 /// Supports various api interfaces for testing purposes.
 #[derive(Debug, Clone, Default, Copy)]
@@ -51,6 +52,17 @@ impl TransactionsProvider for NoopProvider {
         Ok(None)
     }
 
+    fn transaction_by_hash_with_meta(
+        &self,
+        _hash: TxHash,
+    ) -> Result<Option<(TransactionSigned, TransactionMeta)>> {
+        Ok(None)
+    }
+
+    fn transaction_block(&self, _id: TxNumber) -> Result<Option<BlockNumber>> {
+        Ok(None)
+    }
+
     fn transactions_by_block(&self, _block_id: BlockId) -> Result<Option<Vec<TransactionSigned>>> {
         Ok(None)
     }
@@ -61,6 +73,37 @@ impl TransactionsProvider for NoopProvider {
     ) -> Result<Vec<Vec<TransactionSigned>>> {
         Ok(Vec::default())
     }
+
+    fn transactions_by_call_addresses(
+        &self,
+        _addresses: &[Address],
+        _range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<Vec<(BlockNumber, u64)>>> {
+        Ok(None)
+    }
+}
+
+impl ReceiptProvider for NoopProvider {
+    fn receipt(&self, _id: TxNumber) -> Result<Option<Receipt>> {
+        Ok(None)
+    }
+
+    fn receipt_by_hash(&self, _hash: TxHash) -> Result<Option<Receipt>> {
+        Ok(None)
+    }
+
+    fn receipts_by_block(&self, _block: BlockId) -> Result<Option<Vec<Receipt>>> {
+        Ok(None)
+    }
+
+    fn blocks_by_log_index(
+        &self,
+        _addresses: &[Address],
+        _topics: &[Vec<H256>],
+        _range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<Vec<BlockNumber>>> {
+        Ok(None)
+    }
 }
 
 impl HeaderProvider for NoopProvider {
@@ -99,6 +142,10 @@ impl StateProvider for NoopProvider {
     fn bytecode_by_hash(&self, _code_hash: H256) -> Result<Option<Bytecode>> {
         Ok(None)
     }
+
+    fn proof(&self, _address: Address, _keys: &[H256]) -> Result<AccountProof> {
+        Ok(AccountProof::default())
+    }
 }
 
 impl EvmEnvProvider for NoopProvider {
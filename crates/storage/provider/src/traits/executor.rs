@@ -3,6 +3,7 @@
 use crate::{execution_result::ExecutionResult, StateProvider};
 use reth_interfaces::executor::Error;
 use reth_primitives::{Address, Block, ChainSpec, U256};
+use std::collections::BTreeSet;
 
 /// Executor factory that would create the EVM with particular state provider.
 ///
@@ -42,4 +43,16 @@ pub trait BlockExecutor<SP: StateProvider> {
         total_difficulty: U256,
         senders: Option<Vec<Address>>,
     ) -> Result<ExecutionResult, Error>;
+
+    /// Executes the transactions of the block and returns the addresses that sent or received a
+    /// call in each of them, including internal calls.
+    ///
+    /// This is used for indexing and neither verifies the block nor applies the post block
+    /// changes.
+    fn call_addresses(
+        &mut self,
+        block: &Block,
+        total_difficulty: U256,
+        senders: Option<Vec<Address>>,
+    ) -> Result<Vec<BTreeSet<Address>>, Error>;
 }
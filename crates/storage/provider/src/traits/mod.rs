@@ -18,6 +18,9 @@ pub use evm_env::EvmEnvProvider;
 mod header;
 pub use header::HeaderProvider;
 
+mod receipts;
+pub use receipts::ReceiptProvider;
+
 mod state;
 pub use state::{StateProvider, StateProviderFactory};
 
@@ -0,0 +1,35 @@
+use reth_interfaces::Result;
+use reth_primitives::{Address, BlockId, BlockNumber, Receipt, TxHash, TxNumber, H256};
+use std::ops::RangeInclusive;
+
+/// Client trait for fetching [Receipt] data.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ReceiptProvider: Send + Sync {
+    /// Get receipt by transaction number
+    fn receipt(&self, id: TxNumber) -> Result<Option<Receipt>>;
+
+    /// Get receipt by transaction hash.
+    fn receipt_by_hash(&self, hash: TxHash) -> Result<Option<Receipt>>;
+
+    /// Get receipts by block id.
+    ///
+    /// Returns `None` if the block is not found.
+    fn receipts_by_block(&self, block: BlockId) -> Result<Option<Vec<Receipt>>>;
+
+    /// Get the numbers of the blocks in the range that contain a log matching the addresses and
+    /// the topics, using the log history index.
+    ///
+    /// A block matches if one of its logs was emitted by any of the `addresses` and contains any
+    /// of the topics of every entry in `topics` at that position. Empty lists match everything.
+    /// The result may contain blocks without a matching log, which need to be checked by the
+    /// caller.
+    ///
+    /// Returns `None` if the index is not available for the range or there is nothing to look
+    /// up.
+    fn blocks_by_log_index(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<H256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<Vec<BlockNumber>>>;
+}
@@ -1,5 +1,5 @@
 use super::AccountProvider;
-use crate::BlockHashProvider;
+use crate::{trie::AccountProof, BlockHashProvider};
 use auto_impl::auto_impl;
 use reth_interfaces::Result;
 use reth_primitives::{
@@ -15,6 +15,10 @@ pub trait StateProvider: BlockHashProvider + AccountProvider + Send + Sync {
     /// Get account code by its hash
     fn bytecode_by_hash(&self, code_hash: H256) -> Result<Option<Bytecode>>;
 
+    /// Get the account proof and the storage proofs of the given storage keys, as specified in
+    /// EIP-1186.
+    fn proof(&self, address: Address, keys: &[H256]) -> Result<AccountProof>;
+
     /// Get account code by its address.
     ///
     /// Returns `None` if the account doesn't exist or account is not a contract
@@ -1,7 +1,9 @@
 use crate::BlockIdProvider;
 use reth_interfaces::Result;
-use reth_primitives::{BlockId, BlockNumber, TransactionSigned, TxHash, TxNumber};
-use std::ops::RangeBounds;
+use reth_primitives::{
+    Address, BlockId, BlockNumber, TransactionMeta, TransactionSigned, TxHash, TxNumber,
+};
+use std::ops::{RangeBounds, RangeInclusive};
 
 ///  Client trait for fetching [TransactionSigned] related data.
 #[auto_impl::auto_impl(&, Arc)]
@@ -12,6 +14,16 @@ pub trait TransactionsProvider: BlockIdProvider + Send + Sync {
     /// Get transaction by transaction hash.
     fn transaction_by_hash(&self, hash: TxHash) -> Result<Option<TransactionSigned>>;
 
+    /// Get transaction by transaction hash and additional metadata of the block the transaction
+    /// was mined in
+    fn transaction_by_hash_with_meta(
+        &self,
+        hash: TxHash,
+    ) -> Result<Option<(TransactionSigned, TransactionMeta)>>;
+
+    /// Get the number of the block that contains the transaction with the given id.
+    fn transaction_block(&self, id: TxNumber) -> Result<Option<BlockNumber>>;
+
     /// Get transactions by block id.
     fn transactions_by_block(&self, block: BlockId) -> Result<Option<Vec<TransactionSigned>>>;
 
@@ -20,4 +32,16 @@ pub trait TransactionsProvider: BlockIdProvider + Send + Sync {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> Result<Vec<Vec<TransactionSigned>>>;
+
+    /// Get the block numbers and the indices within their blocks of the transactions in the block
+    /// range in which any of the addresses sent or received a call, including internal calls.
+    ///
+    /// The result may contain additional transactions and is ordered by block and index.
+    ///
+    /// Returns `None` if the call address index is not available for the whole range.
+    fn transactions_by_call_addresses(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<Vec<(BlockNumber, u64)>>>;
 }
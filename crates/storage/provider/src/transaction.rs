@@ -4,6 +4,11 @@ use reth_db::{
     database::{Database, DatabaseGAT},
     models::{
         sharded_key,
+        stage::{
+            StageId, ACCOUNT_HASHING, BODIES, EXECUTION, FINISH, HEADERS, INDEX_ACCOUNT_HISTORY,
+            INDEX_CALL_ADDRESS_HISTORY, INDEX_LOG_HISTORY, INDEX_STORAGE_HISTORY, MERKLE_EXECUTION,
+            MERKLE_UNWIND, SENDER_RECOVERY, STORAGE_HASHING, TOTAL_DIFFICULTY, TRANSACTION_LOOKUP,
+        },
         storage_sharded_key::{self, StorageShardedKey},
         ShardedKey, StoredBlockBody, TransitionIdAddress,
     },
@@ -12,24 +17,49 @@ use reth_db::{
     transaction::{DbTx, DbTxMut},
     TransitionList,
 };
-use reth_interfaces::{db::Error as DbError, provider::ProviderError};
+use reth_interfaces::{
+    db::Error as DbError, executor::Error as ExecError, provider::ProviderError,
+};
 use reth_primitives::{
     keccak256, Account, Address, BlockHash, BlockNumber, Bytecode, ChainSpec, Hardfork, Header,
-    SealedBlock, StorageEntry, TransitionId, TxNumber, H256, U256,
+    IntegerList, SealedBlock, SealedBlockWithSenders, StorageEntry, TransitionId, TxNumber, H256,
+    U256,
 };
 use reth_tracing::tracing::{info, trace};
 use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
-    ops::{Deref, DerefMut},
+    ops::{Deref, DerefMut, RangeInclusive},
 };
 
 use crate::{
     insert_canonical_block,
     trie::{DBTrieLoader, TrieError},
+    BlockExecutor, ExecutorFactory, HistoricalStateProviderRef,
+};
+
+use crate::execution_result::{
+    AccountChangeSet, AccountInfoChangeSet, ExecutionResult, TransactionChangeSet,
 };
 
-use crate::execution_result::{AccountChangeSet, ExecutionResult};
+/// The stages whose data is written by [Transaction::insert_block].
+const INSERT_BLOCK_STAGES: [StageId; 15] = [
+    HEADERS,
+    TOTAL_DIFFICULTY,
+    BODIES,
+    SENDER_RECOVERY,
+    EXECUTION,
+    MERKLE_UNWIND,
+    ACCOUNT_HASHING,
+    STORAGE_HASHING,
+    MERKLE_EXECUTION,
+    TRANSACTION_LOOKUP,
+    INDEX_STORAGE_HISTORY,
+    INDEX_ACCOUNT_HISTORY,
+    INDEX_LOG_HISTORY,
+    INDEX_CALL_ADDRESS_HISTORY,
+    FINISH,
+];
 
 /// A container for any DB transaction that will open a new inner transaction when the current
 /// one is committed.
@@ -239,11 +269,20 @@ where
     /// Load last shard and check if it is full and remove if it is not. If list is empty, last
     /// shard was full or there is no shards at all.
     fn take_last_account_shard(&self, address: Address) -> Result<Vec<u64>, TransactionError> {
-        let mut cursor = self.cursor_read::<tables::AccountHistory>()?;
-        let last = cursor.seek_exact(ShardedKey::new(address, u64::MAX))?;
+        self.take_last_shard::<tables::AccountHistory, _>(address)
+    }
+
+    /// Load last shard of the key from a history table sharded by [`ShardedKey`] and remove it. If
+    /// list is empty, last shard was full or there is no shards at all.
+    fn take_last_shard<T, K>(&self, key: K) -> Result<Vec<u64>, TransactionError>
+    where
+        T: Table<Key = ShardedKey<K>, Value = IntegerList>,
+    {
+        let mut cursor = self.cursor_read::<T>()?;
+        let last = cursor.seek_exact(ShardedKey::new(key, u64::MAX))?;
         if let Some((shard_key, list)) = last {
             // delete old shard so new one can be inserted.
-            self.delete::<tables::AccountHistory>(shard_key, None)?;
+            self.delete::<T>(shard_key, None)?;
             let list = list.iter(0).map(|i| i as u64).collect::<Vec<_>>();
             return Ok(list)
         }
@@ -276,19 +315,22 @@ where
 {
     /// Insert full block and make it canonical
     ///
-    /// This is atomic operation and transaction will do one commit at the end of the function.
-    pub fn insert_block(
+    /// The changes are not committed, so several blocks can be unwound and inserted in one
+    /// transaction.
+    pub fn insert_block<EF: ExecutorFactory>(
         &mut self,
         block: &SealedBlock,
-        chain_spec: &ChainSpec,
+        executor_factory: &EF,
         changeset: ExecutionResult,
     ) -> Result<(), TransactionError> {
         // Header, Body, SenderRecovery, TD, TxLookup stages
-        let (from, to) = insert_canonical_block(self.deref_mut(), block, false).unwrap();
+        let has_block_reward = !changeset.block_changesets.is_empty();
+        let (from, to) = insert_canonical_block(self.deref_mut(), block, has_block_reward)?;
 
         let parent_block_number = block.number - 1;
 
         // execution stage
+        let chain_spec = executor_factory.chain_spec();
         self.insert_execution_result(vec![changeset], chain_spec, parent_block_number)?;
 
         // storage hashing stage
@@ -332,8 +374,507 @@ where
             self.insert_storage_history_index(indices)?;
         }
 
-        // commit block to database
-        self.commit()?;
+        // the optional log history stage is only indexed if it is enabled and synced up to the
+        // parent.
+        if INDEX_LOG_HISTORY.get_progress(&**self)? == Some(parent_block_number) {
+            let (address_blocks, topic_blocks) =
+                self.get_log_history_indices(block.number..=block.number)?;
+            self.insert_log_history_index(address_blocks, topic_blocks)?;
+        }
+
+        // the optional call address history stage as well, the block is traced on top of the
+        // state before it, which is read through the account and storage history indexed above.
+        if INDEX_CALL_ADDRESS_HISTORY.get_progress(&**self)? == Some(parent_block_number) {
+            let transitions =
+                self.get_call_address_transitions(executor_factory, block.number..=block.number)?;
+            self.insert_call_address_history_index(transitions)?;
+        }
+
+        // advance the stages that were synced up to the parent, so the pipeline continues after
+        // this block instead of inserting it again.
+        for stage in INSERT_BLOCK_STAGES {
+            if stage.get_progress(&**self)? == Some(parent_block_number) {
+                stage.save_progress(&**self, block.number)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove all canonical blocks above `unwind_to` and return them with their senders and
+    /// execution results.
+    ///
+    /// This reverts everything [Transaction::insert_block] writes: history indices, hashed and
+    /// plain state, the state trie, changesets, receipts, bodies and headers.
+    ///
+    /// The changes are not committed, so several blocks can be unwound and inserted in one
+    /// transaction.
+    pub fn take_canonical_blocks<EF: ExecutorFactory>(
+        &mut self,
+        unwind_to: BlockNumber,
+        executor_factory: &EF,
+    ) -> Result<Vec<(SealedBlockWithSenders, ExecutionResult)>, TransactionError> {
+        let tip = match self.cursor_read::<tables::CanonicalHeaders>()?.last()? {
+            Some((tip, _)) if tip > unwind_to => tip,
+            _ => return Ok(Vec::new()),
+        };
+
+        let from = self.get_block_transition(unwind_to)?;
+        let to = self.get_block_transition(tip)?;
+
+        // Read the blocks and their execution results before any of their data is removed.
+        let blocks = (unwind_to + 1..=tip)
+            .map(|number| self.get_block_with_senders(number))
+            .collect::<Result<Vec<_>, _>>()?;
+        let results = self.get_tip_execution_results(&blocks)?;
+
+        // call address history stage, the indexed blocks are traced again to find the addresses
+        // to unwind, before their state history is removed.
+        let call_address_progress = INDEX_CALL_ADDRESS_HISTORY.get_progress(&**self)?;
+        if let Some(progress) = call_address_progress.filter(|progress| *progress > unwind_to) {
+            let transitions = self.get_call_address_transitions(
+                executor_factory,
+                unwind_to + 1..=progress.min(tip),
+            )?;
+            self.unwind_history_index::<tables::CallAddressHistory, _>(
+                transitions.into_iter().map(|(address, transitions)| (address, transitions[0])),
+            )?;
+        }
+
+        // log history stage, the addresses and topics to unwind are read from the receipts of the
+        // indexed blocks.
+        let log_progress = INDEX_LOG_HISTORY.get_progress(&**self)?;
+        if let Some(progress) = log_progress.filter(|progress| *progress > unwind_to) {
+            let (address_blocks, topic_blocks) =
+                self.get_log_history_indices(unwind_to + 1..=progress.min(tip))?;
+            // block numbers are sorted, the first one is the lowest block to unwind.
+            self.unwind_history_index::<tables::LogAddressHistory, _>(
+                address_blocks.into_iter().map(|(address, blocks)| (address, blocks[0])),
+            )?;
+            self.unwind_history_index::<tables::LogTopicHistory, _>(
+                topic_blocks.into_iter().map(|(topic, blocks)| (topic, blocks[0])),
+            )?;
+        }
+
+        // account and storage history stages
+        self.unwind_account_history_indices(from, to)?;
+        self.unwind_storage_history_indices(from, to)?;
+
+        // the data of all stages is unwound, their progress moves back to the new tip.
+        for stage in INSERT_BLOCK_STAGES {
+            if stage.get_progress(&**self)?.map_or(false, |progress| progress > unwind_to) {
+                stage.save_progress(&**self, unwind_to)?;
+            }
+        }
+
+        // account and storage hashing stages
+        self.unwind_account_hashing(from, to)?;
+        self.unwind_storage_hashing(from, to)?;
+
+        // merkle tree, needs the changesets and the reverted hashed state.
+        {
+            let target_root = self.get_header(unwind_to)?.state_root;
+            if self.get::<tables::AccountsTrie>(target_root)?.is_none() {
+                let current_root = self.get_header(tip)?.state_root;
+                let loader = DBTrieLoader::default();
+                let root = loader.update_root(self, current_root, from..to)?;
+                if root != target_root {
+                    return Err(TransactionError::StateTrieRootMismatch {
+                        got: root,
+                        expected: target_root,
+                        block_number: unwind_to,
+                        block_hash: self.get_block_hash(unwind_to)?,
+                    })
+                }
+            }
+        }
+
+        // execution stage
+        self.unwind_plain_state(from)?;
+
+        // Header, Body, SenderRecovery, TD, TxLookup stages
+        for block in blocks.iter() {
+            self.remove_block(block)?;
+        }
+
+        Ok(blocks.into_iter().zip(results).collect())
+    }
+
+    /// Rebuild the execution results of the last canonical blocks from the stored changesets
+    /// and receipts.
+    ///
+    /// The changesets only hold the value before every change, the value after a change is the
+    /// value before the next change of the same account or slot or, for the last change, the
+    /// value in the plain state. The blocks are therefore expected to be the canonical tip, in
+    /// order.
+    fn get_tip_execution_results(
+        &self,
+        blocks: &[SealedBlockWithSenders],
+    ) -> Result<Vec<ExecutionResult>, TransactionError> {
+        let Some(first) = blocks.first() else { return Ok(Vec::new()) };
+        let from = self.get_block_transition(first.number - 1)?;
+        let to = self.get_block_transition(blocks[blocks.len() - 1].number)?;
+
+        let mut account_changes = BTreeMap::<TransitionId, Vec<(Address, Option<Account>)>>::new();
+        for entry in self.cursor_read::<tables::AccountChangeSet>()?.walk_range(from..to)? {
+            let (transition_id, account_before) = entry?;
+            account_changes
+                .entry(transition_id)
+                .or_default()
+                .push((account_before.address, account_before.info));
+        }
+        let mut storage_changes = BTreeMap::<TransitionId, Vec<(Address, StorageEntry)>>::new();
+        for entry in self.cursor_read::<tables::StorageChangeSet>()?.walk_range(
+            TransitionIdAddress((from, Address::zero()))..
+                TransitionIdAddress((to, Address::zero())),
+        )? {
+            let (key, storage) = entry?;
+            storage_changes.entry(key.transition_id()).or_default().push((key.address(), storage));
+        }
+
+        // walk the transitions backwards, starting at the plain state, to get the value after
+        // every change.
+        let mut plain_storage = self.cursor_dup_read::<tables::PlainStorageState>()?;
+        let mut accounts = BTreeMap::<Address, Option<Account>>::new();
+        let mut storages = BTreeMap::<(Address, H256), U256>::new();
+        let mut changesets = BTreeMap::<TransitionId, BTreeMap<Address, AccountChangeSet>>::new();
+        for transition_id in (from..to).rev() {
+            let mut changeset = BTreeMap::<Address, AccountChangeSet>::new();
+            for (address, old) in account_changes.remove(&transition_id).unwrap_or_default() {
+                let new = match accounts.insert(address, old) {
+                    Some(new) => new,
+                    None => self.get::<tables::PlainAccountState>(address)?,
+                };
+                let account = AccountInfoChangeSet::new(old, new);
+                let wipe_storage = matches!(account, AccountInfoChangeSet::Destroyed { .. });
+                changeset.insert(
+                    address,
+                    AccountChangeSet { account, storage: BTreeMap::new(), wipe_storage },
+                );
+            }
+            for (address, StorageEntry { key, value: old }) in
+                storage_changes.remove(&transition_id).unwrap_or_default()
+            {
+                let new = match storages.insert((address, key), old) {
+                    Some(new) => new,
+                    None => plain_storage
+                        .seek_by_key_subkey(address, key)?
+                        .filter(|entry| entry.key == key)
+                        .map(|entry| entry.value)
+                        .unwrap_or_default(),
+                };
+                changeset
+                    .entry(address)
+                    .or_insert_with(|| AccountChangeSet {
+                        account: AccountInfoChangeSet::NoChange,
+                        storage: BTreeMap::new(),
+                        wipe_storage: false,
+                    })
+                    .storage
+                    .insert(U256::from_be_bytes(key.0), (old, new));
+            }
+            changesets.insert(transition_id, changeset);
+        }
+
+        let mut receipts = self.cursor_read::<tables::Receipts>()?;
+        let mut transition_id = from;
+        let mut results = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let body = self.get_block_body(block.number)?;
+            let mut tx_changesets = Vec::with_capacity(body.tx_count as usize);
+            for tx_id in body.tx_id_range() {
+                let (_, receipt) =
+                    receipts.seek_exact(tx_id)?.ok_or(ProviderError::Receipt { id: tx_id })?;
+                let changeset = changesets.remove(&transition_id).unwrap_or_default();
+                let mut new_bytecodes = BTreeMap::new();
+                for change in changeset.values() {
+                    let AccountInfoChangeSet::Created { new } = change.account else { continue };
+                    let Some(hash) = new.bytecode_hash else { continue };
+                    if let Some(bytecode) = self.get::<tables::Bytecodes>(hash)? {
+                        new_bytecodes.insert(hash, bytecode.0);
+                    }
+                }
+                tx_changesets.push(TransactionChangeSet { receipt, changeset, new_bytecodes });
+                transition_id += 1;
+            }
+
+            // the post block changes have their own transition after the transactions.
+            let end = self.get_block_transition(block.number)?;
+            let block_changesets = if transition_id < end {
+                changesets
+                    .remove(&transition_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(address, changeset)| (address, changeset.account))
+                    .collect()
+            } else {
+                BTreeMap::new()
+            };
+            transition_id = end;
+            results.push(ExecutionResult { tx_changesets, block_changesets });
+        }
+        Ok(results)
+    }
+
+    /// Read the canonical block with the given number together with its senders.
+    pub fn get_block_with_senders(
+        &self,
+        number: BlockNumber,
+    ) -> Result<SealedBlockWithSenders, TransactionError> {
+        let hash = self.get_block_hash(number)?;
+        let header = self.get_header(number)?.seal(hash);
+        let body = self.get_block_body(number)?;
+
+        let tx_range = body.start_tx_id..body.start_tx_id + body.tx_count;
+        let transactions = self
+            .cursor_read::<tables::Transactions>()?
+            .walk_range(tx_range.clone())?
+            .map(|entry| entry.map(|(_, tx)| tx))
+            .collect::<Result<Vec<_>, _>>()?;
+        let senders = self
+            .cursor_read::<tables::TxSenders>()?
+            .walk_range(tx_range)?
+            .map(|entry| entry.map(|(_, sender)| sender))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let ommers = self
+            .get::<tables::BlockOmmers>(number)?
+            .map(|stored| stored.ommers.into_iter().map(|header| header.seal_slow()).collect())
+            .unwrap_or_default();
+        let withdrawals = header.withdrawals_root.is_some().then(|| {
+            self.get::<tables::BlockWithdrawals>(number)
+                .map(|stored| stored.map(|stored| stored.withdrawals).unwrap_or_default())
+        });
+        let withdrawals = withdrawals.transpose()?;
+
+        Ok(SealedBlockWithSenders {
+            block: SealedBlock { header, body: transactions, ommers, withdrawals },
+            senders,
+        })
+    }
+
+    /// Remove the block and its transactions from all block related tables.
+    fn remove_block(&self, block: &SealedBlock) -> Result<(), TransactionError> {
+        let number = block.number;
+        let body = self.get_block_body(number)?;
+        for (tx_id, transaction) in (body.start_tx_id..).zip(block.body.iter()) {
+            self.delete::<tables::Receipts>(tx_id, None)?;
+            self.delete::<tables::Transactions>(tx_id, None)?;
+            self.delete::<tables::TxSenders>(tx_id, None)?;
+            self.delete::<tables::TxTransitionIndex>(tx_id, None)?;
+            self.delete::<tables::TxHashNumber>(transaction.hash(), None)?;
+        }
+        self.delete::<tables::BlockBodies>(number, None)?;
+        self.delete::<tables::BlockOmmers>(number, None)?;
+        self.delete::<tables::BlockWithdrawals>(number, None)?;
+        self.delete::<tables::BlockTransitionIndex>(number, None)?;
+
+        self.delete::<tables::CanonicalHeaders>(number, None)?;
+        self.delete::<tables::Headers>(number, None)?;
+        self.delete::<tables::HeaderNumbers>(block.hash(), None)?;
+        self.delete::<tables::HeaderTD>(number, None)?;
+        Ok(())
+    }
+
+    /// Revert [tables::PlainAccountState] and [tables::PlainStorageState] to the state at
+    /// transition `from` and discard all changesets from that transition onwards.
+    fn unwind_plain_state(&self, from: TransitionId) -> Result<(), TransactionError> {
+        let mut account_changeset = self.cursor_dup_write::<tables::AccountChangeSet>()?;
+        let account_changes = account_changeset.walk(Some(from))?.collect::<Result<Vec<_>, _>>()?;
+        for (_, changeset) in account_changes.iter().rev() {
+            if let Some(account) = changeset.info {
+                self.put::<tables::PlainAccountState>(changeset.address, account)?;
+            } else {
+                self.delete::<tables::PlainAccountState>(changeset.address, None)?;
+            }
+        }
+
+        let mut storage_changeset = self.cursor_dup_write::<tables::StorageChangeSet>()?;
+        let storage_changes = storage_changeset
+            .walk(Some(TransitionIdAddress((from, Address::zero()))))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut plain_storage = self.cursor_dup_write::<tables::PlainStorageState>()?;
+        for (key, storage) in storage_changes.iter().rev() {
+            let address = key.address();
+            if plain_storage
+                .seek_by_key_subkey(address, storage.key)?
+                .filter(|entry| entry.key == storage.key)
+                .is_some()
+            {
+                plain_storage.delete_current()?;
+            }
+            if storage.value != U256::ZERO {
+                plain_storage.upsert(address, *storage)?;
+            }
+        }
+
+        for (transition_id, _) in account_changes.into_iter().dedup_by(|a, b| a.0 == b.0) {
+            self.delete::<tables::AccountChangeSet>(transition_id, None)?;
+        }
+        for (key, _) in storage_changes.into_iter().dedup_by(|a, b| a.0 == b.0) {
+            self.delete::<tables::StorageChangeSet>(key, None)?;
+        }
+        Ok(())
+    }
+
+    /// Revert [tables::HashedAccount] using the account changesets in the transition range.
+    fn unwind_account_hashing(
+        &self,
+        from: TransitionId,
+        to: TransitionId,
+    ) -> Result<(), TransactionError> {
+        let mut hashed_accounts = self.cursor_write::<tables::HashedAccount>()?;
+        // the oldest value of every changed account is its value at the start of the range.
+        let accounts = self
+            .cursor_read::<tables::AccountChangeSet>()?
+            .walk_range(from..to)?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .rev()
+            .map(|(_, account_before)| (keccak256(account_before.address), account_before.info))
+            .collect::<BTreeMap<_, _>>();
+        for (hashed_address, account) in accounts {
+            if let Some(account) = account {
+                hashed_accounts.upsert(hashed_address, account)?;
+            } else if hashed_accounts.seek_exact(hashed_address)?.is_some() {
+                hashed_accounts.delete_current()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Revert [tables::HashedStorage] using the storage changesets in the transition range.
+    fn unwind_storage_hashing(
+        &self,
+        from: TransitionId,
+        to: TransitionId,
+    ) -> Result<(), TransactionError> {
+        let mut hashed_storage = self.cursor_dup_write::<tables::HashedStorage>()?;
+        // the oldest value of every changed slot is its value at the start of the range.
+        let storages = self
+            .cursor_read::<tables::StorageChangeSet>()?
+            .walk_range(
+                TransitionIdAddress((from, Address::zero()))..
+                    TransitionIdAddress((to, Address::zero())),
+            )?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .rev()
+            .map(|(key, entry)| ((keccak256(key.address()), keccak256(entry.key)), entry.value))
+            .collect::<BTreeMap<_, _>>();
+        for ((hashed_address, key), value) in storages {
+            if hashed_storage
+                .seek_by_key_subkey(hashed_address, key)?
+                .filter(|entry| entry.key == key)
+                .is_some()
+            {
+                hashed_storage.delete_current()?;
+            }
+            if value != U256::ZERO {
+                hashed_storage.upsert(hashed_address, StorageEntry { key, value })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove all transitions in the range from [tables::AccountHistory].
+    fn unwind_account_history_indices(
+        &self,
+        from: TransitionId,
+        to: TransitionId,
+    ) -> Result<(), TransactionError> {
+        // lowest transition of every changed account in the range.
+        let lowest = self
+            .cursor_read::<tables::AccountChangeSet>()?
+            .walk_range(from..to)?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .rev()
+            .map(|(transition_id, account)| (account.address, transition_id))
+            .collect::<BTreeMap<_, _>>();
+
+        self.unwind_history_index::<tables::AccountHistory, _>(lowest)
+    }
+
+    /// Remove all indices of every key that are equal or higher than the given lowest index from
+    /// the shards of the history table.
+    fn unwind_history_index<T, K>(
+        &self,
+        lowest: impl IntoIterator<Item = (K, u64)>,
+    ) -> Result<(), TransactionError>
+    where
+        T: Table<Key = ShardedKey<K>, Value = IntegerList>,
+        K: PartialEq + Clone,
+    {
+        let mut cursor = self.cursor_write::<T>()?;
+        for (key, lowest) in lowest {
+            let mut partial_shard = Vec::new();
+            let mut item = cursor.seek_exact(ShardedKey::new(key.clone(), u64::MAX))?;
+            while let Some((sharded_key, list)) = item {
+                if sharded_key.key != key {
+                    break
+                }
+                cursor.delete_current()?;
+                let first = list.iter(0).next().expect("List can't be empty") as u64;
+                if first >= lowest {
+                    item = cursor.prev()?;
+                    continue
+                }
+                partial_shard =
+                    list.iter(0).map(|i| i as u64).take_while(|i| *i < lowest).collect();
+                break
+            }
+            self.put_shards::<T, _>(key, partial_shard)?;
+        }
+        Ok(())
+    }
+
+    /// Remove all transitions in the range from [tables::StorageHistory].
+    fn unwind_storage_history_indices(
+        &self,
+        from: TransitionId,
+        to: TransitionId,
+    ) -> Result<(), TransactionError> {
+        // lowest transition of every changed storage slot in the range.
+        let lowest = self
+            .cursor_read::<tables::StorageChangeSet>()?
+            .walk_range(
+                TransitionIdAddress((from, Address::zero()))..
+                    TransitionIdAddress((to, Address::zero())),
+            )?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .rev()
+            .map(|(key, storage)| ((key.address(), storage.key), key.transition_id()))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut cursor = self.cursor_write::<tables::StorageHistory>()?;
+        for ((address, storage_key), transition_id) in lowest {
+            let mut partial_shard = Vec::new();
+            let mut item =
+                cursor.seek_exact(StorageShardedKey::new(address, storage_key, u64::MAX))?;
+            while let Some((sharded_key, list)) = item {
+                if sharded_key.address != address || sharded_key.sharded_key.key != storage_key {
+                    break
+                }
+                cursor.delete_current()?;
+                let first = list.iter(0).next().expect("List can't be empty") as u64;
+                if first >= transition_id {
+                    item = cursor.prev()?;
+                    continue
+                }
+                partial_shard =
+                    list.iter(0).map(|i| i as u64).take_while(|i| *i < transition_id).collect();
+                break
+            }
+            if !partial_shard.is_empty() {
+                self.put::<tables::StorageHistory>(
+                    StorageShardedKey::new(address, storage_key, u64::MAX),
+                    TransitionList::new(partial_shard)
+                        .expect("There is at least one element in list and it is sorted."),
+                )?;
+            }
+        }
         Ok(())
     }
 
@@ -585,35 +1126,155 @@ where
         for (address, mut indices) in account_transitions {
             let mut last_shard = self.take_last_account_shard(address)?;
             last_shard.append(&mut indices);
-            // chunk indices and insert them in shards of N size.
-            let mut chunks = last_shard
-                .iter()
-                .chunks(sharded_key::NUM_OF_INDICES_IN_SHARD)
-                .into_iter()
-                .map(|chunks| chunks.map(|i| *i as usize).collect::<Vec<usize>>())
-                .collect::<Vec<_>>();
-            let last_chunk = chunks.pop();
+            self.put_shards::<tables::AccountHistory, _>(address, last_shard)?;
+        }
+        Ok(())
+    }
 
-            chunks.into_iter().try_for_each(|list| {
-                self.put::<tables::AccountHistory>(
-                    ShardedKey::new(
-                        address,
-                        *list.last().expect("Chuck does not return empty list") as TransitionId,
-                    ),
-                    TransitionList::new(list).expect("Indices are presorted and not empty"),
-                )
-            })?;
-            // Insert last list with u64::MAX
-            if let Some(last_list) = last_chunk {
-                self.put::<tables::AccountHistory>(
-                    ShardedKey::new(address, u64::MAX),
-                    TransitionList::new(last_list).expect("Indices are presorted and not empty"),
-                )?
+    /// Get the transitions in which each address was the sender or the recipient of a call,
+    /// including internal calls, by tracing the blocks in the range. Used inside
+    /// IndexCallAddressHistory stage
+    ///
+    /// Every block is traced on top of the state before its first transition, which is read
+    /// through the account and storage history indices.
+    pub fn get_call_address_transitions<EF: ExecutorFactory>(
+        &self,
+        executor_factory: &EF,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<BTreeMap<Address, Vec<TransitionId>>, TransactionError> {
+        let mut transitions = BTreeMap::<Address, Vec<TransitionId>>::new();
+        for block_number in range {
+            let block = self.get_block_with_senders(block_number)?;
+            let td = self.get_td(block_number)?;
+            let (_, first_transition) = self.get_next_block_ids(block_number)?;
+
+            let mut executor = executor_factory
+                .with_sp(HistoricalStateProviderRef::new(&**self, first_transition));
+            let addresses = executor
+                .call_addresses(&block.block.unseal(), td, Some(block.senders))
+                .map_err(|error| TransactionError::BlockTracing { block_number, error })?;
+
+            for (index, addresses) in addresses.into_iter().enumerate() {
+                for address in addresses {
+                    transitions.entry(address).or_default().push(first_transition + index as u64);
+                }
             }
         }
+        Ok(transitions)
+    }
+
+    /// Insert call address index to database. Used inside IndexCallAddressHistory stage
+    ///
+    /// The last shard of an address is merged with the new transitions.
+    pub fn insert_call_address_history_index(
+        &self,
+        call_address_transitions: BTreeMap<Address, Vec<u64>>,
+    ) -> Result<(), TransactionError> {
+        self.merge_history_index::<tables::CallAddressHistory, _>(call_address_transitions)
+    }
+
+    /// Insert log address and topic index to database. Used inside IndexLogHistory stage
+    ///
+    /// Like [Transaction::insert_call_address_history_index] the last shards are merged with the
+    /// new block numbers.
+    pub fn insert_log_history_index(
+        &self,
+        address_blocks: BTreeMap<Address, Vec<BlockNumber>>,
+        topic_blocks: BTreeMap<H256, Vec<BlockNumber>>,
+    ) -> Result<(), TransactionError> {
+        self.merge_history_index::<tables::LogAddressHistory, _>(address_blocks)?;
+        self.merge_history_index::<tables::LogTopicHistory, _>(topic_blocks)
+    }
+
+    /// Merge the indices of every key into its last shard, sorting and deduplicating them, and
+    /// insert the result in shards of N size.
+    fn merge_history_index<T, K>(
+        &self,
+        indices: BTreeMap<K, Vec<u64>>,
+    ) -> Result<(), TransactionError>
+    where
+        T: Table<Key = ShardedKey<K>, Value = IntegerList>,
+        K: Clone,
+    {
+        for (key, mut indices) in indices {
+            let mut last_shard = self.take_last_shard::<T, _>(key.clone())?;
+            last_shard.append(&mut indices);
+            last_shard.sort_unstable();
+            last_shard.dedup();
+            self.put_shards::<T, _>(key, last_shard)?;
+        }
+        Ok(())
+    }
+
+    /// Chunk the sorted indices of the key and insert them in shards of N size into the history
+    /// table. The last shard is keyed with `u64::MAX`.
+    fn put_shards<T, K>(&self, key: K, indices: Vec<u64>) -> Result<(), TransactionError>
+    where
+        T: Table<Key = ShardedKey<K>, Value = IntegerList>,
+        K: Clone,
+    {
+        // chunk indices and insert them in shards of N size.
+        let mut chunks = indices
+            .iter()
+            .chunks(sharded_key::NUM_OF_INDICES_IN_SHARD)
+            .into_iter()
+            .map(|chunks| chunks.map(|i| *i as usize).collect::<Vec<usize>>())
+            .collect::<Vec<_>>();
+        let last_chunk = chunks.pop();
+
+        chunks.into_iter().try_for_each(|list| {
+            self.put::<T>(
+                ShardedKey::new(
+                    key.clone(),
+                    *list.last().expect("Chuck does not return empty list") as u64,
+                ),
+                IntegerList::new(list).expect("Indices are presorted and not empty"),
+            )
+        })?;
+        // Insert last list with u64::MAX
+        if let Some(last_list) = last_chunk {
+            self.put::<T>(
+                ShardedKey::new(key, u64::MAX),
+                IntegerList::new(last_list).expect("Indices are presorted and not empty"),
+            )?
+        }
         Ok(())
     }
 
+    /// Get the block numbers in which each address emitted a log and in which each topic was part
+    /// of a log, read from the receipts of the blocks in the range. Used inside IndexLogHistory
+    /// stage
+    pub fn get_log_history_indices(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<
+        (BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<H256, Vec<BlockNumber>>),
+        TransactionError,
+    > {
+        let mut address_blocks = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut topic_blocks = BTreeMap::<H256, Vec<BlockNumber>>::new();
+        let mut receipts_cursor = self.cursor_read::<tables::Receipts>()?;
+        for entry in self.cursor_read::<tables::BlockBodies>()?.walk_range(range)? {
+            let (block_number, body) = entry?;
+            for receipt in receipts_cursor.walk_range(body.tx_id_range())? {
+                let (_, receipt) = receipt?;
+                for log in receipt.logs {
+                    let blocks = address_blocks.entry(log.address).or_default();
+                    if blocks.last() != Some(&block_number) {
+                        blocks.push(block_number);
+                    }
+                    for topic in log.topics {
+                        let blocks = topic_blocks.entry(topic).or_default();
+                        if blocks.last() != Some(&block_number) {
+                            blocks.push(block_number);
+                        }
+                    }
+                }
+            }
+        }
+        Ok((address_blocks, topic_blocks))
+    }
+
     /// Used inside execution stage to commit created account storage changesets for transaction or
     /// block state change.
     pub fn insert_execution_result(
@@ -635,8 +1296,12 @@ where
             block_number += 1;
             let spurious_dragon_active =
                 chain_spec.fork(Hardfork::SpuriousDragon).active_at_block(block_number);
+            let mut tx_id = self.get_block_body(block_number)?.start_tx_id;
             // insert state change set
             for result in results.tx_changesets.into_iter() {
+                self.put::<tables::Receipts>(tx_id, result.receipt)?;
+                tx_id += 1;
+
                 for (address, account_change_set) in result.changeset.into_iter() {
                     let AccountChangeSet { account, wipe_storage, storage } = account_change_set;
                     // apply account change to db. Updates AccountChangeSet and PlainAccountState
@@ -744,6 +1409,17 @@ pub enum TransactionError {
     /// The transaction encountered a database integrity error.
     #[error("A database integrity error occurred: {0}")]
     DatabaseIntegrity(#[from] ProviderError),
+    /// The transaction encountered an error while inserting block data.
+    #[error(transparent)]
+    Interface(#[from] reth_interfaces::Error),
+    /// The transaction failed to trace a block.
+    #[error("Failed to trace block #{block_number}: {error}")]
+    BlockTracing {
+        /// The number of the block
+        block_number: BlockNumber,
+        /// The execution error
+        error: ExecError,
+    },
     /// The transaction encountered merkle trie error.
     #[error("Merkle trie calculation error: {0}")]
     MerkleTrie(#[from] TrieError),
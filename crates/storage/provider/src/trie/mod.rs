@@ -1,4 +1,7 @@
-use crate::Transaction;
+use crate::{
+    execution_result::{AccountInfoChangeSet, ExecutionResult},
+    Transaction,
+};
 use cita_trie::{PatriciaTrie, Trie};
 use hasher::HasherKeccak;
 use reth_db::{
@@ -9,8 +12,8 @@ use reth_db::{
     transaction::{DbTx, DbTxMut},
 };
 use reth_primitives::{
-    keccak256, proofs::EMPTY_ROOT, Account, Address, StorageEntry, StorageTrieEntry, TransitionId,
-    H256, KECCAK_EMPTY, U256,
+    keccak256, proofs::EMPTY_ROOT, Account, Address, Bytes, StorageEntry, StorageTrieEntry,
+    TransitionId, H256, KECCAK_EMPTY, U256,
 };
 use reth_rlp::{
     encode_fixed_size, Decodable, DecodeError, Encodable, RlpDecodable, RlpEncodable,
@@ -18,9 +21,10 @@ use reth_rlp::{
 };
 use reth_tracing::tracing::*;
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    marker::PhantomData,
     ops::Range,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 /// Merkle Trie error types
@@ -197,6 +201,115 @@ impl<'tx, 'itx, DB: Database> DupHashDatabase<'tx, 'itx, DB> {
     }
 }
 
+/// Read-only database wrapper implementing HashDB trait, used to generate proofs.
+///
+/// Reads the nodes of the accounts trie or of the storage trie of a single account. Nodes that are
+/// written when trie paths are updated are only kept in memory.
+struct ProofDatabase<'a, 'tx, TX: DbTx<'tx>> {
+    tx: &'a TX,
+    /// The hashed address of the storage trie, or `None` for the accounts trie.
+    hashed_address: Option<H256>,
+    /// Nodes written to the trie.
+    nodes: Mutex<HashMap<H256, Vec<u8>>>,
+    _phantom: PhantomData<&'tx ()>,
+}
+
+impl<'a, 'tx, TX> cita_trie::DB for ProofDatabase<'a, 'tx, TX>
+where
+    TX: DbTx<'tx>,
+{
+    type Error = TrieError;
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        let hash = H256::from_slice(key);
+        if let Some(node) = self.nodes.lock().expect("not poisoned").get(&hash) {
+            return Ok(Some(node.clone()))
+        }
+        match self.hashed_address {
+            Some(hashed_address) => Ok(self
+                .tx
+                .cursor_dup_read::<tables::StoragesTrie>()?
+                .seek_by_key_subkey(hashed_address, hash)?
+                .filter(|entry| entry.hash == hash)
+                .map(|entry| entry.node)),
+            None => Ok(self.tx.get::<tables::AccountsTrie>(hash)?),
+        }
+    }
+
+    fn contains(&self, key: &[u8]) -> Result<bool, Self::Error> {
+        Ok(<Self as cita_trie::DB>::get(self, key)?.is_some())
+    }
+
+    fn insert(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Self::Error> {
+        self.nodes.lock().expect("not poisoned").insert(H256::from_slice(&key), value);
+        Ok(())
+    }
+
+    fn insert_batch(&self, keys: Vec<Vec<u8>>, values: Vec<Vec<u8>>) -> Result<(), Self::Error> {
+        let mut nodes = self.nodes.lock().expect("not poisoned");
+        for (key, value) in keys.into_iter().zip(values.into_iter()) {
+            nodes.insert(H256::from_slice(&key), value);
+        }
+        Ok(())
+    }
+
+    fn remove_batch(&self, keys: &[Vec<u8>]) -> Result<(), Self::Error> {
+        // Only in-memory nodes are removed, nodes of the tables are still valid for their root.
+        let mut nodes = self.nodes.lock().expect("not poisoned");
+        for key in keys {
+            nodes.remove(&H256::from_slice(key));
+        }
+        Ok(())
+    }
+
+    fn remove(&self, key: &[u8]) -> Result<(), Self::Error> {
+        <Self as cita_trie::DB>::remove_batch(self, &[key.to_vec()])
+    }
+
+    fn flush(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, 'tx, TX: DbTx<'tx>> ProofDatabase<'a, 'tx, TX> {
+    /// Instantiates a new read-only database for the accounts trie if `hashed_address` is `None`
+    /// or the storage trie of the account, with an existing root.
+    fn from_root(tx: &'a TX, hashed_address: Option<H256>, root: H256) -> Result<Self, TrieError> {
+        let db =
+            Self { tx, hashed_address, nodes: Default::default(), _phantom: Default::default() };
+        if root == EMPTY_ROOT {
+            db.nodes.lock().expect("not poisoned").insert(root, [EMPTY_STRING_CODE].to_vec());
+        } else if !cita_trie::DB::contains(&db, root.as_bytes())? {
+            return Err(TrieError::MissingRoot(root))
+        }
+        Ok(db)
+    }
+}
+
+/// Merkle proofs of an account and some of its storage slots, as specified in
+/// [EIP-1186](https://eips.ethereum.org/EIPS/eip-1186).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountProof {
+    /// The nodes of the accounts trie on the path to the account, starting with the root node.
+    pub proof: Vec<Bytes>,
+    /// The storage root of the account.
+    pub storage_root: H256,
+    /// The proofs of the requested storage slots, in the order of the requested keys.
+    pub storage_proofs: Vec<Vec<Bytes>>,
+}
+
+/// The values of an account that are written to a trie in memory, e.g. the state of the account
+/// before a range of transitions or after the execution of a block.
+#[derive(Debug, Default)]
+struct AccountUpdate {
+    /// The new account, `Some(None)` if it does not exist and `None` if it did not change.
+    account: Option<Option<Account>>,
+    /// Whether the storage is cleared before the new storage values are written.
+    wipe_storage: bool,
+    /// The new storage values, by hashed storage key.
+    storage: BTreeMap<H256, U256>,
+}
+
 /// An Ethereum account, for RLP encoding traits deriving.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default, RlpEncodable, RlpDecodable)]
 pub(crate) struct EthAccount {
@@ -426,11 +539,270 @@ impl DBTrieLoader {
 
         Ok(hashed_changes)
     }
+
+    /// Generates the account proof, the storage root and the storage proofs of an account for
+    /// the state with the given root.
+    pub fn generate_proof<'tx, TX: DbTx<'tx>>(
+        &self,
+        tx: &TX,
+        root: H256,
+        address: Address,
+        storage_keys: &[H256],
+    ) -> Result<AccountProof, TrieError> {
+        self.generate_proof_with_reverts(tx, root, BTreeMap::new(), address, storage_keys)
+    }
+
+    /// Generates the proofs of an account for the state before the start of the transition range.
+    ///
+    /// The trie with the given root holds the state at the end of the range. The trie paths of all
+    /// accounts and storage slots that changed in the range are rebuilt in memory out of the
+    /// changesets, on top of it.
+    pub fn generate_historical_proof<'tx, TX: DbTx<'tx>>(
+        &self,
+        tx: &TX,
+        root: H256,
+        tid_range: Range<TransitionId>,
+        address: Address,
+        storage_keys: &[H256],
+    ) -> Result<AccountProof, TrieError> {
+        let reverts = self.gather_reverts(tx, tid_range)?;
+        self.generate_proof_with_reverts(tx, root, reverts, address, storage_keys)
+    }
+
+    /// Calculates the state root after the execution of a block, without writing to the database.
+    ///
+    /// The trie paths of all accounts and storage slots that were changed by the execution result
+    /// are rebuilt in memory, on top of the trie with the given root of the parent state.
+    pub fn calculate_root_with_execution_result<'tx, TX: DbTx<'tx>>(
+        &self,
+        tx: &TX,
+        root: H256,
+        result: &ExecutionResult,
+        has_state_clear_eip: bool,
+    ) -> Result<H256, TrieError> {
+        self.calculate_historical_root_with_execution_results(
+            tx,
+            root,
+            0..0,
+            [(result, has_state_clear_eip)],
+        )
+    }
+
+    /// Calculates the state root after the execution of blocks on top of the state before the
+    /// start of the transition range, without writing to the database.
+    ///
+    /// The trie with the given root holds the state at the end of the range. The changes of the
+    /// range are reverted and the changes of the execution results are applied in memory, in
+    /// order. Every execution result is paired with whether the state clear EIP is active for its
+    /// block.
+    pub fn calculate_historical_root_with_execution_results<'a, 'tx, TX: DbTx<'tx>>(
+        &self,
+        tx: &TX,
+        root: H256,
+        tid_range: Range<TransitionId>,
+        results: impl IntoIterator<Item = (&'a ExecutionResult, bool)>,
+    ) -> Result<H256, TrieError> {
+        let mut updates = if tid_range.is_empty() {
+            BTreeMap::new()
+        } else {
+            self.gather_reverts(tx, tid_range)?
+        };
+        for (result, has_state_clear_eip) in results {
+            Self::apply_execution_result(&mut updates, result, has_state_clear_eip);
+        }
+
+        let hasher = Arc::new(HasherKeccak::new());
+        let (mut trie, _) = self.apply_updates(tx, root, updates, &hasher)?;
+        Ok(H256::from_slice(trie.root()?.as_slice()))
+    }
+
+    /// Applies the changes of an execution result to the updates, by hashed address.
+    fn apply_execution_result(
+        updates: &mut BTreeMap<H256, AccountUpdate>,
+        result: &ExecutionResult,
+        has_state_clear_eip: bool,
+    ) {
+        // the new value of the account, `None` if the account didn't change
+        let new_account = |account: &AccountInfoChangeSet| match account {
+            // empty accounts are not created after the state clear EIP, see
+            // `AccountInfoChangeSet::apply_to_db`
+            AccountInfoChangeSet::Created { new } if has_state_clear_eip && new.is_empty() => None,
+            AccountInfoChangeSet::Created { new } | AccountInfoChangeSet::Changed { new, .. } => {
+                Some(Some(*new))
+            }
+            AccountInfoChangeSet::Destroyed { .. } => Some(None),
+            AccountInfoChangeSet::NoChange => None,
+        };
+
+        // the changes are applied in order, a wipe drops all previous storage changes
+        for (address, change) in result.tx_changesets.iter().flat_map(|tx| &tx.changeset) {
+            let update = updates.entry(keccak256(address)).or_default();
+            if let Some(account) = new_account(&change.account) {
+                update.account = Some(account);
+            }
+            if change.wipe_storage {
+                update.wipe_storage = true;
+                update.storage.clear();
+            }
+            for (key, (_, value)) in &change.storage {
+                update.storage.insert(keccak256(H256(key.to_be_bytes())), *value);
+            }
+        }
+        for (address, account) in &result.block_changesets {
+            if let Some(account) = new_account(account) {
+                updates.entry(keccak256(address)).or_default().account = Some(account);
+            }
+        }
+    }
+
+    fn generate_proof_with_reverts<'tx, TX: DbTx<'tx>>(
+        &self,
+        tx: &TX,
+        root: H256,
+        reverts: BTreeMap<H256, AccountUpdate>,
+        address: Address,
+        storage_keys: &[H256],
+    ) -> Result<AccountProof, TrieError> {
+        let hasher = Arc::new(HasherKeccak::new());
+        let (trie, mut storage_dbs) = self.apply_updates(tx, root, reverts, &hasher)?;
+
+        let hashed_address = keccak256(address);
+        let proof =
+            trie.get_proof(hashed_address.as_bytes())?.into_iter().map(Bytes::from).collect();
+        let storage_root = trie
+            .get(hashed_address.as_bytes())?
+            .map(|account| EthAccount::decode(&mut account.as_slice()))
+            .transpose()?
+            .map_or(EMPTY_ROOT, |account| account.storage_root);
+
+        let storage_db = match storage_dbs.remove(&hashed_address) {
+            Some(storage_db) => storage_db,
+            None => Arc::new(ProofDatabase::from_root(tx, Some(hashed_address), storage_root)?),
+        };
+        let storage_trie = PatriciaTrie::from(storage_db, hasher, storage_root.as_bytes())?;
+        let storage_proofs = storage_keys
+            .iter()
+            .map(|key| {
+                let proof = storage_trie.get_proof(keccak256(key).as_bytes())?;
+                Ok(proof.into_iter().map(Bytes::from).collect())
+            })
+            .collect::<Result<_, TrieError>>()?;
+
+        Ok(AccountProof { proof, storage_root, storage_proofs })
+    }
+
+    /// Writes the updates of the accounts, by hashed address, in memory to the accounts trie with
+    /// the given root and the storage tries of the accounts.
+    ///
+    /// Returns the accounts trie with the updated paths committed, and the databases of the
+    /// storage tries that were updated.
+    #[allow(clippy::type_complexity)]
+    fn apply_updates<'a, 'tx, TX: DbTx<'tx>>(
+        &self,
+        tx: &'a TX,
+        root: H256,
+        updates: BTreeMap<H256, AccountUpdate>,
+        hasher: &Arc<HasherKeccak>,
+    ) -> Result<
+        (
+            PatriciaTrie<ProofDatabase<'a, 'tx, TX>, HasherKeccak>,
+            HashMap<H256, Arc<ProofDatabase<'a, 'tx, TX>>>,
+        ),
+        TrieError,
+    > {
+        let db = Arc::new(ProofDatabase::from_root(tx, None, root)?);
+
+        let mut trie = PatriciaTrie::from(Arc::clone(&db), Arc::clone(hasher), root.as_bytes())?;
+
+        // the databases of the storage tries that were rebuilt
+        let mut storage_dbs = HashMap::new();
+        for (hashed_address, update) in updates {
+            let current = trie
+                .get(hashed_address.as_bytes())?
+                .map(|account| EthAccount::decode(&mut account.as_slice()))
+                .transpose()?;
+
+            let mut storage_root = match current {
+                Some(account) if !update.wipe_storage => account.storage_root,
+                _ => EMPTY_ROOT,
+            };
+            if !update.storage.is_empty() {
+                let storage_db =
+                    Arc::new(ProofDatabase::from_root(tx, Some(hashed_address), storage_root)?);
+                let mut storage_trie = PatriciaTrie::from(
+                    Arc::clone(&storage_db),
+                    Arc::clone(hasher),
+                    storage_root.as_bytes(),
+                )?;
+                for (hashed_key, value) in update.storage {
+                    if value == U256::ZERO {
+                        storage_trie.remove(hashed_key.as_bytes())?;
+                    } else {
+                        let out = encode_fixed_size(&value).to_vec();
+                        storage_trie.insert(hashed_key.as_bytes().to_vec(), out)?;
+                    }
+                }
+                storage_root = H256::from_slice(storage_trie.root()?.as_slice());
+                storage_dbs.insert(hashed_address, storage_db);
+            }
+
+            let account = match update.account {
+                Some(account) => {
+                    account.map(|account| EthAccount::from_with_root(account, storage_root))
+                }
+                None => current.map(|account| EthAccount { storage_root, ..account }),
+            };
+            if let Some(account) = account {
+                let mut out = Vec::new();
+                Encodable::encode(&account, &mut out);
+                trie.insert(hashed_address.as_bytes().to_vec(), out)?;
+            } else {
+                trie.remove(hashed_address.as_bytes())?;
+            }
+        }
+        // commit the rebuilt paths
+        trie.root()?;
+
+        Ok((trie, storage_dbs))
+    }
+
+    /// Collects the values of all accounts and storage slots that changed in the transition range,
+    /// as they were before the start of the range.
+    fn gather_reverts<'tx, TX: DbTx<'tx>>(
+        &self,
+        tx: &TX,
+        tid_range: Range<TransitionId>,
+    ) -> Result<BTreeMap<H256, AccountUpdate>, TrieError> {
+        let mut reverts: BTreeMap<Address, AccountUpdate> = BTreeMap::new();
+
+        let mut account_cursor = tx.cursor_read::<tables::AccountChangeSet>()?;
+        let mut walker = account_cursor.walk_range(tid_range.clone())?;
+
+        while let Some((_, AccountBeforeTx { address, info })) = walker.next().transpose()? {
+            // the first change from the transition on holds the value before the transition
+            reverts.entry(address).or_default().account.get_or_insert(info);
+        }
+
+        let mut storage_cursor = tx.cursor_dup_read::<tables::StorageChangeSet>()?;
+
+        let start = TransitionIdAddress((tid_range.start, Address::zero()));
+        let end = TransitionIdAddress((tid_range.end, Address::zero()));
+        let mut walker = storage_cursor.walk_range(start..end)?;
+
+        while let Some((TransitionIdAddress((_, address)), StorageEntry { key, value })) =
+            walker.next().transpose()?
+        {
+            reverts.entry(address).or_default().storage.entry(keccak256(key)).or_insert(value);
+        }
+
+        Ok(reverts.into_iter().map(|(address, revert)| (keccak256(address), revert)).collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::execution_result::{AccountChangeSet, TransactionChangeSet};
     use assert_matches::assert_matches;
     use proptest::{prelude::ProptestConfig, proptest};
     use reth_db::{mdbx::test_utils::create_test_rw_db, tables, transaction::DbTxMut};
@@ -648,6 +1020,260 @@ mod tests {
         );
     }
 
+    /// Verifies a proof against a root with an in-memory trie, returning the proven value.
+    fn verify_proof(root: H256, key: H256, proof: Vec<Bytes>) -> Option<Vec<u8>> {
+        let trie = PatriciaTrie::new(
+            Arc::new(cita_trie::MemoryDB::new(true)),
+            Arc::new(HasherKeccak::new()),
+        );
+        let proof = proof.into_iter().map(|node| node.to_vec()).collect();
+        trie.verify_proof(root.as_bytes(), key.as_bytes(), proof).unwrap()
+    }
+
+    #[test]
+    fn generate_proofs() {
+        let trie = DBTrieLoader::default();
+        let db = create_test_rw_db();
+        let tx = Transaction::new(db.as_ref()).unwrap();
+
+        let address = Address::from(hex!("9fe4abd71ad081f091bd06dd1c16f7e92927561e"));
+        let other = Address::from(hex!("f8a6edaad4a332e6e550d0915a7fd5300b0b12d1"));
+        let missing = Address::from(hex!("0000000000000000000000000000000000000001"));
+        let hashed_address = keccak256(address);
+
+        let old_account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let new_account = Account { nonce: 2, balance: U256::from(5), bytecode_hash: None };
+        let other_account = Account { nonce: 3, balance: U256::from(78978), bytecode_hash: None };
+        let slot = H256::from_low_u64_be(2);
+
+        // transition 7 changed the account and its storage slot from 3 to 4
+        tx.put::<tables::HashedAccount>(hashed_address, new_account).unwrap();
+        tx.put::<tables::HashedAccount>(keccak256(other), other_account).unwrap();
+        tx.put::<tables::HashedStorage>(
+            hashed_address,
+            StorageEntry { key: keccak256(slot), value: U256::from(4) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSet>(7, AccountBeforeTx { address, info: Some(old_account) })
+            .unwrap();
+        tx.put::<tables::StorageChangeSet>(
+            (7, address).into(),
+            StorageEntry { key: slot, value: U256::from(3) },
+        )
+        .unwrap();
+        // transition 8 is not in the tries yet
+        tx.put::<tables::AccountChangeSet>(8, AccountBeforeTx { address: other, info: None })
+            .unwrap();
+
+        let root = trie.calculate_root(&tx).unwrap();
+
+        let state_root = |account: Account, value: U256| {
+            let storage_root =
+                H256(sec_trie_root::<KeccakHasher, _, _, _>([(slot, encode_fixed_size(&value))]).0);
+            let accounts = [(address, account, storage_root), (other, other_account, EMPTY_ROOT)]
+                .map(|(address, account, storage_root)| {
+                    let mut out = Vec::new();
+                    EthAccount::from_with_root(account, storage_root).encode(&mut out);
+                    (address, out)
+                });
+            (H256(sec_trie_root::<KeccakHasher, _, _, _>(accounts).0), storage_root)
+        };
+        let (expected_root, expected_storage_root) = state_root(new_account, U256::from(4));
+        assert_eq!(root, expected_root);
+
+        // latest state
+        let proof = trie.generate_proof(&*tx, root, address, &[slot]).unwrap();
+        assert_eq!(proof.storage_root, expected_storage_root);
+        let mut encoded_account = Vec::new();
+        EthAccount::from_with_root(new_account, expected_storage_root).encode(&mut encoded_account);
+        assert_eq!(verify_proof(root, hashed_address, proof.proof), Some(encoded_account));
+        assert_eq!(
+            verify_proof(expected_storage_root, keccak256(slot), proof.storage_proofs[0].clone()),
+            Some(encode_fixed_size(&U256::from(4)).to_vec())
+        );
+
+        // state before transition 7
+        let (old_root, old_storage_root) = state_root(old_account, U256::from(3));
+        let proof = trie.generate_historical_proof(&*tx, root, 7..8, address, &[slot]).unwrap();
+        assert_eq!(proof.storage_root, old_storage_root);
+        let mut encoded_account = Vec::new();
+        EthAccount::from_with_root(old_account, old_storage_root).encode(&mut encoded_account);
+        assert_eq!(verify_proof(old_root, hashed_address, proof.proof), Some(encoded_account));
+        assert_eq!(
+            verify_proof(old_storage_root, keccak256(slot), proof.storage_proofs[0].clone()),
+            Some(encode_fixed_size(&U256::from(3)).to_vec())
+        );
+
+        // the trie tables are untouched by historical proofs
+        assert_matches!(trie.generate_proof(&*tx, root, address, &[]), Ok(_));
+
+        // exclusion proof of a missing account
+        let proof = trie.generate_proof(&*tx, root, missing, &[slot]).unwrap();
+        assert_eq!(proof.storage_root, EMPTY_ROOT);
+        assert_eq!(verify_proof(root, keccak256(missing), proof.proof), None);
+    }
+
+    #[test]
+    fn root_with_execution_result() {
+        let trie = DBTrieLoader::default();
+        let db = create_test_rw_db();
+        let tx = Transaction::new(db.as_ref()).unwrap();
+
+        let [a, b, c, d] = [1, 2, 3, 4].map(Address::from_low_u64_be);
+        let [slot1, slot2, slot7] = [1, 2, 7].map(H256::from_low_u64_be);
+        let account_a = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let account_b = Account { nonce: 2, balance: U256::from(20), bytecode_hash: None };
+        let put_storage = |address: Address, slot: H256, value: u64| {
+            let entry = StorageEntry { key: keccak256(slot), value: U256::from(value) };
+            tx.put::<tables::HashedStorage>(keccak256(address), entry).unwrap();
+        };
+        tx.put::<tables::HashedAccount>(keccak256(a), account_a).unwrap();
+        tx.put::<tables::HashedAccount>(keccak256(b), account_b).unwrap();
+        put_storage(a, slot1, 3);
+        put_storage(a, slot2, 4);
+        put_storage(b, slot1, 5);
+        let root = trie.calculate_root(&tx).unwrap();
+
+        // the first transaction changes `a` and its storage and creates `c` and an empty `d`, the
+        // second one destroys `b` and the block reward goes to `a`
+        let new_a = Account { nonce: 2, ..account_a };
+        let rewarded_a = Account { balance: U256::from(100), ..new_a };
+        let account_c = Account { nonce: 1, balance: U256::from(1), bytecode_hash: None };
+        let change = |account, storage: Vec<(u64, u64, u64)>, wipe_storage| AccountChangeSet {
+            account,
+            storage: storage
+                .into_iter()
+                .map(|(key, old, new)| (U256::from(key), (U256::from(old), U256::from(new))))
+                .collect(),
+            wipe_storage,
+        };
+        let changeset = |changes: Vec<(Address, AccountChangeSet)>| TransactionChangeSet {
+            receipt: Default::default(),
+            changeset: changes.into_iter().collect(),
+            new_bytecodes: Default::default(),
+        };
+        let result = ExecutionResult {
+            tx_changesets: vec![
+                changeset(vec![
+                    (
+                        a,
+                        change(
+                            AccountInfoChangeSet::new(Some(account_a), Some(new_a)),
+                            vec![(1, 3, 0), (7, 0, 8)],
+                            false,
+                        ),
+                    ),
+                    (
+                        c,
+                        change(
+                            AccountInfoChangeSet::Created { new: account_c },
+                            vec![(1, 0, 9)],
+                            false,
+                        ),
+                    ),
+                    (
+                        d,
+                        change(
+                            AccountInfoChangeSet::Created { new: Account::default() },
+                            vec![],
+                            false,
+                        ),
+                    ),
+                ]),
+                changeset(vec![(
+                    b,
+                    change(AccountInfoChangeSet::Destroyed { old: account_b }, vec![], true),
+                )]),
+            ],
+            block_changesets: BTreeMap::from([(
+                a,
+                AccountInfoChangeSet::new(Some(new_a), Some(rewarded_a)),
+            )]),
+        };
+        let got = trie.calculate_root_with_execution_result(&*tx, root, &result, true).unwrap();
+        assert_ne!(got, root);
+
+        // write the post state and calculate its root from scratch
+        tx.put::<tables::HashedAccount>(keccak256(a), rewarded_a).unwrap();
+        tx.put::<tables::HashedAccount>(keccak256(c), account_c).unwrap();
+        tx.delete::<tables::HashedAccount>(keccak256(b), None).unwrap();
+        tx.delete::<tables::HashedStorage>(keccak256(a), None).unwrap();
+        tx.delete::<tables::HashedStorage>(keccak256(b), None).unwrap();
+        put_storage(a, slot2, 4);
+        put_storage(a, slot7, 8);
+        put_storage(c, slot1, 9);
+        assert_eq!(trie.calculate_root(&tx).unwrap(), got);
+    }
+
+    #[test]
+    fn historical_root_with_execution_results() {
+        let trie = DBTrieLoader::default();
+        let db = create_test_rw_db();
+        let tx = Transaction::new(db.as_ref()).unwrap();
+
+        let [a, b] = [1, 2].map(Address::from_low_u64_be);
+        let [slot1, slot2] = [1, 2].map(H256::from_low_u64_be);
+        let old_a = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let new_a = Account { nonce: 2, ..old_a };
+        let account_b = Account { nonce: 3, balance: U256::from(20), bytecode_hash: None };
+        let put_storage = |address: Address, slot: H256, value: u64| {
+            let entry = StorageEntry { key: keccak256(slot), value: U256::from(value) };
+            tx.put::<tables::HashedStorage>(keccak256(address), entry).unwrap();
+        };
+
+        // transition 0 changed `a` and its storage slot from 3 to 4
+        tx.put::<tables::HashedAccount>(keccak256(a), new_a).unwrap();
+        tx.put::<tables::HashedAccount>(keccak256(b), account_b).unwrap();
+        put_storage(a, slot1, 4);
+        tx.put::<tables::AccountChangeSet>(0, AccountBeforeTx { address: a, info: Some(old_a) })
+            .unwrap();
+        tx.put::<tables::StorageChangeSet>(
+            (0, a).into(),
+            StorageEntry { key: slot1, value: U256::from(3) },
+        )
+        .unwrap();
+        let root = trie.calculate_root(&tx).unwrap();
+
+        // a block on top of the state before transition 0 changes `b` and the storage of `a`
+        let new_b = Account { nonce: 4, ..account_b };
+        let result = ExecutionResult {
+            tx_changesets: vec![TransactionChangeSet {
+                receipt: Default::default(),
+                changeset: BTreeMap::from([
+                    (
+                        a,
+                        AccountChangeSet {
+                            account: AccountInfoChangeSet::NoChange,
+                            storage: BTreeMap::from([(U256::from(2), (U256::ZERO, U256::from(5)))]),
+                            wipe_storage: false,
+                        },
+                    ),
+                    (
+                        b,
+                        AccountChangeSet {
+                            account: AccountInfoChangeSet::new(Some(account_b), Some(new_b)),
+                            storage: BTreeMap::new(),
+                            wipe_storage: false,
+                        },
+                    ),
+                ]),
+                new_bytecodes: Default::default(),
+            }],
+            block_changesets: Default::default(),
+        };
+        let got = trie
+            .calculate_historical_root_with_execution_results(&*tx, root, 0..1, [(&result, true)])
+            .unwrap();
+
+        // write the post state and calculate its root from scratch
+        tx.put::<tables::HashedAccount>(keccak256(a), old_a).unwrap();
+        tx.put::<tables::HashedAccount>(keccak256(b), new_b).unwrap();
+        tx.delete::<tables::HashedStorage>(keccak256(a), None).unwrap();
+        put_storage(a, slot1, 3);
+        put_storage(a, slot2, 5);
+        assert_eq!(trie.calculate_root(&tx).unwrap(), got);
+    }
+
     fn test_with_accounts(accounts: BTreeMap<Address, (Account, BTreeSet<StorageEntry>)>) {
         let trie = DBTrieLoader::default();
         let db = create_test_rw_db();
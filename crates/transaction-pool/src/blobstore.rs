@@ -0,0 +1,97 @@
+//! Storage for the sidecars of blob transactions.
+
+use parking_lot::RwLock;
+use reth_primitives::{BlobTransactionSidecar, TxHash};
+use std::collections::HashMap;
+
+/// An in-memory store for the [`BlobTransactionSidecar`]s of the blob transactions in the pool.
+///
+/// Blob transactions only commit to their blobs, the sidecar is kept separate from the pooled
+/// transaction so that it's only handed out when it's actually needed, e.g. when the transaction
+/// is requested by a peer.
+#[derive(Debug, Default)]
+pub struct BlobStore {
+    /// All sidecars, keyed by the hash of the transaction they belong to.
+    inner: RwLock<HashMap<TxHash, BlobTransactionSidecar>>,
+}
+
+// === impl BlobStore ===
+
+impl BlobStore {
+    /// Inserts the sidecar of the transaction, replacing any existing sidecar.
+    pub fn insert(&self, tx: TxHash, sidecar: BlobTransactionSidecar) {
+        self.inner.write().insert(tx, sidecar);
+    }
+
+    /// Returns the sidecar of the transaction, if it exists.
+    pub fn get(&self, tx: &TxHash) -> Option<BlobTransactionSidecar> {
+        self.inner.read().get(tx).cloned()
+    }
+
+    /// Returns whether the store contains the sidecar of the transaction.
+    pub fn contains(&self, tx: &TxHash) -> bool {
+        self.inner.read().contains_key(tx)
+    }
+
+    /// Removes the sidecar of the transaction and returns it, if it exists.
+    pub fn remove(&self, tx: &TxHash) -> Option<BlobTransactionSidecar> {
+        self.inner.write().remove(tx)
+    }
+
+    /// Removes the sidecars of all given transactions.
+    pub fn remove_all<'a>(&self, txs: impl IntoIterator<Item = &'a TxHash>) {
+        let mut inner = self.inner.write();
+        for tx in txs {
+            inner.remove(tx);
+        }
+    }
+
+    /// Retains only the sidecars of the transactions for which the predicate returns `true`.
+    pub fn retain(&self, mut f: impl FnMut(&TxHash) -> bool) {
+        self.inner.write().retain(|tx, _| f(tx))
+    }
+
+    /// Returns the number of stored sidecars.
+    pub fn len(&self) -> usize {
+        self.inner.read().len()
+    }
+
+    /// Returns `true` if no sidecars are stored.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().is_empty()
+    }
+
+    /// Returns the total size of all stored sidecars.
+    pub fn data_size(&self) -> usize {
+        self.inner.read().values().map(BlobTransactionSidecar::size).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Bytes;
+
+    #[test]
+    fn insert_get_remove() {
+        let store = BlobStore::default();
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![Bytes::from(vec![1u8; 64])],
+            commitments: vec![Bytes::from(vec![2u8; 48])],
+            proofs: vec![Bytes::from(vec![3u8; 48])],
+        };
+        let hash = TxHash::random();
+        let other = TxHash::random();
+
+        store.insert(hash, sidecar.clone());
+        store.insert(other, sidecar.clone());
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.data_size(), 2 * sidecar.size());
+        assert_eq!(store.get(&hash), Some(sidecar.clone()));
+
+        store.retain(|tx| *tx != other);
+        assert!(!store.contains(&other));
+        assert_eq!(store.remove(&hash), Some(sidecar));
+        assert!(store.is_empty());
+    }
+}
@@ -1,6 +1,11 @@
+use std::{path::PathBuf, time::Duration};
+
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub(crate) const MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
+/// Interval at which the local transaction journal is rewritten, compatible with geth
+pub const DEFAULT_REJOURNAL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 ///! Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -12,6 +17,12 @@ pub struct PoolConfig {
     pub queued_limit: SubPoolLimit,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
+    /// Location of the journal that persists local transactions across restarts.
+    ///
+    /// Local transactions are not journaled if this is `None`.
+    pub journal: Option<PathBuf>,
+    /// Interval at which the journal is rewritten with the local transactions of the pool.
+    pub rejournal_interval: Duration,
 }
 
 impl Default for PoolConfig {
@@ -21,6 +32,8 @@ impl Default for PoolConfig {
             basefee_limit: Default::default(),
             queued_limit: Default::default(),
             max_account_slots: MAX_ACCOUNT_SLOTS_PER_SENDER,
+            journal: None,
+            rejournal_interval: DEFAULT_REJOURNAL_INTERVAL,
         }
     }
 }
@@ -1,6 +1,6 @@
 //! Transaction pool errors
 
-use reth_primitives::{Address, InvalidTransactionError, TxHash};
+use reth_primitives::{Address, InvalidBlobSidecarError, InvalidTransactionError, TxHash};
 
 /// Transaction pool result type.
 pub type PoolResult<T> = Result<T, PoolError>;
@@ -24,6 +24,9 @@ pub enum PoolError {
     /// Thrown when the transaction is considered invalid.
     #[error("[{0:?}] {1:?}")]
     InvalidTransaction(TxHash, InvalidPoolTransactionError),
+    /// Thrown when the sidecar of a blob transaction doesn't match the transaction.
+    #[error("[{0:?}] {1:?}")]
+    InvalidBlobSidecar(TxHash, InvalidBlobSidecarError),
     /// Any other error that occurred while inserting/validating a transaction. e.g. IO database
     /// error
     #[error("[{0:?}] {1:?}")]
@@ -41,6 +44,7 @@ impl PoolError {
             PoolError::SpammerExceededCapacity(_, hash) => hash,
             PoolError::DiscardedOnInsert(hash) => hash,
             PoolError::InvalidTransaction(hash, _) => hash,
+            PoolError::InvalidBlobSidecar(hash, _) => hash,
             PoolError::Other(hash, _) => hash,
         }
     }
@@ -70,4 +74,7 @@ pub enum InvalidPoolTransactionError {
     /// making the transaction invalid, rather a DOS protection.
     #[error("Input data too large")]
     OversizedData(usize, usize),
+    /// Thrown if a blob transaction is added to the pool without its sidecar.
+    #[error("Blob transaction without sidecar")]
+    MissingBlobSidecar,
 }
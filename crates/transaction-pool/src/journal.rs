@@ -0,0 +1,169 @@
+//! Journal of local transactions that persists them across restarts.
+//!
+//! Local transactions are appended to the journal as RLP when they're added to the pool. Since the
+//! journal only grows, it is periodically rewritten with the local transactions that are still in
+//! the pool, see [`LocalTransactionJournal::rotate`].
+//!
+//! All operations are blocking file I/O and are executed on the blocking pool by
+//! [`maintain_local_transaction_journal`](crate::maintain::maintain_local_transaction_journal).
+
+use reth_primitives::TransactionSigned;
+use reth_rlp::{Decodable, Encodable};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use tracing::warn;
+
+/// An append-only file of RLP encoded local transactions.
+#[derive(Debug)]
+pub struct LocalTransactionJournal {
+    /// Location of the journal file.
+    path: PathBuf,
+    /// The append handle of the journal file, opened on the first write.
+    file: Option<File>,
+}
+
+// === impl LocalTransactionJournal ===
+
+impl LocalTransactionJournal {
+    /// Creates a new journal at the given path.
+    ///
+    /// The file is created on the first write.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), file: None }
+    }
+
+    /// Returns the location of the journal file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads all transactions from the journal.
+    ///
+    /// Returns an empty list if the journal doesn't exist yet. A corrupted entry, for example a
+    /// partially written one after a crash, and everything after it is skipped.
+    pub fn load(&self) -> io::Result<Vec<TransactionSigned>> {
+        let data = match fs::read(&self.path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut buf = &data[..];
+        let mut transactions = Vec::new();
+        while !buf.is_empty() {
+            match TransactionSigned::decode(&mut buf) {
+                Ok(tx) => transactions.push(tx),
+                Err(err) => {
+                    warn!(target: "txpool::journal", ?err, path = ?self.path, "Discarding corrupted journal entries");
+                    break
+                }
+            }
+        }
+        Ok(transactions)
+    }
+
+    /// Appends the transactions to the journal and flushes them to disk.
+    pub fn insert<'a>(
+        &mut self,
+        transactions: impl IntoIterator<Item = &'a TransactionSigned>,
+    ) -> io::Result<()> {
+        let mut buf = Vec::new();
+        for transaction in transactions {
+            transaction.encode(&mut buf);
+        }
+        let file = self.append_handle()?;
+        file.write_all(&buf)?;
+        file.sync_data()
+    }
+
+    /// Replaces the content of the journal with the given transactions.
+    ///
+    /// The transactions are written to a temporary file first, which then replaces the journal, so
+    /// the journal is never left partially written.
+    pub fn rotate<'a>(
+        &mut self,
+        transactions: impl IntoIterator<Item = &'a TransactionSigned>,
+    ) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("new");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp)?);
+            let mut buf = Vec::new();
+            for transaction in transactions {
+                buf.clear();
+                transaction.encode(&mut buf);
+                writer.write_all(&buf)?;
+            }
+            writer.into_inner()?.sync_all()?;
+        }
+        // the append handle still refers to the replaced file
+        self.file = None;
+        fs::rename(tmp, &self.path)
+    }
+
+    /// Returns the append handle of the journal file, which is created if it doesn't exist.
+    fn append_handle(&mut self) -> io::Result<&mut File> {
+        let file = match self.file.take() {
+            Some(file) => file,
+            None => {
+                if let Some(parent) = self.path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                OpenOptions::new().create(true).append(true).open(&self.path)?
+            }
+        };
+        Ok(self.file.insert(file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Signature, Transaction, TransactionKind, TxLegacy};
+
+    fn transaction(nonce: u64) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                nonce,
+                gas_price: 1_000_000_000,
+                gas_limit: 21_000,
+                to: TransactionKind::Call(Default::default()),
+                value: 1,
+                input: Default::default(),
+            }),
+            Signature::default(),
+        )
+    }
+
+    #[test]
+    fn insert_load_rotate() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut journal = LocalTransactionJournal::new(dir.path().join("txpool/transactions.rlp"));
+        assert!(journal.load().unwrap().is_empty());
+
+        let transactions = (0..4).map(transaction).collect::<Vec<_>>();
+        for tx in &transactions[..3] {
+            journal.insert([tx]).unwrap();
+        }
+        assert_eq!(journal.load().unwrap(), transactions[..3]);
+
+        journal.rotate(&transactions[1..3]).unwrap();
+        assert_eq!(journal.load().unwrap(), transactions[1..3]);
+
+        // appends to the rotated journal
+        journal.insert(&transactions[3..]).unwrap();
+        assert_eq!(journal.load().unwrap(), transactions[1..]);
+
+        // a partially written entry is discarded
+        let mut data = fs::read(journal.path()).unwrap();
+        data.extend_from_slice(&[0xf8]);
+        fs::write(journal.path(), data).unwrap();
+        assert_eq!(journal.load().unwrap(), transactions[1..]);
+    }
+}
@@ -59,6 +59,9 @@
 //!   - update using account changes: balance changes
 //!   - base fee updates
 //!
+//! The [`maintain_transaction_pool`](crate::maintain::maintain_transaction_pool) task applies
+//! these updates for every change of the canonical chain.
+//!
 //! ## Implementation details
 //!
 //! The `TransactionPool` trait exposes all externally used functionality of the pool, such as
@@ -80,11 +83,14 @@
 //! that provides the `TransactionPool` interface.
 
 pub use crate::{
-    config::PoolConfig,
+    blobstore::BlobStore,
+    config::{PoolConfig, DEFAULT_REJOURNAL_INTERVAL},
+    journal::LocalTransactionJournal,
     ordering::{CostOrdering, TransactionOrdering},
+    pool::state::SubPool,
     traits::{
-        BestTransactions, OnNewBlockEvent, PoolTransaction, PooledTransaction, PropagateKind,
-        PropagatedTransactions, TransactionOrigin, TransactionPool,
+        BestTransactions, ChangedAccount, OnNewBlockEvent, PoolTransaction, PooledTransaction,
+        PropagateKind, PropagatedTransactions, StateDiff, TransactionOrigin, TransactionPool,
     },
     validate::{
         EthTransactionValidator, TransactionValidationOutcome, TransactionValidator,
@@ -98,13 +104,18 @@ use crate::{
 };
 
 use crate::error::PoolError;
-use reth_primitives::{TxHash, U256};
+use reth_primitives::{
+    Address, BlobTransactionSidecar, FromRecoveredTransaction, TransactionSigned, TxHash, U256,
+};
 use std::{collections::HashMap, sync::Arc};
-use tokio::sync::mpsc::Receiver;
+use tokio::sync::mpsc::{Receiver, UnboundedReceiver};
 
+mod blobstore;
 mod config;
 pub mod error;
 mod identifier;
+mod journal;
+pub mod maintain;
 pub mod metrics;
 mod ordering;
 pub mod pool;
@@ -203,6 +214,40 @@ where
     pub fn is_empty(&self) -> bool {
         self.pool.is_empty()
     }
+
+    /// Re-validates and re-inserts the transactions of the local transaction journal.
+    ///
+    /// Returns the number of transactions that were added to the pool.
+    pub async fn add_journaled_transactions(&self, transactions: Vec<TransactionSigned>) -> usize {
+        let transactions = transactions
+            .into_iter()
+            .filter_map(TransactionSigned::into_ecrecovered)
+            .map(V::Transaction::from_recovered_transaction);
+        let validated = futures_util::future::join_all(
+            transactions.map(|tx| self.validate(TransactionOrigin::Local, tx)),
+        )
+        .await;
+
+        self.pool
+            .add_transactions(TransactionOrigin::Local, validated.into_iter().map(|(_, tx)| tx))
+            .into_iter()
+            .filter(Result::is_ok)
+            .count()
+    }
+
+    /// Returns the local transactions of the pool that are persisted by the journal.
+    pub fn journaled_transactions(&self) -> Vec<TransactionSigned> {
+        self.pool.journaled_transactions()
+    }
+
+    /// Returns a new listener for local transactions added to the pool that should be appended to
+    /// the journal.
+    pub fn journal_listener(
+        &self,
+    ) -> UnboundedReceiver<Arc<ValidPoolTransaction<<V as TransactionValidator>::Transaction>>>
+    {
+        self.pool.add_journal_listener()
+    }
 }
 
 /// implements the `TransactionPool` interface for various transaction pool API consumers.
@@ -222,6 +267,10 @@ where
         self.pool.on_new_block(event);
     }
 
+    fn update_base_fee(&self, pending_block_base_fee: u128) {
+        self.pool.update_base_fee(pending_block_base_fee);
+    }
+
     async fn add_transaction(
         &self,
         origin: TransactionOrigin,
@@ -242,6 +291,27 @@ where
         }
     }
 
+    async fn add_blob_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        sidecar: BlobTransactionSidecar,
+    ) -> PoolResult<TxHash> {
+        let hash = *transaction.hash();
+        if let Err(err) = self.pool.validator().validate_blob_sidecar(&transaction, &sidecar) {
+            return Err(PoolError::InvalidBlobSidecar(hash, err))
+        }
+
+        // The sidecar is stored first, so it's available as soon as the transaction is announced.
+        self.pool.insert_blob_sidecar(hash, sidecar);
+        let res = self.add_transaction(origin, transaction).await;
+        // keep the sidecar if the transaction was already in the pool
+        if res.is_err() && !self.contains(&hash) {
+            self.pool.remove_blob_sidecar(&hash);
+        }
+        res
+    }
+
     async fn add_transactions(
         &self,
         origin: TransactionOrigin,
@@ -269,6 +339,14 @@ where
         self.pool.pooled_transactions()
     }
 
+    fn pending_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.pending_transactions()
+    }
+
+    fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.queued_transactions()
+    }
+
     fn best_transactions(
         &self,
     ) -> Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<Self::Transaction>>>> {
@@ -290,6 +368,17 @@ where
         self.inner().get(tx_hash)
     }
 
+    fn get_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.inner().get_transactions_by_sender(sender)
+    }
+
+    fn mined_transaction_origin(&self, tx_hash: &TxHash) -> Option<TransactionOrigin> {
+        self.inner().mined_transaction_origin(tx_hash)
+    }
+
     fn get_all(
         &self,
         txs: impl IntoIterator<Item = TxHash>,
@@ -297,6 +386,10 @@ where
         self.inner().get_all(txs)
     }
 
+    fn get_blob_sidecar(&self, tx_hash: &TxHash) -> Option<BlobTransactionSidecar> {
+        self.inner().get_blob_sidecar(tx_hash)
+    }
+
     fn on_propagated(&self, txs: PropagatedTransactions) {
         self.inner().on_propagated(txs)
     }
@@ -0,0 +1,388 @@
+//! Support for keeping the transaction pool in sync with the canonical chain.
+
+use crate::{
+    traits::{ChangedAccount, StateDiff},
+    LocalTransactionJournal, OnNewBlockEvent, Pool, TransactionOrdering, TransactionOrigin,
+    TransactionPool, TransactionValidator,
+};
+use parking_lot::Mutex;
+use reth_interfaces::events::{CanonStateNotification, CanonStateNotifications};
+use reth_primitives::{
+    BlobTransactionSidecar, FromRecoveredTransaction, IntoRecoveredTransaction, TxHash, TxType,
+};
+use reth_provider::{AccountProvider, StateProviderFactory};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    sync::Arc,
+};
+use tracing::{debug, info, warn};
+
+/// Maintains the state of the transaction pool by handling changes of the canonical chain.
+///
+/// For every notification this:
+///   - removes the transactions that were mined in the new canonical blocks
+///   - updates the base fee of the pending block, if blocks were only reverted the pending block
+///     builds on the parent of the first reverted block
+///   - reloads the nonce and balance of all accounts that were changed by the new or the reverted
+///     blocks, which rechecks the transactions of those senders
+///   - re-injects the transactions of reverted blocks that are not part of the new chain, local
+///     transactions keep their origin and blob transactions are re-injected with the sidecar the
+///     pool kept for them
+///
+/// This future resolves once the notification channel is closed.
+pub async fn maintain_transaction_pool<Client, Pool>(
+    client: Client,
+    pool: Pool,
+    mut events: CanonStateNotifications,
+) where
+    Client: StateProviderFactory,
+    Pool: TransactionPool,
+{
+    while let Some(notification) = events.recv().await {
+        // if blocks were only reverted, the parent of the first reverted block is the new tip and
+        // the base fee of the first reverted block is the base fee of the pending block
+        let (tip_number, tip_hash, pending_block_base_fee) =
+            if let Some(tip) = notification.committed().last() {
+                (tip.block.number, tip.block.hash(), tip.block.next_block_base_fee())
+            } else if let Some(first) = notification.reverted().first() {
+                (
+                    first.block.number.saturating_sub(1),
+                    first.block.parent_hash,
+                    first.block.base_fee_per_gas,
+                )
+            } else {
+                continue
+            };
+
+        let mined_transactions = notification
+            .committed()
+            .iter()
+            .flat_map(|block| block.block.body.iter().map(|tx| tx.hash()))
+            .collect::<Vec<_>>();
+
+        let state_changes = match changed_accounts(&client, &notification) {
+            Ok(changed_accounts) => StateDiff { changed_accounts },
+            Err(err) => {
+                warn!(target: "txpool", %err, "Failed to load the changed accounts");
+                StateDiff::default()
+            }
+        };
+
+        debug!(
+            target: "txpool",
+            block = tip_number,
+            hash = ?tip_hash,
+            mined = mined_transactions.len(),
+            changed_accounts = state_changes.changed_accounts.len(),
+            "Updating pool for new canonical block"
+        );
+
+        let mined = mined_transactions.iter().copied().collect::<HashSet<_>>();
+        pool.on_new_block(OnNewBlockEvent {
+            hash: tip_hash,
+            pending_block_base_fee: pending_block_base_fee.unwrap_or_default() as u128,
+            state_changes,
+            mined_transactions,
+        });
+
+        // re-inject the transactions of the reverted blocks that didn't make it into the new chain,
+        // this happens after the pool update, so they are validated against the new state.
+        let reinjected = reverted_transactions(&pool, &notification, &mined);
+        for (origin, transactions) in reinjected.transactions {
+            debug!(target: "txpool", ?origin, transactions = transactions.len(), "Re-injecting reorged transactions");
+            if let Err(err) = pool.add_transactions(origin, transactions).await {
+                warn!(target: "txpool", %err, "Failed to re-inject reorged transactions");
+            }
+        }
+        for (origin, transaction, sidecar) in reinjected.blob_transactions {
+            if let Err(err) = pool.add_blob_transaction(origin, transaction, sidecar).await {
+                warn!(target: "txpool", %err, "Failed to re-inject reorged blob transaction");
+            }
+        }
+    }
+}
+
+/// Maintains the local transaction journal of the pool, if enabled.
+///
+/// This re-inserts the journaled local transactions into the pool, appends every new local
+/// transaction to the journal and rewrites the journal with the local transactions of the pool
+/// every [`rejournal_interval`](crate::PoolConfig::rejournal_interval).
+///
+/// The journal is only accessed by this task and all of its file I/O runs on the blocking pool.
+pub async fn maintain_local_transaction_journal<V, T>(pool: Pool<V, T>)
+where
+    V: TransactionValidator,
+    T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
+{
+    let Some(path) = pool.config().journal.clone() else { return };
+    let journal = Arc::new(Mutex::new(LocalTransactionJournal::new(path)));
+
+    // subscribe before reloading, so that no local transaction that is added meanwhile is missed
+    let mut local_transactions = pool.journal_listener();
+
+    match with_journal(&journal, |journal| journal.load()).await {
+        Ok(transactions) => {
+            let added = pool.add_journaled_transactions(transactions).await;
+            info!(target: "txpool", added, "Reloaded local transactions from the journal")
+        }
+        Err(err) => warn!(target: "txpool", %err, "Failed to reload the local transaction journal"),
+    }
+
+    // the first tick completes immediately, which drops the stale entries of the reloaded journal
+    let mut interval = tokio::time::interval(pool.config().rejournal_interval);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let transactions = pool.journaled_transactions();
+                let rotate = move |journal: &mut LocalTransactionJournal| {
+                    journal.rotate(&transactions)
+                };
+                if let Err(err) = with_journal(&journal, rotate).await {
+                    warn!(target: "txpool", %err, "Failed to rotate the local transaction journal");
+                }
+            }
+            transaction = local_transactions.recv() => {
+                let Some(transaction) = transaction else { return };
+                // write all queued transactions at once
+                let transactions = std::iter::once(transaction)
+                    .chain(std::iter::from_fn(|| local_transactions.try_recv().ok()))
+                    .map(|tx| tx.transaction.to_recovered_transaction().into_signed())
+                    .collect::<Vec<_>>();
+                let insert = move |journal: &mut LocalTransactionJournal| {
+                    journal.insert(&transactions)
+                };
+                if let Err(err) = with_journal(&journal, insert).await {
+                    warn!(target: "txpool", %err, "Failed to journal local transactions");
+                }
+            }
+        }
+    }
+}
+
+/// Runs the operation on the journal on the blocking pool.
+async fn with_journal<F, R>(journal: &Arc<Mutex<LocalTransactionJournal>>, f: F) -> io::Result<R>
+where
+    F: FnOnce(&mut LocalTransactionJournal) -> io::Result<R> + Send + 'static,
+    R: Send + 'static,
+{
+    let journal = Arc::clone(journal);
+    tokio::task::spawn_blocking(move || f(&mut journal.lock()))
+        .await
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
+}
+
+/// The transactions of reverted blocks that are re-injected into the pool.
+struct ReinjectedTransactions<T> {
+    /// The transactions, grouped by the origin they were mined with.
+    transactions: HashMap<TransactionOrigin, Vec<T>>,
+    /// The blob transactions with the origin they were mined with and their sidecar.
+    blob_transactions: Vec<(TransactionOrigin, T, BlobTransactionSidecar)>,
+}
+
+/// Returns the transactions of the reverted blocks of the notification that were not mined in the
+/// new canonical blocks.
+///
+/// Blob transactions can only be re-injected with their sidecar, which isn't part of the block.
+/// Blob transactions whose sidecar is no longer kept by the pool are dropped.
+fn reverted_transactions<Pool: TransactionPool>(
+    pool: &Pool,
+    notification: &CanonStateNotification,
+    mined: &HashSet<TxHash>,
+) -> ReinjectedTransactions<Pool::Transaction> {
+    let mut transactions = HashMap::<_, Vec<_>>::new();
+    let mut blob_transactions = Vec::new();
+    for tx in notification.reverted().iter().flat_map(|block| block.block.body.iter()) {
+        let hash = tx.hash();
+        if mined.contains(&hash) {
+            continue
+        }
+        let Some(tx) = tx.clone().into_ecrecovered() else { continue };
+        let origin = pool.mined_transaction_origin(&hash).unwrap_or(TransactionOrigin::External);
+        if tx.tx_type() == TxType::EIP4844 {
+            match pool.get_blob_sidecar(&hash) {
+                Some(sidecar) => blob_transactions.push((
+                    origin,
+                    Pool::Transaction::from_recovered_transaction(tx),
+                    sidecar,
+                )),
+                None => {
+                    debug!(target: "txpool", ?hash, "Dropping reorged blob transaction without sidecar")
+                }
+            }
+            continue
+        }
+        transactions
+            .entry(origin)
+            .or_default()
+            .push(Pool::Transaction::from_recovered_transaction(tx));
+    }
+    ReinjectedTransactions { transactions, blob_transactions }
+}
+
+/// Loads the current state of all accounts changed by the new and the reverted blocks of the
+/// notification.
+fn changed_accounts<Client: StateProviderFactory>(
+    client: &Client,
+    notification: &CanonStateNotification,
+) -> reth_interfaces::Result<Vec<ChangedAccount>> {
+    let addresses = notification
+        .reverted()
+        .iter()
+        .chain(notification.committed())
+        .flat_map(|block| block.changed_accounts.iter().copied())
+        .collect::<HashSet<_>>();
+
+    let state = client.latest()?;
+    let mut changed_accounts = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        let account = state.basic_account(address)?.unwrap_or_default();
+        changed_accounts.push(ChangedAccount {
+            address,
+            nonce: account.nonce,
+            balance: account.balance,
+        });
+    }
+    Ok(changed_accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{testing_pool, NoopTransactionValidator},
+        CostOrdering, PooledTransaction,
+    };
+    use reth_interfaces::{events::CanonicalBlock, test_utils::generators::sign_message};
+    use reth_primitives::{
+        constants::BYTES_PER_BLOB, eip4844::MAINNET_KZG_TRUSTED_SETUP, Header, SealedBlock,
+        Transaction, TransactionKind, TransactionSigned, TxEip1559, TxEip4844, H256,
+    };
+    use reth_provider::test_utils::NoopProvider;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    type BlobTestPool =
+        Pool<NoopTransactionValidator<PooledTransaction>, CostOrdering<PooledTransaction>>;
+
+    fn blob_pool() -> BlobTestPool {
+        Pool::new(
+            Arc::new(NoopTransactionValidator::default()),
+            Arc::new(CostOrdering::default()),
+            Default::default(),
+        )
+    }
+
+    /// Creates a signed blob transaction and its sidecar.
+    fn blob_transaction() -> (TransactionSigned, BlobTransactionSidecar) {
+        // the first byte of each field element is kept zero so it's canonical
+        let mut blob = vec![0u8; BYTES_PER_BLOB];
+        blob.iter_mut().skip(1).step_by(32).for_each(|b| *b = 1);
+        let sidecar =
+            BlobTransactionSidecar::from_blobs(vec![blob.into()], &MAINNET_KZG_TRUSTED_SETUP)
+                .unwrap();
+        let transaction = Transaction::Eip4844(TxEip4844 {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 21_000,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 1,
+            to: TransactionKind::Call(Default::default()),
+            value: 0,
+            access_list: Default::default(),
+            blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+            max_fee_per_blob_gas: 5,
+            input: Default::default(),
+        });
+        (sign(transaction), sidecar)
+    }
+
+    fn sign(transaction: Transaction) -> TransactionSigned {
+        let signature =
+            sign_message(H256::from_low_u64_be(1), transaction.signature_hash()).unwrap();
+        TransactionSigned::from_transaction_and_signature(transaction, signature)
+    }
+
+    fn block(number: u64, body: Vec<TransactionSigned>) -> CanonicalBlock {
+        CanonicalBlock {
+            block: SealedBlock {
+                header: Header { number, ..Default::default() }.seal_slow(),
+                body,
+                ..Default::default()
+            },
+            receipts: vec![],
+            changed_accounts: vec![],
+        }
+    }
+
+    /// Runs the maintenance task until all notifications are handled.
+    async fn maintain<P: TransactionPool + Clone>(
+        pool: &P,
+        notifications: Vec<CanonStateNotification>,
+    ) {
+        let (tx, rx) = unbounded_channel();
+        for notification in notifications {
+            tx.send(notification).unwrap();
+        }
+        drop(tx);
+        maintain_transaction_pool(NoopProvider::default(), pool.clone(), rx).await;
+    }
+
+    #[tokio::test]
+    async fn reinject_reorged_blob_transaction() {
+        let pool = blob_pool();
+        let (transaction, sidecar) = blob_transaction();
+        let hash = transaction.hash;
+        let recovered = transaction.clone().into_ecrecovered().unwrap();
+        pool.add_blob_transaction(
+            TransactionOrigin::External,
+            PooledTransaction::from_recovered_transaction(recovered),
+            sidecar.clone(),
+        )
+        .await
+        .unwrap();
+
+        // the sidecar of the mined transaction is kept
+        let mined = block(1, vec![transaction]);
+        maintain(
+            &pool,
+            vec![CanonStateNotification::Commit { new: Arc::new(vec![mined.clone()]) }],
+        )
+        .await;
+        assert!(!pool.contains(&hash));
+        assert_eq!(pool.get_blob_sidecar(&hash), Some(sidecar.clone()));
+
+        let reorg = CanonStateNotification::Reorg {
+            old: Arc::new(vec![mined]),
+            new: Arc::new(vec![block(1, vec![])]),
+        };
+        maintain(&pool, vec![reorg]).await;
+        assert!(pool.contains(&hash));
+        assert_eq!(pool.get_blob_sidecar(&hash), Some(sidecar));
+    }
+
+    #[tokio::test]
+    async fn reinject_transactions_of_revert_without_new_blocks() {
+        let pool = testing_pool();
+        let transaction = sign(Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 21_000,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 1,
+            to: TransactionKind::Call(Default::default()),
+            ..Default::default()
+        }));
+        let hash = transaction.hash;
+
+        let mined = block(1, vec![transaction]);
+        maintain(
+            &pool,
+            vec![
+                CanonStateNotification::Commit { new: Arc::new(vec![mined.clone()]) },
+                CanonStateNotification::Reorg { old: Arc::new(vec![mined]), new: Arc::new(vec![]) },
+            ],
+        )
+        .await;
+        assert!(pool.contains(&hash));
+    }
+}
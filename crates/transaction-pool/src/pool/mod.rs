@@ -68,20 +68,36 @@
 #![allow(dead_code)] // TODO(mattsse): remove once remaining checks implemented
 
 use crate::{
-    error::{PoolError, PoolResult},
+    blobstore::BlobStore,
+    error::{InvalidPoolTransactionError, PoolError, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
-    pool::{listener::PoolEventBroadcast, state::SubPool, txpool::TxPool},
+    pool::{
+        listener::PoolEventBroadcast,
+        state::SubPool,
+        txpool::{SenderInfo, TxPool, UpdateOutcome},
+    },
     traits::{
-        NewTransactionEvent, PoolSize, PoolTransaction, PropagatedTransactions, TransactionOrigin,
+        ChangedAccount, NewTransactionEvent, PoolSize, PoolTransaction, PropagatedTransactions,
+        TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
     OnNewBlockEvent, PoolConfig, TransactionOrdering, TransactionValidator,
 };
 use best::BestTransactions;
 pub use events::TransactionEvent;
+use fnv::FnvHashMap;
 use parking_lot::{Mutex, RwLock};
-use reth_primitives::{Address, TxHash, H256};
-use std::{collections::HashSet, fmt, sync::Arc, time::Instant};
+use reth_primitives::{
+    Address, BlobTransactionSidecar, IntoRecoveredTransaction, PooledTransactionsElement,
+    TransactionSigned, TxHash, EIP4844_TX_TYPE_ID, H256,
+};
+use reth_rlp::Encodable;
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+    sync::Arc,
+    time::Instant,
+};
 use tokio::sync::mpsc;
 use tracing::warn;
 
@@ -95,6 +111,10 @@ pub(crate) mod state;
 pub mod txpool;
 mod update;
 
+/// The number of the most recently mined blob transactions whose sidecars are kept, so that the
+/// transactions can be re-injected with their sidecar if their block is reorged.
+pub(crate) const MAX_MINED_BLOB_TRANSACTIONS: usize = 256;
+
 /// Transaction pool internals.
 pub struct PoolInner<V: TransactionValidator, T: TransactionOrdering> {
     /// Internal mapping of addresses to plain ints.
@@ -103,6 +123,12 @@ pub struct PoolInner<V: TransactionValidator, T: TransactionOrdering> {
     validator: Arc<V>,
     /// The internal pool that manages all transactions.
     pool: RwLock<TxPool<T>>,
+    /// The sidecars of the blob transactions in the pool and of the most recently mined blob
+    /// transactions.
+    blob_store: BlobStore,
+    /// The hashes of the most recently mined blob transactions whose sidecars are kept, oldest
+    /// first, bounded by [`MAX_MINED_BLOB_TRANSACTIONS`].
+    mined_blob_transactions: Mutex<VecDeque<TxHash>>,
     /// Pool settings.
     config: PoolConfig,
     /// Manages listeners for transaction state change events.
@@ -111,6 +137,8 @@ pub struct PoolInner<V: TransactionValidator, T: TransactionOrdering> {
     pending_transaction_listener: Mutex<Vec<mpsc::Sender<TxHash>>>,
     /// Listeners for new transactions added to the pool.
     transaction_listener: Mutex<Vec<mpsc::Sender<NewTransactionEvent<T::Transaction>>>>,
+    /// Listeners for new local transactions that are persisted by the journal.
+    journal_listener: Mutex<Vec<mpsc::UnboundedSender<Arc<ValidPoolTransaction<T::Transaction>>>>>,
 }
 
 // === impl PoolInner ===
@@ -127,8 +155,11 @@ where
             validator,
             event_listener: Default::default(),
             pool: RwLock::new(TxPool::new(ordering, config.clone())),
+            blob_store: Default::default(),
+            mined_blob_transactions: Default::default(),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
+            journal_listener: Default::default(),
             config,
         }
     }
@@ -170,6 +201,18 @@ where
         rx
     }
 
+    /// Adds a new listener to the pool that gets notified about every new local transaction that
+    /// should be journaled.
+    ///
+    /// The channel is unbounded so that no local transaction is missed by the journal.
+    pub fn add_journal_listener(
+        &self,
+    ) -> mpsc::UnboundedReceiver<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.journal_listener.lock().push(tx);
+        rx
+    }
+
     /// Returns hashes of _all_ transactions in the pool.
     pub(crate) fn pooled_transactions_hashes(&self) -> Vec<TxHash> {
         let pool = self.pool.read();
@@ -182,12 +225,97 @@ where
         pool.all().transactions_iter().collect()
     }
 
+    /// Returns all transactions of the pending sub-pool.
+    pub(crate) fn pending_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let pool = self.pool.read();
+        pool.all().transactions_by_subpool(|subpool| subpool.is_pending()).collect()
+    }
+
+    /// Returns all transactions of the queued and basefee sub-pools.
+    pub(crate) fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let pool = self.pool.read();
+        pool.all().transactions_by_subpool(|subpool| !subpool.is_pending()).collect()
+    }
+
     /// Updates the entire pool after a new block was executed.
     pub(crate) fn on_new_block(&self, block: OnNewBlockEvent) {
-        let outcome = self.pool.write().on_new_block(block);
+        let changed_senders = self.changed_senders(block.state_changes.changed_accounts.iter());
+        self.retain_mined_blob_sidecars(&block.mined_transactions);
+        let outcome = self.pool.write().on_new_block(block, changed_senders);
+        self.prune_blob_sidecars();
         self.notify_on_new_block(outcome);
     }
 
+    /// Updates the pool after the base fee of the pending block changed.
+    pub(crate) fn update_base_fee(&self, pending_block_base_fee: u128) {
+        let outcome = self.pool.write().update_base_fee(pending_block_base_fee);
+        self.blob_store.remove_all(&outcome.discarded);
+        self.notify_on_update(outcome);
+    }
+
+    /// Stores the sidecar of a blob transaction.
+    pub(crate) fn insert_blob_sidecar(&self, tx_hash: TxHash, sidecar: BlobTransactionSidecar) {
+        self.blob_store.insert(tx_hash, sidecar)
+    }
+
+    /// Removes the sidecar of a blob transaction.
+    pub(crate) fn remove_blob_sidecar(&self, tx_hash: &TxHash) {
+        self.blob_store.remove(tx_hash);
+    }
+
+    /// Returns the sidecar of the blob transaction, if it exists.
+    pub(crate) fn get_blob_sidecar(&self, tx_hash: &TxHash) -> Option<BlobTransactionSidecar> {
+        self.blob_store.get(tx_hash)
+    }
+
+    /// Keeps the sidecars of the mined blob transactions until they are among the oldest
+    /// [`MAX_MINED_BLOB_TRANSACTIONS`] mined blob transactions.
+    fn retain_mined_blob_sidecars(&self, mined_transactions: &[TxHash]) {
+        let mut mined = self.mined_blob_transactions.lock();
+        for tx_hash in mined_transactions {
+            if self.blob_store.contains(tx_hash) && !mined.contains(tx_hash) {
+                mined.push_back(*tx_hash);
+            }
+        }
+        while mined.len() > MAX_MINED_BLOB_TRANSACTIONS {
+            mined.pop_front();
+        }
+    }
+
+    /// Removes the sidecars of all transactions that are no longer in the pool, except for the
+    /// recently mined blob transactions.
+    ///
+    /// This also covers blob transactions that were replaced by another transaction of the same
+    /// sender and nonce.
+    fn prune_blob_sidecars(&self) {
+        if self.blob_store.is_empty() {
+            return
+        }
+        let pool = self.pool.read();
+        let mined = self.mined_blob_transactions.lock();
+        self.blob_store.retain(|tx| pool.contains(tx) || mined.contains(tx))
+    }
+
+    /// Returns the new state of all changed accounts that are senders of transactions in the pool.
+    ///
+    /// Accounts that are unknown to the pool are skipped, since there are no transactions to
+    /// update.
+    fn changed_senders<'a>(
+        &self,
+        accounts: impl Iterator<Item = &'a ChangedAccount>,
+    ) -> FnvHashMap<SenderId, SenderInfo> {
+        let identifiers = self.identifiers.read();
+        accounts
+            .filter_map(|account| {
+                let sender_id = identifiers.sender_id(&account.address)?;
+                Some((
+                    sender_id,
+                    SenderInfo { state_nonce: account.nonce, balance: account.balance },
+                ))
+            })
+            .collect()
+    }
+
     /// Add a single validated transaction into the pool.
     ///
     /// Note: this is only used internally by [`Self::add_transactions()`], all new transaction(s)
@@ -201,7 +329,24 @@ where
             TransactionValidationOutcome::Valid { balance, state_nonce, transaction } => {
                 let sender_id = self.get_sender_id(transaction.sender());
                 let transaction_id = TransactionId::new(sender_id, transaction.nonce());
-                let encoded_length = transaction.encoded_length();
+                // Blob transactions are shared with peers in their network form, which includes
+                // the sidecar, so that is the size that's announced.
+                let encoded_length = if transaction.tx_type() == EIP4844_TX_TYPE_ID {
+                    let Some(sidecar) = self.blob_store.get(transaction.hash()) else {
+                        self.event_listener.write().discarded(transaction.hash());
+                        return Err(PoolError::InvalidTransaction(
+                            *transaction.hash(),
+                            InvalidPoolTransactionError::MissingBlobSidecar,
+                        ))
+                    };
+                    PooledTransactionsElement::blob_transaction(
+                        transaction.to_recovered_transaction().into_signed(),
+                        sidecar,
+                    )
+                    .length()
+                } else {
+                    transaction.encoded_length()
+                };
 
                 let tx = ValidPoolTransaction {
                     cost: transaction.cost(),
@@ -224,8 +369,16 @@ where
                 // Notify tx event listeners
                 self.notify_event_listeners(&added);
 
+                let event = added.into_new_transaction_event();
+
+                // Persist local transactions, private transactions are not journaled since they'd
+                // be reloaded as regular local transactions
+                if event.transaction.is_local() && !event.transaction.is_private() {
+                    self.on_new_local_transaction(&event.transaction);
+                }
+
                 // Notify listeners for _all_ transactions
-                self.on_new_transaction(added.into_new_transaction_event());
+                self.on_new_transaction(event);
 
                 Ok(hash)
             }
@@ -258,6 +411,7 @@ where
         if discarded.is_empty() {
             return added
         }
+        self.blob_store.remove_all(&discarded);
 
         // It may happen that a newly added transaction is immediately discarded, so we need to
         // adjust the result here
@@ -272,6 +426,23 @@ where
             .collect()
     }
 
+    /// Returns all local transactions in the pool that are journaled.
+    pub(crate) fn journaled_transactions(&self) -> Vec<TransactionSigned> {
+        self.pool
+            .read()
+            .all()
+            .transactions_iter()
+            .filter(|tx| tx.is_local() && !tx.is_private())
+            .map(|tx| tx.transaction.to_recovered_transaction().into_signed())
+            .collect()
+    }
+
+    /// Notify the journal listeners about a new local transaction.
+    fn on_new_local_transaction(&self, transaction: &Arc<ValidPoolTransaction<T::Transaction>>) {
+        let mut journal_listeners = self.journal_listener.lock();
+        journal_listeners.retain(|listener| listener.send(Arc::clone(transaction)).is_ok());
+    }
+
     /// Notify all listeners about a new pending transaction.
     fn on_new_pending_transaction(&self, ready: &TxHash) {
         let mut transaction_listeners = self.pending_transaction_listener.lock();
@@ -314,12 +485,24 @@ where
 
     /// Notifies transaction listeners about changes after a block was processed.
     fn notify_on_new_block(&self, outcome: OnNewBlockOutcome) {
-        let OnNewBlockOutcome { mined, promoted, discarded, block_hash } = outcome;
+        let OnNewBlockOutcome { mined, promoted, demoted, discarded, block_hash } = outcome;
 
         let mut listener = self.event_listener.write();
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
         promoted.iter().for_each(|tx| listener.pending(tx, None));
+        demoted.iter().for_each(|tx| listener.queued(tx));
+        discarded.iter().for_each(|tx| listener.discarded(tx));
+    }
+
+    /// Notifies transaction listeners about the sub-pool moves after the base fee changed.
+    fn notify_on_update(&self, outcome: UpdateOutcome) {
+        let UpdateOutcome { promoted, demoted, discarded } = outcome;
+
+        let mut listener = self.event_listener.write();
+
+        promoted.iter().for_each(|tx| listener.pending(tx, None));
+        demoted.iter().for_each(|tx| listener.queued(tx));
         discarded.iter().for_each(|tx| listener.discarded(tx));
     }
 
@@ -356,6 +539,7 @@ where
         let mut listener = self.event_listener.write();
 
         removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        self.blob_store.remove_all(removed.iter().map(|tx| tx.hash()));
 
         removed
     }
@@ -374,6 +558,20 @@ where
         self.pool.read().get(tx_hash)
     }
 
+    /// Returns all transactions of the sender, ordered by nonce.
+    pub(crate) fn get_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let Some(sender_id) = self.identifiers.read().sender_id(&sender) else { return Vec::new() };
+        self.pool.read().get_transactions_by_sender(sender_id)
+    }
+
+    /// Returns the origin of the local transaction with the given hash if it was recently mined.
+    pub(crate) fn mined_transaction_origin(&self, tx_hash: &TxHash) -> Option<TransactionOrigin> {
+        self.pool.read().mined_transaction_origin(tx_hash)
+    }
+
     /// Returns all the transactions belonging to the hashes.
     ///
     /// If no transaction exists, it is skipped.
@@ -486,6 +684,8 @@ pub(crate) struct OnNewBlockOutcome {
     pub(crate) mined: Vec<TxHash>,
     /// Transactions promoted to the ready queue.
     pub(crate) promoted: Vec<TxHash>,
+    /// Transactions moved from the ready queue to a parked sub-pool.
+    pub(crate) demoted: Vec<TxHash>,
     /// transaction that were discarded during the update
     pub(crate) discarded: Vec<TxHash>,
 }
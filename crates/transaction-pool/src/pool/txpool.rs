@@ -12,7 +12,7 @@ use crate::{
         update::{Destination, PoolUpdate},
         AddedPendingTransaction, AddedTransaction, OnNewBlockOutcome,
     },
-    traits::{PoolSize, StateDiff},
+    traits::{PoolSize, TransactionOrigin},
     OnNewBlockEvent, PoolConfig, PoolResult, PoolTransaction, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
@@ -20,7 +20,7 @@ use fnv::FnvHashMap;
 use reth_primitives::{TxHash, H256};
 use std::{
     cmp::Ordering,
-    collections::{btree_map::Entry, hash_map, BTreeMap, HashMap},
+    collections::{btree_map::Entry, hash_map, BTreeMap, HashMap, VecDeque},
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
@@ -33,6 +33,10 @@ use std::{
 /// than 1.
 pub(crate) const MIN_PROTOCOL_BASE_FEE: u128 = 7;
 
+/// The number of mined local transactions whose origin is remembered, so they are re-injected
+/// with it if their block is reorged.
+pub(crate) const MAX_MINED_LOCAL_TRANSACTIONS: usize = 4096;
+
 /// A pool that manages transactions.
 ///
 /// This pool maintains the state of all transactions and stores them accordingly.
@@ -90,10 +94,35 @@ pub struct TxPool<T: TransactionOrdering> {
     basefee_pool: ParkedPool<BasefeeOrd<T::Transaction>>,
     /// All transactions in the pool.
     all_transactions: AllTransactions<T::Transaction>,
+    /// Origins of the most recently mined local transactions.
+    mined_local_transactions: MinedLocalTransactions,
     /// Transaction pool metrics
     metrics: TxPoolMetrics,
 }
 
+/// The origins of the most recently mined local transactions, bounded by
+/// [`MAX_MINED_LOCAL_TRANSACTIONS`].
+#[derive(Debug, Default)]
+struct MinedLocalTransactions {
+    origins: HashMap<TxHash, TransactionOrigin>,
+    /// The hashes in the order they were mined, oldest first.
+    order: VecDeque<TxHash>,
+}
+
+impl MinedLocalTransactions {
+    fn insert(&mut self, tx_hash: TxHash, origin: TransactionOrigin) {
+        if self.origins.insert(tx_hash, origin).is_some() {
+            return
+        }
+        self.order.push_back(tx_hash);
+        if self.order.len() > MAX_MINED_LOCAL_TRANSACTIONS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.origins.remove(&oldest);
+            }
+        }
+    }
+}
+
 // === impl TxPool ===
 
 impl<T: TransactionOrdering> TxPool<T> {
@@ -106,6 +135,7 @@ impl<T: TransactionOrdering> TxPool<T> {
             basefee_pool: Default::default(),
             all_transactions: AllTransactions::new(config.max_account_slots),
             config,
+            mined_local_transactions: Default::default(),
             metrics: Default::default(),
         }
     }
@@ -127,12 +157,17 @@ impl<T: TransactionOrdering> TxPool<T> {
         }
     }
 
-    /// Updates the pool based on the changed base fee.
+    /// Updates the pool based on the changed base fee of the pending block.
     ///
-    /// This enforces the dynamic fee requirement.
-    pub(crate) fn update_base_fee(&mut self, _new_base_fee: U256) {
-        // TODO update according to the changed base_fee
-        todo!()
+    /// This enforces the dynamic fee requirement: transactions (and their descendants) that no
+    /// longer meet the new base fee are moved out of the pending sub-pool and parked transactions
+    /// that now meet it are promoted.
+    pub(crate) fn update_base_fee(&mut self, pending_block_base_fee: u128) -> UpdateOutcome {
+        if self.all_transactions.pending_basefee == pending_block_base_fee {
+            return UpdateOutcome::default()
+        }
+        let updates = self.all_transactions.update(pending_block_base_fee, &FnvHashMap::default());
+        self.process_updates(updates)
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block.
@@ -153,6 +188,19 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.by_hash.get(tx_hash).cloned()
     }
 
+    /// Returns all transactions of the sender, ordered by nonce.
+    pub(crate) fn get_transactions_by_sender(
+        &self,
+        sender: SenderId,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.all_transactions.txs_iter(sender).map(|(_, tx)| Arc::clone(&tx.transaction)).collect()
+    }
+
+    /// Returns the origin of the local transaction with the given hash if it was recently mined.
+    pub(crate) fn mined_transaction_origin(&self, tx_hash: &TxHash) -> Option<TransactionOrigin> {
+        self.mined_local_transactions.origins.get(tx_hash).copied()
+    }
+
     /// Returns transactions for the multiple given hashes, if they exist.
     pub(crate) fn get_all<'a>(
         &'a self,
@@ -164,26 +212,49 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// Updates the entire pool after a new block was mined.
     ///
     /// This removes all mined transactions, updates according to the new base fee and rechecks
-    /// sender allowance.
-    pub(crate) fn on_new_block(&mut self, event: OnNewBlockEvent) -> OnNewBlockOutcome {
+    /// sender allowance against the new state of the `changed_senders`.
+    pub(crate) fn on_new_block(
+        &mut self,
+        event: OnNewBlockEvent,
+        changed_senders: FnvHashMap<SenderId, SenderInfo>,
+    ) -> OnNewBlockOutcome {
         // Remove all transaction that were included in the block
         for tx_hash in &event.mined_transactions {
-            self.remove_transaction_by_hash(tx_hash);
+            if let Some(tx) = self.remove_transaction_by_hash(tx_hash) {
+                if tx.is_local() {
+                    self.mined_local_transactions.insert(*tx_hash, tx.origin);
+                }
+            }
             // Update removed transactions metric
             self.metrics.removed_transactions.increment(1);
         }
 
+        // Remove all transactions of the changed senders with a nonce below the new on chain nonce,
+        // these can never be executed, for example because another transaction of the sender with
+        // the same nonce was mined.
+        let mut stale = Vec::new();
+        for (sender, info) in &changed_senders {
+            stale.extend(self.all_transactions.txs_below_nonce(*sender, info.state_nonce));
+            self.sender_info.entry(*sender).or_default().update(info.state_nonce, info.balance);
+        }
+        let stale = stale
+            .iter()
+            .filter_map(|id| self.remove_transaction(id))
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+
         // Apply the state changes to the total set of transactions which triggers sub-pool updates.
-        let updates =
-            self.all_transactions.update(event.pending_block_base_fee, &event.state_changes);
+        let updates = self.all_transactions.update(event.pending_block_base_fee, &changed_senders);
 
         // Process the sub-pool updates
-        let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+        let UpdateOutcome { promoted, demoted, mut discarded } = self.process_updates(updates);
+        discarded.extend(stale);
 
         OnNewBlockOutcome {
             block_hash: event.hash,
             mined: event.mined_transactions,
             promoted,
+            demoted,
             discarded,
         }
     }
@@ -225,7 +296,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 self.add_new_transaction(transaction.clone(), replaced_tx, move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
-                let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+                let UpdateOutcome { promoted, discarded, .. } = self.process_updates(updates);
 
                 // This transaction was moved to the pending pool.
                 let res = if move_to.is_pending() {
@@ -285,6 +356,8 @@ impl<T: TransactionOrdering> TxPool<T> {
                     self.move_transaction(current, move_to, &id);
                     if matches!(move_to, SubPool::Pending) {
                         outcome.promoted.push(hash);
+                    } else if matches!(current, SubPool::Pending) {
+                        outcome.demoted.push(hash);
                     }
                 }
             }
@@ -520,6 +593,15 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.by_hash.values().cloned()
     }
 
+    /// Returns an iterator over all transactions that reside in a sub-pool matching the filter,
+    /// ordered by sender and nonce.
+    pub(crate) fn transactions_by_subpool<'a>(
+        &'a self,
+        filter: impl Fn(SubPool) -> bool + 'a,
+    ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T>>> + 'a {
+        self.txs.values().filter(move |tx| filter(tx.subpool)).map(|tx| Arc::clone(&tx.transaction))
+    }
+
     /// Returns if the transaction for the given hash is already included in this pool
     pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
         self.by_hash.contains_key(tx_hash)
@@ -562,16 +644,22 @@ impl<T: PoolTransaction> AllTransactions<T> {
     ///       - `pending` if basefee condition is met.
     ///       - `basefee` if basefee condition is _not_ met.
     ///
-    /// Additionally, this will also update the `cumulative_gas_used` for transactions of a sender
-    /// that got transaction included in the block.
+    /// Additionally, this will also update the `cumulative_cost` and the nonce gap condition for
+    /// transactions of the `changed_senders`, for example senders that got transactions included
+    /// in the block.
     pub(crate) fn update(
         &mut self,
         pending_block_base_fee: u128,
-        _state_diffs: &StateDiff,
+        changed_senders: &FnvHashMap<SenderId, SenderInfo>,
     ) -> Vec<PoolUpdate> {
         // update new basefee
         self.pending_basefee = pending_block_base_fee;
 
+        // recheck the nonce and balance conditions of the senders with a new on chain state
+        for (sender, info) in changed_senders {
+            self.update_sender_state(*sender, info);
+        }
+
         // TODO(mattsse): probably good idea to allocate some capacity here.
         let mut updates = Vec::new();
 
@@ -587,26 +675,26 @@ impl<T: PoolTransaction> AllTransactions<T> {
         // The `unique_sender` loop will process the first transaction of all senders, update its
         // state and internally update all consecutive transactions
         'unique_sender: while let Some((id, tx)) = iter.next() {
-            // Advances the iterator to the next sender
+            // Advances the iterator to the next sender, the skipped transactions are parked, but
+            // their state may have changed if the sender's state changed.
             macro_rules! next_sender {
                 ($iter:ident) => {
-                    'this: while let Some((peek, _)) = iter.peek() {
+                    'this: while let Some((peek, tx)) = iter.peek_mut() {
                         if peek.sender != id.sender {
                             break 'this
                         }
+                        Self::record_subpool_update(&mut updates, tx);
                         iter.next();
                     }
                 };
             }
             // If there's a nonce gap, we can shortcircuit, because there's nothing to update.
             if tx.state.has_nonce_gap() {
+                Self::record_subpool_update(&mut updates, tx);
                 next_sender!(iter);
                 continue
             }
 
-            // TODO(mattsse): if account has balance changes or mined transactions the balance needs
-            // to be checked here
-
             // Since this is the first transaction of the sender, it has no parked ancestors
             tx.state.insert(TxState::NO_PARKED_ANCESTORS);
 
@@ -650,6 +738,45 @@ impl<T: PoolTransaction> AllTransactions<T> {
         updates
     }
 
+    /// Rechecks the nonce gap and balance conditions of all transactions of the sender against the
+    /// sender's new on chain nonce and balance.
+    ///
+    /// This expects that all transactions with a nonce below the on chain nonce were removed.
+    fn update_sender_state(&mut self, sender: SenderId, info: &SenderInfo) {
+        let on_chain_id = TransactionId::new(sender, info.state_nonce);
+        let mut next_nonce = on_chain_id.nonce;
+        let mut has_nonce_gap = false;
+        let mut cumulative_cost = U256::ZERO;
+
+        for (id, tx) in self.descendant_txs_mut(&on_chain_id) {
+            // once there's a nonce gap, all following transactions have one as well
+            has_nonce_gap |= next_nonce != id.nonce;
+            if has_nonce_gap {
+                tx.state.remove(TxState::NO_NONCE_GAPS);
+            } else {
+                tx.state.insert(TxState::NO_NONCE_GAPS);
+            }
+            next_nonce = id.next_nonce();
+
+            tx.cumulative_cost = cumulative_cost;
+            cumulative_cost = tx.next_cumulative_cost();
+            if cumulative_cost > info.balance {
+                // sender lacks sufficient funds to pay for this transaction
+                tx.state.remove(TxState::ENOUGH_BALANCE);
+            } else {
+                tx.state.insert(TxState::ENOUGH_BALANCE);
+            }
+        }
+    }
+
+    /// Returns the ids of all transactions of the sender with a nonce below the given nonce.
+    pub(crate) fn txs_below_nonce(&self, sender: SenderId, nonce: u64) -> Vec<TransactionId> {
+        self.txs
+            .range(TransactionId::new(sender, 0)..TransactionId::new(sender, nonce))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
     /// This will update the transaction's `subpool` based on its state.
     ///
     /// If the sub-pool derived from the state differs from the current pool, it will record a
@@ -684,8 +811,6 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
     /// Returns an iterator over all transactions for the given sender, starting with the lowest
     /// nonce
-    #[cfg(test)]
-    #[allow(unused)]
     pub(crate) fn txs_iter(
         &self,
         sender: SenderId,
@@ -956,6 +1081,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 if tx_id.eq(id) {
                     // if it is the new transaction, track the state
                     state = tx.state;
+                    tx.subpool = tx.state.into();
                 } else {
                     tx.subpool = tx.state.into();
                     if current_pool != tx.subpool {
@@ -1082,9 +1208,11 @@ impl<T: PoolTransaction> PoolInternalTransaction<T> {
 #[derive(Default, Debug)]
 pub struct UpdateOutcome {
     /// transactions promoted to the ready queue
-    promoted: Vec<TxHash>,
+    pub(crate) promoted: Vec<TxHash>,
+    /// transactions moved from the ready queue to a parked sub-pool
+    pub(crate) demoted: Vec<TxHash>,
     /// transaction that failed and became discarded
-    discarded: Vec<TxHash>,
+    pub(crate) discarded: Vec<TxHash>,
 }
 
 /// Represents the outcome of a prune
@@ -1118,11 +1246,11 @@ impl<T: PoolTransaction> fmt::Debug for PruneResult<T> {
 
 /// Stores relevant context about a sender.
 #[derive(Debug, Clone, Default)]
-struct SenderInfo {
+pub(crate) struct SenderInfo {
     /// current nonce of the sender.
-    state_nonce: u64,
+    pub(crate) state_nonce: u64,
     /// Balance of the sender at the current point.
-    balance: U256,
+    pub(crate) balance: U256,
 }
 
 // === impl SenderInfo ===
@@ -1143,7 +1271,7 @@ impl SenderInfo {
 mod tests {
     use super::*;
     use crate::{
-        test_utils::{MockTransaction, MockTransactionFactory},
+        test_utils::{mock_tx_pool, MockTransaction, MockTransactionFactory},
         traits::TransactionOrigin,
     };
 
@@ -1274,6 +1402,31 @@ mod tests {
         assert_eq!(SubPool::Pending, first_in_pool.subpool);
     }
 
+    #[test]
+    fn transactions_by_subpool() {
+        let on_chain_balance = U256::from(10_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+        let tx = MockTransaction::eip1559().set_gas_price(100).inc_limit();
+        let pending = f.validated(tx.clone());
+        pool.insert_tx(pending.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        // nonce gap
+        let queued = f.validated(tx.next().next());
+        pool.insert_tx(queued.clone(), on_chain_balance, on_chain_nonce).unwrap();
+
+        assert_eq!(SubPool::Pending, pool.get(pending.id()).unwrap().subpool);
+        let pending_txs =
+            pool.transactions_by_subpool(|subpool| subpool.is_pending()).collect::<Vec<_>>();
+        assert_eq!(pending_txs.len(), 1);
+        assert_eq!(pending_txs[0].hash(), pending.hash());
+
+        let queued_txs =
+            pool.transactions_by_subpool(|subpool| !subpool.is_pending()).collect::<Vec<_>>();
+        assert_eq!(queued_txs.len(), 1);
+        assert_eq!(queued_txs[0].hash(), queued.hash());
+    }
+
     #[test]
     fn insert_previous_blocking() {
         let on_chain_balance = U256::from(1_000);
@@ -1376,4 +1529,113 @@ mod tests {
             Err(InsertErr::TxGasLimitMoreThanAvailableBlockGas { .. })
         ));
     }
+
+    #[test]
+    fn on_new_block_applies_changed_senders() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = mock_tx_pool();
+        let tx = MockTransaction::eip1559().with_gas_limit(21_000);
+        for tx in [tx.clone(), tx.next(), tx.next().next()] {
+            pool.add_transaction(f.validated(tx), U256::MAX, 0).unwrap();
+        }
+        assert_eq!(pool.pending().len(), 3);
+
+        // another transaction of the sender with nonce 0 was mined and drained its balance
+        let sender = f.tx_id(&tx).sender;
+        let changed_senders =
+            FnvHashMap::from_iter([(sender, SenderInfo { state_nonce: 1, balance: U256::ZERO })]);
+        let event = OnNewBlockEvent {
+            hash: H256::random(),
+            pending_block_base_fee: 0,
+            state_changes: Default::default(),
+            mined_transactions: vec![],
+        };
+        let outcome = pool.on_new_block(event, changed_senders);
+
+        assert_eq!(outcome.discarded, vec![tx.get_hash()]);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.pending().len(), 0);
+        assert_eq!(pool.queued().len(), 2);
+    }
+
+    #[test]
+    fn get_transactions_by_sender() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = mock_tx_pool();
+        let tx = MockTransaction::eip1559().with_gas_limit(21_000);
+        let next = tx.next();
+        let other = MockTransaction::eip1559().with_gas_limit(21_000);
+        for tx in [next.clone(), other.clone(), tx.clone()] {
+            pool.add_transaction(f.validated(tx), U256::MAX, 0).unwrap();
+        }
+
+        let by_sender = pool
+            .get_transactions_by_sender(f.tx_id(&tx).sender)
+            .iter()
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+        assert_eq!(by_sender, vec![tx.get_hash(), next.get_hash()]);
+        assert_eq!(pool.get_transactions_by_sender(f.tx_id(&other).sender).len(), 1);
+    }
+
+    #[test]
+    fn on_new_block_remembers_mined_local_origins() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = mock_tx_pool();
+        let local = MockTransaction::eip1559().with_gas_limit(21_000);
+        let external = MockTransaction::eip1559().with_gas_limit(21_000);
+        pool.add_transaction(
+            f.validated_with_origin(TransactionOrigin::Local, local.clone()),
+            U256::MAX,
+            0,
+        )
+        .unwrap();
+        pool.add_transaction(f.validated(external.clone()), U256::MAX, 0).unwrap();
+
+        let event = OnNewBlockEvent {
+            hash: H256::random(),
+            pending_block_base_fee: 0,
+            state_changes: Default::default(),
+            mined_transactions: vec![local.get_hash(), external.get_hash()],
+        };
+        pool.on_new_block(event, Default::default());
+
+        assert_eq!(pool.len(), 0);
+        assert_eq!(
+            pool.mined_transaction_origin(&local.get_hash()),
+            Some(TransactionOrigin::Local)
+        );
+        assert_eq!(pool.mined_transaction_origin(&external.get_hash()), None);
+    }
+
+    #[test]
+    fn update_base_fee_moves_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = mock_tx_pool();
+        let tx = MockTransaction::eip1559().with_gas_limit(21_000).with_max_fee(100);
+        let txs = [tx.clone(), tx.next(), tx.next().next().with_max_fee(200)];
+        for tx in txs.clone() {
+            pool.add_transaction(f.validated(tx), U256::MAX, 0).unwrap();
+        }
+        assert_eq!(pool.pending().len(), 3);
+
+        // the first transaction no longer meets the base fee, which also parks its descendants
+        let outcome = pool.update_base_fee(150);
+        assert!(outcome.promoted.is_empty());
+        assert_eq!(outcome.demoted.len(), 3);
+        assert_eq!(pool.pending().len(), 0);
+        assert_eq!(pool.base_fee().len(), 1);
+        assert_eq!(pool.queued().len(), 2);
+
+        // unchanged base fee is a noop
+        let outcome = pool.update_base_fee(150);
+        assert!(outcome.promoted.is_empty() && outcome.demoted.is_empty());
+
+        let outcome = pool.update_base_fee(100);
+        assert_eq!(outcome.promoted, txs.iter().map(|tx| tx.get_hash()).collect::<Vec<_>>());
+        assert!(outcome.demoted.is_empty());
+        assert_eq!(pool.pending().len(), 3);
+        assert_eq!(pool.base_fee().len(), 0);
+        assert_eq!(pool.queued().len(), 0);
+    }
 }
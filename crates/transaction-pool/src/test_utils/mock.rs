@@ -12,8 +12,9 @@ use rand::{
     prelude::Distribution,
 };
 use reth_primitives::{
-    Address, FromRecoveredTransaction, IntoRecoveredTransaction, Transaction, TransactionKind,
-    TransactionSignedEcRecovered, TxEip1559, TxHash, TxLegacy, TxType, H256, U128, U256,
+    Address, FromRecoveredTransaction, IntoRecoveredTransaction, Signature, Transaction,
+    TransactionKind, TransactionSigned, TransactionSignedEcRecovered, TxEip1559, TxHash, TxLegacy,
+    TxType, H256, U128, U256,
 };
 use std::{ops::Range, sync::Arc, time::Instant};
 
@@ -335,6 +336,10 @@ impl PoolTransaction for MockTransaction {
         }
     }
 
+    fn blob_versioned_hashes(&self) -> &[H256] {
+        &[]
+    }
+
     fn kind(&self) -> &TransactionKind {
         match self {
             MockTransaction::Legacy { to, .. } => to,
@@ -405,7 +410,7 @@ impl FromRecoveredTransaction for MockTransaction {
                 to,
                 value: U256::from(value),
             },
-            Transaction::Eip2930 { .. } => {
+            Transaction::Eip2930 { .. } | Transaction::Eip4844 { .. } => {
                 unimplemented!()
             }
         }
@@ -414,7 +419,42 @@ impl FromRecoveredTransaction for MockTransaction {
 
 impl IntoRecoveredTransaction for MockTransaction {
     fn to_recovered_transaction(&self) -> TransactionSignedEcRecovered {
-        todo!()
+        let transaction = match self.clone() {
+            MockTransaction::Legacy { nonce, gas_price, gas_limit, to, value, .. } => {
+                Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_price,
+                    gas_limit,
+                    to,
+                    value: value.to(),
+                    input: Default::default(),
+                })
+            }
+            MockTransaction::Eip1559 {
+                nonce,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                gas_limit,
+                to,
+                value,
+                ..
+            } => Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value: value.to(),
+                input: Default::default(),
+                access_list: Default::default(),
+            }),
+        };
+        // mock transactions aren't signed
+        let signed =
+            TransactionSigned::from_transaction_and_signature(transaction, Signature::default());
+        TransactionSignedEcRecovered::from_signed_transaction(signed, self.get_sender())
     }
 }
 
@@ -1,7 +1,7 @@
 use crate::{error::PoolResult, pool::state::SubPool, validate::ValidPoolTransaction};
 use reth_primitives::{
-    Address, FromRecoveredTransaction, IntoRecoveredTransaction, PeerId, Transaction,
-    TransactionKind, TransactionSignedEcRecovered, TxHash, H256, U256,
+    Address, BlobTransactionSidecar, FromRecoveredTransaction, IntoRecoveredTransaction, PeerId,
+    Transaction, TransactionKind, TransactionSignedEcRecovered, TxHash, H256, U256,
 };
 use reth_rlp::Encodable;
 use std::{collections::HashMap, fmt, sync::Arc};
@@ -34,6 +34,12 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// affects the dynamic fee requirement of pending transactions in the pool.
     fn on_new_block(&self, event: OnNewBlockEvent);
 
+    /// Updates the pool with the new base fee of the pending block.
+    ///
+    /// This moves transactions between the pending and the base fee sub-pools, so that the pending
+    /// sub-pool only contains transactions that are executable with the given base fee.
+    fn update_base_fee(&self, pending_block_base_fee: u128);
+
     /// Imports an _external_ transaction.
     ///
     /// This is intended to be used by the network to insert incoming transactions received over the
@@ -46,6 +52,9 @@ pub trait TransactionPool: Send + Sync + Clone {
 
     /// Adds an _unvalidated_ transaction into the pool.
     ///
+    /// Blob transactions must be added with their sidecar via
+    /// [`TransactionPool::add_blob_transaction`].
+    ///
     /// Consumer: RPC
     async fn add_transaction(
         &self,
@@ -53,6 +62,20 @@ pub trait TransactionPool: Send + Sync + Clone {
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash>;
 
+    /// Adds an _unvalidated_ blob transaction together with its sidecar into the pool.
+    ///
+    /// The sidecar is checked against the versioned hashes of the transaction and its KZG proofs
+    /// are verified, see [`crate::TransactionValidator::validate_blob_sidecar`]. It's only stored
+    /// if the transaction was added to the pool.
+    ///
+    /// Consumer: P2P
+    async fn add_blob_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        sidecar: BlobTransactionSidecar,
+    ) -> PoolResult<TxHash>;
+
     /// Adds the given _unvalidated_ transaction into the pool.
     ///
     /// Returns a list of results.
@@ -86,6 +109,18 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Consumer: P2P
     fn pooled_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns all transactions that are ready to be included in the next block, these are the
+    /// transactions of the [SubPool::Pending] sub-pool.
+    ///
+    /// Consumer: RPC
+    fn pending_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
+    /// Returns all transactions that can't be included in the next block yet, these are the
+    /// transactions of the [SubPool::Queued] and [SubPool::BaseFee] sub-pools.
+    ///
+    /// Consumer: RPC
+    fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Returns an iterator that yields transactions that are ready for block production.
     ///
     /// Consumer: Block production
@@ -118,6 +153,18 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Returns the transaction for the given hash.
     fn get(&self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns all transactions of the sender that are in the pool, ordered by nonce.
+    fn get_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
+    /// Returns the origin of the local transaction with the given hash if it was mined recently.
+    ///
+    /// Transactions of reorged blocks are re-injected with this origin, external transactions
+    /// are not tracked.
+    fn mined_transaction_origin(&self, tx_hash: &TxHash) -> Option<TransactionOrigin>;
+
     /// Returns all transactions objects for the given hashes.
     ///
     /// This adheres to the expected behavior of [`GetPooledTransactions`](https://github.com/ethereum/devp2p/blob/master/caps/eth.md#getpooledtransactions-0x09):
@@ -128,6 +175,14 @@ pub trait TransactionPool: Send + Sync + Clone {
         txs: impl IntoIterator<Item = TxHash>,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns the sidecar of the blob transaction for the given hash.
+    ///
+    /// The sidecars of recently mined blob transactions are kept as well, so that they can be
+    /// re-injected if their block is reorged.
+    ///
+    /// Consumer: P2P
+    fn get_blob_sidecar(&self, tx_hash: &TxHash) -> Option<BlobTransactionSidecar>;
+
     /// Notify the pool about transactions that are propagated to peers.
     ///
     /// Consumer: P2P
@@ -190,7 +245,7 @@ impl<T: PoolTransaction> Clone for NewTransactionEvent<T> {
 ///
 /// Depending on where the transaction was picked up, it affects how the transaction is handled
 /// internally, e.g. limits for simultaneous transaction of one sender.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum TransactionOrigin {
     /// Transaction is coming from a local source.
     Local,
@@ -199,14 +254,23 @@ pub enum TransactionOrigin {
     /// This is usually considered an "untrusted" source, for example received from another in the
     /// network.
     External,
+    /// Transaction is coming from a local source but must not be propagated to the network.
+    ///
+    /// These are treated like [`TransactionOrigin::Local`] transactions by the pool.
+    Private,
 }
 
 // === impl TransactionOrigin ===
 
 impl TransactionOrigin {
-    /// Whether the transaction originates from a local source.
+    /// Whether the transaction originates from a local source, this includes private transactions.
     pub fn is_local(&self) -> bool {
-        matches!(self, TransactionOrigin::Local)
+        matches!(self, TransactionOrigin::Local | TransactionOrigin::Private)
+    }
+
+    /// Whether the transaction is private and must not be propagated to the network.
+    pub fn is_private(&self) -> bool {
+        matches!(self, TransactionOrigin::Private)
     }
 }
 
@@ -226,9 +290,22 @@ pub struct OnNewBlockEvent {
 }
 
 /// Contains a list of changed state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct StateDiff {
-    // TODO(mattsse) this could be an `Arc<revm::State>>`
+    /// The accounts that changed, for example because they sent a mined transaction or their
+    /// balance changed.
+    pub changed_accounts: Vec<ChangedAccount>,
+}
+
+/// The state of an account after it changed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ChangedAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The current nonce of the account.
+    pub nonce: u64,
+    /// The current balance of the account.
+    pub balance: U256,
 }
 
 /// An `Iterator` that only returns transactions that are ready to be executed.
@@ -250,7 +327,9 @@ impl<T> BestTransactions for std::iter::Empty<T> {
 }
 
 /// Trait for transaction types used inside the pool
-pub trait PoolTransaction: fmt::Debug + Send + Sync + FromRecoveredTransaction {
+pub trait PoolTransaction:
+    fmt::Debug + Send + Sync + FromRecoveredTransaction + IntoRecoveredTransaction
+{
     /// Hash of the transaction.
     fn hash(&self) -> &TxHash;
 
@@ -283,6 +362,11 @@ pub trait PoolTransaction: fmt::Debug + Send + Sync + FromRecoveredTransaction {
     /// This will return `None` for non-EIP1559 transactions
     fn max_priority_fee_per_gas(&self) -> Option<u128>;
 
+    /// Returns the EIP-4844 versioned hashes of the blobs of the transaction.
+    ///
+    /// This will be empty for non-EIP4844 transactions
+    fn blob_versioned_hashes(&self) -> &[H256];
+
     /// Returns the transaction's [`TransactionKind`], which is the address of the recipient or
     /// [`TransactionKind::Create`] if the transaction is a contract creation.
     fn kind(&self) -> &TransactionKind;
@@ -309,7 +393,8 @@ pub struct PooledTransaction {
     /// EcRecovered transaction info
     pub(crate) transaction: TransactionSignedEcRecovered,
 
-    /// For EIP-1559 transactions that is `feeCap x gasLimit + transferred_value
+    /// For EIP-1559 transactions that is `feeCap x gasLimit + transferred_value`, EIP-4844
+    /// transactions additionally pay `blobFeeCap x blobGas`
     pub(crate) cost: U256,
 
     /// This is `priority + basefee`for EIP-1559 and `gasPrice` for legacy transactions.
@@ -366,6 +451,7 @@ impl PoolTransaction for PooledTransaction {
             Transaction::Legacy(_) => None,
             Transaction::Eip2930(_) => None,
             Transaction::Eip1559(tx) => Some(tx.max_fee_per_gas),
+            Transaction::Eip4844(tx) => Some(tx.max_fee_per_gas),
         }
     }
 
@@ -377,9 +463,17 @@ impl PoolTransaction for PooledTransaction {
             Transaction::Legacy(_) => None,
             Transaction::Eip2930(_) => None,
             Transaction::Eip1559(tx) => Some(tx.max_priority_fee_per_gas),
+            Transaction::Eip4844(tx) => Some(tx.max_priority_fee_per_gas),
         }
     }
 
+    /// Returns the EIP-4844 versioned hashes of the blobs of the transaction.
+    ///
+    /// This will be empty for non-EIP4844 transactions
+    fn blob_versioned_hashes(&self) -> &[H256] {
+        self.transaction.blob_versioned_hashes()
+    }
+
     /// Returns the transaction's [`TransactionKind`], which is the address of the recipient or
     /// [`TransactionKind::Create`] if the transaction is a contract creation.
     fn kind(&self) -> &TransactionKind {
@@ -426,6 +520,13 @@ impl FromRecoveredTransaction for PooledTransaction {
                 let effective_gas_price = t.max_priority_fee_per_gas;
                 (cost, effective_gas_price)
             }
+            Transaction::Eip4844(t) => {
+                let cost = U256::from(t.max_fee_per_gas) * U256::from(t.gas_limit) +
+                    U256::from(t.value) +
+                    U256::from(t.max_fee_per_blob_gas) * U256::from(t.blob_gas());
+                let effective_gas_price = t.max_priority_fee_per_gas;
+                (cost, effective_gas_price)
+            }
         };
 
         PooledTransaction { transaction: tx, cost, effective_gas_price }
@@ -7,11 +7,14 @@ use crate::{
     MAX_INIT_CODE_SIZE, TX_MAX_SIZE,
 };
 use reth_primitives::{
-    Address, InvalidTransactionError, TransactionKind, TxHash, EIP1559_TX_TYPE_ID,
-    EIP2930_TX_TYPE_ID, LEGACY_TX_TYPE_ID, U256,
+    constants::{MAX_BLOBS_PER_BLOCK, VERSIONED_HASH_VERSION_KZG},
+    eip4844::{KzgSettings, MAINNET_KZG_TRUSTED_SETUP},
+    Address, BlobTransactionSidecar, InvalidBlobSidecarError, InvalidTransactionError,
+    TransactionKind, TxHash, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    LEGACY_TX_TYPE_ID, U256,
 };
 use reth_provider::AccountProvider;
-use std::{fmt, time::Instant};
+use std::{fmt, sync::Arc, time::Instant};
 
 /// A Result type returned after checking a transaction's validity.
 #[derive(Debug)]
@@ -62,6 +65,19 @@ pub trait TransactionValidator: Send + Sync {
         transaction: Self::Transaction,
     ) -> TransactionValidationOutcome<Self::Transaction>;
 
+    /// Validates the sidecar of a blob transaction.
+    ///
+    /// The sidecar must match the versioned hashes of the transaction and the KZG proofs must
+    /// verify the blobs against their commitments, see [`BlobTransactionSidecar::validate`]. By
+    /// default the proofs are verified with the [`MAINNET_KZG_TRUSTED_SETUP`].
+    fn validate_blob_sidecar(
+        &self,
+        transaction: &Self::Transaction,
+        sidecar: &BlobTransactionSidecar,
+    ) -> Result<(), InvalidBlobSidecarError> {
+        sidecar.validate(transaction.blob_versioned_hashes(), &MAINNET_KZG_TRUSTED_SETUP)
+    }
+
     /// Ensure that the code size is not greater than `max_init_code_size`.
     /// `max_init_code_size` should be configurable so this will take it as an argument.
     fn ensure_max_init_code_size(
@@ -94,10 +110,14 @@ pub struct EthTransactionValidator<Client> {
     eip2718: bool,
     /// Fork indicator whether we are using EIP-1559 type transactions.
     eip1559: bool,
+    /// Fork indicator whether we are in the Cancun stage and accept EIP-4844 blob transactions.
+    cancun: bool,
     /// The current max gas limit
     current_max_gas_limit: u64,
     /// gasprice
     gas_price: Option<u128>,
+    /// The KZG settings used to verify the blobs of blob transactions.
+    kzg_settings: Arc<KzgSettings>,
 }
 
 #[async_trait::async_trait]
@@ -106,6 +126,14 @@ impl<T: PoolTransaction + AccountProvider + Clone> TransactionValidator
 {
     type Transaction = T;
 
+    fn validate_blob_sidecar(
+        &self,
+        transaction: &Self::Transaction,
+        sidecar: &BlobTransactionSidecar,
+    ) -> Result<(), InvalidBlobSidecarError> {
+        sidecar.validate(transaction.blob_versioned_hashes(), &self.kzg_settings)
+    }
+
     async fn validate_transaction(
         &self,
         origin: TransactionOrigin,
@@ -137,6 +165,19 @@ impl<T: PoolTransaction + AccountProvider + Clone> TransactionValidator
                 }
             }
 
+            EIP4844_TX_TYPE_ID => {
+                // Reject blob transactions until EIP-4844 activates.
+                if !self.cancun {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidTransactionError::Eip4844Disabled.into(),
+                    )
+                }
+                if let Err(err) = ensure_valid_blob_transaction(&transaction) {
+                    return TransactionValidationOutcome::Invalid(transaction, err.into())
+                }
+            }
+
             _ => {
                 return TransactionValidationOutcome::Invalid(
                     transaction,
@@ -253,6 +294,30 @@ impl<T: PoolTransaction + AccountProvider + Clone> TransactionValidator
     }
 }
 
+/// Ensures the blob transaction can't create a contract and carries between one and
+/// [`MAX_BLOBS_PER_BLOCK`] blobs with KZG versioned hashes.
+fn ensure_valid_blob_transaction<T: PoolTransaction>(
+    transaction: &T,
+) -> Result<(), InvalidTransactionError> {
+    if *transaction.kind() == TransactionKind::Create {
+        return Err(InvalidTransactionError::BlobCreateTransaction)
+    }
+    let hashes = transaction.blob_versioned_hashes();
+    if hashes.is_empty() {
+        return Err(InvalidTransactionError::EmptyBlobs)
+    }
+    if hashes.len() > MAX_BLOBS_PER_BLOCK as usize {
+        return Err(InvalidTransactionError::TooManyBlobs {
+            have: hashes.len(),
+            max: MAX_BLOBS_PER_BLOCK as usize,
+        })
+    }
+    if let Some(hash) = hashes.iter().find(|hash| hash.0[0] != VERSIONED_HASH_VERSION_KZG) {
+        return Err(InvalidTransactionError::InvalidBlobVersionedHash(*hash))
+    }
+    Ok(())
+}
+
 /// A valid transaction in the pool.
 pub struct ValidPoolTransaction<T: PoolTransaction> {
     /// The transaction
@@ -268,6 +333,8 @@ pub struct ValidPoolTransaction<T: PoolTransaction> {
     /// Where this transaction originated from.
     pub origin: TransactionOrigin,
     /// The length of the rlp encoded transaction (cached)
+    ///
+    /// For blob transactions this is the length of the network form, including the sidecar.
     pub encoded_length: usize,
 }
 
@@ -324,6 +391,11 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.origin.is_local()
     }
 
+    /// Whether the transaction is private and must not be propagated to the network.
+    pub fn is_private(&self) -> bool {
+        self.origin.is_private()
+    }
+
     /// The heap allocated size of this transaction.
     pub(crate) fn size(&self) -> usize {
         self.transaction.size()